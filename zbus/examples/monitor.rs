@@ -0,0 +1,139 @@
+// A simple D-Bus monitor, printing all the messages going through the session (or system) bus.
+//
+// Usage: monitor [--system] [--format pretty|compact|json]
+//
+// The output format is chosen with `--format`:
+//
+// * `pretty` (the default) - the summary of each message, as given by its `Display` implementation.
+// * `compact` - one line per message with the timestamp, message type, sender, destination, path,
+//   member and the body in the GVariant text format, separated by spaces. Missing fields are
+//   printed as `-`.
+// * `json` - one JSON object per line (JSON lines), with the same information as the compact
+//   format, plus the serial numbers, error name and body signature. Suitable for piping into `jq`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use zbus::{
+    blocking::{fdo::MonitoringProxy, Connection, MessageIterator},
+    message::{Message, Type},
+    zvariant::Structure,
+};
+
+#[derive(Clone, Copy)]
+enum Format {
+    Pretty,
+    Compact,
+    Json,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt::init();
+
+    let mut system = false;
+    let mut format = Format::Pretty;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--system" => system = true,
+            "--session" => system = false,
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("pretty") => Format::Pretty,
+                    Some("compact") => Format::Compact,
+                    Some("json") => Format::Json,
+                    f => panic!("Expected `pretty`, `compact` or `json` format. Got: {f:?}"),
+                }
+            }
+            _ => panic!("Unexpected argument: {arg}"),
+        }
+    }
+
+    let connection = if system {
+        Connection::system()?
+    } else {
+        Connection::session()?
+    };
+    MonitoringProxy::new(&connection)?.become_monitor(&[], 0)?;
+
+    for msg in MessageIterator::from(connection) {
+        let msg = msg?;
+        match format {
+            Format::Pretty => println!("{msg}"),
+            Format::Compact => println!("{}", compact(&msg)),
+            Format::Json => println!("{}", json(&msg)),
+        }
+    }
+
+    Ok(())
+}
+
+fn compact(msg: &Message) -> String {
+    let header = msg.header();
+    let or_dash = |s: Option<String>| s.unwrap_or_else(|| "-".into());
+    let member = match (header.interface(), header.member(), header.error_name()) {
+        (_, _, Some(error)) => Some(error.to_string()),
+        (Some(iface), Some(member), _) => Some(format!("{iface}.{member}")),
+        (None, Some(member), _) => Some(member.to_string()),
+        _ => None,
+    };
+
+    format!(
+        "{} {} {} {} {} {} {}",
+        timestamp(),
+        message_type(msg.message_type()),
+        or_dash(header.sender().map(|s| s.to_string())),
+        or_dash(header.destination().map(|d| d.to_string())),
+        or_dash(header.path().map(|p| p.to_string())),
+        or_dash(member),
+        body(msg),
+    )
+}
+
+fn json(msg: &Message) -> String {
+    let header = msg.header();
+
+    serde_json::json!({
+        "timestamp": timestamp(),
+        "type": message_type(msg.message_type()),
+        "serial": msg.primary_header().serial_num(),
+        "reply_serial": header.reply_serial(),
+        "sender": header.sender().map(ToString::to_string),
+        "destination": header.destination().map(ToString::to_string),
+        "path": header.path().map(ToString::to_string),
+        "interface": header.interface().map(ToString::to_string),
+        "member": header.member().map(ToString::to_string),
+        "error_name": header.error_name().map(ToString::to_string),
+        "signature": header.signature().map(ToString::to_string),
+        "body": body(msg),
+    })
+    .to_string()
+}
+
+// Seconds since the epoch, with microsecond precision.
+fn timestamp() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    format!("{}.{:06}", now.as_secs(), now.subsec_micros())
+}
+
+fn message_type(ty: Type) -> &'static str {
+    match ty {
+        Type::MethodCall => "method_call",
+        Type::MethodReturn => "method_return",
+        Type::Error => "error",
+        Type::Signal => "signal",
+    }
+}
+
+// The body in the GVariant text format.
+fn body(msg: &Message) -> String {
+    match msg.body_signature() {
+        Some(signature) if !signature.is_empty() => match msg.body::<Structure<'_>>() {
+            Ok(body) => body.to_string(),
+            Err(e) => format!("<failed to parse body: {e}>"),
+        },
+        _ => "()".into(),
+    }
+}
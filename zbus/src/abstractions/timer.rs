@@ -0,0 +1,16 @@
+//! Runtime-agnostic timer abstraction.
+
+use std::time::Duration;
+
+/// Wait for `duration` to elapse.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(feature = "tokio"))]
+    {
+        async_io::Timer::after(duration).await;
+    }
+
+    #[cfg(feature = "tokio")]
+    {
+        tokio::time::sleep(duration).await;
+    }
+}
@@ -0,0 +1,319 @@
+//! Generation of D-Bus activation files.
+//!
+//! A service is started on demand by the bus (i.e activated) if a [service file] declares which
+//! executable provides its well-known name. On systemd-based systems, the activation is typically
+//! delegated to a systemd unit. Writing these files by hand duplicates the well-known name, which
+//! is also hard-coded in the service's code to request it, and the two can easily drift apart.
+//!
+//! [`ServiceFile`] and [`SystemdUnit`] generate these files from the name the service code uses,
+//! e.g from a build script:
+//!
+//! ```no_run
+//! // build.rs
+//! use zbus::activation::{ServiceFile, SystemdUnit};
+//!
+//! // Shared with the service code, e.g through `include!`.
+//! const NAME: &str = "org.zbus.MyService";
+//!
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! let unit = SystemdUnit::new(NAME, "/usr/bin/my-service")?
+//!     .description("My zbus service")?;
+//! unit.write_to(&out_dir)?;
+//! ServiceFile::new(NAME, "/usr/bin/my-service")?
+//!     .systemd_service(unit.file_name())?
+//!     .write_to(&out_dir)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [service file]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-bus-starting-services
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+use zbus_names::{OwnedWellKnownName, WellKnownName};
+
+use crate::{Error, Result};
+
+/// A D-Bus activation service file.
+///
+/// The [`Display`](fmt::Display) implementation produces the content of the file, which is to be
+/// installed as [`ServiceFile::file_name`] in the `services` directory of the bus (e.g
+/// `/usr/share/dbus-1/services` for the session bus and `/usr/share/dbus-1/system-services` for the
+/// system bus).
+///
+/// # Example
+///
+/// ```
+/// use zbus::activation::ServiceFile;
+///
+/// let file = ServiceFile::new("org.zbus.MyService", "/usr/bin/my-service --activated")?
+///     .systemd_service("my-service.service")?;
+/// assert_eq!(file.file_name(), "org.zbus.MyService.service");
+/// assert_eq!(
+///     file.to_string(),
+///     "[D-BUS Service]\n\
+///      Name=org.zbus.MyService\n\
+///      Exec=/usr/bin/my-service --activated\n\
+///      SystemdService=my-service.service\n",
+/// );
+/// # Ok::<(), zbus::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceFile {
+    name: OwnedWellKnownName,
+    exec: String,
+    user: Option<String>,
+    systemd_service: Option<String>,
+}
+
+impl ServiceFile {
+    /// Create a service file for the well-known name `name`, provided by running `exec`.
+    ///
+    /// `exec` is the command line to run, the executable path being absolute.
+    pub fn new<'n, N>(name: N, exec: &str) -> Result<Self>
+    where
+        N: TryInto<WellKnownName<'n>>,
+        N::Error: Into<Error>,
+    {
+        Ok(Self {
+            name: name.try_into().map_err(Into::into)?.into(),
+            exec: exec_line(exec)?,
+            user: None,
+            systemd_service: None,
+        })
+    }
+
+    /// Set the user to run the service as.
+    ///
+    /// This is required for system bus services. Fails if `user` contains control characters.
+    pub fn user(mut self, user: &str) -> Result<Self> {
+        self.user = Some(field_value("User", user)?);
+
+        Ok(self)
+    }
+
+    /// Delegate the activation to the given systemd unit.
+    ///
+    /// Fails if `unit` contains control characters.
+    pub fn systemd_service(mut self, unit: impl Into<String>) -> Result<Self> {
+        self.systemd_service = Some(field_value("SystemdService", &unit.into())?);
+
+        Ok(self)
+    }
+
+    /// The well-known name of the service.
+    pub fn name(&self) -> &WellKnownName<'static> {
+        self.name.inner()
+    }
+
+    /// The name of the file: the well-known name, with a `.service` extension.
+    pub fn file_name(&self) -> String {
+        format!("{}.service", self.name)
+    }
+
+    /// Write the file to the directory `dir`, returning its path.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        write_file(dir.as_ref(), &self.file_name(), &self.to_string())
+    }
+}
+
+impl fmt::Display for ServiceFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[D-BUS Service]")?;
+        writeln!(f, "Name={}", self.name)?;
+        writeln!(f, "Exec={}", self.exec)?;
+        if let Some(user) = &self.user {
+            writeln!(f, "User={user}")?;
+        }
+        if let Some(unit) = &self.systemd_service {
+            writeln!(f, "SystemdService={unit}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A systemd unit for a D-Bus activated service.
+///
+/// The unit is of the `dbus` type, so systemd considers the service started once it owns its
+/// well-known name. Refer to it from the [`ServiceFile`] through
+/// [`ServiceFile::systemd_service`].
+///
+/// # Example
+///
+/// ```
+/// use zbus::activation::SystemdUnit;
+///
+/// let unit = SystemdUnit::new("org.zbus.MyService", "/usr/bin/my-service")?
+///     .description("My service")?;
+/// assert_eq!(unit.file_name(), "dbus-org.zbus.MyService.service");
+/// assert_eq!(
+///     unit.to_string(),
+///     "[Unit]\n\
+///      Description=My service\n\
+///      \n\
+///      [Service]\n\
+///      Type=dbus\n\
+///      BusName=org.zbus.MyService\n\
+///      ExecStart=/usr/bin/my-service\n",
+/// );
+/// # Ok::<(), zbus::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemdUnit {
+    name: OwnedWellKnownName,
+    exec: String,
+    description: Option<String>,
+    unit_name: Option<String>,
+}
+
+impl SystemdUnit {
+    /// Create a unit for the well-known name `name`, provided by running `exec`.
+    ///
+    /// `exec` is the command line to run, the executable path being absolute.
+    pub fn new<'n, N>(name: N, exec: &str) -> Result<Self>
+    where
+        N: TryInto<WellKnownName<'n>>,
+        N::Error: Into<Error>,
+    {
+        Ok(Self {
+            name: name.try_into().map_err(Into::into)?.into(),
+            exec: exec_line(exec)?,
+            description: None,
+            unit_name: None,
+        })
+    }
+
+    /// Set the description of the unit.
+    ///
+    /// Fails if `description` contains control characters.
+    pub fn description(mut self, description: &str) -> Result<Self> {
+        self.description = Some(field_value("Description", description)?);
+
+        Ok(self)
+    }
+
+    /// Set the name of the unit, without the `.service` extension.
+    ///
+    /// Defaults to `dbus-` followed by the well-known name, the conventional alias of D-Bus
+    /// activated units.
+    ///
+    /// Fails if `name` contains control characters or a `/`, since it's used as a file name.
+    pub fn unit_name(mut self, name: &str) -> Result<Self> {
+        if name.contains('/') {
+            return Err(Error::Failure(format!(
+                "Unit name can't contain `/`: `{name}`"
+            )));
+        }
+        self.unit_name = Some(field_value("Unit name", name)?);
+
+        Ok(self)
+    }
+
+    /// The name of the file, to be referred to by [`ServiceFile::systemd_service`].
+    pub fn file_name(&self) -> String {
+        match &self.unit_name {
+            Some(name) => format!("{name}.service"),
+            None => format!("dbus-{}.service", self.name),
+        }
+    }
+
+    /// Write the file to the directory `dir`, returning its path.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        write_file(dir.as_ref(), &self.file_name(), &self.to_string())
+    }
+}
+
+impl fmt::Display for SystemdUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Unit]")?;
+        if let Some(description) = &self.description {
+            writeln!(f, "Description={description}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "[Service]")?;
+        writeln!(f, "Type=dbus")?;
+        writeln!(f, "BusName={}", self.name)?;
+        writeln!(f, "ExecStart={}", self.exec)?;
+
+        Ok(())
+    }
+}
+
+// Both file formats are line-based, so a newline (or any other control character) in a value would
+// let it inject arbitrary entries.
+fn field_value(field: &str, value: &str) -> Result<String> {
+    if value.contains(char::is_control) {
+        return Err(Error::Failure(format!(
+            "{field} can't contain newlines or other control characters"
+        )));
+    }
+
+    Ok(value.to_string())
+}
+
+// Both file formats also require an absolute executable path.
+fn exec_line(exec: &str) -> Result<String> {
+    let exec = field_value("Exec line", exec)?;
+    if !exec.starts_with('/') {
+        return Err(Error::Failure(format!(
+            "Executable path must be absolute: `{exec}`"
+        )));
+    }
+
+    Ok(exec)
+}
+
+fn write_file(dir: &Path, file_name: &str, content: &str) -> io::Result<PathBuf> {
+    let path = dir.join(file_name);
+    fs::write(&path, content)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_file() {
+        let file = ServiceFile::new("org.zbus.Test", "/usr/libexec/zbus-test")
+            .unwrap()
+            .user("zbus")
+            .unwrap();
+        assert_eq!(file.name(), "org.zbus.Test");
+        assert_eq!(
+            file.to_string(),
+            "[D-BUS Service]\nName=org.zbus.Test\nExec=/usr/libexec/zbus-test\nUser=zbus\n"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = file.write_to(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("org.zbus.Test.service"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), file.to_string());
+
+        assert!(ServiceFile::new("not a name", "/usr/bin/true").is_err());
+        assert!(ServiceFile::new("org.zbus.Test", "zbus-test").is_err());
+        assert!(ServiceFile::new("org.zbus.Test", "/bin/sh\n[Oops]").is_err());
+        assert!(file.clone().user("zbus\nExec=/bin/sh").is_err());
+        assert!(file.systemd_service("zbus\r.service").is_err());
+    }
+
+    #[test]
+    fn systemd_unit() {
+        let unit = SystemdUnit::new("org.zbus.Test", "/usr/libexec/zbus-test --dbus")
+            .unwrap()
+            .unit_name("zbus-test")
+            .unwrap();
+        assert_eq!(unit.file_name(), "zbus-test.service");
+        assert_eq!(
+            unit.to_string(),
+            "[Unit]\n\n[Service]\nType=dbus\nBusName=org.zbus.Test\n\
+             ExecStart=/usr/libexec/zbus-test --dbus\n"
+        );
+
+        assert!(unit.clone().description("Test\n[Install]").is_err());
+        assert!(unit.clone().unit_name("zbus\ttest").is_err());
+        assert!(unit.unit_name("../zbus-test").is_err());
+    }
+}
@@ -57,6 +57,23 @@ pub struct TcpAddress {
 }
 
 impl TcpAddress {
+    /// Create a new `tcp:` address for the given `host` and `port`.
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            bind: None,
+            port,
+            family: None,
+        }
+    }
+
+    /// Set the `tcp:` address `family` value.
+    pub fn set_family(mut self, family: Option<TcpAddressFamily>) -> Self {
+        self.family = family;
+
+        self
+    }
+
     /// Returns the `tcp:` address `host` value.
     pub fn host(&self) -> &str {
         &self.host
@@ -86,8 +103,9 @@ impl TcpAddress {
 
         let host = opts
             .get("host")
-            .ok_or_else(|| Error::Address("tcp address is missing `host`".into()))?
-            .to_string();
+            .ok_or_else(|| Error::Address("tcp address is missing `host`".into()))?;
+        let host = String::from_utf8(decode_percents(host)?)
+            .map_err(|_| Error::Address("tcp `host` is not valid UTF-8".into()))?;
         let port = opts
             .get("port")
             .ok_or_else(|| Error::Address("tcp address is missing `port`".into()))?;
@@ -127,11 +145,110 @@ impl TcpAddress {
     }
 }
 
+/// A `unixexec:` D-Bus address.
+///
+/// The address describes a program to spawn, with its standard input and output connected to a
+/// UNIX domain socket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnixExecAddress {
+    pub(crate) path: OsString,
+    pub(crate) argv0: Option<String>,
+    pub(crate) args: Vec<String>,
+}
+
+impl UnixExecAddress {
+    /// Create a new `unixexec:` address for the program at `path`.
+    pub fn new(path: impl Into<OsString>) -> Self {
+        Self {
+            path: path.into(),
+            argv0: None,
+            args: vec![],
+        }
+    }
+
+    /// Set the `argv0` value, the name the program is spawned with.
+    pub fn set_argv0(mut self, argv0: Option<String>) -> Self {
+        self.argv0 = argv0;
+
+        self
+    }
+
+    /// Set the arguments (`argv1` onwards) passed to the program.
+    pub fn set_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+
+        self
+    }
+
+    /// Returns the `unixexec:` address `path` value.
+    pub fn path(&self) -> &OsString {
+        &self.path
+    }
+
+    /// Returns the `unixexec:` address `argv0` value.
+    pub fn argv0(&self) -> Option<&str> {
+        self.argv0.as_deref()
+    }
+
+    /// Returns the `unixexec:` address `argv1` and following values.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    // Helper for FromStr
+    fn from_unixexec(opts: HashMap<&str, &str>) -> Result<Self> {
+        let path = opts
+            .get("path")
+            .ok_or_else(|| Error::Address("unixexec address is missing `path`".into()))?;
+        let path = decode_os_string(path)?;
+        let decode_arg = |arg: &str| {
+            String::from_utf8(decode_percents(arg)?)
+                .map_err(|_| Error::Address("unixexec argument is not valid UTF-8".into()))
+        };
+        let argv0 = opts.get("argv0").map(|a| decode_arg(a)).transpose()?;
+
+        let mut args = vec![];
+        while let Some(arg) = opts.get(format!("argv{}", args.len() + 1).as_str()) {
+            args.push(decode_arg(arg)?);
+        }
+        let unknown = opts.keys().find(|k| {
+            k.strip_prefix("argv")
+                .and_then(|n| n.parse::<usize>().ok())
+                .map(|n| n > args.len())
+                .unwrap_or(false)
+        });
+        if let Some(k) = unknown {
+            return Err(Error::Address(format!(
+                "unixexec argument `{k}` is not contiguous"
+            )));
+        }
+
+        Ok(Self { path, argv0, args })
+    }
+
+    fn write_options(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("path=")?;
+        encode_os_str(f, &self.path)?;
+
+        if let Some(argv0) = &self.argv0 {
+            f.write_str(",argv0=")?;
+            encode_percents(f, argv0.as_bytes())?;
+        }
+
+        for (i, arg) in self.args.iter().enumerate() {
+            write!(f, ",argv{}=", i + 1)?;
+            encode_percents(f, arg.as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(any(
     all(feature = "vsock", not(feature = "tokio")),
     feature = "tokio-vsock"
 ))]
-/// A `tcp:` D-Bus address.
+/// A `vsock:` D-Bus address.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VsockAddress {
     pub(crate) cid: u32,
@@ -190,6 +307,10 @@ pub enum Address {
     ///
     /// This address is mostly relevant to server (typically bus broker) implementations.
     UnixTmpDir(OsString),
+    /// A program to spawn, communicating with it over its standard input and output.
+    ///
+    /// Connecting to this address is currently unsupported, but it can be parsed and displayed.
+    UnixExec(UnixExecAddress),
 }
 
 #[cfg(not(feature = "tokio"))]
@@ -397,6 +518,8 @@ impl Address {
                 // you can't connect to a unix:dir
                 Err(Error::Unsupported)
             }
+            // Spawning the program isn't supported yet.
+            Address::UnixExec(_) => Err(Error::Unsupported),
         }
     }
 
@@ -458,14 +581,14 @@ impl Address {
         let dir = opts.get("dir");
         let tmpdir = opts.get("tmpdir");
         let addr = match (path, abs, dir, tmpdir) {
-            (Some(p), None, None, None) => Address::Unix(OsString::from(p)),
+            (Some(p), None, None, None) => Address::Unix(decode_os_string(p)?),
             (None, Some(p), None, None) => {
                 let mut s = OsString::from("\0");
-                s.push(p);
+                s.push(decode_os_string(p)?);
                 Address::Unix(s)
             }
-            (None, None, Some(p), None) => Address::UnixDir(OsString::from(p)),
-            (None, None, None, Some(p)) => Address::UnixTmpDir(OsString::from(p)),
+            (None, None, Some(p), None) => Address::UnixDir(decode_os_string(p)?),
+            (None, None, None, Some(p)) => Address::UnixTmpDir(decode_os_string(p)?),
             _ => {
                 return Err(Error::Address("unix: address is invalid".to_owned()));
             }
@@ -553,6 +676,34 @@ fn decode_percents(value: &str) -> Result<Vec<u8>> {
     Ok(decoded)
 }
 
+fn decode_os_string(value: &str) -> Result<OsString> {
+    let decoded = decode_percents(value)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        Ok(OsString::from_vec(decoded))
+    }
+
+    #[cfg(not(unix))]
+    String::from_utf8(decoded)
+        .map(OsString::from)
+        .map_err(|_| Error::Address("path is not valid UTF-8".to_owned()))
+}
+
+fn encode_os_str(f: &mut Formatter<'_>, value: &std::ffi::OsStr) -> std::fmt::Result {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        encode_percents(f, value.as_bytes())
+    }
+
+    #[cfg(not(unix))]
+    encode_percents(f, value.to_str().ok_or(std::fmt::Error)?.as_bytes())
+}
+
 fn encode_percents(f: &mut Formatter<'_>, mut value: &[u8]) -> std::fmt::Result {
     const LOOKUP: &str = "\
 %00%01%02%03%04%05%06%07%08%09%0a%0b%0c%0d%0e%0f\
@@ -683,6 +834,11 @@ impl Display for Address {
             Self::Launchd(env) => {
                 write!(f, "launchd:env={}", env)?;
             }
+
+            Self::UnixExec(addr) => {
+                f.write_str("unixexec:")?;
+                addr.write_options(f)?;
+            }
         }
 
         Ok(())
@@ -743,6 +899,7 @@ impl FromStr for Address {
                     })
                     .transpose()?,
             )),
+            "unixexec" => UnixExecAddress::from_unixexec(options).map(Self::UnixExec),
             "launchd" => Ok(Self::Launchd(
                 options
                     .get("env")
@@ -767,7 +924,7 @@ impl TryFrom<&str> for Address {
 
 #[cfg(test)]
 mod tests {
-    use super::{Address, TcpAddress, TcpAddressFamily};
+    use super::{Address, TcpAddress, TcpAddressFamily, UnixExecAddress};
     use crate::Error;
     use std::str::FromStr;
     use test_log::test;
@@ -905,6 +1062,33 @@ mod tests {
             Address::UnixTmpDir("/some/dir".into()),
             Address::from_str("unix:tmpdir=/some/dir").unwrap()
         );
+        assert_eq!(
+            Address::Unix("/tmp/dbus foo".into()),
+            Address::from_str("unix:path=/tmp/dbus%20foo").unwrap()
+        );
+        assert_eq!(
+            Address::Tcp(
+                TcpAddress::new("localhost", 4142).set_family(Some(TcpAddressFamily::Ipv4))
+            ),
+            Address::from_str("tcp:host=localhost,port=4142,family=ipv4").unwrap()
+        );
+        assert_eq!(
+            Address::UnixExec(
+                UnixExecAddress::new("/usr/bin/ssh")
+                    .set_argv0(Some("ssh".into()))
+                    .set_args(vec!["host".into(), "dbus stdio".into()])
+            ),
+            Address::from_str("unixexec:path=/usr/bin/ssh,argv0=ssh,argv1=host,argv2=dbus%20stdio")
+                .unwrap()
+        );
+        match Address::from_str("unixexec:argv0=foo").unwrap_err() {
+            Error::Address(e) => assert_eq!(e, "unixexec address is missing `path`"),
+            _ => panic!(),
+        }
+        match Address::from_str("unixexec:path=/bin/foo,argv2=bar").unwrap_err() {
+            Error::Address(e) => assert_eq!(e, "unixexec argument `argv2` is not contiguous"),
+            _ => panic!(),
+        }
     }
 
     #[test]
@@ -979,6 +1163,13 @@ mod tests {
             Address::Launchd("my_cool_key".to_owned()).to_string(),
             "launchd:env=my_cool_key"
         );
+        assert_eq!(
+            Address::UnixExec(
+                UnixExecAddress::new("/usr/bin/ssh").set_args(vec!["dbus stdio".into()])
+            )
+            .to_string(),
+            "unixexec:path=/usr/bin/ssh,argv1=dbus%20stdio"
+        );
 
         #[cfg(all(feature = "vsock", not(feature = "tokio")))]
         assert_eq!(
@@ -999,6 +1190,13 @@ mod tests {
         crate::utils::block_on(async { addr.connect().await }).unwrap();
     }
 
+    #[test]
+    fn connect_unixexec() {
+        let addr = Address::from_str("unixexec:path=/usr/bin/ssh").unwrap();
+        let res = crate::utils::block_on(addr.connect());
+        assert!(matches!(res, Err(Error::Unsupported)));
+    }
+
     #[test]
     fn connect_nonce_tcp() {
         struct PercentEncoded<'a>(&'a [u8]);
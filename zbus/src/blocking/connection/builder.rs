@@ -16,7 +16,7 @@ use crate::{
     address::Address,
     blocking::Connection,
     names::{UniqueName, WellKnownName},
-    object_server::Interface,
+    object_server::{DispatchOrder, Interface, Policy},
     utils::block_on,
     AuthMechanism, Error, Guid, Result,
 };
@@ -100,6 +100,13 @@ impl<'a> Builder<'a> {
         Self(self.0.cookie_id(id))
     }
 
+    /// Enable or disable Unix file descriptor passing.
+    ///
+    /// See [`crate::connection::Builder::unix_fd`] for details.
+    pub fn unix_fd(self, enabled: bool) -> Self {
+        Self(self.0.unix_fd(enabled))
+    }
+
     /// The to-be-created connection will be a peer-to-peer connection.
     pub fn p2p(self) -> Self {
         Self(self.0.p2p())
@@ -136,6 +143,20 @@ impl<'a> Builder<'a> {
         Self(self.0.max_queued(max))
     }
 
+    /// Set the maximum number of bytes read from the socket at once.
+    ///
+    /// See [`crate::connection::Builder::read_buffer_size`] for details.
+    pub fn read_buffer_size(self, size: usize) -> Self {
+        Self(self.0.read_buffer_size(size))
+    }
+
+    /// Discard the incoming messages with a body larger than `max` bytes.
+    ///
+    /// See [`crate::connection::Builder::max_body_size`] for details.
+    pub fn max_body_size(self, max: usize) -> Self {
+        Self(self.0.max_body_size(max))
+    }
+
     /// Register a D-Bus [`Interface`] to be served at a given path.
     ///
     /// This is similar to [`zbus::blocking::ObjectServer::at`], except that it allows you to have
@@ -151,6 +172,30 @@ impl<'a> Builder<'a> {
         self.0.serve_at(path, iface).map(Self)
     }
 
+    /// Set the order in which the [`zbus::ObjectServer`] dispatches method calls.
+    ///
+    /// See [`crate::connection::Builder::method_dispatch_order`] for details.
+    pub fn method_dispatch_order(self, order: DispatchOrder) -> Self {
+        Self(self.0.method_dispatch_order(order))
+    }
+
+    /// Restrict the messages exchanged with the peer, through `policy`.
+    ///
+    /// See [`crate::connection::Builder::policy`] for details.
+    pub fn policy(self, policy: Policy) -> Self {
+        Self(self.0.policy(policy))
+    }
+
+    /// Observe the method calls made on the connection and their replies, through `observer`.
+    ///
+    /// See [`crate::connection::CallObserver`] for details.
+    pub fn call_observer<O>(self, observer: O) -> Self
+    where
+        O: crate::connection::CallObserver,
+    {
+        Self(self.0.call_observer(observer))
+    }
+
     /// Register a well-known name for this connection on the bus.
     ///
     /// This is similar to [`zbus::blocking::Connection::request_name`], except the name is
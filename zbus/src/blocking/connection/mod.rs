@@ -5,7 +5,7 @@ use event_listener::EventListener;
 use static_assertions::assert_impl_all;
 use std::{io, ops::Deref};
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
-use zvariant::ObjectPath;
+use zvariant::{ObjectPath, Signature};
 
 use crate::{
     blocking::ObjectServer,
@@ -56,12 +56,21 @@ impl Connection {
         self.inner.server_guid()
     }
 
+    /// The policy restricting the messages exchanged with the peer, if any.
+    pub fn policy(&self) -> Option<&crate::object_server::Policy> {
+        self.inner.policy()
+    }
+
     /// The unique name as assigned by the message bus or `None` if not a message bus connection.
     pub fn unique_name(&self) -> Option<&OwnedUniqueName> {
         self.inner.unique_name()
     }
 
     /// Send `msg` to the peer.
+    ///
+    /// The message is sent as is: no reply is awaited or tracked, even if `msg` is a method call.
+    /// Use a [`crate::blocking::MessageIterator`] to receive the reply (or any other message)
+    /// yourself. See [`crate::Connection::send`] for details.
     pub fn send(&self, msg: &Message) -> Result<()> {
         block_on(self.inner.send(msg))
     }
@@ -102,6 +111,41 @@ impl Connection {
         )
     }
 
+    /// Send a method call, and check the body signature of the reply.
+    ///
+    /// See [`crate::Connection::call_method_checked`] for details.
+    pub fn call_method_checked<'d, 'p, 'i, 'm, 's, D, P, I, M, B, S>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        iface: Option<I>,
+        method_name: M,
+        body: &B,
+        reply_signature: S,
+    ) -> Result<Message>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        S: TryInto<Signature<'s>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        S::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        block_on(self.inner.call_method_checked(
+            destination,
+            path,
+            iface,
+            method_name,
+            body,
+            reply_signature,
+        ))
+    }
+
     /// Emit a signal.
     ///
     /// Create a signal message, and send it over the connection.
@@ -170,6 +214,17 @@ impl Connection {
         block_on(self.inner.reply_dbus_error(call, err))
     }
 
+    /// Wait for the first message matching `predicate`.
+    ///
+    /// Blocking version of [`crate::Connection::receive_specific`]. Since this blocks right away,
+    /// the message to wait for must not be triggered before calling this method.
+    pub fn receive_specific<P>(&self, predicate: P) -> Result<Message>
+    where
+        P: FnMut(&Message) -> bool + Send + 'static,
+    {
+        block_on(self.inner.receive_specific(predicate))
+    }
+
     /// Register a well-known name for this service on the bus.
     ///
     /// Blocking version of [`crate::Connection::request_name`]. See docs there for more details
@@ -257,6 +312,14 @@ impl Connection {
         block_on(self.inner.peer_credentials())
     }
 
+    /// The Unix user ID the peer authenticated as.
+    ///
+    /// See [`crate::Connection::authenticated_uid`] for details.
+    #[cfg(unix)]
+    pub fn authenticated_uid(&self) -> Option<u32> {
+        self.inner.authenticated_uid()
+    }
+
     /// Close the connection.
     ///
     /// After this call, all reading and writing operations will fail.
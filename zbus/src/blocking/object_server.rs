@@ -1,9 +1,10 @@
 //! The object server API.
 
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref};
 
 use static_assertions::assert_impl_all;
-use zvariant::ObjectPath;
+use zbus_names::OwnedInterfaceName;
+use zvariant::{ObjectPath, OwnedObjectPath};
 
 use crate::{
     object_server::{Interface, InterfaceDeref, InterfaceDerefMut, SignalContext},
@@ -174,6 +175,14 @@ impl ObjectServer {
         block_on(self.azync.remove::<I, P>(path))
     }
 
+    /// The paths of all the objects registered with this server, along with the names of the
+    /// interfaces at each path.
+    ///
+    /// See [`crate::ObjectServer::nodes`] for details.
+    pub fn nodes(&self) -> HashMap<OwnedObjectPath, Vec<OwnedInterfaceName>> {
+        block_on(self.azync.nodes())
+    }
+
     /// Get the interface at the given path.
     ///
     /// # Errors
@@ -224,6 +233,13 @@ impl ObjectServer {
         })
     }
 
+    /// Shut the object server, and its connection, down gracefully.
+    ///
+    /// See [`crate::ObjectServer::shutdown`] for details.
+    pub fn shutdown(&self, timeout: std::time::Duration) -> Result<()> {
+        block_on(self.azync.shutdown(timeout))
+    }
+
     /// Get a reference to the underlying async ObjectServer.
     pub fn inner(&self) -> &crate::ObjectServer {
         &self.azync
@@ -52,6 +52,14 @@ impl<'a, T> Builder<'a, T> {
         Self(self.0.uncached_properties(properties))
     }
 
+    /// Retry method calls once the destination service is activated (default: `false`).
+    ///
+    /// See [`crate::proxy::Builder::activation_retry`] for details.
+    #[must_use]
+    pub fn activation_retry(self, retry: bool) -> Self {
+        Self(self.0.activation_retry(retry))
+    }
+
     /// Build a proxy from the builder.
     ///
     /// # Panics
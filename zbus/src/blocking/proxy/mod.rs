@@ -5,7 +5,7 @@ use futures_util::StreamExt;
 use static_assertions::assert_impl_all;
 use std::ops::Deref;
 use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
-use zvariant::{ObjectPath, OwnedValue, Value};
+use zvariant::{ObjectPath, OwnedValue, Signature, Value};
 
 use crate::{
     blocking::Connection, message::Message, proxy::MethodFlags, utils::block_on, Error, Result,
@@ -231,6 +231,51 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call(method_name, body))
     }
 
+    /// Call a method and return the reply, after checking its body signature.
+    ///
+    /// See [`crate::Proxy::call_method_checked`] for details.
+    pub fn call_method_checked<'m, 's, M, B, S>(
+        &self,
+        method_name: M,
+        body: &B,
+        reply_signature: S,
+    ) -> Result<Message>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<Error>,
+    {
+        block_on(
+            self.inner()
+                .call_method_checked(method_name, body, reply_signature),
+        )
+    }
+
+    /// Call a method and return the reply body, after checking its signature.
+    ///
+    /// See [`crate::Proxy::call_checked`] for details.
+    pub fn call_checked<'m, 's, M, B, S, R>(
+        &self,
+        method_name: M,
+        body: &B,
+        reply_signature: S,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<Error>,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        block_on(
+            self.inner()
+                .call_checked(method_name, body, reply_signature),
+        )
+    }
+
     /// Call a method and return the reply body, optionally supplying a set of
     /// method flags to control the way the method call message is sent and handled.
     ///
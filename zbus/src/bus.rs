@@ -0,0 +1,935 @@
+//! A minimal, in-process message bus.
+//!
+//! This module is only available with the `bus` feature enabled.
+use enumflags2::BitFlags;
+use futures_util::StreamExt;
+use static_assertions::assert_impl_all;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc, Mutex, Weak,
+    },
+};
+use tracing::{debug, trace, warn};
+use zbus_names::{BusName, OwnedUniqueName, OwnedWellKnownName, UniqueName, WellKnownName};
+
+use crate::{
+    connection::{self, socket::Socket},
+    fdo::{self, ReleaseNameReply, RequestNameFlags, RequestNameReply},
+    message::{Builder, Flags, Message, Type},
+    Connection, DBusError, Guid, MatchRule, MessageStream, OwnedMatchRule, Result, Task,
+};
+
+const BUS_NAME: &str = "org.freedesktop.DBus";
+const BUS_PATH: &str = "/org/freedesktop/DBus";
+
+type Policy = dyn Fn(&UniqueName<'_>, &Message) -> bool + Send + Sync;
+
+/// A minimal message bus.
+///
+/// `Bus` implements the core of a D-Bus message bus in-process, on top of the peer-to-peer server
+/// support of [`Connection`]:
+///
+/// * assignment of unique names to peers, through the `Hello` method call,
+/// * the registry of well-known names, including the queueing and replacement semantics of
+///   `RequestName` and `ReleaseName`, as well as the `NameOwnerChanged`, `NameAcquired` and
+///   `NameLost` signals,
+/// * routing of unicast messages to their destination, and of broadcast signals according to the
+///   match rules of the peers (`AddMatch` and `RemoveMatch`),
+/// * an optional [policy](Bus::set_policy) deciding which messages are allowed.
+///
+/// It's meant for tests, sandboxes and embedded systems that can't ship `dbus-daemon`. Service
+/// activation, eavesdropping, monitoring and the `org.freedesktop.DBus` properties are not
+/// supported.
+///
+/// Peers are added through [`Bus::add_peer`], which takes an already connected socket (e.g
+/// accepted from a listener). For peers in the same process, [`Bus::connect`] is a shortcut that
+/// returns a client connection to the bus.
+///
+/// # Example
+///
+/// ```
+/// # zbus::block_on(async {
+/// use zbus::{bus::Bus, fdo::DBusProxy};
+///
+/// let bus = Bus::new();
+/// let service = bus.connect().await?;
+/// service.request_name("org.zbus.MyService").await?;
+///
+/// let client = bus.connect().await?;
+/// let dbus = DBusProxy::new(&client).await?;
+/// let owner = dbus.get_name_owner("org.zbus.MyService".try_into()?).await?;
+/// assert_eq!(&owner, service.unique_name().unwrap());
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Bus {
+    inner: Arc<Inner>,
+}
+
+assert_impl_all!(Bus: Send, Sync, Unpin);
+
+struct Inner {
+    guid: Guid,
+    next_id: AtomicUsize,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    peers: HashMap<OwnedUniqueName, Peer>,
+    // The primary owner of each name comes first, followed by the queued ones.
+    names: HashMap<OwnedWellKnownName, VecDeque<(OwnedUniqueName, BitFlags<RequestNameFlags>)>>,
+    policy: Option<Arc<Policy>>,
+}
+
+struct Peer {
+    conn: Connection,
+    registered: bool,
+    rules: Vec<OwnedMatchRule>,
+    _task: Task<()>,
+}
+
+impl Bus {
+    /// Create a new bus, with a newly generated GUID.
+    pub fn new() -> Self {
+        Self::with_guid(Guid::generate())
+    }
+
+    /// Create a new bus, with the given GUID.
+    pub fn with_guid(guid: Guid) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                guid,
+                next_id: AtomicUsize::new(1),
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    /// Set the policy deciding which messages are allowed.
+    ///
+    /// The policy is called for each message sent by a peer (except for the initial `Hello` call),
+    /// including the calls to the bus itself, along with the unique name of the sender. Messages
+    /// for which it returns `false` are dropped. If a rejected message is a method call expecting
+    /// a reply, the sender gets an `org.freedesktop.DBus.Error.AccessDenied` error.
+    ///
+    /// The policy is shared by all the clones of the bus and replaces any previously set one. It
+    /// can be set at any time, and applies to the messages handled from then on.
+    pub fn set_policy<F>(&self, policy: F)
+    where
+        F: Fn(&UniqueName<'_>, &Message) -> bool + Send + Sync + 'static,
+    {
+        self.inner.state().policy = Some(Arc::new(policy));
+    }
+
+    /// The GUID of the bus.
+    pub fn guid(&self) -> &Guid {
+        &self.inner.guid
+    }
+
+    /// Add a peer, connected through `socket`.
+    ///
+    /// This performs the server side of the authentication handshake and then serves the peer
+    /// until it disconnects (or the `Bus` is dropped). The peer becomes visible on the bus once it
+    /// calls `Hello`, which is done automatically by the zbus bus connections.
+    ///
+    /// Returns the unique name reserved for the peer.
+    pub async fn add_peer<S>(&self, socket: S) -> Result<OwnedUniqueName>
+    where
+        S: Socket + 'static,
+    {
+        let (conn, stream) = connection::Builder::socket(socket)
+            .server(&self.inner.guid)
+            .p2p()
+            .build_message_stream()
+            .await?;
+
+        let id = self.inner.next_id.fetch_add(1, SeqCst);
+        let name = OwnedUniqueName::try_from(format!(":1.{id}"))?;
+        // Keep the state locked until the peer is added, so it's known when its first message
+        // gets handled.
+        let mut state = self.inner.state();
+        let task = conn.executor().spawn(
+            serve_peer(Arc::downgrade(&self.inner), name.clone(), stream),
+            &format!("bus peer {name}"),
+        );
+        state.peers.insert(
+            name.clone(),
+            Peer {
+                conn,
+                registered: false,
+                rules: vec![],
+                _task: task,
+            },
+        );
+
+        Ok(name)
+    }
+
+    /// Create a new connection to the bus.
+    #[cfg(any(unix, all(windows, not(feature = "tokio"))))]
+    pub async fn connect(&self) -> Result<Connection> {
+        #[cfg(all(unix, not(feature = "tokio")))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+        #[cfg(all(windows, not(feature = "tokio")))]
+        use uds_windows::UnixStream;
+
+        let (server, client) = UnixStream::pair()?;
+        #[cfg(not(feature = "tokio"))]
+        let server = async_io::Async::new(server)?;
+        let (_, conn) = futures_util::future::try_join(
+            self.add_peer(server),
+            connection::Builder::unix_stream(client).build(),
+        )
+        .await?;
+
+        Ok(conn)
+    }
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Bus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bus")
+            .field("guid", &self.inner.guid)
+            .field("policy", &self.inner.state().policy.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+async fn serve_peer(inner: Weak<Inner>, name: OwnedUniqueName, mut stream: MessageStream) {
+    while let Some(msg) = stream.next().await {
+        let inner = match inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+        let res = match msg {
+            Ok(msg) => inner.handle_message(&name, msg).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = res {
+            warn!("Error handling message from {name}: {e}");
+        }
+    }
+
+    debug!("Peer {name} disconnected");
+    if let Some(inner) = inner.upgrade() {
+        inner.remove_peer(&name).await;
+    }
+}
+
+// A message to send and the connection to send it over.
+type Outgoing = Vec<(Connection, Message)>;
+
+impl Inner {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("lock poisoned")
+    }
+
+    async fn handle_message(&self, sender: &OwnedUniqueName, msg: Message) -> Result<()> {
+        trace!("Received message from {sender}: {msg}");
+        let (registered, policy) = {
+            let state = self.state();
+            match state.peers.get(sender) {
+                Some(peer) => (peer.registered, state.policy.clone()),
+                None => return Ok(()),
+            }
+        };
+
+        // Set the sender, as the bus is the only one who can vouch for it.
+        let msg = Builder::from(msg.header())
+            .sender(sender)?
+            .build_with_body_of(&msg)?;
+
+        let is_hello = msg.message_type() == Type::MethodCall
+            && msg.header().destination().map_or(false, |d| d == BUS_NAME)
+            && msg.header().member().map_or(false, |m| m == "Hello");
+        if !registered && !is_hello {
+            let outgoing = self.error_to(
+                sender,
+                &msg,
+                "org.freedesktop.DBus.Error.AccessDenied",
+                "Client tried to send a message other than Hello without being registered",
+            )?;
+            return self.send(outgoing).await;
+        }
+        if !is_hello {
+            if let Some(policy) = policy {
+                if !policy(sender, &msg) {
+                    debug!("Message from {sender} rejected by the bus policy: {msg}");
+                    let outgoing = self.error_to(
+                        sender,
+                        &msg,
+                        "org.freedesktop.DBus.Error.AccessDenied",
+                        "Rejected by the bus policy",
+                    )?;
+                    return self.send(outgoing).await;
+                }
+            }
+        }
+
+        let outgoing = match msg.header().destination() {
+            // `org.freedesktop.DBus` parses as a unique name, so compare the string itself.
+            Some(destination) if destination.as_str() == BUS_NAME => {
+                self.handle_bus_call(sender, &msg)?
+            }
+            Some(destination) => {
+                let owner = self.state().owner(destination);
+                match owner {
+                    Some(owner) => self.outgoing_to(&owner, msg.clone()),
+                    None => self.error_to(
+                        sender,
+                        &msg,
+                        "org.freedesktop.DBus.Error.ServiceUnknown",
+                        &format!("The name {destination} was not provided by any .service files"),
+                    )?,
+                }
+            }
+            None if msg.message_type() == Type::Signal => self.state().broadcast(&msg),
+            None => {
+                trace!("Dropping message without a destination: {msg}");
+
+                vec![]
+            }
+        };
+
+        self.send(outgoing).await
+    }
+
+    // Handle a call to the bus itself. Returns the messages to send in response.
+    fn handle_bus_call(&self, sender: &OwnedUniqueName, msg: &Message) -> Result<Outgoing> {
+        if msg.message_type() != Type::MethodCall {
+            return Ok(vec![]);
+        }
+        let header = msg.header();
+        let interface = header.interface();
+        let member = match header.member() {
+            Some(member) => member,
+            None => return Ok(vec![]),
+        };
+
+        let mut state = self.state();
+        let mut signals = vec![];
+        let reply = match (interface.map(|i| i.as_str()), member.as_str()) {
+            (Some("org.freedesktop.DBus.Peer"), "Ping") => method_reply(msg, &()),
+            (Some("org.freedesktop.DBus.Peer"), "GetMachineId") => match fdo::machine_id() {
+                Ok(id) => method_reply(msg, &id),
+                Err(e) => error_reply(msg, e.name().as_str(), e.description().unwrap_or("")),
+            },
+            (None | Some(BUS_NAME), member) => match member {
+                "Hello" => match state.peers.get_mut(sender) {
+                    Some(peer) if peer.registered => error_reply(
+                        msg,
+                        "org.freedesktop.DBus.Error.Failed",
+                        "Already handled an Hello message",
+                    ),
+                    Some(peer) => {
+                        peer.registered = true;
+                        signals.push(name_acquired(sender, sender.as_str())?);
+                        signals.push(name_owner_changed(sender.as_str(), "", sender.as_str())?);
+
+                        method_reply(msg, &sender.as_str())
+                    }
+                    None => return Ok(vec![]),
+                },
+                "RequestName" => match msg.body::<(&str, u32)>() {
+                    Ok((name, flags)) => match well_known_name_arg(msg, name) {
+                        Ok(name) => {
+                            let flags = BitFlags::from_bits_truncate(flags);
+                            let (reply, changes) = state.request_name(sender, name, flags)?;
+                            signals.extend(changes);
+
+                            method_reply(msg, &reply)
+                        }
+                        Err(reply) => reply,
+                    },
+                    Err(_) => invalid_args(msg),
+                },
+                "ReleaseName" => match msg.body::<&str>() {
+                    Ok(name) => match well_known_name_arg(msg, name) {
+                        Ok(name) => {
+                            let (reply, changes) = state.release_name(sender, &name)?;
+                            signals.extend(changes);
+
+                            method_reply(msg, &reply)
+                        }
+                        Err(reply) => reply,
+                    },
+                    Err(_) => invalid_args(msg),
+                },
+                "GetNameOwner" => {
+                    let owner = msg
+                        .body::<BusName<'_>>()
+                        .ok()
+                        .and_then(|name| state.owner(&name));
+                    match owner {
+                        Some(owner) => method_reply(msg, &owner),
+                        None => error_reply(
+                            msg,
+                            "org.freedesktop.DBus.Error.NameHasNoOwner",
+                            "Could not get owner of name: no such name",
+                        ),
+                    }
+                }
+                "NameHasOwner" => {
+                    let has_owner = msg
+                        .body::<BusName<'_>>()
+                        .ok()
+                        .and_then(|name| state.owner(&name))
+                        .is_some();
+
+                    method_reply(msg, &has_owner)
+                }
+                "ListNames" => {
+                    let names: Vec<&str> = std::iter::once(BUS_NAME)
+                        .chain(
+                            state
+                                .peers
+                                .iter()
+                                .filter(|(_, peer)| peer.registered)
+                                .map(|(name, _)| name.as_str()),
+                        )
+                        .chain(state.names.keys().map(|name| name.as_str()))
+                        .collect();
+
+                    method_reply(msg, &names)
+                }
+                "ListActivatableNames" => method_reply(msg, &vec![BUS_NAME]),
+                "ListQueuedOwners" => {
+                    match msg.body::<WellKnownName<'_>>().ok().and_then(|name| {
+                        state
+                            .names
+                            .get(name.as_str())
+                            .map(|queue| queue.iter().map(|(owner, _)| owner.as_str()).collect())
+                    }) {
+                        Some(owners) => method_reply::<Vec<&str>>(msg, &owners),
+                        None => error_reply(
+                            msg,
+                            "org.freedesktop.DBus.Error.NameHasNoOwner",
+                            "Could not get owners of name: no such name",
+                        ),
+                    }
+                }
+                "AddMatch" => match msg.body::<&str>().map(MatchRule::try_from) {
+                    Ok(Ok(rule)) => {
+                        if let Some(peer) = state.peers.get_mut(sender) {
+                            peer.rules.push(rule.to_owned().into());
+                        }
+
+                        method_reply(msg, &())
+                    }
+                    _ => error_reply(
+                        msg,
+                        "org.freedesktop.DBus.Error.MatchRuleInvalid",
+                        "Invalid match rule",
+                    ),
+                },
+                "RemoveMatch" => {
+                    let rule = msg
+                        .body::<&str>()
+                        .ok()
+                        .and_then(|rule| MatchRule::try_from(rule).ok());
+                    let removed = match (rule, state.peers.get_mut(sender)) {
+                        (Some(rule), Some(peer)) => {
+                            match peer.rules.iter().position(|r| **r == rule) {
+                                Some(i) => {
+                                    peer.rules.remove(i);
+
+                                    true
+                                }
+                                None => false,
+                            }
+                        }
+                        _ => false,
+                    };
+                    if removed {
+                        method_reply(msg, &())
+                    } else {
+                        error_reply(
+                            msg,
+                            "org.freedesktop.DBus.Error.MatchRuleNotFound",
+                            "The given match rule wasn't found and can't be removed",
+                        )
+                    }
+                }
+                "GetId" => method_reply(msg, &self.guid.as_str()),
+                _ => unknown_method(msg),
+            },
+            _ => unknown_method(msg),
+        }?;
+
+        let mut outgoing = vec![];
+        match state.peers.get(sender) {
+            Some(peer) if expects_reply(msg) => outgoing.push((peer.conn.clone(), reply)),
+            _ => (),
+        }
+        for signal in signals {
+            match signal.header().destination() {
+                Some(destination) => {
+                    if let Some(peer) = state.peers.get(destination.as_str()) {
+                        outgoing.push((peer.conn.clone(), signal.clone()));
+                    }
+                }
+                None => outgoing.extend(state.broadcast(&signal)),
+            }
+        }
+
+        Ok(outgoing)
+    }
+
+    async fn remove_peer(&self, name: &OwnedUniqueName) {
+        let (peer, outgoing) = {
+            let mut state = self.state();
+            let registered = match state.peers.get(name) {
+                Some(peer) => peer.registered,
+                None => return,
+            };
+
+            let owned: Vec<_> = state
+                .names
+                .iter()
+                .filter(|(_, queue)| queue.iter().any(|(owner, _)| owner == name))
+                .map(|(well_known, _)| well_known.clone())
+                .collect();
+            let mut signals = vec![];
+            for well_known in owned {
+                // Can't fail as we only build messages with valid names.
+                if let Ok((_, changes)) = state.release_name(name, &well_known) {
+                    signals.extend(changes);
+                }
+            }
+            // The peer is gone so don't send it anything. Its connection is kept alive until we're
+            // done though, since we're running on its executor.
+            let peer = state.peers.remove(name);
+            if registered {
+                if let Ok(signal) = name_owner_changed(name.as_str(), name.as_str(), "") {
+                    signals.push(signal);
+                }
+            }
+
+            let mut outgoing = vec![];
+            for signal in signals {
+                match signal.header().destination() {
+                    Some(destination) => {
+                        if let Some(peer) = state.peers.get(destination.as_str()) {
+                            outgoing.push((peer.conn.clone(), signal.clone()));
+                        }
+                    }
+                    None => outgoing.extend(state.broadcast(&signal)),
+                }
+            }
+
+            (peer, outgoing)
+        };
+
+        if let Err(e) = self.send(outgoing).await {
+            warn!("Error sending signals about {name} leaving: {e}");
+        }
+        // We're running in the peer's task, so don't cancel it.
+        if let Some(peer) = peer {
+            peer._task.detach();
+        }
+    }
+
+    // The error reply to `msg` for `sender`, unless no reply is expected.
+    fn error_to(
+        &self,
+        sender: &UniqueName<'_>,
+        msg: &Message,
+        name: &str,
+        description: &str,
+    ) -> Result<Outgoing> {
+        if !expects_reply(msg) {
+            return Ok(vec![]);
+        }
+        let reply = error_reply(msg, name, description)?;
+
+        Ok(self.outgoing_to(sender, reply))
+    }
+
+    fn outgoing_to(&self, name: &UniqueName<'_>, msg: Message) -> Outgoing {
+        self.state()
+            .peers
+            .get(name.as_str())
+            .map(|peer| vec![(peer.conn.clone(), msg)])
+            .unwrap_or_default()
+    }
+
+    async fn send(&self, outgoing: Outgoing) -> Result<()> {
+        for (conn, msg) in outgoing {
+            trace!("Sending message: {msg}");
+            if let Err(e) = conn.send(&msg).await {
+                // The peer is probably gone, which will be handled by its own task.
+                debug!("Failed to send message: {e}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl State {
+    // The unique name of the owner of `name`, if any.
+    fn owner(&self, name: &BusName<'_>) -> Option<OwnedUniqueName> {
+        if name.as_str() == BUS_NAME {
+            return Some(UniqueName::from_static_str_unchecked(BUS_NAME).into());
+        }
+
+        match name {
+            BusName::Unique(name) => self
+                .peers
+                .get_key_value(name.as_str())
+                .filter(|(_, peer)| peer.registered)
+                .map(|(name, _)| name.clone()),
+            BusName::WellKnown(name) => self
+                .names
+                .get(name.as_str())
+                .and_then(|queue| queue.front())
+                .map(|(owner, _)| owner.clone()),
+        }
+    }
+
+    // The signal sent to all the peers with a matching rule.
+    fn broadcast(&self, signal: &Message) -> Outgoing {
+        self.peers
+            .values()
+            .filter(|peer| peer.registered)
+            .filter(|peer| {
+                peer.rules
+                    .iter()
+                    .any(|rule| self.rule_matches(rule, signal))
+            })
+            .map(|peer| (peer.conn.clone(), signal.clone()))
+            .collect()
+    }
+
+    fn rule_matches(&self, rule: &MatchRule<'_>, msg: &Message) -> bool {
+        // `MatchRule::matches` can't resolve well-known names, so we do it here.
+        if let Some(sender @ BusName::WellKnown(_)) = rule.sender() {
+            let owner = self.owner(sender);
+            let hdr = msg.header();
+            match (owner, hdr.sender()) {
+                (Some(owner), Some(sender)) if owner == *sender => (),
+                _ => return false,
+            }
+        }
+
+        rule.matches(msg).unwrap_or(false)
+    }
+
+    fn request_name(
+        &mut self,
+        owner: &OwnedUniqueName,
+        name: OwnedWellKnownName,
+        flags: BitFlags<RequestNameFlags>,
+    ) -> Result<(RequestNameReply, Vec<Message>)> {
+        let queue = self.names.entry(name.clone()).or_default();
+        let mut signals = vec![];
+        let reply = match queue.front().cloned() {
+            None => {
+                queue.push_back((owner.clone(), flags));
+                signals.push(name_owner_changed(name.as_str(), "", owner.as_str())?);
+                signals.push(name_acquired(owner, name.as_str())?);
+
+                RequestNameReply::PrimaryOwner
+            }
+            Some((primary, _)) if primary == *owner => {
+                queue[0].1 = flags;
+
+                RequestNameReply::AlreadyOwner
+            }
+            Some((primary, primary_flags))
+                if primary_flags.contains(RequestNameFlags::AllowReplacement)
+                    && flags.contains(RequestNameFlags::ReplaceExisting) =>
+            {
+                queue.retain(|(o, _)| o != owner);
+                queue.pop_front();
+                if !primary_flags.contains(RequestNameFlags::DoNotQueue) {
+                    queue.push_front((primary.clone(), primary_flags));
+                }
+                queue.push_front((owner.clone(), flags));
+                signals.push(name_lost(&primary, name.as_str())?);
+                signals.push(name_owner_changed(
+                    name.as_str(),
+                    primary.as_str(),
+                    owner.as_str(),
+                )?);
+                signals.push(name_acquired(owner, name.as_str())?);
+
+                RequestNameReply::PrimaryOwner
+            }
+            Some(_) if flags.contains(RequestNameFlags::DoNotQueue) => {
+                queue.retain(|(o, _)| o != owner);
+
+                RequestNameReply::Exists
+            }
+            Some(_) => {
+                match queue.iter_mut().find(|(o, _)| o == owner) {
+                    Some(entry) => entry.1 = flags,
+                    None => queue.push_back((owner.clone(), flags)),
+                }
+
+                RequestNameReply::InQueue
+            }
+        };
+
+        Ok((reply, signals))
+    }
+
+    fn release_name(
+        &mut self,
+        owner: &OwnedUniqueName,
+        name: &OwnedWellKnownName,
+    ) -> Result<(ReleaseNameReply, Vec<Message>)> {
+        let queue = match self.names.get_mut(name) {
+            Some(queue) => queue,
+            None => return Ok((ReleaseNameReply::NonExistent, vec![])),
+        };
+        let mut signals = vec![];
+        match queue.iter().position(|(o, _)| o == owner) {
+            None => return Ok((ReleaseNameReply::NotOwner, vec![])),
+            Some(0) => {
+                queue.pop_front();
+                signals.push(name_lost(owner, name.as_str())?);
+                match queue.front() {
+                    Some((new_owner, _)) => {
+                        signals.push(name_owner_changed(
+                            name.as_str(),
+                            owner.as_str(),
+                            new_owner.as_str(),
+                        )?);
+                        signals.push(name_acquired(new_owner, name.as_str())?);
+                    }
+                    None => {
+                        signals.push(name_owner_changed(name.as_str(), owner.as_str(), "")?);
+                        self.names.remove(name);
+                    }
+                }
+            }
+            Some(i) => {
+                queue.remove(i);
+            }
+        }
+
+        Ok((ReleaseNameReply::Released, signals))
+    }
+}
+
+// Validate the well-known name argument `name` of `msg`, or return the error reply.
+fn well_known_name_arg(
+    msg: &Message,
+    name: &str,
+) -> std::result::Result<OwnedWellKnownName, Result<Message>> {
+    match WellKnownName::try_from(name) {
+        Ok(name) if name != BUS_NAME => Ok(name.into()),
+        _ => Err(error_reply(
+            msg,
+            "org.freedesktop.DBus.Error.InvalidArgs",
+            &format!("Cannot acquire or release the name {name}"),
+        )),
+    }
+}
+
+fn invalid_args(call: &Message) -> Result<Message> {
+    error_reply(
+        call,
+        "org.freedesktop.DBus.Error.InvalidArgs",
+        "Invalid arguments",
+    )
+}
+
+fn expects_reply(msg: &Message) -> bool {
+    msg.message_type() == Type::MethodCall
+        && !msg
+            .primary_header()
+            .flags()
+            .contains(Flags::NoReplyExpected)
+}
+
+fn method_reply<B>(call: &Message, body: &B) -> Result<Message>
+where
+    B: serde::ser::Serialize + zvariant::DynamicType,
+{
+    Message::method_reply(call)?.sender(BUS_NAME)?.build(body)
+}
+
+fn error_reply(call: &Message, name: &str, description: &str) -> Result<Message> {
+    Message::method_error(call, name)?
+        .sender(BUS_NAME)?
+        .build(&description)
+}
+
+fn unknown_method(call: &Message) -> Result<Message> {
+    let description = format!(
+        "Unknown method {}",
+        call.header()
+            .member()
+            .map(|m| m.to_string())
+            .unwrap_or_default()
+    );
+
+    error_reply(
+        call,
+        "org.freedesktop.DBus.Error.UnknownMethod",
+        &description,
+    )
+}
+
+fn bus_signal<B>(destination: Option<&UniqueName<'_>>, member: &str, body: &B) -> Result<Message>
+where
+    B: serde::ser::Serialize + zvariant::DynamicType,
+{
+    let mut builder = Message::signal(BUS_PATH, BUS_NAME, member)?.sender(BUS_NAME)?;
+    if let Some(destination) = destination {
+        builder = builder.destination(destination.to_owned())?;
+    }
+
+    builder.build(body)
+}
+
+fn name_owner_changed(name: &str, old_owner: &str, new_owner: &str) -> Result<Message> {
+    bus_signal(None, "NameOwnerChanged", &(name, old_owner, new_owner))
+}
+
+fn name_acquired(owner: &UniqueName<'_>, name: &str) -> Result<Message> {
+    bus_signal(Some(owner), "NameAcquired", &name)
+}
+
+fn name_lost(owner: &UniqueName<'_>, name: &str) -> Result<Message> {
+    bus_signal(Some(owner), "NameLost", &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use ntest::timeout;
+    use test_log::test;
+
+    use super::*;
+    use crate::{
+        fdo::{DBusProxy, PeerProxy},
+        Proxy,
+    };
+
+    #[test]
+    #[timeout(15000)]
+    fn bus() {
+        crate::utils::block_on(test_bus()).unwrap();
+    }
+
+    async fn test_bus() -> Result<()> {
+        struct Echo;
+
+        #[crate::dbus_interface(name = "org.zbus.bus.Echo")]
+        impl Echo {
+            fn echo(&self, s: String) -> String {
+                s
+            }
+        }
+
+        let bus = Bus::new();
+        bus.set_policy(|_, msg| msg.header().member().map_or(true, |m| m != "Forbidden"));
+        let service = bus.connect().await?;
+        service.object_server().at("/", Echo).await?;
+        service.request_name("org.zbus.bus.Service").await?;
+
+        let client = bus.connect().await?;
+        assert_ne!(client.unique_name(), service.unique_name());
+
+        // Method calls routed through well-known and unique names.
+        let proxy = Proxy::new(&client, "org.zbus.bus.Service", "/", "org.zbus.bus.Echo").await?;
+        let reply: String = proxy.call("Echo", &("hello")).await?;
+        assert_eq!(reply, "hello");
+        let proxy = Proxy::new(
+            &client,
+            service.unique_name().unwrap().to_owned(),
+            "/",
+            "org.zbus.bus.Echo",
+        )
+        .await?;
+        let reply: String = proxy.call("Echo", &("world")).await?;
+        assert_eq!(reply, "world");
+
+        // Rejected by the policy.
+        let err = proxy.call::<_, _, ()>("Forbidden", &()).await.unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::MethodError(name, _, _) if *name == "org.freedesktop.DBus.Error.AccessDenied"),
+            "unexpected error: {err}",
+        );
+
+        // Name registry.
+        let dbus = DBusProxy::new(&client).await?;
+        let names = dbus.list_names().await?;
+        assert!(names.iter().any(|n| *n == "org.zbus.bus.Service"));
+        assert!(
+            !dbus
+                .name_has_owner("org.zbus.bus.Nobody".try_into()?)
+                .await?
+        );
+        assert_eq!(
+            dbus.request_name(
+                "org.zbus.bus.Service".try_into()?,
+                RequestNameFlags::DoNotQueue.into()
+            )
+            .await?,
+            RequestNameReply::Exists,
+        );
+
+        // Calls to the bus itself.
+        assert_eq!(
+            dbus.get_name_owner(BUS_NAME.try_into()?).await?.as_str(),
+            BUS_NAME
+        );
+        let peer = PeerProxy::builder(&client)
+            .destination(BUS_NAME)?
+            .path(BUS_PATH)?
+            .build()
+            .await?;
+        assert_eq!(peer.get_machine_id().await?, fdo::machine_id().unwrap());
+
+        // Broadcast signals routed according to the match rules.
+        let mut owner_changed = dbus.receive_name_owner_changed().await?;
+        let mut stream = proxy.receive_signal("Greeting").await?;
+        service
+            .emit_signal(None::<()>, "/", "org.zbus.bus.Echo", "Greeting", &())
+            .await?;
+        let signal = stream.next().await.unwrap();
+        assert_eq!(signal.header().member().unwrap(), "Greeting");
+        assert_eq!(
+            signal.header().sender(),
+            service.unique_name().map(|n| &**n)
+        );
+
+        // Released names are given to the next owner in the queue, if any.
+        service.release_name("org.zbus.bus.Service").await?;
+        let signal = owner_changed.next().await.unwrap();
+        let args = signal.args()?;
+        assert_eq!(*args.name(), "org.zbus.bus.Service");
+        assert!(args.new_owner().is_none());
+
+        // The policy can be replaced once the bus is shared, through any of its clones.
+        bus.clone()
+            .set_policy(|_, msg| msg.header().member().map_or(true, |m| m != "Echo"));
+        let err = proxy
+            .call::<_, _, String>("Echo", &("hello"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::MethodError(name, _, _) if *name == "org.freedesktop.DBus.Error.AccessDenied"),
+            "unexpected error: {err}",
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,512 @@
+//! Forwarding of messages between connections.
+use futures_util::{future, pin_mut, StreamExt};
+use static_assertions::assert_impl_all;
+use std::{
+    collections::HashMap,
+    fmt,
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+};
+use tracing::{debug, trace, warn};
+use zbus_names::OwnedUniqueName;
+
+use crate::{
+    message::{header::next_serial_num, Builder, Flags, Message, Type},
+    Connection, MessageStream, Result,
+};
+
+/// The direction a message is being forwarded in, by a [`MessageBridge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// From the first connection to the second one.
+    AToB,
+    /// From the second connection to the first one.
+    BToA,
+}
+
+type Filter = dyn Fn(Direction, &Message) -> bool + Send + Sync;
+type Rewrite =
+    dyn for<'m> Fn(Direction, &'m Message, Builder<'m>) -> Result<Builder<'m>> + Send + Sync;
+
+// The method calls forwarded in one direction and awaiting a reply: the serial of the forwarded
+// call mapped to the serial and sender of the original call.
+type Pending = Mutex<HashMap<NonZeroU32, ReplyTo>>;
+type ReplyTo = (NonZeroU32, Option<OwnedUniqueName>);
+
+// The maximum number of method calls awaiting a reply, in each direction. Past that, method calls
+// are rejected until some replies come back, so a peer that never replies can't make the bridge
+// grow without bounds.
+const MAX_PENDING_CALLS: usize = 4096;
+
+/// Forwards messages between two connections.
+///
+/// A `MessageBridge` receives all the messages from each of its two connections and sends them
+/// over the other one. This is the building block for D-Bus proxies like [`xdg-dbus-proxy`],
+/// which sit between a (sandboxed) peer and a bus and only let some messages through.
+///
+/// Since both sides have their own notion of serial numbers, each forwarded message gets a new
+/// serial number. The bridge keeps track of the method calls it forwarded so that the method
+/// returns and errors coming back are given the `REPLY_SERIAL` (and `DESTINATION`) of the original
+/// call. Replies that don't correspond to any forwarded method call (e.g the reply to the `Hello`
+/// call zbus makes on bus connections) are dropped.
+///
+/// Two optional hooks allow customizing the forwarding:
+///
+/// * The [filter](MessageBridge::filter) decides which messages get forwarded. Rejected method
+///   calls that expect a reply are answered with an `org.freedesktop.DBus.Error.AccessDenied`
+///   error, on the side they came from. Rejected replies are replaced by such an error, so the
+///   caller doesn't wait for a reply that will never come.
+/// * The [rewrite hook](MessageBridge::rewrite) gets a chance to modify the header of each
+///   forwarded message (e.g to set the sender or destination) before it is sent.
+///
+/// At most 4096 method calls can be awaiting a reply in each direction. Method calls past this
+/// limit are answered with an `org.freedesktop.DBus.Error.LimitsExceeded` error.
+///
+/// Failing to forward a message (e.g because the rewrite hook returned an error) doesn't stop the
+/// bridge: the failure is logged and the bridge carries on with the next message. If the message is
+/// a method call, or the reply to one, its caller is answered with an
+/// `org.freedesktop.DBus.Error.Failed` error instead.
+///
+/// Note that the bridge doesn't do anything about the messages the connections themselves are
+/// interested in. In particular, neither connection should be serving any objects, or their
+/// replies will be mixed with the forwarded ones.
+///
+/// # Example
+///
+/// Letting a peer talk to the session bus, but only to a specific service:
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use zbus::{
+///     connection::{bridge::Direction, Builder, MessageBridge},
+///     Guid,
+/// };
+/// # #[cfg(not(feature = "tokio"))]
+/// use std::os::unix::net::UnixStream;
+/// # #[cfg(feature = "tokio")]
+/// # use tokio::net::UnixStream;
+///
+/// # let (_, stream) = UnixStream::pair()?;
+/// // `stream` is connected to the peer.
+/// let peer = Builder::unix_stream(stream)
+///     .server(&Guid::generate())
+///     .p2p()
+///     .build()
+///     .await?;
+/// let bus = Builder::session()?.build().await?;
+///
+/// MessageBridge::new(&peer, &bus)
+///     .filter(|direction, msg| match direction {
+///         Direction::AToB => msg
+///             .header()
+///             .destination()
+///             .map(|d| d == "org.zbus.MyService")
+///             .unwrap_or(false),
+///         Direction::BToA => true,
+///     })
+///     .run()
+///     .await?;
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`xdg-dbus-proxy`]: https://github.com/flatpak/xdg-dbus-proxy
+#[derive(Clone)]
+pub struct MessageBridge {
+    a: Connection,
+    b: Connection,
+    filter: Option<Arc<Filter>>,
+    rewrite: Option<Arc<Rewrite>>,
+}
+
+assert_impl_all!(MessageBridge: Send, Sync, Unpin);
+
+impl MessageBridge {
+    /// Create a bridge between the connections `a` and `b`.
+    pub fn new(a: &Connection, b: &Connection) -> Self {
+        Self {
+            a: a.clone(),
+            b: b.clone(),
+            filter: None,
+            rewrite: None,
+        }
+    }
+
+    /// Set the filter deciding which messages get forwarded.
+    ///
+    /// The filter is called for each message received, with the direction it would be forwarded
+    /// in. Only the messages for which it returns `true` are forwarded.
+    ///
+    /// Replies are only given to the filter if they correspond to a forwarded method call.
+    #[must_use]
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(Direction, &Message) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+
+        self
+    }
+
+    /// Set the hook to rewrite the header of forwarded messages.
+    ///
+    /// The hook is given the message received and a message [`Builder`] initialized from its
+    /// header, and returns the builder to create the forwarded message from. The serial number is
+    /// set by the bridge afterwards, and so are the reply serial number and destination of replies.
+    /// The body and file descriptors are always forwarded as is.
+    #[must_use]
+    pub fn rewrite<F>(mut self, rewrite: F) -> Self
+    where
+        F: for<'m> Fn(Direction, &'m Message, Builder<'m>) -> Result<Builder<'m>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.rewrite = Some(Arc::new(rewrite));
+
+        self
+    }
+
+    /// Forward messages in both directions, until either of the connections is closed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if receiving a message fails. Failures to forward a message are only logged.
+    pub async fn run(self) -> Result<()> {
+        // Messages forwarded in each direction, awaiting a reply from the other side.
+        let a_to_b_pending = Pending::default();
+        let b_to_a_pending = Pending::default();
+
+        let a_to_b = self.forward(Direction::AToB, &a_to_b_pending, &b_to_a_pending);
+        let b_to_a = self.forward(Direction::BToA, &b_to_a_pending, &a_to_b_pending);
+        pin_mut!(a_to_b);
+        pin_mut!(b_to_a);
+
+        future::select(a_to_b, b_to_a).await.factor_first().0
+    }
+
+    async fn forward(
+        &self,
+        direction: Direction,
+        pending: &Pending,
+        replies: &Pending,
+    ) -> Result<()> {
+        let from = match direction {
+            Direction::AToB => &self.a,
+            Direction::BToA => &self.b,
+        };
+        let mut stream = MessageStream::from(from);
+        while let Some(msg) = stream.next().await {
+            let msg = msg?;
+
+            if let Err(e) = self
+                .forward_message(direction, pending, replies, &msg)
+                .await
+            {
+                warn!("Failed to forward message {:?}: {}: {}", direction, msg, e);
+            }
+        }
+        trace!(
+            "Connection closed, stopping forwarding in {:?} direction",
+            direction
+        );
+
+        Ok(())
+    }
+
+    async fn forward_message(
+        &self,
+        direction: Direction,
+        pending: &Pending,
+        replies: &Pending,
+        msg: &Message,
+    ) -> Result<()> {
+        let (from, to) = match direction {
+            Direction::AToB => (&self.a, &self.b),
+            Direction::BToA => (&self.b, &self.a),
+        };
+
+        // Replies are routed back to the original caller, if we forwarded the call.
+        let reply_to = match msg.message_type() {
+            Type::MethodReturn | Type::Error => {
+                let reply_to = msg
+                    .header()
+                    .reply_serial()
+                    .and_then(|serial| replies.lock().expect("lock poisoned").remove(&serial));
+                match reply_to {
+                    Some(reply_to) => Some(reply_to),
+                    None => {
+                        trace!("Dropping reply to a method call we didn't forward: {}", msg);
+
+                        return Ok(());
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(filter) = &self.filter {
+            if !filter(direction, msg) {
+                debug!("Message rejected by bridge filter: {}", msg);
+                return match reply_to {
+                    Some(reply_to) => {
+                        let reply = reject_reply(
+                            msg,
+                            reply_to,
+                            ACCESS_DENIED,
+                            "Reply rejected by the bridge",
+                        )?;
+
+                        to.send(&reply).await
+                    }
+                    None => {
+                        reject(from, msg, ACCESS_DENIED, "Message rejected by the bridge").await
+                    }
+                };
+            }
+        }
+
+        // Only this direction adds calls to `pending` so there's still room for this one below.
+        if expects_reply(msg) && pending.lock().expect("lock poisoned").len() >= MAX_PENDING_CALLS {
+            debug!("Too many calls awaiting a reply, rejecting: {}", msg);
+
+            return reject(
+                from,
+                msg,
+                LIMITS_EXCEEDED,
+                "Too many calls awaiting a reply",
+            )
+            .await;
+        }
+
+        let res = match self.rewrite_message(direction, msg, reply_to.clone()) {
+            Ok(forwarded) => {
+                let serial = forwarded.primary_header().serial_num();
+                if expects_reply(msg) {
+                    pending.lock().expect("lock poisoned").insert(
+                        serial,
+                        (
+                            msg.primary_header().serial_num(),
+                            msg.header().sender().map(|s| s.to_owned().into()),
+                        ),
+                    );
+                }
+                trace!("Forwarding message {:?}: {}", direction, msg);
+                let res = to.send(&forwarded).await;
+                if res.is_err() {
+                    pending.lock().expect("lock poisoned").remove(&serial);
+                }
+
+                res
+            }
+            Err(e) => Err(e),
+        };
+        if res.is_err() {
+            // Don't leave the caller waiting for a reply that will never come. The original error
+            // is the one worth reporting, so failing to answer is only traced.
+            let description = "Failed to forward the message";
+            let answer = match reply_to {
+                Some(reply_to) => match reject_reply(msg, reply_to, FAILED, description) {
+                    Ok(reply) => to.send(&reply).await,
+                    Err(e) => Err(e),
+                },
+                None => reject(from, msg, FAILED, description).await,
+            };
+            if let Err(e) = answer {
+                trace!("Failed to answer the caller: {}", e);
+            }
+        }
+
+        res
+    }
+
+    fn rewrite_message(
+        &self,
+        direction: Direction,
+        msg: &Message,
+        reply_to: Option<ReplyTo>,
+    ) -> Result<Message> {
+        let mut builder = Builder::from(msg.header());
+        if let Some(rewrite) = &self.rewrite {
+            builder = rewrite(direction, msg, builder)?;
+        }
+        builder = builder.serial_num(next_serial_num());
+        if let Some((serial, caller)) = reply_to {
+            builder = builder.reply_serial(serial);
+            builder = match caller {
+                Some(caller) => builder.destination(caller)?,
+                None => builder.no_destination(),
+            };
+        }
+
+        builder.build_with_body_of(msg)
+    }
+}
+
+impl fmt::Debug for MessageBridge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageBridge")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .field("filter", &self.filter.is_some())
+            .field("rewrite", &self.rewrite.is_some())
+            .finish()
+    }
+}
+
+const ACCESS_DENIED: &str = "org.freedesktop.DBus.Error.AccessDenied";
+const LIMITS_EXCEEDED: &str = "org.freedesktop.DBus.Error.LimitsExceeded";
+const FAILED: &str = "org.freedesktop.DBus.Error.Failed";
+
+fn expects_reply(msg: &Message) -> bool {
+    msg.message_type() == Type::MethodCall
+        && !msg
+            .primary_header()
+            .flags()
+            .contains(Flags::NoReplyExpected)
+}
+
+// Answer a rejected method call with an error, unless no reply is expected.
+async fn reject(conn: &Connection, msg: &Message, name: &str, description: &str) -> Result<()> {
+    if !expects_reply(msg) {
+        return Ok(());
+    }
+
+    let reply = Message::method_error(msg, name)?.build(&description)?;
+
+    conn.send(&reply).await
+}
+
+// The error to send to the caller of a forwarded method call, in place of its reply.
+fn reject_reply(
+    reply: &Message,
+    (serial, caller): ReplyTo,
+    name: &str,
+    description: &str,
+) -> Result<Message> {
+    let builder = Message::method_error(reply, name)?.reply_serial(serial);
+    let builder = match caller {
+        Some(caller) => builder.destination(caller)?,
+        None => builder.no_destination(),
+    };
+
+    builder.build(&description)
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use ntest::timeout;
+    use test_log::test;
+
+    use super::*;
+    use crate::{connection::Builder as ConnectionBuilder, Guid};
+
+    #[test]
+    #[timeout(15000)]
+    fn bridge() {
+        crate::utils::block_on(test_bridge()).unwrap();
+    }
+
+    async fn p2p_pipe() -> Result<(Connection, Connection)> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+
+        futures_util::try_join!(
+            ConnectionBuilder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .build(),
+            ConnectionBuilder::unix_stream(p1).p2p().build(),
+        )
+    }
+
+    async fn test_bridge() -> Result<()> {
+        struct Echo;
+
+        #[crate::dbus_interface(name = "org.zbus.bridge.Echo")]
+        impl Echo {
+            fn echo(&self, s: String) -> String {
+                s
+            }
+
+            fn secret(&self) -> String {
+                "secret".into()
+            }
+        }
+
+        // client <-> (a, b) <-> service
+        let (a, client) = p2p_pipe().await?;
+        let (service, b) = p2p_pipe().await?;
+        service.object_server().at("/", Echo).await?;
+
+        let bridge = MessageBridge::new(&a, &b)
+            .filter(|direction, msg| match direction {
+                Direction::AToB => msg.header().member().map_or(true, |m| m != "Secret"),
+                Direction::BToA => msg.body::<String>().map_or(true, |s| s != "classified"),
+            })
+            .rewrite(
+                |direction, msg, builder| match (direction, msg.message_type()) {
+                    (Direction::AToB, Type::MethodCall)
+                        if msg.header().member().map_or(false, |m| m == "Broken") =>
+                    {
+                        Err(crate::Error::Failure("broken rewrite".into()))
+                    }
+                    (Direction::AToB, Type::MethodCall) => builder.member("Echo"),
+                    _ => Ok(builder),
+                },
+            );
+        let _task = client.executor().spawn(bridge.run(), "bridge");
+
+        let proxy =
+            crate::Proxy::new(&client, "org.zbus.Service", "/", "org.zbus.bridge.Echo").await?;
+
+        // Multiple calls in flight, to check the replies are routed correctly.
+        let (hello, world) = futures_util::try_join!(
+            proxy.call::<_, _, String>("Echo", &("hello")),
+            proxy.call::<_, _, String>("Echo", &("world")),
+        )?;
+        assert_eq!(hello, "hello");
+        assert_eq!(world, "world");
+
+        // Renamed to `Echo` by the rewrite hook.
+        let reply: String = proxy.call("Repeat", &("again")).await?;
+        assert_eq!(reply, "again");
+
+        // Rejected by the filter.
+        let err = proxy.call::<_, _, String>("Secret", &()).await.unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::MethodError(name, _, _) if *name == "org.freedesktop.DBus.Error.AccessDenied"),
+            "unexpected error: {err}",
+        );
+
+        // Reply rejected by the filter: the caller gets an error instead.
+        let err = proxy
+            .call::<_, _, String>("Echo", &("classified"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::MethodError(name, _, _) if *name == "org.freedesktop.DBus.Error.AccessDenied"),
+            "unexpected error: {err}",
+        );
+
+        // Failing to forward a call is reported to the caller and doesn't stop the bridge.
+        let err = proxy
+            .call::<_, _, String>("Broken", &("hello"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::MethodError(name, _, _) if *name == "org.freedesktop.DBus.Error.Failed"),
+            "unexpected error: {err}",
+        );
+        let reply: String = proxy.call("Echo", &("still there")).await?;
+        assert_eq!(reply, "still there");
+
+        Ok(())
+    }
+}
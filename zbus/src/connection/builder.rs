@@ -27,13 +27,15 @@ use crate::{
     address::{self, Address},
     async_lock::RwLock,
     names::{InterfaceName, UniqueName, WellKnownName},
-    object_server::Interface,
-    Connection, Error, Executor, Guid, Result,
+    object_server::{DispatchOrder, Interface, Policy},
+    Connection, Error, Executor, Guid, MessageStream, Result,
 };
 
 use super::{
     handshake::{AuthMechanism, Authenticated},
+    observer::{CallObserver, DynCallObserver},
     socket::{BoxedSplit, ReadHalf, Socket, Split, WriteHalf},
+    socket_reader::ReadConfig,
 };
 
 const DEFAULT_MAX_QUEUED: usize = 64;
@@ -71,6 +73,12 @@ pub struct Builder<'a> {
     unique_name: Option<UniqueName<'a>>,
     cookie_context: Option<super::handshake::CookieContext<'a>>,
     cookie_id: Option<usize>,
+    unix_fd: bool,
+    dispatch_order: DispatchOrder,
+    policy: Option<Policy>,
+    read_config: ReadConfig,
+    #[derivative(Debug = "ignore")]
+    call_observer: Option<Arc<dyn DynCallObserver>>,
 }
 
 assert_impl_all!(Builder<'_>: Send, Sync, Unpin);
@@ -164,6 +172,14 @@ impl<'a> Builder<'a> {
     }
 
     /// Specify the mechanisms to use during authentication.
+    ///
+    /// For client connections, the mechanisms are tried in the given order. By default, `EXTERNAL`,
+    /// `DBUS_COOKIE_SHA1` and `ANONYMOUS` are tried.
+    ///
+    /// For server connections, these are the mechanisms accepted from the clients. By default,
+    /// only `EXTERNAL` is accepted. Pass a list without [`AuthMechanism::External`] if the peers
+    /// can't authenticate with their credentials (e.g [`AuthMechanism::Anonymous`] alone, to accept
+    /// any peer).
     pub fn auth_mechanisms(mut self, auth_mechanisms: &[AuthMechanism]) -> Self {
         self.auth_mechanisms = Some(VecDeque::from(auth_mechanisms.to_vec()));
 
@@ -201,6 +217,19 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Enable or disable Unix file descriptor passing.
+    ///
+    /// When disabled, file descriptor passing isn't negotiated during the authentication
+    /// handshake: client connections don't ask for it and server connections decline it. Messages
+    /// carrying file descriptors then can't be sent over the connection.
+    ///
+    /// Enabled by default, but only effective if the underlying socket supports it.
+    pub fn unix_fd(mut self, enabled: bool) -> Self {
+        self.unix_fd = enabled;
+
+        self
+    }
+
     /// The to-be-created connection will be a peer-to-peer connection.
     pub fn p2p(mut self) -> Self {
         self.p2p = true;
@@ -212,6 +241,10 @@ impl<'a> Builder<'a> {
     ///
     /// The to-be-created connection will wait for incoming client authentication handshake and
     /// negotiation messages, for peer-to-peer communications after successful creation.
+    ///
+    /// The GUID is typically [generated](Guid::generate) but any valid GUID can be used. For
+    /// instance, a proxy standing in for a bus can present the [GUID of the
+    /// bus](Connection::server_guid) to its peers.
     pub fn server(mut self, guid: &'a Guid) -> Self {
         self.guid = Some(guid);
 
@@ -249,6 +282,52 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Set the maximum number of bytes read from the socket at once.
+    ///
+    /// By default, the buffer for an incoming message is allocated at once, as soon as its size is
+    /// known from its header. With a limited read buffer size, the buffer grows as the message is
+    /// received instead, in chunks of at most `size` bytes. This avoids allocating memory for data
+    /// that the peer hasn't sent (yet), at the cost of more reads from the socket.
+    ///
+    /// `size` must not be 0.
+    pub fn read_buffer_size(mut self, size: usize) -> Self {
+        self.read_config.buffer_size = size.max(1);
+
+        self
+    }
+
+    /// Discard the incoming messages with a body larger than `max` bytes.
+    ///
+    /// This is a low-memory mode for constrained environments: the size of the body is checked
+    /// from the header of the message, before its body is received. The body of a discarded
+    /// message is then read and dropped in small chunks, without ever being buffered completely.
+    ///
+    /// Discarded signals are not delivered anywhere and only logged. Discarded method calls are
+    /// replied to with an `org.freedesktop.DBus.Error.Failed` error, and discarded replies are
+    /// replaced by such an error, returned to the caller instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::connection::Builder;
+    ///
+    /// let conn = Builder::session()?
+    ///     .max_queued(8)
+    ///     .read_buffer_size(1024)
+    ///     .max_body_size(16 * 1024)
+    ///     .build()
+    ///     .await?;
+    /// # drop(conn);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn max_body_size(mut self, max: usize) -> Self {
+        self.read_config.max_body_size = Some(max);
+
+        self
+    }
+
     /// Enable or disable the internal executor thread.
     ///
     /// The thread is enabled by default.
@@ -279,6 +358,56 @@ impl<'a> Builder<'a> {
         Ok(self)
     }
 
+    /// Set the order in which the [`zbus::ObjectServer`] dispatches method calls.
+    ///
+    /// By default, method calls are dispatched concurrently, in no particular order. See
+    /// [`DispatchOrder`] for the alternatives.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{connection::Builder, object_server::DispatchOrder};
+    ///
+    /// // Calls to an object are handled one after another, but calls to different objects don't
+    /// // need to wait for each other.
+    /// let conn = Builder::session()?
+    ///     .method_dispatch_order(DispatchOrder::PerObject)
+    ///     .build()
+    ///     .await?;
+    /// # drop(conn);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn method_dispatch_order(mut self, order: DispatchOrder) -> Self {
+        self.dispatch_order = order;
+
+        self
+    }
+
+    /// Restrict the messages exchanged with the peer, through `policy`.
+    ///
+    /// The method calls received from the peer are checked against the policy before being
+    /// dispatched to the [`zbus::ObjectServer`], and the signals sent to the peer before being sent.
+    /// This is mostly useful for peer-to-peer servers. See [`Policy`] for details.
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = Some(policy);
+
+        self
+    }
+
+    /// Observe the method calls made on the connection and their replies, through `observer`.
+    ///
+    /// See [`CallObserver`] for details.
+    pub fn call_observer<O>(mut self, observer: O) -> Self
+    where
+        O: CallObserver,
+    {
+        self.call_observer = Some(Arc::new(observer));
+
+        self
+    }
+
     /// Register a well-known name for this connection on the bus.
     ///
     /// This is similar to [`zbus::Connection::request_name`], except the name is requested as part
@@ -325,11 +454,29 @@ impl<'a> Builder<'a> {
     /// Until server-side bus connection is supported, attempting to build such a connection will
     /// result in [`Error::Unsupported`] error.
     pub async fn build(self) -> Result<Connection> {
+        self.build_with_stream(false).await.map(|(conn, _)| conn)
+    }
+
+    /// Build the connection, along with a stream of all the messages it receives.
+    ///
+    /// Unlike a stream created after the connection is built, this one is guaranteed to receive
+    /// the very first messages sent by the peer. Used by the bus to receive the `Hello` call.
+    #[cfg(feature = "bus")]
+    pub(crate) async fn build_message_stream(self) -> Result<(Connection, MessageStream)> {
+        self.build_with_stream(true)
+            .await
+            .map(|(conn, stream)| (conn, stream.expect("no message stream")))
+    }
+
+    async fn build_with_stream(
+        self,
+        create_stream: bool,
+    ) -> Result<(Connection, Option<MessageStream>)> {
         let executor = Executor::new();
         #[cfg(not(feature = "tokio"))]
         let internal_executor = self.internal_executor;
         // Box the future as it's large and can cause stack overflow.
-        let conn = Box::pin(executor.run(self.build_(executor.clone()))).await?;
+        let conn = Box::pin(executor.run(self.build_(executor.clone(), create_stream))).await?;
 
         #[cfg(not(feature = "tokio"))]
         start_internal_executor(&executor, internal_executor)?;
@@ -337,12 +484,16 @@ impl<'a> Builder<'a> {
         Ok(conn)
     }
 
-    async fn build_(mut self, executor: Executor<'static>) -> Result<Connection> {
+    async fn build_(
+        mut self,
+        executor: Executor<'static>,
+        create_stream: bool,
+    ) -> Result<(Connection, Option<MessageStream>)> {
         let mut stream = self.stream_for_target().await?;
         let mut auth = match self.guid {
             None => {
                 // SASL Handshake
-                Authenticated::client(stream, self.auth_mechanisms).await?
+                Authenticated::client(stream, self.auth_mechanisms, self.unix_fd).await?
             }
             Some(guid) => {
                 if !self.p2p {
@@ -365,6 +516,7 @@ impl<'a> Builder<'a> {
                     self.auth_mechanisms,
                     self.cookie_id,
                     self.cookie_context.unwrap_or_default(),
+                    self.unix_fd,
                 )
                 .await?
             }
@@ -373,7 +525,15 @@ impl<'a> Builder<'a> {
         let socket_read = auth.socket_read.take().unwrap();
         let already_received_bytes = auth.already_received_bytes.take().unwrap();
 
-        let mut conn = Connection::new(auth, !self.p2p, executor).await?;
+        let mut conn = Connection::new(
+            auth,
+            !self.p2p,
+            executor,
+            self.dispatch_order,
+            self.policy,
+            self.call_observer,
+        )
+        .await?;
         conn.set_max_queued(self.max_queued.unwrap_or(DEFAULT_MAX_QUEUED));
         if let Some(unique_name) = self.unique_name {
             conn.set_unique_name(unique_name)?;
@@ -397,8 +557,10 @@ impl<'a> Builder<'a> {
             listener.await;
         }
 
+        let stream = create_stream.then(|| MessageStream::from(&conn));
+
         // Start the socket reader task.
-        conn.init_socket_reader(socket_read, already_received_bytes);
+        conn.init_socket_reader(socket_read, already_received_bytes, self.read_config);
 
         if !self.p2p {
             // Now that the server has approved us, we must send the bus Hello, as per specs
@@ -409,7 +571,7 @@ impl<'a> Builder<'a> {
             conn.request_name(name).await?;
         }
 
-        Ok(conn)
+        Ok((conn, stream))
     }
 
     fn new(target: Target) -> Self {
@@ -425,6 +587,11 @@ impl<'a> Builder<'a> {
             unique_name: None,
             cookie_id: None,
             cookie_context: None,
+            unix_fd: true,
+            dispatch_order: DispatchOrder::default(),
+            policy: None,
+            read_config: ReadConfig::default(),
+            call_observer: None,
         }
     }
 
@@ -56,6 +56,10 @@ pub struct Authenticated {
     /// Whether file descriptor passing has been accepted by both sides
     #[cfg(unix)]
     pub(crate) cap_unix_fd: bool,
+    /// The Unix user ID of the client, if authenticated through the `EXTERNAL` mechanism (server
+    /// side only)
+    #[cfg(unix)]
+    pub(crate) client_uid: Option<u32>,
 
     pub(crate) socket_read: Option<Box<dyn ReadHalf>>,
     pub(crate) already_received_bytes: Option<Vec<u8>>,
@@ -66,8 +70,12 @@ impl Authenticated {
     pub async fn client(
         socket: BoxedSplit,
         mechanisms: Option<VecDeque<AuthMechanism>>,
+        unix_fd: bool,
     ) -> Result<Self> {
-        ClientHandshake::new(socket, mechanisms).perform().await
+        ClientHandshake::new(socket, mechanisms)
+            .negotiate_unix_fd(unix_fd)
+            .perform()
+            .await
     }
 
     /// Create a server-side `Authenticated` for the given `socket`.
@@ -81,6 +89,7 @@ impl Authenticated {
         auth_mechanisms: Option<VecDeque<AuthMechanism>>,
         cookie_id: Option<usize>,
         cookie_context: CookieContext<'_>,
+        unix_fd: bool,
     ) -> Result<Self> {
         ServerHandshake::new(
             socket,
@@ -93,6 +102,7 @@ impl Authenticated {
             cookie_id,
             cookie_context,
         )?
+        .negotiate_unix_fd(unix_fd)
         .perform()
         .await
     }
@@ -176,6 +186,15 @@ impl ClientHandshake {
         }
     }
 
+    /// Whether to negotiate Unix file descriptor passing with the server.
+    ///
+    /// Enabled by default, but only effective if the socket supports it.
+    pub fn negotiate_unix_fd(mut self, enabled: bool) -> Self {
+        self.common.negotiate_unix_fd = enabled;
+
+        self
+    }
+
     fn mechanism_init(&mut self) -> Result<(ClientHandshakeStep, Command)> {
         use ClientHandshakeStep::*;
         let mech = self.common.mechanism()?;
@@ -454,7 +473,9 @@ impl Handshake for ClientHandshake {
                         (WaitingForOK, Command::Ok(guid)) => {
                             trace!("Received OK from server");
                             self.common.server_guid = Some(guid);
-                            if self.common.socket.read_mut().can_pass_unix_fd() {
+                            if self.common.negotiate_unix_fd
+                                && self.common.socket.read_mut().can_pass_unix_fd()
+                            {
                                 (WaitingForAgreeUnixFD, Command::NegotiateUnixFD)
                             } else {
                                 (Done, Command::Begin)
@@ -496,6 +517,8 @@ impl Handshake for ClientHandshake {
                         server_guid: self.common.server_guid.unwrap(),
                         #[cfg(unix)]
                         cap_unix_fd: self.common.cap_unix_fd,
+                        #[cfg(unix)]
+                        client_uid: None,
                         already_received_bytes: Some(self.common.recv_buffer),
                     });
                 }
@@ -545,6 +568,9 @@ pub struct ServerHandshake<'s> {
     client_sid: Option<String>,
     cookie_id: Option<usize>,
     cookie_context: CookieContext<'s>,
+    // The Unix user ID the client authenticated as, through the `EXTERNAL` mechanism.
+    #[cfg(unix)]
+    authenticated_uid: Option<u32>,
 }
 
 impl<'s> ServerHandshake<'s> {
@@ -576,9 +602,20 @@ impl<'s> ServerHandshake<'s> {
             client_sid,
             cookie_id,
             cookie_context,
+            #[cfg(unix)]
+            authenticated_uid: None,
         })
     }
 
+    /// Whether to agree to Unix file descriptor passing, if the client asks for it.
+    ///
+    /// Enabled by default, but only effective if the socket supports it.
+    pub fn negotiate_unix_fd(mut self, enabled: bool) -> Self {
+        self.common.negotiate_unix_fd = enabled;
+
+        self
+    }
+
     async fn auth_ok(&mut self) -> Result<()> {
         let cmd = Command::Ok(self.guid().clone());
         trace!("Sending authentication OK");
@@ -588,24 +625,37 @@ impl<'s> ServerHandshake<'s> {
         Ok(())
     }
 
-    async fn check_external_auth(&mut self, sasl_id: &[u8]) -> Result<()> {
-        let auth_ok = {
-            let id = std::str::from_utf8(sasl_id)
-                .map_err(|e| Error::Handshake(format!("Invalid ID: {e}")))?;
-            #[cfg(unix)]
-            {
-                let uid = id
-                    .parse::<u32>()
-                    .map_err(|e| Error::Handshake(format!("Invalid UID: {e}")))?;
-                self.client_uid.map(|u| u == uid).unwrap_or(false)
+    // Check the `EXTERNAL` authentication of the client. If the client didn't give an ID, it's
+    // asking to be authenticated as whoever the socket credentials say it is, so we only need to
+    // make sure we know them.
+    async fn check_external_auth(&mut self, sasl_id: Option<&[u8]>) -> Result<()> {
+        let auth_ok = match sasl_id {
+            Some(sasl_id) => {
+                let id = std::str::from_utf8(sasl_id)
+                    .map_err(|e| Error::Handshake(format!("Invalid ID: {e}")))?;
+                #[cfg(unix)]
+                {
+                    let uid = id
+                        .parse::<u32>()
+                        .map_err(|e| Error::Handshake(format!("Invalid UID: {e}")))?;
+                    self.client_uid.map(|u| u == uid).unwrap_or(false)
+                }
+                #[cfg(windows)]
+                {
+                    self.client_sid.as_ref().map(|u| u == id).unwrap_or(false)
+                }
             }
+            #[cfg(unix)]
+            None => self.client_uid.is_some(),
             #[cfg(windows)]
-            {
-                self.client_sid.as_ref().map(|u| u == id).unwrap_or(false)
-            }
+            None => self.client_sid.is_some(),
         };
 
         if auth_ok {
+            #[cfg(unix)]
+            {
+                self.authenticated_uid = self.client_uid;
+            }
             self.auth_ok().await
         } else {
             self.rejected_error().await
@@ -674,6 +724,10 @@ impl<'s> ServerHandshake<'s> {
         let cmd = Command::Rejected(mechanisms);
         trace!("Sending authentication error");
         self.common.write_command(cmd).await?;
+        #[cfg(unix)]
+        {
+            self.authenticated_uid = None;
+        }
         self.step = ServerHandshakeStep::WaitingForAuth;
 
         Ok(())
@@ -727,7 +781,7 @@ impl Handshake for ServerHandshake<'_> {
                                     self.auth_ok().await?;
                                 }
                                 (Some(AuthMechanism::External), Some(sasl_id)) => {
-                                    self.check_external_auth(sasl_id).await?;
+                                    self.check_external_auth(Some(sasl_id)).await?;
                                 }
                                 (Some(AuthMechanism::Cookie), Some(sasl_id)) => {
                                     self.check_cookie_auth(sasl_id).await?;
@@ -748,9 +802,8 @@ impl Handshake for ServerHandshake<'_> {
                     trace!("Waiting for authentication");
                     let reply = self.common.read_command().await?;
                     match (mech, reply) {
-                        (AuthMechanism::External, Command::Data(None)) => self.auth_ok().await?,
-                        (AuthMechanism::External, Command::Data(Some(data))) => {
-                            self.check_external_auth(&data).await?;
+                        (AuthMechanism::External, Command::Data(data)) => {
+                            self.check_external_auth(data.as_deref()).await?;
                         }
                         (AuthMechanism::Anonymous, Command::Data(_)) => self.auth_ok().await?,
                         (_, Command::Data(_)) => self.rejected_error().await?,
@@ -772,7 +825,11 @@ impl Handshake for ServerHandshake<'_> {
                         #[cfg(unix)]
                         Command::NegotiateUnixFD => {
                             trace!("Received NEGOTIATE_UNIX_FD command from the client");
-                            if self.common.socket.read().can_pass_unix_fd() {
+                            if !self.common.negotiate_unix_fd {
+                                trace!("FD transmission disabled. Rejecting..");
+                                let cmd = Command::Error("FD-passing disabled".to_string());
+                                self.common.write_command(cmd).await?;
+                            } else if self.common.socket.read().can_pass_unix_fd() {
                                 self.common.cap_unix_fd = true;
                                 trace!("Sending AGREE_UNIX_FD to the client");
                                 self.common.write_command(Command::AgreeUnixFD).await?;
@@ -801,6 +858,8 @@ impl Handshake for ServerHandshake<'_> {
                         server_guid: self.common.server_guid.expect("Server GUID not set"),
                         #[cfg(unix)]
                         cap_unix_fd: self.common.cap_unix_fd,
+                        #[cfg(unix)]
+                        client_uid: self.authenticated_uid,
                         already_received_bytes: Some(self.common.recv_buffer),
                     });
                 }
@@ -933,6 +992,7 @@ pub struct HandshakeCommon {
     recv_buffer: Vec<u8>,
     server_guid: Option<Guid>,
     cap_unix_fd: bool,
+    negotiate_unix_fd: bool,
     // the current AUTH mechanism is front, ordered by priority
     mechanisms: VecDeque<AuthMechanism>,
 }
@@ -949,6 +1009,7 @@ impl HandshakeCommon {
             recv_buffer: Vec::new(),
             server_guid,
             cap_unix_fd: false,
+            negotiate_unix_fd: true,
             mechanisms,
         }
     }
@@ -1085,6 +1146,37 @@ mod tests {
 
         assert_eq!(client.server_guid, server.server_guid);
         assert_eq!(client.cap_unix_fd, server.cap_unix_fd);
+        assert_eq!(client.client_uid, None);
+        assert_eq!(server.client_uid, Some(Uid::effective().into()));
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn handshake_without_unix_fd() {
+        for (client_fd, server_fd) in [(false, true), (true, false)] {
+            let (p0, p1) = create_async_socket_pair();
+
+            let client =
+                ClientHandshake::new(Split::new_boxed(p0), None).negotiate_unix_fd(client_fd);
+            let server = ServerHandshake::new(
+                Split::new_boxed(p1),
+                Guid::generate(),
+                Some(Uid::effective().into()),
+                None,
+                None,
+                CookieContext::default(),
+            )
+            .unwrap()
+            .negotiate_unix_fd(server_fd);
+
+            let (client, server) = crate::utils::block_on(join(
+                async move { client.perform().await.unwrap() },
+                async move { server.perform().await.unwrap() },
+            ));
+
+            assert!(!client.cap_unix_fd);
+            assert!(!server.cap_unix_fd);
+        }
     }
 
     #[test]
@@ -1158,7 +1250,28 @@ mod tests {
         .unwrap();
 
         crate::utils::block_on(p0.write_all(b"\0AUTH EXTERNAL\r\nDATA\r\nBEGIN\r\n")).unwrap();
-        crate::utils::block_on(server.perform()).unwrap();
+        let server = crate::utils::block_on(server.perform()).unwrap();
+
+        assert_eq!(server.client_uid, Some(Uid::effective().into()));
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn missing_external_data_without_credentials() {
+        let (mut p0, p1) = create_async_socket_pair();
+        let server = ServerHandshake::new(
+            Split::new_boxed(p1),
+            Guid::generate(),
+            None,
+            None,
+            None,
+            CookieContext::default(),
+        )
+        .unwrap();
+
+        // Without the peer credentials, there's nothing to authenticate the client against.
+        crate::utils::block_on(p0.write_all(b"\0AUTH EXTERNAL\r\nDATA\r\nBEGIN\r\n")).unwrap();
+        crate::utils::block_on(server.perform()).unwrap_err();
     }
 
     #[test]
@@ -1176,7 +1289,9 @@ mod tests {
         .unwrap();
 
         crate::utils::block_on(p0.write_all(b"\0AUTH ANONYMOUS abcd\r\nBEGIN\r\n")).unwrap();
-        crate::utils::block_on(server.perform()).unwrap();
+        let server = crate::utils::block_on(server.perform()).unwrap();
+
+        assert_eq!(server.client_uid, None);
     }
 
     #[test]
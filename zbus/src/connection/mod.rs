@@ -6,17 +6,24 @@ use once_cell::sync::OnceCell;
 use ordered_stream::{OrderedFuture, OrderedStream, PollResult};
 use static_assertions::assert_impl_all;
 use std::{
-    collections::HashMap,
+    any::Any,
+    collections::{HashMap, VecDeque},
     io::{self, ErrorKind},
     num::NonZeroU32,
     ops::Deref,
     pin::Pin,
-    sync::{Arc, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
     task::{Context, Poll},
 };
 use tracing::{debug, info_span, instrument, trace, trace_span, warn, Instrument};
-use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
-use zvariant::ObjectPath;
+use zbus_names::{
+    BusName, ErrorName, InterfaceName, MemberName, OwnedInterfaceName, OwnedUniqueName,
+    WellKnownName,
+};
+use zvariant::{ObjectPath, OwnedObjectPath, Signature};
 
 use futures_core::Future;
 use futures_util::StreamExt;
@@ -26,11 +33,15 @@ use crate::{
     blocking,
     fdo::{self, ConnectionCredentials, RequestNameFlags, RequestNameReply},
     message::{Flags, Message, Type},
+    object_server::{Access, DispatchOrder, Policy},
     proxy::CacheProperties,
     DBusError, Error, Executor, Guid, MatchRule, MessageStream, ObjectServer, OwnedMatchRule,
     Result, Task,
 };
 
+pub mod bridge;
+pub use bridge::MessageBridge;
+
 mod builder;
 pub use builder::Builder;
 
@@ -38,7 +49,11 @@ pub mod socket;
 pub use socket::Socket;
 
 mod socket_reader;
-use socket_reader::SocketReader;
+use socket_reader::{ReadConfig, SocketReader};
+
+mod observer;
+pub use observer::CallObserver;
+use observer::DynCallObserver;
 
 pub(crate) mod handshake;
 use handshake::Authenticated;
@@ -47,11 +62,14 @@ const DEFAULT_MAX_QUEUED: usize = 64;
 const DEFAULT_MAX_METHOD_RETURN_QUEUED: usize = 8;
 
 /// Inner state shared by Connection and WeakConnection
-#[derive(Debug)]
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
 pub(crate) struct ConnectionInner {
     server_guid: Guid,
     #[cfg(unix)]
     cap_unix_fd: bool,
+    #[cfg(unix)]
+    authenticated_uid: Option<u32>,
     bus_conn: bool,
     unique_name: OnceCell<OwnedUniqueName>,
     registered_names: Mutex<HashMap<WellKnownName<'static>, NameStatus>>,
@@ -67,6 +85,7 @@ pub(crate) struct ConnectionInner {
     socket_reader_task: OnceCell<Task<()>>,
 
     pub(crate) msg_receiver: InactiveReceiver<Result<Message>>,
+    pub(crate) msg_drop_newest: Arc<AtomicBool>,
     pub(crate) method_return_receiver: InactiveReceiver<Result<Message>>,
     msg_senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
 
@@ -74,11 +93,58 @@ pub(crate) struct ConnectionInner {
 
     object_server: OnceCell<blocking::ObjectServer>,
     object_server_dispatch_task: OnceCell<Task<()>>,
+    dispatch_order: DispatchOrder,
+    policy: Option<Policy>,
+    #[derivative(Debug = "ignore")]
+    call_observer: Option<Arc<dyn DynCallObserver>>,
+}
+
+type Subscriptions = HashMap<OwnedMatchRule, Subscription>;
+
+// A match rule subscription: the number of subscribers, the (shared) queue and whether the newest
+// messages are to be dropped when the queue is full.
+type Subscription = (u64, InactiveReceiver<Result<Message>>, Arc<AtomicBool>);
+
+// The method calls waiting for the ones before them to the same object (or interface) to be
+// dispatched, when the dispatch is ordered.
+type DispatchQueues = Arc<std::sync::Mutex<HashMap<DispatchKey, VecDeque<Message>>>>;
+type DispatchKey = (OwnedObjectPath, Option<OwnedInterfaceName>);
+
+/// The sending end of a message queue.
+#[derive(Debug)]
+pub(crate) struct MsgBroadcaster {
+    sender: Broadcaster<Result<Message>>,
+    // When set, messages are dropped if the queue is full, instead of waiting for room.
+    drop_newest: Arc<AtomicBool>,
 }
 
-type Subscriptions = HashMap<OwnedMatchRule, (u64, InactiveReceiver<Result<Message>>)>;
+impl MsgBroadcaster {
+    fn new(sender: Broadcaster<Result<Message>>, drop_newest: Arc<AtomicBool>) -> Self {
+        Self {
+            sender,
+            drop_newest,
+        }
+    }
 
-pub(crate) type MsgBroadcaster = Broadcaster<Result<Message>>;
+    /// Put `msg` on the queue.
+    ///
+    /// Unless the newest messages are to be dropped, this waits for room in the queue if it's
+    /// full (and not in overflow mode).
+    pub(crate) async fn broadcast(&self, msg: Result<Message>) -> std::result::Result<(), String> {
+        if self.drop_newest.load(Ordering::Relaxed) {
+            self.sender
+                .try_broadcast(msg)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        } else {
+            self.sender
+                .broadcast(msg)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    }
+}
 
 /// A D-Bus connection.
 ///
@@ -106,10 +172,20 @@ pub(crate) type MsgBroadcaster = Broadcaster<Result<Message>>;
 /// method. When the queue is full, no more messages can be received until room is created for more.
 /// This is why it's important to ensure that all [`crate::MessageStream`] and
 /// [`crate::blocking::MessageIterator`] instances are continuously polled and iterated on,
-/// respectively.
+/// respectively. Alternatively, streams can be made to drop messages instead, through
+/// [`crate::MessageStream::set_overflow_policy`].
 ///
 /// For sending messages you can either use [`Connection::send`] method.
 ///
+/// ### Low-level API
+///
+/// [`Connection::send`] and [`crate::MessageStream`] (created from a `Connection`) form the
+/// low-level API of the connection. Unlike the higher-level API (e.g [`Connection::call_method`]),
+/// they don't wait for or track replies and the messages don't go through the `ObjectServer`:
+/// messages are sent as is and all incoming messages are received as is. This makes them the
+/// right tool for building message routers, bridges and protocol translators on top of zbus. Note
+/// that the higher-level machinery, if used, still sees all the incoming messages as well.
+///
 /// [method calls]: struct.Connection.html#method.call_method
 /// [signals]: struct.Connection.html#method.emit_signal
 /// [`dbus_proxy`]: attr.dbus_proxy.html
@@ -143,6 +219,23 @@ pub(crate) type MsgBroadcaster = Broadcaster<Result<Message>>;
 /// # }).unwrap();
 /// ```
 ///
+/// #### Forwarding messages between two connections
+///
+/// ```rust,no_run
+/// # zbus::block_on(async {
+/// use futures_util::stream::TryStreamExt;
+/// use zbus::{Connection, MessageStream};
+///
+/// # let (from, to) = (Connection::session().await?, Connection::session().await?);
+/// let mut stream = MessageStream::from(&from);
+/// while let Some(msg) = stream.try_next().await? {
+///     to.send(&msg).await?;
+/// }
+///
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
 /// #### Monitoring all messages
 ///
 /// Let's eavesdrop on the session bus 😈 using the [Monitor] interface:
@@ -199,12 +292,18 @@ assert_impl_all!(Connection: Send, Sync, Unpin);
 /// This is useful for cache population method calls, where joining the [`JoinableStream`] with
 /// an update signal stream can be used to ensure that cache updates are not overwritten by a cache
 /// population whose task is scheduled later.
-#[derive(Debug)]
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
 pub(crate) struct PendingMethodCall {
     stream: Option<MessageStream>,
     serial: NonZeroU32,
+    // The call and the observer's baggage, if the connection has a call observer.
+    #[derivative(Debug = "ignore")]
+    observed: Option<ObservedCall>,
 }
 
+type ObservedCall = (Message, Arc<dyn DynCallObserver>, Box<dyn Any + Send>);
+
 impl Future for PendingMethodCall {
     type Output = Result<Message>;
 
@@ -239,10 +338,15 @@ impl OrderedFuture for PendingMethodCall {
                         if msg.header().reply_serial() != Some(this.serial) {
                             continue;
                         }
+                        if !matches!(msg.message_type(), Type::Error | Type::MethodReturn) {
+                            continue;
+                        }
+                        if let Some((call, observer, baggage)) = this.observed.take() {
+                            observer.reply_received(&call, &msg, baggage);
+                        }
                         let res = match msg.message_type() {
                             Type::Error => Err(msg.into()),
-                            Type::MethodReturn => Ok(msg),
-                            _ => continue,
+                            _ => Ok(msg),
                         };
                         this.stream = None;
                         return Poll::Ready(Some((ordering, res)));
@@ -270,11 +374,32 @@ impl OrderedFuture for PendingMethodCall {
 
 impl Connection {
     /// Send `msg` to the peer.
+    ///
+    /// This is the low-level method that all others use to send messages. The message is sent as
+    /// is: no reply is awaited or tracked, even if `msg` is a method call. Use a
+    /// [`crate::MessageStream`] to receive the reply (or any other message) yourself. See the
+    /// [type-level documentation](Connection#low-level-api) for more details.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Unsupported`] if `msg` carries file descriptors but the connection
+    /// doesn't support passing them.
+    ///
+    /// Signals denied by the [policy](Connection::policy) of the connection are silently dropped.
     pub async fn send(&self, msg: &Message) -> Result<()> {
         #[cfg(unix)]
         if !msg.fds().is_empty() && !self.inner.cap_unix_fd {
             return Err(Error::Unsupported);
         }
+        if msg.message_type() == Type::Signal
+            && self
+                .policy()
+                .map_or(false, |p| p.check(msg) == Access::Deny)
+        {
+            debug!("Signal denied by the connection policy: {}", msg);
+
+            return Ok(());
+        }
         let serial = msg.primary_header().serial_num();
 
         trace!("Sending message: {:?}", msg);
@@ -336,6 +461,43 @@ impl Connection {
         .await
     }
 
+    /// Send a method call, and check the body signature of the reply.
+    ///
+    /// Same as [`Connection::call_method`], except that it returns [`Error::BodySignatureMismatch`]
+    /// if the signature of the reply body isn't `reply_signature`. See
+    /// [`Message::check_body_signature`] for details.
+    pub async fn call_method_checked<'d, 'p, 'i, 'm, 's, D, P, I, M, B, S>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        interface: Option<I>,
+        method_name: M,
+        body: &B,
+        reply_signature: S,
+    ) -> Result<Message>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        S: TryInto<Signature<'s>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        S::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        // Validate the signature before making the call.
+        let reply_signature = reply_signature.try_into().map_err(Into::into)?;
+        let reply = self
+            .call_method(destination, path, interface, method_name, body)
+            .await?;
+        reply.check_body_signature(reply_signature)?;
+
+        Ok(reply)
+    }
+
     /// Send a method call.
     ///
     /// Send the given message, which must be a method call, over the connection and return an
@@ -379,7 +541,7 @@ impl Connection {
         for flag in flags {
             builder = builder.with_flags(flag)?;
         }
-        let msg = builder.build(body)?;
+        let mut msg = builder.clone().build(body)?;
 
         let msg_receiver = self.inner.method_return_receiver.activate_cloned();
         let stream = Some(MessageStream::for_subscription_channel(
@@ -389,11 +551,27 @@ impl Connection {
             self,
         ));
         let serial = msg.primary_header().serial_num();
+        let observed = match &self.inner.call_observer {
+            Some(observer) => {
+                let baggage = observer.call_sent(&msg);
+                if let Some(context) = observer.trace_context(&*baggage) {
+                    // Same serial, as the builder is the same.
+                    msg = builder.trace_context(context.into()).build(body)?;
+                }
+
+                Some((msg.clone(), observer.clone(), baggage))
+            }
+            None => None,
+        };
         self.send(&msg).await?;
         if flags.contains(Flags::NoReplyExpected) {
             Ok(None)
         } else {
-            Ok(Some(PendingMethodCall { stream, serial }))
+            Ok(Some(PendingMethodCall {
+                stream,
+                serial,
+                observed,
+            }))
         }
     }
 
@@ -478,6 +656,61 @@ impl Connection {
         self.send(&m).await
     }
 
+    /// Wait for the first message matching `predicate`.
+    ///
+    /// The messages received on the connection keep being dispatched as usual (to the object
+    /// server, the proxies and the other message streams), this only looks at them. This is
+    /// useful for request/response protocols built on raw messages, e.g waiting for the
+    /// `org.freedesktop.portal.Request.Response` signal after a portal call.
+    ///
+    /// The messages are recorded from the moment this method is called, not when the returned
+    /// future is first polled. Hence the future can (and should) be created before sending the
+    /// message triggering the one to wait for, to not miss it.
+    ///
+    /// # Errors
+    ///
+    /// Any error reading from the socket is returned, including the connection being closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{message::Type, Connection};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let name_acquired = conn.receive_specific(|msg| {
+    ///     msg.message_type() == Type::Signal
+    ///         && msg.header().member().map(|m| m.as_str()) == Some("NameAcquired")
+    /// });
+    /// conn.request_name("org.zbus.ReceiveSpecific").await?;
+    /// let msg = name_acquired.await?;
+    /// println!("{:?}", msg.body::<String>()?);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn receive_specific<P>(
+        &self,
+        mut predicate: P,
+    ) -> impl Future<Output = Result<Message>> + Send + 'static
+    where
+        P: FnMut(&Message) -> bool + Send + 'static,
+    {
+        let mut stream = MessageStream::from(self);
+
+        async move {
+            while let Some(msg) = stream.next().await {
+                let msg = msg?;
+                if predicate(&msg) {
+                    return Ok(msg);
+                }
+            }
+
+            Err(Error::InputOutput(
+                io::Error::new(ErrorKind::BrokenPipe, "socket closed").into(),
+            ))
+        }
+    }
+
     /// Register a well-known name for this connection.
     ///
     /// When connecting to a bus, the name is requested from the bus. In case of p2p connection, the
@@ -770,9 +1003,31 @@ impl Connection {
             .map_err(Into::into)
     }
 
+    // The well-known names registered through `self`.
+    pub(crate) async fn registered_names(&self) -> Vec<WellKnownName<'static>> {
+        self.inner
+            .registered_names
+            .lock()
+            .await
+            .keys()
+            .cloned()
+            .collect()
+    }
+
     /// Checks if `self` is a connection to a message bus.
     ///
     /// This will return `false` for p2p connections.
+    ///
+    /// Operations that only make sense on a bus, behave as follows on p2p connections, so that the
+    /// same code can work with both kinds of connections:
+    ///
+    /// * [`Connection::request_name`] and [`Connection::release_name`] only keep track of the names
+    ///   locally, without asking the peer.
+    /// * Method calls received by the associated `ObjectServer` are handled regardless of the
+    ///   destination they were sent to, since the peer is the only possible recipient.
+    /// * Signal streams created through [`crate::Proxy`] don't filter on the sender and don't
+    ///   track the owner of the destination name.
+    /// * [`crate::Proxy::receive_owner_changed`] fails with [`Error::NotABus`].
     pub fn is_bus(&self) -> bool {
         self.inner.bus_conn
     }
@@ -821,6 +1076,13 @@ impl Connection {
         self.inner.server_guid.as_str()
     }
 
+    /// The policy restricting the messages exchanged with the peer, if any.
+    ///
+    /// Set through [`Builder::policy`].
+    pub fn policy(&self) -> Option<&Policy> {
+        self.inner.policy.as_ref()
+    }
+
     /// The underlying executor.
     ///
     /// When a connection is built with internal_executor set to false, zbus will not spawn a
@@ -942,7 +1204,7 @@ impl Connection {
                             }
                             let rule = builder.build();
                             match conn.add_match(rule.into(), None).await {
-                                Ok(stream) => stream,
+                                Ok((stream, _)) => stream,
                                 Err(e) => {
                                     // Very unlikely but can happen I guess if connection is closed.
                                     debug!("Failed to create message stream: {}", e);
@@ -962,6 +1224,7 @@ impl Connection {
                     }
 
                     trace!("waiting for incoming method call messages..");
+                    let queues = DispatchQueues::default();
                     while let Some(msg) = stream.next().await.and_then(|m| {
                         if let Err(e) = &m {
                             debug!("Error while reading from object server stream: {:?}", e);
@@ -973,44 +1236,65 @@ impl Connection {
                             match hdr.destination() {
                                 // Unique name is already checked by the match rule.
                                 Some(BusName::Unique(_)) | None => (),
+                                // Without a bus to route messages, the peer is the only possible
+                                // recipient, regardless of the destination name it used.
+                                Some(BusName::WellKnown(_)) if !conn.is_bus() => (),
                                 Some(BusName::WellKnown(dest)) => {
                                     let names = conn.inner.registered_names.lock().await;
                                     // destination doesn't matter if no name has been registered
                                     // (probably means name it's registered through external means).
                                     if !names.is_empty() && !names.contains_key(dest) {
-                                        trace!("Got a method call for a different destination: {}", dest);
+                                        trace!(
+                                            "Got a method call for a different destination: {}",
+                                            dest
+                                        );
 
                                         continue;
                                     }
                                 }
                             }
-                            let member = match hdr.member() {
-                                Some(member) => member,
-                                None => {
-                                    warn!("Got a method call with no `MEMBER` field: {}", msg);
+                            if hdr.member().is_none() {
+                                warn!("Got a method call with no `MEMBER` field: {}", msg);
 
-                                    continue;
+                                continue;
+                            }
+                            // Calls we make to ourselves are most likely made from a handler, which
+                            // would then wait forever for them if they were queued behind it.
+                            let reentrant = hdr.sender().is_some()
+                                && hdr.sender() == conn.unique_name().map(|n| n.inner());
+                            let key = match (conn.inner.dispatch_order, hdr.path()) {
+                                (DispatchOrder::Concurrent, _) | (_, None) => None,
+                                _ if reentrant => None,
+                                (DispatchOrder::PerObject, Some(path)) => {
+                                    Some((path.to_owned().into(), None))
                                 }
+                                (DispatchOrder::PerInterface, Some(path)) => Some((
+                                    path.to_owned().into(),
+                                    hdr.interface().map(|i| i.to_owned().into()),
+                                )),
                             };
-                            trace!("Got `{}`. Will spawn a task for dispatch..", msg);
-                            let executor = conn.inner.executor.clone();
-                            let task_name = format!("`{member}` method dispatcher");
-                            executor
-                                .spawn(
-                                    async move {
-                                        trace!("spawned a task to dispatch `{}`.", msg);
-                                        let server = conn.object_server();
-                                        if let Err(e) = server.dispatch_message(&msg).await {
-                                            debug!(
-                                                "Error dispatching message. Message: {:?}, error: {:?}",
-                                                msg, e
-                                            );
+                            let queue = match key {
+                                Some(key) => {
+                                    let mut pending = queues.lock().expect("lock poisoned");
+                                    match pending.get_mut(&key) {
+                                        Some(queue) => {
+                                            // A task is already dispatching calls for the same
+                                            // key, it will get to this one.
+                                            trace!("Queued `{}` for dispatch", msg);
+                                            queue.push_back(msg);
+
+                                            continue;
+                                        }
+                                        None => {
+                                            pending.insert(key.clone(), VecDeque::new());
+
+                                            Some((queues.clone(), key))
                                         }
                                     }
-                                    .instrument(trace_span!("{}", task_name)),
-                                    &task_name,
-                                )
-                                .detach();
+                                }
+                                None => None,
+                            };
+                            conn.spawn_dispatch(msg, queue);
                         } else {
                             // If connection is completely gone, no reason to keep running the task anymore.
                             trace!("Connection is gone, stopping associated object server task");
@@ -1024,11 +1308,57 @@ impl Connection {
         });
     }
 
+    // Spawn a task to dispatch `msg`. If `queue` is given, the task then goes on to dispatch the
+    // calls queued behind `msg`, until there are none left.
+    fn spawn_dispatch(&self, msg: Message, queue: Option<(DispatchQueues, DispatchKey)>) {
+        let conn = self.clone();
+        let task_name = match msg.header().member() {
+            Some(member) => format!("`{member}` method dispatcher"),
+            None => "method dispatcher".to_string(),
+        };
+        self.inner
+            .executor
+            .spawn(
+                async move {
+                    let server = conn.object_server();
+                    let mut msg = msg;
+                    loop {
+                        trace!("spawned a task to dispatch `{}`.", msg);
+                        if let Err(e) = server.dispatch_message(&msg).await {
+                            debug!(
+                                "Error dispatching message. Message: {:?}, error: {:?}",
+                                msg, e
+                            );
+                        }
+
+                        let (queues, key) = match &queue {
+                            Some(queue) => queue,
+                            None => break,
+                        };
+                        let mut pending = queues.lock().expect("lock poisoned");
+                        msg = match pending.get_mut(key).and_then(VecDeque::pop_front) {
+                            Some(next) => next,
+                            None => {
+                                pending.remove(key);
+
+                                break;
+                            }
+                        };
+                    }
+                }
+                .instrument(trace_span!("{}", task_name)),
+                &task_name,
+            )
+            .detach();
+    }
+
+    // Returns the receiving end of the queue, along with the flag telling if the newest messages
+    // are to be dropped when it's full.
     pub(crate) async fn add_match(
         &self,
         rule: OwnedMatchRule,
         max_queued: Option<usize>,
-    ) -> Result<Receiver<Result<Message>>> {
+    ) -> Result<(Receiver<Result<Message>>, Arc<AtomicBool>)> {
         use std::collections::hash_map::Entry;
 
         if self.inner.msg_senders.lock().await.is_empty() {
@@ -1054,17 +1384,18 @@ impl Connection {
                         .add_match_rule(e.key().inner().clone())
                         .await?;
                 }
-                e.insert((1, receiver.clone().deactivate()));
+                let drop_newest = Arc::new(AtomicBool::new(false));
+                e.insert((1, receiver.clone().deactivate(), drop_newest.clone()));
                 self.inner
                     .msg_senders
                     .lock()
                     .await
-                    .insert(Some(rule), sender);
+                    .insert(Some(rule), MsgBroadcaster::new(sender, drop_newest.clone()));
 
-                Ok(receiver)
+                Ok((receiver, drop_newest))
             }
             Entry::Occupied(mut e) => {
-                let (num_subscriptions, receiver) = e.get_mut();
+                let (num_subscriptions, receiver, drop_newest) = e.get_mut();
                 *num_subscriptions += 1;
                 if let Some(max_queued) = max_queued {
                     if max_queued > receiver.capacity() {
@@ -1072,7 +1403,7 @@ impl Connection {
                     }
                 }
 
-                Ok(receiver.activate_cloned())
+                Ok((receiver.activate_cloned(), drop_newest.clone()))
             }
         }
     }
@@ -1137,9 +1468,14 @@ impl Connection {
         auth: Authenticated,
         bus_connection: bool,
         executor: Executor<'static>,
+        dispatch_order: DispatchOrder,
+        policy: Option<Policy>,
+        call_observer: Option<Arc<dyn DynCallObserver>>,
     ) -> Result<Self> {
         #[cfg(unix)]
         let cap_unix_fd = auth.cap_unix_fd;
+        #[cfg(unix)]
+        let authenticated_uid = auth.client_uid;
 
         macro_rules! create_msg_broadcast_channel {
             ($size:expr) => {{
@@ -1152,8 +1488,12 @@ impl Connection {
         }
         // The unfiltered message channel.
         let (msg_sender, msg_receiver) = create_msg_broadcast_channel!(DEFAULT_MAX_QUEUED);
+        let msg_drop_newest = Arc::new(AtomicBool::new(false));
         let mut msg_senders = HashMap::new();
-        msg_senders.insert(None, msg_sender);
+        msg_senders.insert(
+            None,
+            MsgBroadcaster::new(msg_sender, msg_drop_newest.clone()),
+        );
 
         // The special method return & error channel.
         let (method_return_sender, method_return_receiver) =
@@ -1162,9 +1502,15 @@ impl Connection {
             .msg_type(Type::MethodReturn)
             .build()
             .into();
-        msg_senders.insert(Some(rule), method_return_sender.clone());
+        msg_senders.insert(
+            Some(rule),
+            MsgBroadcaster::new(method_return_sender.clone(), Default::default()),
+        );
         let rule = MatchRule::builder().msg_type(Type::Error).build().into();
-        msg_senders.insert(Some(rule), method_return_sender);
+        msg_senders.insert(
+            Some(rule),
+            MsgBroadcaster::new(method_return_sender, Default::default()),
+        );
         let msg_senders = Arc::new(Mutex::new(msg_senders));
         let subscriptions = Mutex::new(HashMap::new());
 
@@ -1175,15 +1521,21 @@ impl Connection {
                 server_guid: auth.server_guid,
                 #[cfg(unix)]
                 cap_unix_fd,
+                #[cfg(unix)]
+                authenticated_uid,
                 bus_conn: bus_connection,
                 unique_name: OnceCell::new(),
                 subscriptions,
                 object_server: OnceCell::new(),
                 object_server_dispatch_task: OnceCell::new(),
+                dispatch_order,
+                policy,
+                call_observer,
                 executor,
                 socket_reader_task: OnceCell::new(),
                 msg_senders,
                 msg_receiver,
+                msg_drop_newest,
                 method_return_receiver,
                 registered_names: Mutex::new(HashMap::new()),
             }),
@@ -1202,6 +1554,11 @@ impl Connection {
         Builder::system()?.build().await
     }
 
+    /// Create a [`WeakConnection`] referring to this connection.
+    pub fn downgrade(&self) -> WeakConnection {
+        WeakConnection::from(self)
+    }
+
     /// Returns a listener, notified on various connection activity.
     ///
     /// This function is meant for the caller to implement idle or timeout on inactivity.
@@ -1226,6 +1583,17 @@ impl Connection {
             .await
     }
 
+    /// The Unix user ID the peer authenticated as.
+    ///
+    /// This is only set on the server side of a peer-to-peer connection, when the client
+    /// authenticated through the `EXTERNAL` mechanism. Unlike [`Connection::peer_credentials`], this
+    /// is the user ID verified during the handshake, against the credentials of the socket, so it
+    /// can be relied upon for access control decisions.
+    #[cfg(unix)]
+    pub fn authenticated_uid(&self) -> Option<u32> {
+        self.inner.authenticated_uid
+    }
+
     /// Close the connection.
     ///
     /// After this call, all reading and writing operations will fail.
@@ -1244,6 +1612,7 @@ impl Connection {
         &self,
         socket_read: Box<dyn socket::ReadHalf>,
         already_read: Vec<u8>,
+        read_config: ReadConfig,
     ) {
         let inner = &self.inner;
         inner
@@ -1254,6 +1623,8 @@ impl Connection {
                     inner.msg_senders.clone(),
                     already_read,
                     inner.activity_event.clone(),
+                    read_config,
+                    self.into(),
                 )
                 .spawn(&inner.executor),
             )
@@ -1267,14 +1638,57 @@ impl From<crate::blocking::Connection> for Connection {
     }
 }
 
-// Internal API that allows keeping a weak connection ref around.
-#[derive(Debug)]
-pub(crate) struct WeakConnection {
+/// A weak reference to a [`Connection`].
+///
+/// Unlike a [`Connection`], this doesn't keep the connection alive. This is useful for long-lived
+/// objects referring to the connection they're used on, without creating reference cycles (e.g an
+/// interface registered on the [`ObjectServer`] of the connection, or a callback stored in it).
+///
+/// Created through [`Connection::downgrade`].
+///
+/// # Example
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use zbus::{connection::WeakConnection, dbus_interface, Connection};
+///
+/// struct Notifier {
+///     conn: WeakConnection,
+/// }
+///
+/// #[dbus_interface(name = "org.zbus.Notifier")]
+/// impl Notifier {
+///     async fn notify(&self) -> zbus::fdo::Result<()> {
+///         let conn = self
+///             .conn
+///             .upgrade()
+///             .ok_or_else(|| zbus::fdo::Error::Failed("connection closed".into()))?;
+///         conn.emit_signal(None::<()>, "/org/zbus/Notifier", "org.zbus.Notifier", "Ping", &())
+///             .await?;
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let conn = Connection::session().await?;
+/// let notifier = Notifier {
+///     conn: conn.downgrade(),
+/// };
+/// conn.object_server().at("/org/zbus/Notifier", notifier).await?;
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeakConnection {
     inner: Weak<ConnectionInner>,
 }
 
+assert_impl_all!(WeakConnection: Send, Sync, Unpin);
+
 impl WeakConnection {
-    /// Upgrade to a Connection.
+    /// Upgrade to a [`Connection`].
+    ///
+    /// Returns `None` if all the `Connection` instances have been dropped.
     pub fn upgrade(&self) -> Option<Connection> {
         self.inner.upgrade().map(|inner| Connection { inner })
     }
@@ -1302,7 +1716,7 @@ mod tests {
     use ntest::timeout;
     use test_log::test;
 
-    use crate::{fdo::DBusProxy, AuthMechanism};
+    use crate::{fdo::DBusProxy, AuthMechanism, OverflowPolicy};
 
     use super::*;
 
@@ -1447,6 +1861,27 @@ mod tests {
         test_p2p(server1, client1, server2, client2).await
     }
 
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn unix_p2p_authenticated_uid() {
+        crate::utils::block_on(test_unix_p2p_authenticated_uid()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_unix_p2p_authenticated_uid() -> Result<()> {
+        // `unix_p2p_pipe` returns the client first.
+        let (client, server) = unix_p2p_pipe().await?;
+
+        assert_eq!(
+            server.authenticated_uid(),
+            Some(nix::unistd::Uid::effective().into())
+        );
+        assert_eq!(client.authenticated_uid(), None);
+
+        Ok(())
+    }
+
     #[cfg(unix)]
     async fn unix_p2p_pipe() -> Result<(Connection, Connection)> {
         #[cfg(not(feature = "tokio"))]
@@ -1466,6 +1901,584 @@ mod tests {
         )
     }
 
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn p2p_bus_only_operations() {
+        crate::utils::block_on(test_p2p_bus_only_operations()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_p2p_bus_only_operations() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Echo;
+
+        #[crate::dbus_interface(name = "org.zbus.p2p.Echo")]
+        impl Echo {
+            fn echo(&self, s: String) -> String {
+                s
+            }
+        }
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .serve_at("/", Echo)?
+                .build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+        assert!(!server.is_bus());
+        assert!(!client.is_bus());
+
+        // Names are only tracked locally.
+        server.request_name("org.zbus.p2p.Server").await?;
+
+        // Method calls are handled regardless of the destination.
+        let proxy =
+            crate::Proxy::new(&client, "org.zbus.p2p.Unknown", "/", "org.zbus.p2p.Echo").await?;
+        let reply: String = proxy.call("Echo", &("hello")).await?;
+        assert_eq!(reply, "hello");
+
+        // Signals are received regardless of the sender.
+        let mut stream = proxy.receive_signal("Greeting").await?;
+        server
+            .emit_signal(None::<()>, "/", "org.zbus.p2p.Echo", "Greeting", &())
+            .await?;
+        assert_eq!(stream.next().await.unwrap().to_string(), "Signal Greeting");
+
+        assert!(matches!(
+            proxy.receive_owner_changed().await,
+            Err(Error::NotABus)
+        ));
+        assert!(server.release_name("org.zbus.p2p.Server").await?);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn ordered_method_dispatch() {
+        crate::utils::block_on(test_ordered_method_dispatch()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_ordered_method_dispatch() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        // A gate that held calls wait on, until a call that isn't held opens it.
+        #[derive(Default)]
+        struct Gate {
+            open: std::sync::atomic::AtomicBool,
+            event: Event,
+        }
+
+        struct Recorder(Arc<std::sync::Mutex<Vec<u32>>>, Arc<Gate>);
+
+        #[crate::dbus_interface(name = "org.zbus.Recorder")]
+        impl Recorder {
+            async fn record(&self, n: u32, hold: bool) {
+                use std::sync::atomic::Ordering;
+
+                let gate = &self.1;
+                if hold {
+                    while !gate.open.load(Ordering::SeqCst) {
+                        let listener = gate.event.listen();
+                        if gate.open.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        listener.await;
+                    }
+                }
+
+                self.0.lock().unwrap().push(n);
+
+                if !hold {
+                    gate.open.store(true, Ordering::SeqCst);
+                    gate.event.notify(usize::MAX);
+                }
+            }
+        }
+
+        let log = Arc::new(std::sync::Mutex::new(vec![]));
+        let gate = Arc::new(Gate::default());
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (_server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .method_dispatch_order(DispatchOrder::PerObject)
+                .serve_at("/a", Recorder(log.clone(), gate.clone()))?
+                .serve_at("/b", Recorder(log.clone(), gate.clone()))?
+                .build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        let mut replies = MessageStream::from(&client);
+        // The first call is held until another one completes. Since the second call has to wait
+        // for the first one, only the third call can release it.
+        for (path, n, hold) in [("/a", 1u32, true), ("/a", 2, false), ("/b", 3, false)] {
+            let msg = Message::method(path, "Record")?
+                .interface("org.zbus.Recorder")?
+                .build(&(n, hold))?;
+            client.send(&msg).await?;
+        }
+        for _ in 0..3 {
+            let reply = replies.try_next().await?.unwrap();
+            assert_eq!(reply.message_type(), Type::MethodReturn);
+        }
+
+        // The held call to `/a` held back the next one to the same object, but not the one to `/b`.
+        assert_eq!(*log.lock().unwrap(), vec![3, 1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn reentrant_ordered_method_dispatch() {
+        crate::utils::block_on(test_reentrant_ordered_method_dispatch()).unwrap();
+    }
+
+    async fn test_reentrant_ordered_method_dispatch() -> Result<()> {
+        struct Reentrant;
+
+        #[crate::dbus_interface(name = "org.zbus.Reentrant")]
+        impl Reentrant {
+            async fn outer(&self, #[zbus(connection)] conn: &Connection) -> fdo::Result<u32> {
+                let reply = conn
+                    .call_method(
+                        conn.unique_name().map(|n| n.as_ref()),
+                        "/reentrant",
+                        Some("org.zbus.Reentrant"),
+                        "Inner",
+                        &(),
+                    )
+                    .await?;
+
+                reply.body().map_err(Into::into)
+            }
+
+            fn inner(&self) -> u32 {
+                42
+            }
+        }
+
+        // A handler calling its own object must not wait for itself.
+        let conn = Builder::session()?
+            .method_dispatch_order(DispatchOrder::PerObject)
+            .serve_at("/reentrant", Reentrant)?
+            .build()
+            .await?;
+        let reply = conn
+            .call_method(
+                conn.unique_name().map(|n| n.as_ref()),
+                "/reentrant",
+                Some("org.zbus.Reentrant"),
+                "Outer",
+                &(),
+            )
+            .await?;
+        assert_eq!(reply.body::<u32>()?, 42);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn policy() {
+        crate::utils::block_on(test_policy()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_policy() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Vault;
+
+        #[crate::dbus_interface(name = "org.zbus.Vault")]
+        impl Vault {
+            fn open(&self) -> u32 {
+                1
+            }
+
+            fn secret(&self) -> u32 {
+                42
+            }
+        }
+
+        struct Safe;
+
+        #[crate::dbus_interface(name = "org.zbus.Safe")]
+        impl Safe {
+            #[dbus_interface(property)]
+            fn code(&self) -> u32 {
+                1234
+            }
+        }
+
+        let policy = Policy::allow_all()
+            .deny(
+                MatchRule::builder()
+                    .msg_type(Type::MethodCall)
+                    .member("Secret")?
+                    .build(),
+            )
+            .deny(
+                MatchRule::builder()
+                    .msg_type(Type::MethodCall)
+                    .interface("org.zbus.Safe")?
+                    .build(),
+            )
+            .deny(
+                MatchRule::builder()
+                    .msg_type(Type::Signal)
+                    .member("Hidden")?
+                    .build(),
+            );
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .policy(policy)
+                .serve_at("/", Vault)?
+                .serve_at("/", Safe)?
+                .build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+        assert!(server.policy().is_some());
+        assert!(client.policy().is_none());
+
+        let call =
+            |member| client.call_method(None::<()>, "/", Some("org.zbus.Vault"), member, &());
+        let reply = call("Open").await?;
+        assert_eq!(reply.body::<u32>()?, 1);
+        match call("Secret").await {
+            Err(Error::MethodError(name, _, _)) => {
+                assert_eq!(name.as_str(), "org.freedesktop.DBus.Error.AccessDenied");
+            }
+            r => panic!("unexpected reply: {r:?}"),
+        }
+
+        // The properties of a denied interface are denied too, through `Properties`.
+        let props = fdo::PropertiesProxy::builder(&client)
+            .path("/")?
+            .build()
+            .await?;
+        let vault = InterfaceName::from_static_str("org.zbus.Vault")?;
+        assert!(props.get_all(Some(vault).into()).await?.is_empty());
+        let safe = InterfaceName::from_static_str("org.zbus.Safe")?;
+        for res in [
+            props.get(safe.clone(), "Code").await.map(drop),
+            props.get_all(Some(safe).into()).await.map(drop),
+        ] {
+            match res {
+                Err(fdo::Error::AccessDenied(_)) => (),
+                r => panic!("unexpected reply: {r:?}"),
+            }
+        }
+
+        let rule = MatchRule::builder().msg_type(Type::Signal).build();
+        let mut signals = MessageStream::for_match_rule(rule, &client, None).await?;
+        for member in ["Hidden", "Shown"] {
+            server
+                .emit_signal(None::<()>, "/", "org.zbus.Vault", member, &())
+                .await?;
+        }
+        let signal = signals.try_next().await?.unwrap();
+        assert_eq!(signal.header().member().unwrap(), "Shown");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn call_observer() {
+        crate::utils::block_on(test_call_observer()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_call_observer() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        type Log = Arc<std::sync::Mutex<Vec<(NonZeroU32, String, Option<NonZeroU32>)>>>;
+
+        // Records the serial and member of each call, then the reply serial of its reply. The index
+        // of the call in the log is propagated as the trace context.
+        struct Recorder(Log);
+
+        impl CallObserver for Recorder {
+            type Baggage = usize;
+
+            fn call_sent(&self, call: &Message) -> usize {
+                let mut log = self.0.lock().unwrap();
+                let member = call.header().member().unwrap().to_string();
+                log.push((call.primary_header().serial_num(), member, None));
+
+                log.len() - 1
+            }
+
+            fn trace_context(&self, i: &usize) -> Option<String> {
+                Some(format!("call-{i}"))
+            }
+
+            fn reply_received(&self, _call: &Message, reply: &Message, i: usize) {
+                self.0.lock().unwrap()[i].2 = reply.header().reply_serial();
+            }
+        }
+
+        struct Echo;
+
+        #[crate::dbus_interface(name = "org.zbus.Echo")]
+        impl Echo {
+            fn echo(&self, s: String) -> String {
+                s
+            }
+
+            fn fail(&self) -> crate::fdo::Result<()> {
+                Err(crate::fdo::Error::Failed("nope".into()))
+            }
+
+            fn trace_context(&self, #[zbus(header)] header: crate::message::Header<'_>) -> String {
+                header.trace_context().unwrap().to_string()
+            }
+        }
+
+        let log = Log::default();
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (_server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .serve_at("/", Echo)?
+                .build(),
+            Builder::unix_stream(p1)
+                .p2p()
+                .call_observer(Recorder(log.clone()))
+                .build(),
+        )?;
+
+        let reply = client
+            .call_method(None::<()>, "/", Some("org.zbus.Echo"), "Echo", &"hi")
+            .await?;
+        assert_eq!(reply.body::<String>()?, "hi");
+        client
+            .call_method(None::<()>, "/", Some("org.zbus.Echo"), "Fail", &())
+            .await
+            .unwrap_err();
+        let reply = client
+            .call_method(None::<()>, "/", Some("org.zbus.Echo"), "TraceContext", &())
+            .await?;
+        assert_eq!(reply.body::<String>()?, "call-2");
+
+        let log = log.lock().unwrap();
+        let members: Vec<_> = log.iter().map(|(_, member, _)| member.as_str()).collect();
+        assert_eq!(members, ["Echo", "Fail", "TraceContext"]);
+        for (serial, _, reply_serial) in log.iter() {
+            assert_eq!(*reply_serial, Some(*serial));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn receive_specific() {
+        crate::utils::block_on(test_receive_specific()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_receive_specific() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0).server(&guid).p2p().build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        let mut stream = MessageStream::from(&client);
+        let second = client
+            .receive_specific(|msg| msg.header().member().map(|m| m.as_str()) == Some("Second"));
+        for member in ["First", "Second"] {
+            server
+                .emit_signal(None::<()>, "/", "org.zbus.Specific", member, &())
+                .await?;
+        }
+
+        let msg = second.await?;
+        assert_eq!(msg.header().member().unwrap(), "Second");
+        // The other streams still get all the messages.
+        let msg = stream.try_next().await?.unwrap();
+        assert_eq!(msg.header().member().unwrap(), "First");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn max_body_size() {
+        crate::utils::block_on(test_max_body_size()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_max_body_size() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Zeros;
+
+        #[crate::dbus_interface(name = "org.zbus.Zeros")]
+        impl Zeros {
+            fn zeros(&self, len: u32) -> Vec<u8> {
+                vec![0; len as usize]
+            }
+        }
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .read_buffer_size(7)
+                .max_body_size(64)
+                .build(),
+            Builder::unix_stream(p1)
+                .p2p()
+                .max_body_size(64)
+                .serve_at("/", Zeros)?
+                .build(),
+        )?;
+
+        let rule = MatchRule::builder().msg_type(Type::Signal).build();
+        let mut signals = MessageStream::for_match_rule(rule, &server, None).await?;
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.Big", "Big", &vec![0u8; 1024])
+            .await?;
+        client
+            .emit_signal(None::<()>, "/", "org.zbus.Big", "Small", &vec![1u8; 32])
+            .await?;
+
+        // The big signal was discarded.
+        let signal = signals.try_next().await?.unwrap();
+        assert_eq!(signal.header().member().unwrap(), "Small");
+        assert_eq!(signal.body::<Vec<u8>>()?, vec![1u8; 32]);
+
+        // Neither a discarded call nor a discarded reply leave the caller waiting.
+        let reply = server
+            .call_method(None::<()>, "/", Some("org.zbus.Zeros"), "Zeros", &32u32)
+            .await?;
+        assert_eq!(reply.body::<Vec<u8>>()?, vec![0u8; 32]);
+        let big = vec![0u32; 1024];
+        let call = client.call_method(None::<()>, "/", Some("org.zbus.Zeros"), "Zeros", &big);
+        let reply = server.call_method(None::<()>, "/", Some("org.zbus.Zeros"), "Zeros", &128u32);
+        for res in [call.await, reply.await] {
+            match res {
+                Err(Error::MethodError(name, _, _)) => {
+                    assert_eq!(name.as_str(), "org.freedesktop.DBus.Error.Failed");
+                }
+                r => panic!("unexpected reply: {r:?}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn overflow_policy() {
+        crate::utils::block_on(test_overflow_policy()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_overflow_policy() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0).server(&guid).p2p().build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        let rule = |member| {
+            MatchRule::builder()
+                .msg_type(Type::Signal)
+                .member(member)
+                .map(|b| b.build().to_owned())
+        };
+        let mut oldest = MessageStream::for_match_rule(rule("Oldest")?, &client, Some(2)).await?;
+        oldest.set_overflow_policy(OverflowPolicy::Report);
+        let mut newest = MessageStream::for_match_rule(rule("Newest")?, &client, Some(2)).await?;
+        newest.set_overflow_policy(OverflowPolicy::DropNewest);
+        let mut done = MessageStream::for_match_rule(rule("Done")?, &client, None).await?;
+        // Overflows happening while the stream is waiting are reported too.
+        assert!(futures_util::poll!(oldest.next()).is_pending());
+
+        for member in ["Oldest", "Newest"] {
+            for i in 0..5u32 {
+                server
+                    .emit_signal(None::<()>, "/", "org.zbus.Overflow", member, &i)
+                    .await?;
+            }
+        }
+        server
+            .emit_signal(None::<()>, "/", "org.zbus.Overflow", "Done", &())
+            .await?;
+        // Messages are dispatched in order so all the others have been queued (or dropped) by now.
+        done.try_next().await?.unwrap();
+
+        assert!(matches!(
+            oldest.next().await.unwrap(),
+            Err(Error::MessagesDropped(3))
+        ));
+        for expected in [3u32, 4] {
+            let msg = oldest.try_next().await?.unwrap();
+            assert_eq!(msg.body::<u32>()?, expected);
+        }
+        for expected in [0u32, 1] {
+            let msg = newest.try_next().await?.unwrap();
+            assert_eq!(msg.body::<u32>()?, expected);
+        }
+
+        Ok(())
+    }
+
     // Compile-test only since we don't have a VM setup to run this with/in.
     #[cfg(any(
         all(feature = "vsock", not(feature = "tokio")),
@@ -0,0 +1,120 @@
+use std::any::Any;
+
+use crate::message::Message;
+
+/// Observes the method calls made on a [`Connection`](super::Connection) and their replies.
+///
+/// This allows correlating requests and responses, e.g to integrate D-Bus calls in distributed
+/// traces (like OpenTelemetry spans). Set through
+/// [`Builder::call_observer`](super::Builder::call_observer).
+///
+/// [`CallObserver::call_sent`] is called for each method call made through
+/// [`Connection::call_method`](super::Connection::call_method) (and hence proxies), right before
+/// it's sent. The call message gives the serial number, sender, destination, path, interface and
+/// member of the call. The observer then returns some [baggage](CallObserver::Baggage) to be
+/// attached to the call, e.g a span or the time the call was made, which is handed back to
+/// [`CallObserver::reply_received`] along with the reply, when it arrives.
+///
+/// The baggage is dropped without calling [`CallObserver::reply_received`] if no reply is received:
+/// if the call doesn't expect one, the connection is closed or the caller stops waiting for it.
+///
+/// The observer can also propagate a tracing context along the call, from its baggage, through
+/// [`CallObserver::trace_context`]. The callee gets it through [`Header::trace_context`], to
+/// continue the trace on its side.
+///
+/// [`Header::trace_context`]: crate::message::Header::trace_context
+///
+/// # Example
+///
+/// Logging the duration of each method call:
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use std::time::Instant;
+/// use zbus::{connection::{Builder, CallObserver}, message::Message};
+///
+/// struct Timer;
+///
+/// impl CallObserver for Timer {
+///     type Baggage = Instant;
+///
+///     fn call_sent(&self, _call: &Message) -> Instant {
+///         Instant::now()
+///     }
+///
+///     fn reply_received(&self, call: &Message, reply: &Message, start: Instant) {
+///         let hdr = call.header();
+///         println!(
+///             "{}.{} (serial {}) to {:?}: {:?} in {:?}",
+///             hdr.interface().map(|i| i.as_str()).unwrap_or_default(),
+///             hdr.member().map(|m| m.as_str()).unwrap_or_default(),
+///             call.primary_header().serial_num(),
+///             hdr.destination(),
+///             reply.message_type(),
+///             start.elapsed(),
+///         );
+///     }
+/// }
+///
+/// let conn = Builder::session()?.call_observer(Timer).build().await?;
+/// # drop(conn);
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+pub trait CallObserver: Send + Sync + 'static {
+    /// The data attached to a method call, until its reply arrives.
+    type Baggage: Send + 'static;
+
+    /// A method call is about to be sent.
+    fn call_sent(&self, call: &Message) -> Self::Baggage;
+
+    /// The tracing context to send along the call, e.g a W3C `traceparent` built from `baggage`.
+    ///
+    /// This is called right after [`CallObserver::call_sent`], and the call is sent with the
+    /// returned context, if any. See [`Header::trace_context`] for the limitations.
+    ///
+    /// The default implementation returns `None`.
+    ///
+    /// [`Header::trace_context`]: crate::message::Header::trace_context
+    fn trace_context(&self, baggage: &Self::Baggage) -> Option<String> {
+        let _ = baggage;
+
+        None
+    }
+
+    /// The reply to a method call was received.
+    ///
+    /// `reply` is either a method return or an error.
+    fn reply_received(&self, call: &Message, reply: &Message, baggage: Self::Baggage);
+}
+
+// A type-erased `CallObserver`.
+pub(crate) trait DynCallObserver: Send + Sync {
+    fn call_sent(&self, call: &Message) -> Box<dyn Any + Send>;
+
+    fn trace_context(&self, baggage: &(dyn Any + Send)) -> Option<String>;
+
+    fn reply_received(&self, call: &Message, reply: &Message, baggage: Box<dyn Any + Send>);
+}
+
+impl<O> DynCallObserver for O
+where
+    O: CallObserver,
+{
+    fn call_sent(&self, call: &Message) -> Box<dyn Any + Send> {
+        Box::new(CallObserver::call_sent(self, call))
+    }
+
+    fn trace_context(&self, baggage: &(dyn Any + Send)) -> Option<String> {
+        baggage
+            .downcast_ref::<O::Baggage>()
+            .and_then(|baggage| CallObserver::trace_context(self, baggage))
+    }
+
+    fn reply_received(&self, call: &Message, reply: &Message, baggage: Box<dyn Any + Send>) {
+        // The baggage always comes from `call_sent` of the same observer.
+        if let Ok(baggage) = baggage.downcast::<O::Baggage>() {
+            CallObserver::reply_received(self, call, reply, *baggage);
+        }
+    }
+}
@@ -348,6 +348,24 @@ fn get_unix_peer_creds_blocking(fd: RawFd) -> io::Result<ConnectionCredentials>
         // FIXME: Handle pid fetching too.
         Ok(ConnectionCredentials::default().set_unix_user_id(uid))
     }
+
+    // On other platforms, we don't know how to get the credentials so the peer can't be
+    // authenticated through them (e.g with the `EXTERNAL` mechanism).
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    )))]
+    {
+        let _ = fd;
+
+        Ok(ConnectionCredentials::default())
+    }
 }
 
 // Send 0 byte as a separate SCM_CREDS message.
@@ -1,17 +1,41 @@
 use std::{collections::HashMap, sync::Arc};
 
 use event_listener::Event;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, warn};
 
 use crate::{
     async_lock::Mutex,
-    connection::MsgBroadcaster,
-    message::header::{PrimaryHeader, MAX_MESSAGE_SIZE, MIN_MESSAGE_SIZE},
+    connection::{MsgBroadcaster, WeakConnection},
+    message::{
+        header::{PrimaryHeader, MAX_MESSAGE_SIZE, MIN_MESSAGE_SIZE},
+        Builder, Flags, Header, Type,
+    },
     padding_for_8_bytes, Executor, Message, OwnedMatchRule, Task,
 };
 
 use super::socket::ReadHalf;
 
+// The size of the buffer used to read (and drop) the messages discarded because of their size.
+const DISCARD_BUFFER_SIZE: usize = 4096;
+
+/// How messages are read from the socket.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReadConfig {
+    /// The maximum number of bytes read from the socket at once.
+    pub(crate) buffer_size: usize,
+    /// The maximum size of the body of a message. Larger messages are discarded.
+    pub(crate) max_body_size: Option<usize>,
+}
+
+impl Default for ReadConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: usize::MAX,
+            max_body_size: None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct SocketReader {
     socket: Box<dyn ReadHalf>,
@@ -19,6 +43,9 @@ pub(crate) struct SocketReader {
     already_received_bytes: Option<Vec<u8>>,
     prev_seq: u64,
     activity_event: Arc<Event>,
+    config: ReadConfig,
+    // Used to reply to the method calls discarded because of their size.
+    conn: WeakConnection,
 }
 
 impl SocketReader {
@@ -27,6 +54,8 @@ impl SocketReader {
         senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
         already_received_bytes: Vec<u8>,
         activity_event: Arc<Event>,
+        config: ReadConfig,
+        conn: WeakConnection,
     ) -> Self {
         Self {
             socket,
@@ -34,6 +63,8 @@ impl SocketReader {
             already_received_bytes: Some(already_received_bytes),
             prev_seq: 0,
             activity_event,
+            config,
+            conn,
         }
     }
 
@@ -46,7 +77,12 @@ impl SocketReader {
     async fn receive_msg(mut self) {
         loop {
             trace!("Waiting for message on the socket..");
-            let msg = self.read_socket().await;
+            let msg = match self.read_socket().await {
+                Ok(Some(msg)) => Ok(msg),
+                // The message was discarded.
+                Ok(None) => continue,
+                Err(e) => Err(e),
+            };
             match &msg {
                 Ok(msg) => trace!("Message received on the socket: {:?}", msg),
                 Err(e) => trace!("Error reading from the socket: {:?}", e),
@@ -73,10 +109,11 @@ impl SocketReader {
                     //
                     // 1. the channel is closed.
                     // 2. No active receivers.
+                    // 3. The channel is full and the newest messages are to be dropped.
                     //
-                    // In either case, just log it.
+                    // In any case, just log it.
                     trace!(
-                        "Error broadcasting message to stream for `{:?}`: {:?}",
+                        "Error broadcasting message to stream for `{:?}`: {}",
                         rule,
                         e
                     );
@@ -93,8 +130,9 @@ impl SocketReader {
         }
     }
 
+    // Read the next message, or `None` if it was discarded.
     #[instrument]
-    async fn read_socket(&mut self) -> crate::Result<Message> {
+    async fn read_socket(&mut self) -> crate::Result<Option<Message>> {
         self.activity_event.notify(usize::MAX);
         let mut bytes = self
             .already_received_bytes
@@ -145,14 +183,62 @@ impl SocketReader {
         if total_len > MAX_MESSAGE_SIZE {
             return Err(crate::Error::ExcessData);
         }
+        if let Some(max) = self.config.max_body_size.filter(|max| body_len > *max) {
+            warn!(
+                "Discarding {:?} message with serial {}: body of {} bytes exceeds the limit of {}",
+                primary_header.msg_type(),
+                primary_header.serial_num(),
+                body_len,
+                max,
+            );
+
+            // Only the header is kept, to reply to the message.
+            if pos < header_len {
+                bytes.resize(header_len, 0);
+            }
+            while pos < header_len {
+                let res = self.socket.recvmsg(&mut bytes[pos..header_len]).await?;
+                let read = {
+                    #[cfg(unix)]
+                    {
+                        fds.extend(res.1);
+                        res.0
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        res
+                    }
+                };
+                pos += read;
+                if read == 0 {
+                    return Err(crate::Error::InputOutput(
+                        std::io::Error::new(
+                            std::io::ErrorKind::UnexpectedEof,
+                            "failed to receive message",
+                        )
+                        .into(),
+                    ));
+                }
+            }
+            self.discard(total_len.saturating_sub(pos)).await?;
+            let header = Message::read_header(&bytes[..header_len])?;
+            let description =
+                format!("Message body of {body_len} bytes exceeds the limit of {max}");
+
+            return self.discarded(&header, description).await;
+        }
 
         // By this point we have a full primary header, so we know the exact length of the complete
-        // message.
-        bytes.resize(total_len, 0);
+        // message. Unless the read buffer size is limited, it's allocated all at once.
+        bytes.truncate(total_len);
 
         // Now we have an incomplete message; read the rest
         while pos < total_len {
-            let res = self.socket.recvmsg(&mut bytes[pos..]).await?;
+            let end = total_len.min(pos.saturating_add(self.config.buffer_size));
+            if bytes.len() < end {
+                bytes.resize(end, 0);
+            }
+            let res = self.socket.recvmsg(&mut bytes[pos..end]).await?;
             let read = {
                 #[cfg(unix)]
                 {
@@ -185,5 +271,86 @@ impl SocketReader {
             fds,
             seq,
         )
+        .map(Some)
+    }
+
+    // Make sure nobody waits forever for a reply to or from a discarded message: a method call is
+    // replied to with an error and a reply is replaced by an error, returned instead.
+    async fn discarded(
+        &mut self,
+        header: &Header<'_>,
+        description: String,
+    ) -> crate::Result<Option<Message>> {
+        const FAILED: &str = "org.freedesktop.DBus.Error.Failed";
+
+        match header.message_type() {
+            Type::MethodCall if !header.primary().flags().contains(Flags::NoReplyExpected) => {
+                let conn = match self.conn.upgrade() {
+                    Some(conn) => conn,
+                    None => return Ok(None),
+                };
+                #[allow(deprecated)]
+                let reply = Builder::error(header, FAILED)?.build(&description)?;
+                if let Err(e) = conn.send(&reply).await {
+                    warn!("Failed to reply to a discarded method call: {}", e);
+                }
+
+                Ok(None)
+            }
+            Type::MethodReturn | Type::Error => {
+                let reply_serial = match header.reply_serial() {
+                    Some(serial) => serial,
+                    None => return Ok(None),
+                };
+                #[allow(deprecated)]
+                let mut builder = Builder::error(header, FAILED)?
+                    .reply_serial(reply_serial)
+                    .no_destination();
+                if let Some(sender) = header.sender() {
+                    builder = builder.sender(sender.clone())?;
+                }
+                if let Some(destination) = header.destination() {
+                    builder = builder.destination(destination.clone())?;
+                }
+                let error = builder.build(&description)?;
+
+                let seq = self.prev_seq + 1;
+                self.prev_seq = seq;
+                Message::from_raw_parts(
+                    error.as_bytes().to_vec(),
+                    #[cfg(unix)]
+                    vec![],
+                    seq,
+                )
+                .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // Read and drop the next `len` bytes.
+    async fn discard(&mut self, mut len: usize) -> crate::Result<()> {
+        let mut buffer = vec![0; len.min(self.config.buffer_size).min(DISCARD_BUFFER_SIZE)];
+        while len > 0 {
+            let end = len.min(buffer.len());
+            let res = self.socket.recvmsg(&mut buffer[..end]).await?;
+            // Any file descriptors received are closed on drop.
+            #[cfg(unix)]
+            let read = res.0;
+            #[cfg(not(unix))]
+            let read = res;
+            if read == 0 {
+                return Err(crate::Error::InputOutput(
+                    std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "failed to receive message",
+                    )
+                    .into(),
+                ));
+            }
+            len -= read;
+        }
+
+        Ok(())
     }
 }
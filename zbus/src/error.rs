@@ -1,7 +1,7 @@
 use static_assertions::assert_impl_all;
 use std::{convert::Infallible, error, fmt, io, sync::Arc};
 use zbus_names::{Error as NamesError, OwnedErrorName};
-use zvariant::Error as VariantError;
+use zvariant::{Error as VariantError, OwnedSignature};
 
 use crate::{
     fdo,
@@ -59,6 +59,16 @@ pub enum Error {
     MissingParameter(&'static str),
     /// Serial number in the message header is 0 (which is invalid).
     InvalidSerial,
+    /// The operation requires a connection to a message bus but the connection is peer-to-peer.
+    NotABus,
+    /// The given number of messages were dropped from a full message queue.
+    ///
+    /// See [`crate::OverflowPolicy::Report`].
+    MessagesDropped(u64),
+    /// The body signature of a message doesn't match the expected one.
+    ///
+    /// The first field is the expected signature and the second one is the actual signature.
+    BodySignatureMismatch(OwnedSignature, OwnedSignature),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -76,6 +86,11 @@ impl PartialEq for Error {
             (Self::MissingField, Self::MissingField) => true,
             (Self::InvalidGUID, Self::InvalidGUID) => true,
             (Self::InvalidSerial, Self::InvalidSerial) => true,
+            (Self::NotABus, Self::NotABus) => true,
+            (Self::MessagesDropped(s), Self::MessagesDropped(o)) => s == o,
+            (Self::BodySignatureMismatch(e1, f1), Self::BodySignatureMismatch(e2, f2)) => {
+                e1 == e2 && f1 == f2
+            }
             (Self::Unsupported, Self::Unsupported) => true,
             (Self::FDO(s), Self::FDO(o)) => s == o,
             (Self::InvalidField, Self::InvalidField) => true,
@@ -113,6 +128,9 @@ impl error::Error for Error {
             Error::Failure(_) => None,
             Error::MissingParameter(_) => None,
             Error::InvalidSerial => None,
+            Error::NotABus => None,
+            Error::MessagesDropped(_) => None,
+            Error::BodySignatureMismatch(_, _) => None,
         }
     }
 }
@@ -147,6 +165,12 @@ impl fmt::Display for Error {
                 write!(f, "Parameter `{}` was not specified but it is required", p)
             }
             Error::InvalidSerial => write!(f, "Serial number in the message header is 0"),
+            Error::NotABus => write!(f, "operation requires a message bus connection"),
+            Error::MessagesDropped(n) => write!(f, "{n} messages dropped from a full queue"),
+            Error::BodySignatureMismatch(expected, found) => write!(
+                f,
+                "body signature mismatch: expected `{expected}`, found `{found}`"
+            ),
         }
     }
 }
@@ -176,6 +200,11 @@ impl Clone for Error {
             Error::Failure(e) => Error::Failure(e.clone()),
             Error::MissingParameter(p) => Error::MissingParameter(p),
             Error::InvalidSerial => Error::InvalidSerial,
+            Error::NotABus => Error::NotABus,
+            Error::MessagesDropped(n) => Error::MessagesDropped(*n),
+            Error::BodySignatureMismatch(expected, found) => {
+                Error::BodySignatureMismatch(expected.clone(), found.clone())
+            }
         }
     }
 }
@@ -17,8 +17,10 @@ use zvariant::{
 };
 
 use crate::{
-    dbus_interface, dbus_proxy, message::Header, object_server::SignalContext, DBusError, Guid,
-    ObjectServer,
+    dbus_interface, dbus_proxy,
+    message::{Header, Message},
+    object_server::{Access, SignalContext},
+    Connection, DBusError, Guid, ObjectServer,
 };
 
 #[rustfmt::skip]
@@ -28,6 +30,7 @@ macro_rules! gen_introspectable_proxy {
         #[dbus_proxy(
             interface = "org.freedesktop.DBus.Introspectable",
             default_path = "/",
+            includable = true,
             gen_async = $gen_async,
             gen_blocking = $gen_blocking,
         )]
@@ -41,6 +44,13 @@ macro_rules! gen_introspectable_proxy {
 
 gen_introspectable_proxy!(true, false);
 assert_impl_all!(IntrospectableProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`IntrospectableProxy`](crate::blocking::fdo::IntrospectableProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type IntrospectableProxyBlocking<'p> = crate::blocking::fdo::IntrospectableProxy<'p>;
+/// The methods of [`IntrospectableProxyBlocking`], for the proxies including its interface.
+pub use crate::blocking::fdo::IntrospectableProxyMethods as IntrospectableProxyBlockingMethods;
 
 /// Server-side implementation for the `org.freedesktop.DBus.Introspectable` interface.
 /// This interface is implemented automatically for any object registered to the
@@ -71,6 +81,7 @@ macro_rules! gen_properties_proxy {
         #[dbus_proxy(
             interface = "org.freedesktop.DBus.Properties",
             assume_defaults = true,
+            includable = true,
             gen_async = $gen_async,
             gen_blocking = $gen_blocking,
         )]
@@ -109,6 +120,13 @@ macro_rules! gen_properties_proxy {
 
 gen_properties_proxy!(true, false);
 assert_impl_all!(PropertiesProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`PropertiesProxy`](crate::blocking::fdo::PropertiesProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type PropertiesProxyBlocking<'p> = crate::blocking::fdo::PropertiesProxy<'p>;
+/// The methods of [`PropertiesProxyBlocking`], for the proxies including its interface.
+pub use crate::blocking::fdo::PropertiesProxyMethods as PropertiesProxyBlockingMethods;
 
 /// Server-side implementation for the `org.freedesktop.DBus.Properties` interface.
 /// This interface is implemented automatically for any object registered to the
@@ -124,9 +142,16 @@ impl Properties {
         interface_name: InterfaceName<'_>,
         property_name: &str,
         #[zbus(object_server)] server: &ObjectServer,
+        #[zbus(connection)] conn: &Connection,
         #[zbus(header)] header: Header<'_>,
     ) -> Result<OwnedValue> {
         let path = header.path().ok_or(crate::Error::MissingField)?;
+        check_property_access(
+            conn,
+            &header,
+            &interface_name,
+            &(&interface_name, property_name),
+        )?;
         let root = server.root().read().await;
         let iface = root
             .get_child(path)
@@ -143,16 +168,20 @@ impl Properties {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn set(
         &self,
         interface_name: InterfaceName<'_>,
         property_name: &str,
         value: Value<'_>,
         #[zbus(object_server)] server: &ObjectServer,
+        #[zbus(connection)] conn: &Connection,
         #[zbus(header)] header: Header<'_>,
         #[zbus(signal_context)] ctxt: SignalContext<'_>,
     ) -> Result<()> {
         let path = header.path().ok_or(crate::Error::MissingField)?;
+        let body = (&interface_name, property_name, &value);
+        check_property_access(conn, &header, &interface_name, &body)?;
         let root = server.root().read().await;
         let iface = root
             .get_child(path)
@@ -188,9 +217,11 @@ impl Properties {
         &self,
         interface_name: InterfaceName<'_>,
         #[zbus(object_server)] server: &ObjectServer,
+        #[zbus(connection)] conn: &Connection,
         #[zbus(header)] header: Header<'_>,
     ) -> Result<HashMap<String, OwnedValue>> {
         let path = header.path().ok_or(crate::Error::MissingField)?;
+        check_property_access(conn, &header, &interface_name, &(&interface_name,))?;
         let root = server.root().read().await;
         let iface = root
             .get_child(path)
@@ -214,6 +245,40 @@ impl Properties {
     ) -> zbus::Result<()>;
 }
 
+/// Checks a `org.freedesktop.DBus.Properties` call against the policy of `conn`, as if it was made
+/// on the interface it refers to.
+fn check_property_access<B>(
+    conn: &Connection,
+    header: &Header<'_>,
+    interface_name: &InterfaceName<'_>,
+    body: &B,
+) -> Result<()>
+where
+    B: serde::ser::Serialize + zvariant::DynamicType,
+{
+    let policy = match conn.policy() {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+    let path = header.path().ok_or(crate::Error::MissingField)?;
+    let member = header.member().ok_or(crate::Error::MissingField)?;
+    let mut builder = Message::method(path, member)?.interface(interface_name)?;
+    if let Some(sender) = header.sender() {
+        builder = builder.sender(sender)?;
+    }
+    if let Some(destination) = header.destination() {
+        builder = builder.destination(destination)?;
+    }
+    let msg = builder.build(body)?;
+    if policy.check(&msg) == Access::Deny {
+        return Err(Error::AccessDenied(format!(
+            "Access to '{interface_name}.{member}' on '{path}' denied"
+        )));
+    }
+
+    Ok(())
+}
+
 /// The type returned by the [`ObjectManagerProxy::get_managed_objects`] method.
 pub type ManagedObjects =
     HashMap<OwnedObjectPath, HashMap<OwnedInterfaceName, HashMap<String, OwnedValue>>>;
@@ -229,6 +294,7 @@ macro_rules! gen_object_manager_proxy {
         #[dbus_proxy(
             interface = "org.freedesktop.DBus.ObjectManager",
             assume_defaults = true,
+            includable = true,
             gen_async = $gen_async,
             gen_blocking = $gen_blocking,
         )]
@@ -267,6 +333,13 @@ macro_rules! gen_object_manager_proxy {
 
 gen_object_manager_proxy!(true, false);
 assert_impl_all!(ObjectManagerProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`ObjectManagerProxy`](crate::blocking::fdo::ObjectManagerProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type ObjectManagerProxyBlocking<'p> = crate::blocking::fdo::ObjectManagerProxy<'p>;
+/// The methods of [`ObjectManagerProxyBlocking`], for the proxies including its interface.
+pub use crate::blocking::fdo::ObjectManagerProxyMethods as ObjectManagerProxyBlockingMethods;
 
 /// Service-side [Object Manager][om] interface implementation.
 ///
@@ -328,6 +401,7 @@ macro_rules! gen_peer_proxy {
         #[dbus_proxy(
             interface = "org.freedesktop.DBus.Peer",
             assume_defaults = true,
+            includable = true,
             gen_async = $gen_async,
             gen_blocking = $gen_blocking,
         )]
@@ -348,6 +422,33 @@ macro_rules! gen_peer_proxy {
 
 gen_peer_proxy!(true, false);
 assert_impl_all!(PeerProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`PeerProxy`](crate::blocking::fdo::PeerProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type PeerProxyBlocking<'p> = crate::blocking::fdo::PeerProxy<'p>;
+/// The methods of [`PeerProxyBlocking`], for the proxies including its interface.
+pub use crate::blocking::fdo::PeerProxyMethods as PeerProxyBlockingMethods;
+
+// The ID of the local machine, as exposed by `org.freedesktop.DBus.Peer.GetMachineId`.
+pub(crate) fn machine_id() -> Result<String> {
+    let mut id = match std::fs::read_to_string("/var/lib/dbus/machine-id") {
+        Ok(id) => id,
+        Err(e) => {
+            if let Ok(id) = std::fs::read_to_string("/etc/machine-id") {
+                id
+            } else {
+                return Err(Error::IOError(format!(
+                    "Failed to read from /var/lib/dbus/machine-id or /etc/machine-id: {e}"
+                )));
+            }
+        }
+    };
+
+    let len = id.trim_end().len();
+    id.truncate(len);
+    Ok(id)
+}
 
 pub(crate) struct Peer;
 
@@ -359,22 +460,7 @@ impl Peer {
     fn ping(&self) {}
 
     fn get_machine_id(&self) -> Result<String> {
-        let mut id = match std::fs::read_to_string("/var/lib/dbus/machine-id") {
-            Ok(id) => id,
-            Err(e) => {
-                if let Ok(id) = std::fs::read_to_string("/etc/machine-id") {
-                    id
-                } else {
-                    return Err(Error::IOError(format!(
-                        "Failed to read from /var/lib/dbus/machine-id or /etc/machine-id: {e}"
-                    )));
-                }
-            }
-        };
-
-        let len = id.trim_end().len();
-        id.truncate(len);
-        Ok(id)
+        machine_id()
     }
 }
 
@@ -422,6 +508,11 @@ macro_rules! gen_monitoring_proxy {
 
 gen_monitoring_proxy!(true, false);
 assert_impl_all!(MonitoringProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`MonitoringProxy`](crate::blocking::fdo::MonitoringProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type MonitoringProxyBlocking<'p> = crate::blocking::fdo::MonitoringProxy<'p>;
 
 #[rustfmt::skip]
 macro_rules! gen_stats_proxy {
@@ -458,6 +549,11 @@ macro_rules! gen_stats_proxy {
 
 gen_stats_proxy!(true, false);
 assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`StatsProxy`](crate::blocking::fdo::StatsProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type StatsProxyBlocking<'p> = crate::blocking::fdo::StatsProxy<'p>;
 
 /// The flags used by the bus [`request_name`] method.
 ///
@@ -771,7 +867,15 @@ macro_rules! gen_dbus_proxy {
             /// activation), as an explicit request.
             fn start_service_by_name(&self, name: WellKnownName<'_>, flags: u32) -> Result<u32>;
 
-            /// This method adds to or modifies that environment when activating services.
+            /// Adds to or modifies the environment of the services activated by the bus.
+            ///
+            /// Each entry of `environment` maps the name of an environment variable to its value.
+            /// Variables not in `environment` are left untouched. This is typically used by session
+            /// managers and launchers to make variables that are only known after the bus is
+            /// started (e.g `DISPLAY` and `WAYLAND_DISPLAY`), available to activated services.
+            ///
+            /// Buses are free to restrict who may call this method (typically only the user that
+            /// owns a session bus may) so be prepared for an `AccessDenied` error.
             fn update_activation_environment(&self, environment: HashMap<&str, &str>)
                 -> Result<()>;
 
@@ -819,6 +923,11 @@ macro_rules! gen_dbus_proxy {
 
 gen_dbus_proxy!(true, false);
 assert_impl_all!(DBusProxy<'_>: Send, Sync, Unpin);
+/// The blocking [`DBusProxy`](crate::blocking::fdo::DBusProxy).
+///
+/// Named like the blocking proxies generated by [`dbus_proxy`], so that its `object` and `include`
+/// attributes can refer to it.
+pub type DBusProxyBlocking<'p> = crate::blocking::fdo::DBusProxy<'p>;
 
 /// Errors from <https://gitlab.freedesktop.org/dbus/dbus/-/blob/master/dbus/dbus-protocol.h>
 #[derive(Clone, Debug, DBusError, PartialEq)]
@@ -41,6 +41,8 @@ pub use dbus_error::*;
 mod error;
 pub use error::*;
 
+pub mod activation;
+
 pub mod address;
 pub use address::Address;
 
@@ -91,6 +93,12 @@ pub use message::NATIVE_ENDIAN_SIG;
 pub mod connection;
 pub use connection::{handshake::AuthMechanism, Connection};
 
+#[cfg(feature = "bus")]
+pub mod bus;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod memfd;
+
 #[deprecated(note = "Use `connection::Builder` instead")]
 #[doc(hidden)]
 pub use connection::Builder as ConnectionBuilder;
@@ -181,6 +189,7 @@ extern crate self as zbus;
 #[doc(hidden)]
 pub mod export {
     pub use async_trait;
+    pub use enumflags2;
     pub use futures_core;
     pub use futures_util;
     pub use ordered_stream;
@@ -189,6 +198,8 @@ pub mod export {
 }
 
 pub use zbus_names as names;
+#[cfg(feature = "xml")]
+pub use zbus_xml as xml;
 pub use zvariant;
 
 #[cfg(unix)]
@@ -896,6 +907,316 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn cached_property_set() {
+        block_on(test_cached_property_set()).unwrap();
+    }
+
+    async fn test_cached_property_set() -> Result<()> {
+        struct ServiceCachedPropertySetTest(u32);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.CachedPropertySetTest")]
+        impl ServiceCachedPropertySetTest {
+            #[dbus_interface(property)]
+            fn value(&self) -> u32 {
+                self.0
+            }
+            #[dbus_interface(property)]
+            fn set_value(&mut self, value: u32) {
+                self.0 = value;
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.CachedPropertySetTest",
+            default_path = "/org/freedesktop/zbus/CachedPropertySetTest"
+        )]
+        trait CachedPropertySetTest {
+            #[dbus_proxy(property)]
+            fn value(&self) -> zbus::Result<u32>;
+            #[dbus_proxy(property)]
+            fn set_value(&self, value: u32) -> zbus::Result<()>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at(
+                "/org/freedesktop/zbus/CachedPropertySetTest",
+                ServiceCachedPropertySetTest(1),
+            )?
+            .build()
+            .await?;
+        let dest = service.unique_name().unwrap();
+
+        let client_conn = crate::Connection::session().await?;
+        let client = CachedPropertySetTestProxy::builder(&client_conn)
+            .destination(dest)?
+            .cache_properties(crate::proxy::CacheProperties::Yes)
+            .build()
+            .await?;
+        assert_eq!(client.value().await?, 1);
+
+        // The cache is updated right away, without waiting for `PropertiesChanged`.
+        client.set_value(2).await?;
+        assert_eq!(client.cached_value()?, Some(2));
+        assert_eq!(client.value().await?, 2);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn cached_property_set_notifies() {
+        block_on(test_cached_property_set_notifies()).unwrap();
+    }
+
+    // The listeners of a property are notified when it's set, even if the peer doesn't emit
+    // `PropertiesChanged`.
+    #[cfg(unix)]
+    async fn test_cached_property_set_notifies() -> Result<()> {
+        use futures_util::{future::FutureExt, stream::TryStreamExt, StreamExt};
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.QuietProperty",
+            default_path = "/org/freedesktop/zbus/QuietProperty"
+        )]
+        trait QuietProperty {
+            #[dbus_proxy(property)]
+            fn value(&self) -> zbus::Result<u32>;
+            #[dbus_proxy(property)]
+            fn set_value(&self, value: u32) -> zbus::Result<()>;
+        }
+
+        // A peer implementing the properties by hand, without ever emitting `PropertiesChanged`.
+        async fn service(conn: &Connection) -> Result<()> {
+            let mut stream = zbus::MessageStream::from(conn);
+            while let Some(msg) = stream.try_next().await? {
+                let header = msg.header();
+                if header.message_type() != zbus::message::Type::MethodCall {
+                    continue;
+                }
+                match header.member().map(|m| m.as_str()) {
+                    Some("GetAll") => {
+                        let props = HashMap::from([("Value", zvariant::Value::from(1u32))]);
+                        conn.reply(&msg, &props).await?;
+                    }
+                    Some("Set") => {
+                        conn.reply(&msg, &()).await?;
+
+                        break;
+                    }
+                    _ => conn.reply(&msg, &()).await?,
+                }
+            }
+
+            Ok(())
+        }
+
+        let guid = crate::Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (service_conn, client_conn) = futures_util::try_join!(
+            crate::connection::Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .build(),
+            crate::connection::Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        let client = async {
+            let client = QuietPropertyProxy::builder(&client_conn)
+                .destination("org.freedesktop.zbus.QuietProperty")?
+                .cache_properties(crate::proxy::CacheProperties::Yes)
+                .build()
+                .await?;
+            assert_eq!(client.value().await?, 1);
+            let mut changes = client.receive_value_changed().await;
+
+            client.set_value(2).await?;
+            let change = changes.next().now_or_never().unwrap().unwrap();
+            assert_eq!(change.get().await?, 2);
+            assert_eq!(client.cached_value()?, Some(2));
+
+            Ok::<_, crate::Error>(())
+        };
+        futures_util::try_join!(service(&service_conn), client)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    #[cfg(unix)]
+    fn gen_server() {
+        block_on(test_gen_server()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_gen_server() -> Result<()> {
+        use futures_util::StreamExt;
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        use crate::proxy::CacheProperties;
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Counter",
+            default_path = "/org/freedesktop/zbus/Counter",
+            gen_server = true
+        )]
+        trait Counter {
+            fn add(&self, n: u32, label: &str) -> zbus::Result<(u32, String)>;
+
+            #[dbus_proxy(name = "Fail")]
+            fn failing(&self) -> zbus::fdo::Result<()>;
+
+            #[dbus_proxy(property)]
+            fn total(&self) -> zbus::Result<u32>;
+
+            #[dbus_proxy(property)]
+            fn set_total(&self, total: u32) -> zbus::Result<()>;
+
+            #[dbus_proxy(signal)]
+            fn overflow(&self, total: u32) -> zbus::Result<()>;
+        }
+
+        #[derive(Default)]
+        struct MyCounter(AtomicU32);
+
+        #[async_trait::async_trait]
+        impl Counter for MyCounter {
+            async fn add(&self, n: u32, label: &str) -> zbus::Result<(u32, String)> {
+                let total = self.0.fetch_add(n, Ordering::SeqCst) + n;
+
+                Ok((total, format!("{label}: {total}")))
+            }
+
+            async fn failing(&self) -> zbus::fdo::Result<()> {
+                Err(zbus::fdo::Error::NotSupported("nope".into()))
+            }
+
+            async fn total(&self) -> zbus::Result<u32> {
+                Ok(self.0.load(Ordering::SeqCst))
+            }
+
+            async fn set_total(&self, total: u32) -> zbus::Result<()> {
+                self.0.store(total, Ordering::SeqCst);
+
+                Ok(())
+            }
+        }
+
+        let guid = crate::Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (service, client) = futures_util::try_join!(
+            crate::connection::Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .serve_at(
+                    "/org/freedesktop/zbus/Counter",
+                    CounterServer(MyCounter::default()),
+                )?
+                .build(),
+            crate::connection::Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        let proxy = CounterProxy::builder(&client)
+            .destination("org.freedesktop.zbus.Counter")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        let mut overflows = proxy.receive_overflow().await?;
+
+        assert_eq!(proxy.add(2, "total").await?, (2, "total: 2".to_string()));
+        proxy.set_total(40).await?;
+        assert_eq!(proxy.add(2, "total").await?, (42, "total: 42".to_string()));
+        assert_eq!(proxy.total().await?, 42);
+        assert_eq!(
+            proxy.failing().await.unwrap_err(),
+            zbus::fdo::Error::NotSupported("nope".into())
+        );
+
+        let ctxt = SignalContext::new(&service, "/org/freedesktop/zbus/Counter")?;
+        CounterServer::<MyCounter>::overflow(&ctxt, 42).await?;
+        let signal = overflows.next().await.unwrap();
+        assert_eq!(signal.args()?.total, 42);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    #[cfg(unix)]
+    fn proxy_include() {
+        block_on(test_proxy_include()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_proxy_include() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+        use zbus_names::InterfaceName;
+
+        use crate::fdo::{IntrospectableProxyMethods, PropertiesProxyMethods};
+
+        struct TestDevice;
+
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Device")]
+        impl TestDevice {
+            fn reset(&self) {}
+
+            #[dbus_interface(property)]
+            fn model(&self) -> &str {
+                "Model T"
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Device",
+            default_path = "/org/freedesktop/zbus/Device",
+            include("zbus::fdo::Properties", "zbus::fdo::Introspectable")
+        )]
+        trait Device {
+            fn reset(&self) -> zbus::Result<()>;
+        }
+
+        let guid = crate::Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (_service, client) = futures_util::try_join!(
+            crate::connection::Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .serve_at("/org/freedesktop/zbus/Device", TestDevice)?
+                .build(),
+            crate::connection::Builder::unix_stream(p1).p2p().build(),
+        )?;
+        let proxy = DeviceProxy::builder(&client)
+            .destination("org.freedesktop.zbus.Device")?
+            .build()
+            .await?;
+
+        proxy.reset().await?;
+        let model = proxy
+            .get(
+                InterfaceName::from_static_str_unchecked("org.freedesktop.zbus.Device"),
+                "Model",
+            )
+            .await?;
+        assert_eq!(String::try_from(model)?, "Model T");
+        let xml = proxy.introspect().await?;
+        assert!(xml.contains("org.freedesktop.zbus.Device"));
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn issue_260() {
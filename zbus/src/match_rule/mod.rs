@@ -265,7 +265,7 @@ impl<'m> MatchRule<'m> {
             };
             match path_spec {
                 PathSpec::Path(path) if path != msg_path => return Ok(false),
-                PathSpec::PathNamespace(path_ns) if !msg_path.starts_with(path_ns.as_str()) => {
+                PathSpec::PathNamespace(path_ns) if !msg_path.has_namespace_prefix(path_ns) => {
                     return Ok(false);
                 }
                 PathSpec::Path(_) | PathSpec::PathNamespace(_) => (),
@@ -556,3 +556,30 @@ impl PartialEq<MatchRule<'_>> for OwnedMatchRule {
         self.0 == *other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Message;
+
+    #[test]
+    fn path_namespace() {
+        let rule = MatchRule::try_from("type='signal',path_namespace='/foo'").unwrap();
+        let signal = |path| {
+            Message::signal(path, "org.zbus.Test", "Signal")
+                .unwrap()
+                .build(&())
+                .unwrap()
+        };
+
+        assert!(rule.matches(&signal("/foo")).unwrap());
+        assert!(rule.matches(&signal("/foo/bar")).unwrap());
+        // Only whole components match, unlike a plain string prefix.
+        assert!(!rule.matches(&signal("/foobar")).unwrap());
+        assert!(!rule.matches(&signal("/")).unwrap());
+
+        // The root namespace matches every path.
+        let rule = MatchRule::try_from("type='signal',path_namespace='/'").unwrap();
+        assert!(rule.matches(&signal("/foobar")).unwrap());
+    }
+}
@@ -0,0 +1,211 @@
+//! Transfer of large byte payloads through sealed memfds.
+//!
+//! The size of D-Bus messages is limited (typically to 128 MiB by the bus, and often much less in
+//! practice) and large byte arrays are expensive to marshal and route. The established pattern
+//! for multi-megabyte transfers is to write the data to a memory file (see `memfd_create(2)`),
+//! seal it so it can't be modified anymore and pass its file descriptor instead.
+//!
+//! [`seal`] and [`read_sealed`] take care of both ends of this pattern, while [`Payload`] wraps
+//! them in a type sent as an `h` argument.
+//!
+//! This module is only available on Linux and Android.
+use nix::{
+    fcntl::{fcntl, FcntlArg, SealFlag},
+    sys::memfd::{memfd_create, MemFdCreateFlag},
+    unistd::dup,
+};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use static_assertions::assert_impl_all;
+use std::{
+    ffi::CStr,
+    fs::File,
+    io::Write,
+    os::unix::{
+        fs::FileExt,
+        io::{AsRawFd, FromRawFd, IntoRawFd},
+    },
+};
+use zvariant::{Fd, Signature, Type};
+
+use crate::{Error, OwnedFd, Result};
+
+/// The size above which data is worth sending through a sealed memfd rather than inline: 64 KiB.
+///
+/// See [`Payload::needs_sealing`].
+pub const DEFAULT_THRESHOLD: usize = 64 * 1024;
+
+// The seals a memfd must have for its content to be trusted not to change under us.
+const REQUIRED_SEALS: SealFlag = SealFlag::from_bits_truncate(
+    SealFlag::F_SEAL_SHRINK.bits() | SealFlag::F_SEAL_GROW.bits() | SealFlag::F_SEAL_WRITE.bits(),
+);
+
+/// Write `data` to a new memfd and seal it.
+///
+/// The returned file descriptor can't be written to, shrunk or grown anymore, and its seals can't
+/// be changed. It can be sent as an `h` argument of a message.
+///
+/// # Errors
+///
+/// Fails if the memfd couldn't be created, written to or sealed.
+pub fn seal(data: &[u8]) -> Result<OwnedFd> {
+    let name = CStr::from_bytes_with_nul(b"zbus-payload\0").expect("invalid memfd name");
+    let fd = memfd_create(
+        name,
+        MemFdCreateFlag::MFD_CLOEXEC | MemFdCreateFlag::MFD_ALLOW_SEALING,
+    )?;
+    let mut file = File::from(fd);
+    file.write_all(data)?;
+    fcntl(
+        file.as_raw_fd(),
+        FcntlArg::F_ADD_SEALS(REQUIRED_SEALS | SealFlag::F_SEAL_SEAL),
+    )?;
+
+    // SAFETY: `into_raw_fd` hands us the ownership of the memfd.
+    Ok(unsafe { OwnedFd::from_raw_fd(file.into_raw_fd()) })
+}
+
+/// Read back the content of a sealed memfd, as created by [`seal`].
+///
+/// The file descriptor is left untouched.
+///
+/// # Errors
+///
+/// Besides I/O errors, fails with [`Error::Failure`] if `fd` isn't sealed against writing,
+/// shrinking and growing, since its content could then change while (or after) being read.
+pub fn read_sealed<F>(fd: &F) -> Result<Vec<u8>>
+where
+    F: AsRawFd + ?Sized,
+{
+    let seals = SealFlag::from_bits_truncate(fcntl(fd.as_raw_fd(), FcntlArg::F_GET_SEALS)?);
+    if !seals.contains(REQUIRED_SEALS) {
+        return Err(Error::Failure("memfd payload isn't sealed".into()));
+    }
+
+    // SAFETY: `dup` returns a new file descriptor, owned by nobody else.
+    let file = unsafe { File::from_raw_fd(dup(fd.as_raw_fd())?) };
+    let len = usize::try_from(file.metadata()?.len())
+        .map_err(|_| Error::Failure("memfd payload too large".into()))?;
+    let mut data = vec![0; len];
+    file.read_exact_at(&mut data, 0)?;
+
+    Ok(data)
+}
+
+/// A byte payload, sent through a sealed memfd.
+///
+/// `Payload` is encoded as a file descriptor (signature `h`) referring to a memfd sealed by
+/// [`seal`], and is read back with [`read_sealed`]. Use it as the argument (or return value) type
+/// for methods transferring large blobs, on both sides of the connection.
+///
+/// Small payloads are cheaper to send inline, as an `ay`. Interfaces transferring data of any size
+/// therefore typically offer a variant of their methods taking an `ay` and one taking an `h`, and
+/// callers pick the one to use with [`Payload::needs_sealing`].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> zbus::Result<()> {
+/// use zbus::memfd::Payload;
+///
+/// // Small payloads are best kept inline.
+/// assert!(!Payload::needs_sealing(&[1, 2, 3]));
+///
+/// // Large ones are moved to a sealed memfd.
+/// let data = vec![42; 1024 * 1024];
+/// assert!(Payload::needs_sealing(&data));
+/// let payload = Payload::new(&data)?;
+/// assert_eq!(payload.to_bytes()?, data);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Payload(OwnedFd);
+
+assert_impl_all!(Payload: Send, Sync, Unpin);
+
+impl Payload {
+    /// Create a payload for `data`, by writing it to a sealed memfd.
+    ///
+    /// See [`seal`] for the possible errors.
+    pub fn new(data: &[u8]) -> Result<Self> {
+        seal(data).map(Self)
+    }
+
+    /// Whether `data` is larger than [`DEFAULT_THRESHOLD`], and so worth sending as a `Payload`
+    /// rather than inline.
+    pub fn needs_sealing(data: &[u8]) -> bool {
+        data.len() > DEFAULT_THRESHOLD
+    }
+
+    /// Read the data from the memfd.
+    ///
+    /// Since the memfd is sealed, the data is the same on every call. See [`read_sealed`] for the
+    /// possible errors, e.g if the sender didn't seal the memfd.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        read_sealed(&self.0)
+    }
+
+    /// The file descriptor of the memfd.
+    pub fn into_fd(self) -> OwnedFd {
+        self.0
+    }
+}
+
+impl Type for Payload {
+    fn signature() -> Signature<'static> {
+        Fd::signature()
+    }
+}
+
+impl Serialize for Payload {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Fd::from(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Payload {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fd = Fd::deserialize(deserializer)?;
+        let fd = dup(fd.as_raw_fd()).map_err(D::Error::custom)?;
+
+        // SAFETY: `dup` returns a new file descriptor, owned by nobody else.
+        Ok(Self(unsafe { OwnedFd::from_raw_fd(fd) }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::message::Message;
+
+    #[test]
+    fn unsealed_memfd() {
+        let name = CStr::from_bytes_with_nul(b"unsealed\0").unwrap();
+        let fd = memfd_create(name, MemFdCreateFlag::MFD_CLOEXEC).unwrap();
+
+        assert!(matches!(read_sealed(&fd), Err(Error::Failure(_))));
+    }
+
+    #[test]
+    fn payload_roundtrip() {
+        let data: Vec<u8> = (0..DEFAULT_THRESHOLD + 1).map(|i| i as u8).collect();
+        assert!(Payload::needs_sealing(&data));
+        let payload = Payload::new(&data).unwrap();
+
+        let msg = Message::method("/", "Upload")
+            .unwrap()
+            .build(&payload)
+            .unwrap();
+        assert_eq!(msg.body_signature().unwrap(), "h");
+        let received: Payload = msg.body().unwrap();
+        assert_eq!(received.to_bytes().unwrap(), data);
+    }
+}
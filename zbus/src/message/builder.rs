@@ -1,5 +1,6 @@
 use std::{
     io::{Cursor, Write},
+    num::NonZeroU32,
     sync::Arc,
 };
 
@@ -12,9 +13,11 @@ use enumflags2::BitFlags;
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
 
 use crate::{
-    message::{Field, FieldCode, Fields, Flags, Header, Message, PrimaryHeader, Sequence, Type},
+    message::{
+        EndianSig, Field, FieldCode, Fields, Flags, Header, Message, PrimaryHeader, Sequence, Type,
+    },
     utils::padding_for_8_bytes,
-    zvariant::{DynamicType, EncodingContext, ObjectPath, Signature},
+    zvariant::{DynamicType, EncodingContext, ObjectPath, Signature, Str},
     Error, Result,
 };
 
@@ -26,12 +29,6 @@ type BuildGenericResult = Vec<RawFd>;
 #[cfg(not(unix))]
 type BuildGenericResult = ();
 
-macro_rules! dbus_context {
-    ($n_bytes_before: expr) => {
-        EncodingContext::<byteorder::NativeEndian>::new_dbus($n_bytes_before)
-    };
-}
-
 /// A builder for [`Message`]
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
@@ -178,6 +175,32 @@ impl<'a> Builder<'a> {
         Ok(self)
     }
 
+    /// Set the serial number of the message.
+    pub(crate) fn serial_num(mut self, serial_num: NonZeroU32) -> Self {
+        self.header.primary_mut().set_serial_num(serial_num);
+        self
+    }
+
+    /// Set the serial number of the message this message is a reply to.
+    pub(crate) fn reply_serial(mut self, serial: NonZeroU32) -> Self {
+        self.header.fields_mut().replace(Field::ReplySerial(serial));
+        self
+    }
+
+    /// Set the tracing context propagated along the message.
+    pub(crate) fn trace_context<'t: 'a>(mut self, context: Str<'t>) -> Self {
+        self.header
+            .fields_mut()
+            .replace(Field::TraceContext(context));
+        self
+    }
+
+    /// Unset the destination of the message.
+    pub(crate) fn no_destination(mut self) -> Self {
+        self.header.fields_mut().remove(FieldCode::Destination);
+        self
+    }
+
     fn reply_to(mut self, reply_to: &Header<'_>) -> Result<Self> {
         let serial = reply_to.primary().serial_num();
         self.header.fields_mut().replace(Field::ReplySerial(serial));
@@ -203,39 +226,42 @@ impl<'a> Builder<'a> {
     where
         B: serde::ser::Serialize + DynamicType,
     {
-        let ctxt = dbus_context!(0);
-
-        // Note: this iterates the body twice, but we prefer efficient handling of large messages
-        // to efficient handling of ones that are complex to serialize.
-        #[cfg(unix)]
-        let (body_len, fds_len) = zvariant::serialized_size_fds(ctxt, body)?;
-        #[cfg(not(unix))]
-        let body_len = zvariant::serialized_size(ctxt, body)?;
-
-        let signature = body.dynamic_signature();
-
-        self.build_generic(
-            signature,
-            body_len,
-            move |cursor| {
-                #[cfg(unix)]
-                {
-                    let (_, fds) = zvariant::to_writer_fds(cursor, ctxt, body)?;
-                    Ok::<Vec<RawFd>, Error>(fds)
-                }
-                #[cfg(not(unix))]
-                {
-                    zvariant::to_writer(cursor, ctxt, body)?;
-                    Ok::<(), Error>(())
-                }
-            },
+        let endian_sig = self.header.primary().endian_sig();
+        with_dbus_context!(endian_sig, 0, |ctxt| {
+            // Note: this iterates the body twice, but we prefer efficient handling of large
+            // messages to efficient handling of ones that are complex to serialize.
             #[cfg(unix)]
-            fds_len,
-        )
+            let (body_len, fds_len) = zvariant::serialized_size_fds(ctxt, body)?;
+            #[cfg(not(unix))]
+            let body_len = zvariant::serialized_size(ctxt, body)?;
+
+            let signature = body.dynamic_signature();
+
+            self.build_generic(
+                ctxt,
+                signature,
+                body_len,
+                move |cursor| {
+                    #[cfg(unix)]
+                    {
+                        let (_, fds) = zvariant::to_writer_fds(cursor, ctxt, body)?;
+                        Ok::<Vec<RawFd>, Error>(fds)
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        zvariant::to_writer(cursor, ctxt, body)?;
+                        Ok::<(), Error>(())
+                    }
+                },
+                #[cfg(unix)]
+                fds_len,
+            )
+        })
     }
 
     /// Create a new message from a raw slice of bytes to populate the body with, rather than by
-    /// serializing a value. The message body will be the exact bytes.
+    /// serializing a value. The message body will be the exact bytes, so they must be encoded in
+    /// the byte order of the message header.
     ///
     /// # Safety
     ///
@@ -254,7 +280,9 @@ impl<'a> Builder<'a> {
         #[cfg(unix)]
         let fds_len = fds.len();
 
-        self.build_generic(
+        let endian_sig = self.header.primary().endian_sig();
+        with_dbus_context!(endian_sig, 0, |ctxt| self.build_generic(
+            ctxt,
             signature,
             body_bytes.len(),
             move |cursor: &mut Cursor<&mut Vec<u8>>| {
@@ -268,20 +296,44 @@ impl<'a> Builder<'a> {
             },
             #[cfg(unix)]
             fds_len,
-        )
+        ))
+    }
+
+    /// Build the [`Message`] with the body (and file descriptors) of `msg`.
+    ///
+    /// Used for forwarding messages with a modified header. The file descriptors are not
+    /// duplicated, so `msg` must be kept alive until the new message is sent.
+    pub(crate) fn build_with_body_of(self, msg: &Message) -> Result<Message> {
+        // The body signature is given without the enclosing parentheses, which would otherwise be
+        // stripped off bodies made of multiple structures.
+        let signature = match msg.body_signature() {
+            Some(signature) if !signature.is_empty() => format!("({signature})"),
+            _ => String::new(),
+        };
+
+        // SAFETY: The body and its signature come from a valid message.
+        unsafe {
+            self.build_raw_body(
+                msg.body_as_bytes()?,
+                signature.as_str(),
+                #[cfg(unix)]
+                msg.fds(),
+            )
+        }
     }
 
-    fn build_generic<WriteFunc>(
+    fn build_generic<B, WriteFunc>(
         self,
+        ctxt: EncodingContext<B>,
         mut signature: Signature<'_>,
         body_len: usize,
         write_body: WriteFunc,
         #[cfg(unix)] fds_len: usize,
     ) -> Result<Message>
     where
+        B: byteorder::ByteOrder,
         WriteFunc: FnOnce(&mut Cursor<&mut Vec<u8>>) -> Result<BuildGenericResult>,
     {
-        let ctxt = dbus_context!(0);
         let mut header = self.header;
 
         if !signature.is_empty() {
@@ -8,7 +8,7 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
-use zvariant::{ObjectPath, Signature, Type, Value};
+use zvariant::{ObjectPath, Signature, Str, Type, Value};
 
 /// The message field code.
 ///
@@ -40,6 +40,29 @@ pub(super) enum FieldCode {
     Signature = 8,
     /// Code for [`Field::UnixFDs`](enum.Field.html#variant.UnixFDs)
     UnixFDs = 9,
+    /// Code for [`Field::TraceContext`](enum.Field.html#variant.TraceContext)
+    ///
+    /// This is a zbus extension, not part of the specification. Far from the codes of the
+    /// specification, not to conflict with any future standard field.
+    TraceContext = 128,
+}
+
+impl FieldCode {
+    fn from_u8(code: u8) -> Option<Self> {
+        Some(match code {
+            1 => Self::Path,
+            2 => Self::Interface,
+            3 => Self::Member,
+            4 => Self::ErrorName,
+            5 => Self::ReplySerial,
+            6 => Self::Destination,
+            7 => Self::Sender,
+            8 => Self::Signature,
+            9 => Self::UnixFDs,
+            128 => Self::TraceContext,
+            _ => return None,
+        })
+    }
 }
 
 assert_impl_all!(FieldCode: Send, Sync, Unpin);
@@ -57,6 +80,7 @@ impl<'f> Field<'f> {
             Field::Sender(_) => FieldCode::Sender,
             Field::Signature(_) => FieldCode::Signature,
             Field::UnixFDs(_) => FieldCode::UnixFDs,
+            Field::TraceContext(_) => FieldCode::TraceContext,
         }
     }
 }
@@ -92,6 +116,8 @@ pub(super) enum Field<'f> {
     Signature(Signature<'f>),
     /// The number of Unix file descriptors that accompany the message.
     UnixFDs(u32),
+    /// The tracing context propagated along a method call, e.g a W3C `traceparent`.
+    TraceContext(Str<'f>),
 }
 
 assert_impl_all!(Field<'_>: Send, Sync, Unpin);
@@ -117,6 +143,7 @@ impl<'f> Serialize for Field<'f> {
             Field::Sender(value) => (FieldCode::Sender, value.as_str().into()),
             Field::Signature(value) => (FieldCode::Signature, value.as_ref().into()),
             Field::UnixFDs(value) => (FieldCode::UnixFDs, (*value).into()),
+            Field::TraceContext(value) => (FieldCode::TraceContext, value.as_ref().into()),
         };
 
         tuple.serialize(serializer)
@@ -128,8 +155,36 @@ impl<'de: 'f, 'f> Deserialize<'de> for Field<'f> {
     where
         D: Deserializer<'de>,
     {
-        let (code, value) = <(FieldCode, Value<'_>)>::deserialize(deserializer)?;
-        Ok(match code {
+        MaybeField::deserialize(deserializer)?
+            .0
+            .ok_or_else(|| D::Error::custom("unknown field code"))
+    }
+}
+
+/// A [`Field`], or `None` if its code is unknown.
+///
+/// The specification requires unknown fields to be ignored, which [`Fields`] does through this.
+///
+/// [`Fields`]: struct.Fields.html
+pub(super) struct MaybeField<'f>(pub(super) Option<Field<'f>>);
+
+impl<'f> Type for MaybeField<'f> {
+    fn signature() -> Signature<'static> {
+        Field::signature()
+    }
+}
+
+impl<'de: 'f, 'f> Deserialize<'de> for MaybeField<'f> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (code, value) = <(u8, Value<'_>)>::deserialize(deserializer)?;
+        let code = match FieldCode::from_u8(code) {
+            Some(code) => code,
+            None => return Ok(Self(None)),
+        };
+        Ok(Self(Some(match code {
             FieldCode::Path => Field::Path(ObjectPath::try_from(value).map_err(D::Error::custom)?),
             FieldCode::Interface => {
                 Field::Interface(InterfaceName::try_from(value).map_err(D::Error::custom)?)
@@ -162,6 +217,11 @@ impl<'de: 'f, 'f> Deserialize<'de> for Field<'f> {
                 Field::Signature(Signature::try_from(value).map_err(D::Error::custom)?)
             }
             FieldCode::UnixFDs => Field::UnixFDs(u32::try_from(value).map_err(D::Error::custom)?),
-        })
+            FieldCode::TraceContext => match Str::try_from(value) {
+                Ok(value) => Field::TraceContext(value),
+                // Not a standard field, so other implementations could use its code differently.
+                Err(_) => return Ok(Self(None)),
+            },
+        })))
     }
 }
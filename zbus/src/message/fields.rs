@@ -1,11 +1,11 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use static_assertions::assert_impl_all;
 use std::num::NonZeroU32;
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
-use zvariant::{ObjectPath, Signature, Type};
+use zvariant::{ObjectPath, Signature, Str, Type};
 
 use crate::{
-    message::{Field, FieldCode, Header, Message},
+    message::{field::MaybeField, Field, FieldCode, Header, Message},
     Result,
 };
 
@@ -15,11 +15,23 @@ const MAX_FIELDS_IN_MESSAGE: usize = 16;
 /// A collection of [`Field`] instances.
 ///
 /// [`Field`]: enum.Field.html
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
-pub(super) struct Fields<'m>(#[serde(borrow)] Vec<Field<'m>>);
+#[derive(Debug, Clone, Serialize, Type)]
+pub(super) struct Fields<'m>(Vec<Field<'m>>);
 
 assert_impl_all!(Fields<'_>: Send, Sync, Unpin);
 
+impl<'de: 'm, 'm> Deserialize<'de> for Fields<'m> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Unknown fields are ignored, as required by the specification.
+        let fields = Vec::<MaybeField<'m>>::deserialize(deserializer)?;
+
+        Ok(Self(fields.into_iter().filter_map(|f| f.0).collect()))
+    }
+}
+
 impl<'m> Fields<'m> {
     /// Creates an empty collection of fields.
     pub fn new() -> Self {
@@ -152,6 +164,7 @@ pub(crate) struct QuickFields {
     sender: FieldPos,
     signature: FieldPos,
     unix_fds: Option<u32>,
+    trace_context: FieldPos,
 }
 
 impl QuickFields {
@@ -166,6 +179,7 @@ impl QuickFields {
             sender: FieldPos::new(buf, header.sender()),
             signature: FieldPos::new(buf, header.signature()),
             unix_fds: header.unix_fds(),
+            trace_context: FieldPos::new(buf, header.trace_context()),
         })
     }
 
@@ -204,6 +218,10 @@ impl QuickFields {
     pub fn unix_fds(&self) -> Option<u32> {
         self.unix_fds
     }
+
+    pub fn trace_context<'m>(&self, msg: &'m Message) -> Option<Str<'m>> {
+        self.trace_context.read(msg.as_bytes())
+    }
 }
 
 impl<'m> Default for Fields<'m> {
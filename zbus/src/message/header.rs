@@ -9,7 +9,7 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, UniqueName};
-use zvariant::{EncodingContext, ObjectPath, Signature, Type as VariantType};
+use zvariant::{EncodingContext, ObjectPath, Signature, Str, Type as VariantType};
 
 use crate::{
     message::{Field, FieldCode, Fields},
@@ -119,12 +119,28 @@ impl PrimaryHeader {
             flags: BitFlags::empty(),
             protocol_version: 1,
             body_len,
-            serial_num: SERIAL_NUM.fetch_add(1, SeqCst).try_into().unwrap(),
+            serial_num: next_serial_num(),
         }
     }
 
     pub(crate) fn read(buf: &[u8]) -> Result<(PrimaryHeader, u32), Error> {
-        let ctx = EncodingContext::<byteorder::NativeEndian>::new_dbus(0);
+        match EndianSig::try_from(buf[0])? {
+            EndianSig::Big => {
+                Self::read_with_context(buf, EncodingContext::<byteorder::BE>::new_dbus(0))
+            }
+            EndianSig::Little => {
+                Self::read_with_context(buf, EncodingContext::<byteorder::LE>::new_dbus(0))
+            }
+        }
+    }
+
+    fn read_with_context<B>(
+        buf: &[u8],
+        ctx: EncodingContext<B>,
+    ) -> Result<(PrimaryHeader, u32), Error>
+    where
+        B: byteorder::ByteOrder,
+    {
         let (primary_header, size) = zvariant::from_slice(buf, ctx)?;
         assert_eq!(size, PRIMARY_HEADER_SIZE);
         let (fields_len, _) = zvariant::from_slice(&buf[PRIMARY_HEADER_SIZE..], ctx)?;
@@ -321,10 +337,30 @@ impl<'m> Header<'m> {
     pub fn unix_fds(&self) -> Option<u32> {
         get_field_u32!(self, UnixFDs)
     }
+
+    /// The tracing context propagated along a method call.
+    ///
+    /// This is set by the [`CallObserver`] of the caller, through
+    /// [`CallObserver::trace_context`], to be extracted by the callee and continue the trace.
+    ///
+    /// **Note:** This is a zbus extension, stored in a header field that isn't part of the D-Bus
+    /// specification. Other implementations ignore it and message buses may strip it, so it's only
+    /// reliably propagated on peer-to-peer connections between zbus peers.
+    ///
+    /// [`CallObserver`]: crate::connection::CallObserver
+    /// [`CallObserver::trace_context`]: crate::connection::CallObserver::trace_context
+    pub fn trace_context(&self) -> Option<&Str<'m>> {
+        get_field!(self, TraceContext)
+    }
 }
 
 static SERIAL_NUM: AtomicU32 = AtomicU32::new(1);
 
+/// Allocate a new serial number for an outgoing message.
+pub(crate) fn next_serial_num() -> NonZeroU32 {
+    SERIAL_NUM.fetch_add(1, SeqCst).try_into().unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::message::{Field, Fields, Header, PrimaryHeader, Type};
@@ -15,6 +15,22 @@ use crate::{
     Error, Result,
 };
 
+// Evaluate `$body` with `$ctxt` set to the D-Bus encoding context for the given endianness.
+macro_rules! with_dbus_context {
+    ($endian_sig: expr, $n_bytes_before: expr, |$ctxt: ident| $body: expr) => {
+        match $endian_sig {
+            EndianSig::Big => {
+                let $ctxt = EncodingContext::<byteorder::BE>::new_dbus($n_bytes_before);
+                $body
+            }
+            EndianSig::Little => {
+                let $ctxt = EncodingContext::<byteorder::LE>::new_dbus($n_bytes_before);
+                $body
+            }
+        }
+    };
+}
+
 mod builder;
 pub use builder::Builder;
 
@@ -28,12 +44,6 @@ pub(crate) mod header;
 use header::MIN_MESSAGE_SIZE;
 pub use header::{EndianSig, Flags, Header, PrimaryHeader, Type, NATIVE_ENDIAN_SIG};
 
-macro_rules! dbus_context {
-    ($n_bytes_before: expr) => {
-        EncodingContext::<byteorder::NativeEndian>::new_dbus($n_bytes_before)
-    };
-}
-
 #[cfg(unix)]
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum Fds {
@@ -165,12 +175,10 @@ impl Message {
         #[cfg(unix)] fds: Vec<OwnedFd>,
         recv_seq: u64,
     ) -> Result<Self> {
-        if EndianSig::try_from(bytes[0])? != NATIVE_ENDIAN_SIG {
-            return Err(Error::IncorrectEndian);
-        }
-
         let (primary_header, fields_len) = PrimaryHeader::read(&bytes)?;
-        let (header, _) = zvariant::from_slice(&bytes, dbus_context!(0))?;
+        let (header, _) = with_dbus_context!(primary_header.endian_sig(), 0, |ctxt| {
+            zvariant::from_slice(&bytes, ctxt)
+        })?;
         #[cfg(unix)]
         let fds = Fds::Owned(fds);
 
@@ -191,6 +199,16 @@ impl Message {
         })
     }
 
+    /// Read the header at the start of `bytes`, which don't need to include the body.
+    pub(crate) fn read_header(bytes: &[u8]) -> Result<Header<'_>> {
+        let (primary_header, _) = PrimaryHeader::read(bytes)?;
+        let (header, _) = with_dbus_context!(primary_header.endian_sig(), 0, |ctxt| {
+            zvariant::from_slice(bytes, ctxt)
+        })?;
+
+        Ok(header)
+    }
+
     /// The signature of the body.
     ///
     /// **Note:** While zbus treats multiple arguments as a struct (to allow you to use the tuple
@@ -201,6 +219,51 @@ impl Message {
         self.inner.quick_fields.signature(self)
     }
 
+    /// Check that the signature of the body is `expected`.
+    ///
+    /// This is useful to catch an unexpected reply from a service before attempting to
+    /// deserialize it, e.g if the service's API changed. Just like [`Message::body_signature`],
+    /// `expected` is the signature on the wire so multiple arguments must not be enclosed in
+    /// parenthesis. An empty signature means no body.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::BodySignatureMismatch`] if the signatures don't match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use zbus::{message::Message, Error};
+    /// # (|| -> zbus::Result<()> {
+    /// let message = Message::method("/", "ping")?.build(&(7u32, "foo"))?;
+    ///
+    /// message.check_body_signature("us")?;
+    /// assert!(matches!(
+    ///     message.check_body_signature("s"),
+    ///     Err(Error::BodySignatureMismatch(_, _)),
+    /// ));
+    /// # Ok(()) })().unwrap()
+    /// ```
+    pub fn check_body_signature<'s, S>(&self, expected: S) -> Result<()>
+    where
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<Error>,
+    {
+        let expected = expected.try_into().map_err(Into::into)?;
+        let found = self
+            .body_signature()
+            .unwrap_or_else(|| Signature::from_static_str_unchecked(""));
+
+        if found == expected {
+            Ok(())
+        } else {
+            Err(Error::BodySignatureMismatch(
+                expected.into_owned().into(),
+                found.to_owned().into(),
+            ))
+        }
+    }
+
     pub fn primary_header(&self) -> &PrimaryHeader {
         &self.inner.primary_header
     }
@@ -240,6 +303,9 @@ impl Message {
         if let Some(u) = quick_fields.unix_fds() {
             fields.add(Field::UnixFDs(u));
         }
+        if let Some(t) = quick_fields.trace_context(self) {
+            fields.add(Field::TraceContext(t));
+        }
 
         Header::new(self.inner.primary_header.clone(), fields)
     }
@@ -278,23 +344,20 @@ impl Message {
     where
         B: serde::de::Deserialize<'d> + VariantType,
     {
-        {
+        with_dbus_context!(self.inner.primary_header.endian_sig(), 0, |ctxt| {
             #[cfg(unix)]
             {
                 zvariant::from_slice_fds(
                     &self.inner.bytes[self.inner.body_offset..],
                     Some(&self.fds()),
-                    dbus_context!(0),
+                    ctxt,
                 )
             }
             #[cfg(not(unix))]
             {
-                zvariant::from_slice(
-                    &self.inner.bytes[self.inner.body_offset..],
-                    dbus_context!(0),
-                )
+                zvariant::from_slice(&self.inner.bytes[self.inner.body_offset..], ctxt)
             }
-        }
+        })
         .map_err(Error::from)
         .map(|b| b.0)
     }
@@ -332,13 +395,13 @@ impl Message {
             .body_signature()
             .unwrap_or_else(|| Signature::from_static_str_unchecked(""));
 
-        {
+        with_dbus_context!(self.inner.primary_header.endian_sig(), 0, |ctxt| {
             #[cfg(unix)]
             {
                 zvariant::from_slice_fds_for_dynamic_signature(
                     &self.inner.bytes[self.inner.body_offset..],
                     Some(&self.fds()),
-                    dbus_context!(0),
+                    ctxt,
                     &body_sig,
                 )
             }
@@ -346,15 +409,76 @@ impl Message {
             {
                 zvariant::from_slice_for_dynamic_signature(
                     &self.inner.bytes[self.inner.body_offset..],
-                    dbus_context!(0),
+                    ctxt,
                     &body_sig,
                 )
             }
-        }
+        })
         .map_err(Error::from)
         .map(|b| b.0)
     }
 
+    /// Deserialize the body using the contained signature, consuming the message.
+    ///
+    /// Unlike with [`Message::body`], the [`OwnedFd`]s in the body take over the file descriptors
+    /// the message received, instead of duplicating them. That is only possible if this is the
+    /// last clone of a received message though. Otherwise, this behaves just like `body`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use zbus::message::Message;
+    /// # (|| -> zbus::Result<()> {
+    /// let message = Message::method("/", "ping")?.build(&(7u32, "foo"))?;
+    /// let (n, s): (u32, String) = message.into_body()?;
+    /// assert_eq!(n, 7);
+    /// assert_eq!(s, "foo");
+    /// # Ok(()) })().unwrap()
+    /// ```
+    pub fn into_body<B>(self) -> Result<B>
+    where
+        B: for<'d> zvariant::DynamicDeserialize<'d>,
+    {
+        #[cfg(unix)]
+        let msg = {
+            let body_sig = self
+                .body_signature()
+                .unwrap_or_else(|| Signature::from_static_str_unchecked(""))
+                .to_owned();
+
+            match Arc::try_unwrap(self.inner) {
+                Ok(Inner {
+                    primary_header,
+                    bytes,
+                    body_offset,
+                    fds: Fds::Owned(fds),
+                    ..
+                }) => {
+                    let fds = fds.into_iter().map(Into::into).collect();
+
+                    return with_dbus_context!(primary_header.endian_sig(), 0, |ctxt| {
+                        zvariant::from_slice_owned_fds_for_dynamic_signature(
+                            &bytes[body_offset..],
+                            fds,
+                            ctxt,
+                            &body_sig,
+                        )
+                    })
+                    .map_err(Error::from)
+                    .map(|b| b.0);
+                }
+                Ok(inner) => Self {
+                    inner: Arc::new(inner),
+                },
+                Err(inner) => Self { inner },
+            }
+        };
+        #[cfg(not(unix))]
+        let msg = self;
+
+        msg.body()
+    }
+
     #[cfg(unix)]
     pub(crate) fn fds(&self) -> Vec<RawFd> {
         match &self.inner.fds {
@@ -476,8 +600,12 @@ mod tests {
 
     #[cfg(unix)]
     use super::Fds;
-    use super::Message;
-    use crate::Error;
+    use super::{Builder, EndianSig, Message};
+    use crate::{
+        utils::padding_for_8_bytes,
+        zvariant::{EncodingContext, ObjectPath, Signature, Value},
+        Error,
+    };
 
     #[test]
     fn test() {
@@ -505,6 +633,12 @@ mod tests {
             body.unwrap_err(),
             Error::Variant(zvariant::Error::SignatureMismatch { .. })
         ));
+        m.check_body_signature(if cfg!(unix) { "hs" } else { "s" })
+            .unwrap();
+        assert!(matches!(
+            m.check_body_signature("u").unwrap_err(),
+            Error::BodySignatureMismatch(expected, _) if expected.as_str() == "u"
+        ));
 
         assert_eq!(m.to_string(), "Method call do from :1.72");
         let r = Message::method_reply(&m)
@@ -518,4 +652,148 @@ mod tests {
             .unwrap();
         assert_eq!(e.to_string(), "Error org.freedesktop.zbus.Error: kaboom!");
     }
+
+    #[test]
+    fn unknown_fields() {
+        let m = Message::method("/org/zbus", "Ping")
+            .unwrap()
+            .trace_context("00-abc-def-01".into())
+            .build(&42u32)
+            .unwrap();
+        assert_eq!(m.header().trace_context().unwrap(), "00-abc-def-01");
+
+        // The same message, with an unknown field code instead of the trace context one.
+        let mut bytes = m.as_bytes().to_vec();
+        let pos = bytes
+            .windows(4)
+            .position(|w| w == [128, 1, b's', 0])
+            .unwrap();
+        bytes[pos] = 200;
+        let m = Message::from_raw_parts(
+            bytes,
+            #[cfg(unix)]
+            vec![],
+            0,
+        )
+        .unwrap();
+        let header = m.header();
+        assert_eq!(header.trace_context(), None);
+        assert_eq!(header.path().unwrap(), "/org/zbus");
+        assert_eq!(header.member().unwrap(), "Ping");
+        assert_eq!(m.body::<u32>().unwrap(), 42);
+
+        // A trace context field that isn't a string, as other implementations could use the
+        // field code for their own purpose.
+        let fields: Vec<(u8, Value<'_>)> = vec![
+            (1, ObjectPath::try_from("/org/zbus").unwrap().into()),
+            (3, Value::from("Ping")),
+            (8, Signature::try_from("u").unwrap().into()),
+            (128, Value::U32(7)),
+        ];
+        let ctxt = EncodingContext::<byteorder::NativeEndian>::new_dbus(0);
+        let mut bytes = zvariant::to_bytes(ctxt, &(m.primary_header(), fields)).unwrap();
+        bytes.resize(bytes.len() + padding_for_8_bytes(bytes.len()), 0);
+        bytes.extend(zvariant::to_bytes(ctxt, &42u32).unwrap());
+        let m = Message::from_raw_parts(
+            bytes,
+            #[cfg(unix)]
+            vec![],
+            0,
+        )
+        .unwrap();
+        let header = m.header();
+        assert_eq!(header.trace_context(), None);
+        assert_eq!(header.path().unwrap(), "/org/zbus");
+        assert_eq!(header.member().unwrap(), "Ping");
+        assert_eq!(m.body::<u32>().unwrap(), 42);
+    }
+
+    #[test]
+    fn foreign_endian() {
+        let m = Message::method("/org/zbus", "Ping")
+            .unwrap()
+            .interface("org.zbus.Test")
+            .unwrap()
+            .build(&(42u32, "foo", vec![1i64, 2]))
+            .unwrap();
+        let body: (u32, &str, Vec<i64>) = m.body().unwrap();
+
+        // Encode the same message in both byte orders, regardless of the native one.
+        for endian_sig in [EndianSig::Big, EndianSig::Little] {
+            let mut header = m.header();
+            header.primary_mut().set_endian_sig(endian_sig);
+            let bytes = with_dbus_context!(endian_sig, 0, |ctxt| {
+                let mut bytes = zvariant::to_bytes(ctxt, &header).unwrap();
+                bytes.resize(m.inner.body_offset, 0);
+                bytes.extend(zvariant::to_bytes(ctxt, &body).unwrap());
+
+                bytes
+            });
+            assert_eq!(bytes.len(), m.as_bytes().len());
+
+            let decoded = unsafe {
+                Message::from_bytes(
+                    bytes,
+                    #[cfg(unix)]
+                    vec![],
+                )
+            }
+            .unwrap();
+            assert_eq!(decoded.primary_header().endian_sig(), endian_sig);
+            assert_eq!(
+                decoded.primary_header().serial_num(),
+                m.primary_header().serial_num()
+            );
+            let header = decoded.header();
+            assert_eq!(header.member().unwrap().as_str(), "Ping");
+            assert_eq!(header.interface().unwrap().as_str(), "org.zbus.Test");
+            assert_eq!(decoded.body::<(u32, &str, Vec<i64>)>().unwrap(), body);
+
+            // Relayed messages keep the byte order of their body.
+            let relayed = Builder::from(decoded.header())
+                .destination("org.zbus.Relayed")
+                .unwrap()
+                .build_with_body_of(&decoded)
+                .unwrap();
+            let relayed = unsafe {
+                Message::from_bytes(
+                    relayed.as_bytes().to_vec(),
+                    #[cfg(unix)]
+                    vec![],
+                )
+            }
+            .unwrap();
+            assert_eq!(relayed.primary_header().endian_sig(), endian_sig);
+            let header = relayed.header();
+            assert_eq!(header.member().unwrap().as_str(), "Ping");
+            assert_eq!(header.destination().unwrap().as_str(), "org.zbus.Relayed");
+            assert_eq!(relayed.body::<(u32, &str, Vec<i64>)>().unwrap(), body);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn into_body_takes_over_fds() {
+        use std::os::unix::io::OwnedFd as StdOwnedFd;
+
+        let file = std::fs::File::open("Cargo.toml").unwrap();
+        let raw_fd = file.as_raw_fd();
+        let bytes = Message::method("/", "do")
+            .unwrap()
+            .build(&(Fd::from(&file), "foo"))
+            .unwrap()
+            .as_bytes()
+            .to_vec();
+
+        // As if the message was received along with the file.
+        let m = unsafe { Message::from_bytes(bytes, vec![StdOwnedFd::from(file).into()]) }.unwrap();
+        // Other clones of the message keep the file descriptors they own.
+        let (fd, s): (zvariant::OwnedFd, String) = m.clone().into_body().unwrap();
+        assert_ne!(fd.as_raw_fd(), raw_fd);
+        assert_eq!(s, "foo");
+        drop(fd);
+
+        let (fd, _): (zvariant::OwnedFd, String) = m.into_body().unwrap();
+        assert_eq!(fd.as_raw_fd(), raw_fd);
+    }
 }
@@ -1,12 +1,17 @@
 use std::{
+    fmt,
+    future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 
-use async_broadcast::Receiver as ActiveReceiver;
+use async_broadcast::{Receiver as ActiveReceiver, TryRecvError};
 use futures_core::stream;
-use futures_util::stream::FusedStream;
+use futures_util::{ready, stream::FusedStream};
 use ordered_stream::{OrderedStream, PollResult};
 use static_assertions::assert_impl_all;
 use tracing::warn;
@@ -14,9 +19,31 @@ use tracing::warn;
 use crate::{
     connection::ConnectionInner,
     message::{Message, Sequence},
-    AsyncDrop, Connection, MatchRule, OwnedMatchRule, Result,
+    AsyncDrop, Connection, Error, MatchRule, OwnedMatchRule, Result,
 };
 
+/// What to do when the message queue of a [`MessageStream`] is full.
+///
+/// See [`MessageStream::set_overflow_policy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OverflowPolicy {
+    /// Stop reading from the socket until there is room in the queue (default).
+    ///
+    /// No message is ever lost but a stream that isn't polled blocks the reception of all
+    /// messages on the connection.
+    #[default]
+    Block,
+    /// Drop the oldest message in the queue to make room for the new one.
+    DropOldest,
+    /// Drop the new message.
+    DropNewest,
+    /// Same as [`OverflowPolicy::DropOldest`] but the stream yields an
+    /// [`Error::MessagesDropped`] item, with the number of messages dropped, before the messages
+    /// following the dropped ones.
+    Report,
+}
+
 /// A [`stream::Stream`] implementation that yields [`Message`] items.
 ///
 /// You can convert a [`Connection`] to this type and back to [`Connection`].
@@ -135,13 +162,12 @@ impl MessageStream {
         R::Error: Into<crate::Error>,
     {
         let rule = rule.try_into().map_err(Into::into)?;
-        let msg_receiver = conn.add_match(rule.clone(), max_queued).await?;
+        let (msg_receiver, drop_newest) = conn.add_match(rule.clone(), max_queued).await?;
+
+        let mut stream = Self::for_subscription_channel(msg_receiver, Some(rule), conn);
+        stream.inner.drop_newest = Some(drop_newest);
 
-        Ok(Self::for_subscription_channel(
-            msg_receiver,
-            Some(rule),
-            conn,
-        ))
+        Ok(stream)
     }
 
     /// The associated match rule, if any.
@@ -164,6 +190,52 @@ impl MessageStream {
         self.inner.msg_receiver.set_capacity(max_queued);
     }
 
+    /// The policy applied when the message queue of this stream is full.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.inner.overflow_policy
+    }
+
+    /// Set the policy to apply when the message queue of this stream is full.
+    ///
+    /// The queue is shared by all the streams created for the same match rule (or from the same
+    /// connection, for the unfiltered streams) so the policy applies to all of them. The only
+    /// exception is the reporting of dropped messages by [`OverflowPolicy::Report`], which is
+    /// specific to each stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use futures_util::StreamExt;
+    /// use zbus::{Connection, Error, MatchRule, MessageStream, OverflowPolicy};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let rule = MatchRule::builder()
+    ///     .msg_type(zbus::message::Type::Signal)
+    ///     .interface("org.freedesktop.Notifications")?
+    ///     .build();
+    /// let mut stream = MessageStream::for_match_rule(rule, &conn, Some(16)).await?;
+    /// stream.set_overflow_policy(OverflowPolicy::Report);
+    ///
+    /// while let Some(msg) = stream.next().await {
+    ///     match msg {
+    ///         Ok(msg) => println!("{msg}"),
+    ///         Err(Error::MessagesDropped(n)) => println!("Missed {n} signals"),
+    ///         Err(e) => return Err(e),
+    ///     }
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        let overflow = matches!(policy, OverflowPolicy::DropOldest | OverflowPolicy::Report);
+        self.inner.msg_receiver.set_overflow(overflow);
+        if let Some(drop_newest) = &self.inner.drop_newest {
+            drop_newest.store(policy == OverflowPolicy::DropNewest, Ordering::Relaxed);
+        }
+        self.inner.overflow_policy = policy;
+    }
+
     pub(crate) fn for_subscription_channel(
         msg_receiver: ActiveReceiver<Result<Message>>,
         rule: Option<OwnedMatchRule>,
@@ -176,6 +248,9 @@ impl MessageStream {
                 conn_inner,
                 msg_receiver,
                 match_rule: rule,
+                overflow_policy: OverflowPolicy::default(),
+                drop_newest: None,
+                waiter: Waiter::default(),
             },
         }
     }
@@ -187,6 +262,36 @@ impl stream::Stream for MessageStream {
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
 
+        if this.inner.overflow_policy == OverflowPolicy::Report {
+            // The `Stream` implementation of the receiver silently skips over overflows, so they're
+            // only ever taken from `try_recv`, which reports and clears them in one go.
+            loop {
+                // Wait for the next message through a separate receiver, created before checking
+                // the queue so it can't miss a message arriving right after.
+                let waiter = this.inner.waiter.0.get_or_insert_with(|| {
+                    let mut receiver = this.inner.msg_receiver.new_receiver();
+
+                    Box::pin(async move {
+                        let _ = receiver.recv().await;
+                    })
+                });
+                let item = match this.inner.msg_receiver.try_recv() {
+                    Ok(msg) => Some(msg),
+                    Err(TryRecvError::Overflowed(n)) => Some(Err(Error::MessagesDropped(n))),
+                    Err(TryRecvError::Closed) => None,
+                    Err(TryRecvError::Empty) => {
+                        ready!(waiter.as_mut().poll(cx));
+                        this.inner.waiter.0 = None;
+
+                        continue;
+                    }
+                };
+                this.inner.waiter.0 = None;
+
+                return Poll::Ready(item);
+            }
+        }
+
         Pin::new(&mut this.inner.msg_receiver).poll_next(cx)
     }
 }
@@ -240,12 +345,16 @@ impl From<Connection> for MessageStream {
     fn from(conn: Connection) -> Self {
         let conn_inner = conn.inner;
         let msg_receiver = conn_inner.msg_receiver.activate_cloned();
+        let drop_newest = Some(conn_inner.msg_drop_newest.clone());
 
         Self {
             inner: Inner {
                 conn_inner,
                 msg_receiver,
                 match_rule: None,
+                overflow_policy: OverflowPolicy::default(),
+                drop_newest,
+                waiter: Waiter::default(),
             },
         }
     }
@@ -276,6 +385,28 @@ struct Inner {
     conn_inner: Arc<ConnectionInner>,
     msg_receiver: ActiveReceiver<Result<Message>>,
     match_rule: Option<OwnedMatchRule>,
+    overflow_policy: OverflowPolicy,
+    // Shared with the socket reader. `None` for internal streams.
+    drop_newest: Option<Arc<AtomicBool>>,
+    waiter: Waiter,
+}
+
+// Waits for a message to be available, when reporting overflows.
+#[derive(Default)]
+struct Waiter(Option<Pin<Box<dyn Future<Output = ()> + Send + Sync>>>);
+
+impl Clone for Waiter {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl fmt::Debug for Waiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Waiter")
+            .field("waiting", &self.0.is_some())
+            .finish()
+    }
 }
 
 impl Drop for Inner {
@@ -0,0 +1,535 @@
+use event_listener::Event;
+use futures_util::{future, pin_mut, StreamExt};
+use static_assertions::assert_impl_all;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tracing::trace;
+use zbus_names::{BusName, OwnedUniqueName};
+
+use crate::{
+    abstractions::timer::sleep,
+    fdo::{DBusProxy, NameOwnerChanged},
+    message::{Message, Type},
+    proxy::CacheProperties,
+    Connection, MatchRule, MessageStream, Result,
+};
+
+/// The method call activity of an [`ObjectServer`](super::ObjectServer).
+///
+/// Used by [`IdleMonitor`] and [`ObjectServer::shutdown`](super::ObjectServer::shutdown).
+#[derive(Debug, Default)]
+pub(crate) struct Activity {
+    // Number of method calls being dispatched.
+    in_flight: AtomicUsize,
+    // Notified when a method call starts or finishes being dispatched, and when a client goes
+    // away.
+    event: Event,
+    track_clients: AtomicBool,
+    // Set once `ObjectServer::shutdown` has been called.
+    shutting_down: AtomicBool,
+    // The unique names of the peers that called a method, if `track_clients` is set.
+    clients: Mutex<HashSet<OwnedUniqueName>>,
+}
+
+impl Activity {
+    /// Record the start of a method call dispatch, lasting until the returned guard is dropped.
+    pub(crate) fn call_started(&self, msg: &Message) -> CallGuard<'_> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        if self.track_clients.load(Ordering::Relaxed) {
+            if let Some(sender) = msg.header().sender() {
+                self.clients
+                    .lock()
+                    .expect("lock poisoned")
+                    .insert(sender.to_owned().into());
+            }
+        }
+        self.event.notify(usize::MAX);
+
+        CallGuard(self)
+    }
+
+    fn client_gone(&self, name: &str) {
+        if self.clients.lock().expect("lock poisoned").remove(name) {
+            self.event.notify(usize::MAX);
+        }
+    }
+
+    pub(crate) fn start_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Wait for all the method calls being dispatched to complete.
+    pub(crate) async fn calls_done(&self) {
+        loop {
+            let listener = self.event.listen();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            listener.await;
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.in_flight.load(Ordering::SeqCst) == 0
+            && self.clients.lock().expect("lock poisoned").is_empty()
+    }
+}
+
+/// Marks the end of a method call dispatch when dropped, even if the dispatch is cancelled.
+pub(crate) struct CallGuard<'a>(&'a Activity);
+
+impl Drop for CallGuard<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.0.event.notify(usize::MAX);
+    }
+}
+
+/// Waits for the [`ObjectServer`](super::ObjectServer) of a connection to be idle.
+///
+/// Bus-activated services are expected to exit after a while without any activity. `IdleMonitor`
+/// helps implementing that: [`IdleMonitor::wait`] resolves once no method call has been handled for
+/// the given duration. A method call taking longer than that to handle doesn't count as idle time.
+///
+/// Optionally, the monitor can also [track the clients](IdleMonitor::track_clients) of the
+/// service, in which case the service is only considered idle once all the peers that called any of
+/// its methods have disconnected from the bus. This is useful for services keeping per-client state
+/// that would otherwise be lost.
+///
+/// # Example
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use std::time::Duration;
+/// use zbus::{connection, object_server::IdleMonitor};
+///
+/// struct Greeter;
+///
+/// #[zbus::dbus_interface(name = "org.zbus.Greeter1")]
+/// impl Greeter {
+///     fn say_hello(&self, name: &str) -> String {
+///         format!("Hello {name}!")
+///     }
+/// }
+///
+/// let conn = connection::Builder::session()?
+///     .serve_at("/org/zbus/Greeter", Greeter)?
+///     .build()
+///     .await?;
+/// // Create the monitor before requesting the name, so no client is missed.
+/// let idle = IdleMonitor::new(&conn, Duration::from_secs(30)).track_clients();
+/// conn.request_name("org.zbus.Greeter").await?;
+///
+/// idle.wait().await?;
+/// // Nobody needs us anymore.
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct IdleMonitor {
+    conn: Connection,
+    timeout: Duration,
+    track_clients: bool,
+}
+
+assert_impl_all!(IdleMonitor: Send, Sync, Unpin);
+
+impl IdleMonitor {
+    /// Create a monitor for the object server of `conn`, considering it idle after `timeout`
+    /// without activity.
+    pub fn new(conn: &Connection, timeout: Duration) -> Self {
+        Self {
+            conn: conn.clone(),
+            timeout,
+            track_clients: false,
+        }
+    }
+
+    /// Also wait for all the clients to disconnect.
+    ///
+    /// From this call on, the unique names of the peers calling methods on the object server are
+    /// recorded, so the monitor should be created before the service becomes reachable (e.g before
+    /// requesting its well-known name).
+    ///
+    /// This has no effect on peer-to-peer connections.
+    #[must_use]
+    pub fn track_clients(mut self) -> Self {
+        if self.conn.is_bus() {
+            self.conn
+                .object_server()
+                .activity()
+                .track_clients
+                .store(true, Ordering::Relaxed);
+            self.track_clients = true;
+        }
+
+        self
+    }
+
+    /// Wait until the object server has been idle for the configured duration.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the clients are tracked and their presence on the bus couldn't be checked.
+    pub async fn wait(&self) -> Result<()> {
+        let server = self.conn.object_server();
+        let activity = server.activity();
+
+        let mut clients_gone = if self.track_clients {
+            Some(self.watch_clients().await?)
+        } else {
+            None
+        };
+
+        loop {
+            let listener = activity.event.listen();
+            if !activity.is_idle() {
+                trace!("Object server busy, waiting for activity to cease");
+                wait_or_client_gone(listener, clients_gone.as_mut(), activity).await;
+
+                continue;
+            }
+
+            let timeout = sleep(self.timeout);
+            pin_mut!(timeout);
+            let activity_or_client_gone =
+                wait_or_client_gone(listener, clients_gone.as_mut(), activity);
+            pin_mut!(activity_or_client_gone);
+            if let future::Either::Left(_) = future::select(timeout, activity_or_client_gone).await
+            {
+                trace!("Object server idle for {:?}", self.timeout);
+
+                return Ok(());
+            }
+        }
+    }
+
+    // Subscribe to clients leaving the bus and forget about the ones that already did.
+    async fn watch_clients(&self) -> Result<MessageStream> {
+        let rule = MatchRule::builder()
+            .msg_type(Type::Signal)
+            .sender("org.freedesktop.DBus")?
+            .interface("org.freedesktop.DBus")?
+            .member("NameOwnerChanged")?
+            // Only interested in names being released.
+            .arg(2, "")?
+            .build();
+        let stream = MessageStream::for_match_rule(rule, &self.conn, None).await?;
+
+        let server = self.conn.object_server();
+        let activity = server.activity();
+        let clients: Vec<_> = activity
+            .clients
+            .lock()
+            .expect("lock poisoned")
+            .iter()
+            .cloned()
+            .collect();
+        let dbus = DBusProxy::builder(&self.conn)
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        for client in clients {
+            if !dbus.name_has_owner(BusName::from(&client)).await? {
+                activity.client_gone(client.as_str());
+            }
+        }
+
+        Ok(stream)
+    }
+}
+
+// Wait for `listener` to be notified, handling clients leaving the bus meanwhile.
+async fn wait_or_client_gone(
+    listener: event_listener::EventListener,
+    clients_gone: Option<&mut MessageStream>,
+    activity: &Activity,
+) {
+    let clients_gone = match clients_gone {
+        Some(stream) => stream,
+        None => return listener.await,
+    };
+
+    pin_mut!(listener);
+    loop {
+        match future::select(&mut listener, clients_gone.next()).await {
+            future::Either::Left(_) => return,
+            future::Either::Right((Some(Ok(msg)), _)) => {
+                let name = NameOwnerChanged::from_message(msg).and_then(|s| {
+                    s.args().ok().and_then(|args| match args.name() {
+                        BusName::Unique(name) => Some(name.to_string()),
+                        BusName::WellKnown(_) => None,
+                    })
+                });
+                if let Some(name) = name {
+                    trace!("Client `{name}` left the bus");
+                    activity.client_gone(&name);
+                }
+            }
+            future::Either::Right((Some(Err(e)), _)) => {
+                trace!("Error receiving `NameOwnerChanged` signal: {e}");
+            }
+            // Connection closed, nothing else will happen.
+            future::Either::Right((None, _)) => return listener.await,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use ntest::timeout;
+    use std::time::Instant;
+    use test_log::test;
+
+    use super::*;
+    use crate::{connection::Builder, Guid};
+
+    #[test]
+    #[timeout(15000)]
+    fn idle_monitor() {
+        crate::utils::block_on(test_idle_monitor()).unwrap();
+    }
+
+    async fn test_idle_monitor() -> Result<()> {
+        use std::sync::Arc;
+
+        // A flag, and an event notified when it's set.
+        #[derive(Default)]
+        struct Flag {
+            set: AtomicBool,
+            event: Event,
+        }
+
+        impl Flag {
+            fn set(&self) {
+                self.set.store(true, Ordering::SeqCst);
+                self.event.notify(usize::MAX);
+            }
+
+            fn is_set(&self) -> bool {
+                self.set.load(Ordering::SeqCst)
+            }
+
+            async fn wait(&self) {
+                while !self.is_set() {
+                    let listener = self.event.listen();
+                    if self.is_set() {
+                        break;
+                    }
+                    listener.await;
+                }
+            }
+        }
+
+        struct Holder {
+            started: Arc<Flag>,
+            released: Arc<Flag>,
+        }
+
+        #[crate::dbus_interface(name = "org.zbus.Holder")]
+        impl Holder {
+            async fn hold(&self) {
+                self.started.set();
+                self.released.wait().await;
+            }
+        }
+
+        let started = Arc::new(Flag::default());
+        let released = Arc::new(Flag::default());
+        let server = Builder::session()?
+            .serve_at(
+                "/",
+                Holder {
+                    started: started.clone(),
+                    released: released.clone(),
+                },
+            )?
+            .build()
+            .await?;
+        let client = Builder::session()?.build().await?;
+        let call = || {
+            client.call_method(
+                server.unique_name().map(|n| n.as_ref()),
+                "/",
+                Some("org.zbus.Holder"),
+                "Hold",
+                &(),
+            )
+        };
+        // Leave a broken monitor some time to find the server idle too early.
+        let step = || sleep(Duration::from_millis(50));
+
+        // The server is busy until the call in flight returns.
+        let monitor = IdleMonitor::new(&server, Duration::from_millis(10));
+        let call_done = Flag::default();
+        let (reply, idle) = futures_util::join!(
+            async {
+                let reply = call().await;
+                call_done.set();
+
+                reply
+            },
+            async {
+                started.wait().await;
+                let (idle, ()) = futures_util::join!(
+                    async { monitor.wait().await.map(|()| call_done.is_set()) },
+                    async {
+                        step().await;
+                        released.set();
+                    },
+                );
+
+                idle
+            },
+        );
+        reply?;
+        assert!(idle?, "idle while a call was in flight");
+
+        // When tracking clients, the server is busy until they're gone.
+        let monitor = IdleMonitor::new(&server, Duration::from_millis(10)).track_clients();
+        call().await?;
+        let client_gone = Flag::default();
+        let (idle, ()) = futures_util::join!(
+            async { monitor.wait().await.map(|()| client_gone.is_set()) },
+            async {
+                step().await;
+                client_gone.set();
+                client.clone().close().await.unwrap();
+            },
+        );
+        assert!(idle?, "idle while a client was connected");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn shutdown() {
+        crate::utils::block_on(test_shutdown()).unwrap();
+    }
+
+    async fn test_shutdown() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Sleeper;
+
+        #[crate::dbus_interface(name = "org.zbus.Sleeper")]
+        impl Sleeper {
+            async fn sleep(&self, ms: u64) -> u64 {
+                sleep(Duration::from_millis(ms)).await;
+
+                ms
+            }
+        }
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .name("org.zbus.Sleeper")?
+                .serve_at("/", Sleeper)?
+                .build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+        let call = |ms: u64| {
+            let client = &client;
+            async move {
+                client
+                    .call_method(None::<()>, "/", Some("org.zbus.Sleeper"), "Sleep", &ms)
+                    .await?
+                    .body::<u64>()
+            }
+        };
+
+        let shutdown = async {
+            sleep(Duration::from_millis(50)).await;
+            let object_server = server.object_server();
+            let (res, rejected) =
+                futures_util::join!(object_server.shutdown(Duration::from_secs(5)), call(0),);
+            assert!(rejected.is_err());
+
+            res
+        };
+        // The call in flight when the shutdown started still got its reply.
+        let (reply, ()) = futures_util::try_join!(call(200), shutdown)?;
+        assert_eq!(reply, 200);
+        assert!(server.registered_names().await.is_empty());
+
+        // The connection is closed.
+        assert!(call(0).await.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn shutdown_from_handler() {
+        crate::utils::block_on(test_shutdown_from_handler()).unwrap();
+    }
+
+    async fn test_shutdown_from_handler() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Service;
+
+        #[crate::dbus_interface(name = "org.zbus.Service")]
+        impl Service {
+            async fn quit(&self, #[zbus(connection)] conn: &Connection) {
+                let conn_clone = conn.clone();
+                conn.executor()
+                    .spawn(
+                        async move {
+                            let _ = conn_clone
+                                .object_server()
+                                .shutdown(Duration::from_secs(10))
+                                .await;
+                        },
+                        "shutdown",
+                    )
+                    .detach();
+            }
+        }
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (_server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .serve_at("/", Service)?
+                .build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        // The `Quit` call gets its reply, and the shutdown doesn't wait for it to time out.
+        let start = Instant::now();
+        let mut stream = MessageStream::from(&client);
+        client
+            .call_method(None::<()>, "/", Some("org.zbus.Service"), "Quit", &())
+            .await?;
+        // Then the server closes the connection.
+        while stream.next().await.is_some() {}
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        Ok(())
+    }
+}
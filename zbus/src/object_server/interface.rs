@@ -1,7 +1,7 @@
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
-    fmt::Write,
+    fmt::{self, Write},
     future::Future,
     pin::Pin,
 };
@@ -9,7 +9,7 @@ use std::{
 use async_trait::async_trait;
 use zbus::message::Flags;
 use zbus_names::{InterfaceName, MemberName};
-use zvariant::{DynamicType, OwnedValue, Value};
+use zvariant::{DynamicType, OwnedValue, Signature, Value};
 
 use crate::{
     fdo, message::Message, object_server::SignalContext, Connection, ObjectServer, Result,
@@ -55,6 +55,101 @@ impl<'a> DispatchResult<'a> {
     }
 }
 
+/// The handler of a `&self` method, in a [`MethodEntry`].
+///
+/// The interface given to the handler is always the one the entry comes from.
+pub type MethodRefHandler = for<'call> fn(
+    &'call (dyn Interface + 'static),
+    &'call ObjectServer,
+    &'call Connection,
+    &'call Message,
+) -> DispatchResult<'call>;
+
+/// The handler of a `&mut self` method, in a [`MethodEntry`].
+///
+/// The interface given to the handler is always the one the entry comes from.
+pub type MethodMutHandler = for<'call> fn(
+    &'call mut (dyn Interface + 'static),
+    &'call ObjectServer,
+    &'call Connection,
+    &'call Message,
+) -> DispatchResult<'call>;
+
+/// The handler of a method, in a [`MethodEntry`].
+#[derive(Clone, Copy)]
+pub enum MethodHandler {
+    /// A `&self` method, called with only a read lock on the interface.
+    Ref(MethodRefHandler),
+    /// A `&mut self` method, called with a write lock on the interface.
+    Mut(MethodMutHandler),
+}
+
+/// An entry of the method table of an interface.
+///
+/// See [`Interface::methods`] for details.
+#[derive(Clone, Copy)]
+pub struct MethodEntry {
+    name: &'static str,
+    handler: MethodHandler,
+    signature: fn() -> Signature<'static>,
+}
+
+impl MethodEntry {
+    /// Create a new entry for the method `name`.
+    ///
+    /// `handler` handles the calls to the method and `signature` returns the signature of its
+    /// input arguments.
+    pub const fn new(
+        name: &'static str,
+        handler: MethodHandler,
+        signature: fn() -> Signature<'static>,
+    ) -> Self {
+        Self {
+            name,
+            handler,
+            signature,
+        }
+    }
+
+    /// Look the method `name` up in the `methods` table, sorted by name.
+    pub fn find<'m>(methods: &'m [MethodEntry], name: &str) -> Option<&'m MethodEntry> {
+        methods
+            .binary_search_by(|entry| entry.name.cmp(name))
+            .ok()
+            .map(|i| &methods[i])
+    }
+
+    /// The name of the method.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The handler of the method.
+    pub const fn handler(&self) -> MethodHandler {
+        self.handler
+    }
+
+    /// The signature of the input arguments of the method.
+    pub fn signature(&self) -> Signature<'static> {
+        (self.signature)()
+    }
+
+    /// Whether the method must be dispatched through [`Interface::call_mut`].
+    pub const fn requires_mut(&self) -> bool {
+        matches!(self.handler, MethodHandler::Mut(_))
+    }
+}
+
+impl fmt::Debug for MethodEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MethodEntry")
+            .field("name", &self.name)
+            .field("signature", &self.signature())
+            .field("requires_mut", &self.requires_mut())
+            .finish()
+    }
+}
+
 /// The trait used to dispatch messages to an interface instance.
 ///
 /// Note: It is not recommended to manually implement this trait. The [`dbus_interface`] macro
@@ -101,6 +196,18 @@ pub trait Interface: Any + Send + Sync {
         ctxt: &SignalContext<'_>,
     ) -> Option<fdo::Result<()>>;
 
+    /// The table of all the methods of the interface, sorted by name.
+    ///
+    /// When available, the object server looks the called method up in this table and calls its
+    /// handler directly, with the right lock on the interface, instead of going through
+    /// [`Interface::call`] and [`Interface::call_mut`].
+    ///
+    /// The default implementation returns `None`, meaning the table isn't available and every call
+    /// is first tried with [`Interface::call`].
+    fn methods(&self) -> Option<&'static [MethodEntry]> {
+        None
+    }
+
     /// Call a method.
     ///
     /// Return [`DispatchResult::NotFound`] if the method doesn't exist, or
@@ -139,7 +246,8 @@ pub trait Interface: Any + Send + Sync {
 // remove this unsafe code.
 impl dyn Interface {
     /// Return Any of self
-    pub(crate) fn downcast_ref<T: Any>(&self) -> Option<&T> {
+    #[doc(hidden)]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
         if <dyn Interface as Any>::type_id(self) == TypeId::of::<T>() {
             // SAFETY: If type ID matches, it means object is of type T
             Some(unsafe { &*(self as *const dyn Interface as *const T) })
@@ -149,7 +257,8 @@ impl dyn Interface {
     }
 
     /// Return Any of self
-    pub(crate) fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+    #[doc(hidden)]
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
         if <dyn Interface as Any>::type_id(self) == TypeId::of::<T>() {
             // SAFETY: If type ID matches, it means object is of type T
             Some(unsafe { &mut *(self as *mut dyn Interface as *mut T) })
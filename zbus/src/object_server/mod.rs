@@ -3,7 +3,7 @@
 use event_listener::{Event, EventListener};
 use serde::Serialize;
 use std::{
-    collections::{hash_map::Entry, HashMap, VecDeque},
+    collections::{hash_map::Entry, HashMap},
     fmt::Write,
     marker::PhantomData,
     ops::{Deref, DerefMut},
@@ -12,7 +12,7 @@ use std::{
 use tracing::{debug, instrument, trace};
 
 use static_assertions::assert_impl_all;
-use zbus_names::InterfaceName;
+use zbus_names::{InterfaceName, OwnedInterfaceName};
 use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Signature, Type, Value};
 
 use crate::{
@@ -25,11 +25,20 @@ use crate::{
 };
 
 mod interface;
-pub use interface::{DispatchResult, Interface};
+pub use interface::{
+    DispatchResult, Interface, MethodEntry, MethodHandler, MethodMutHandler, MethodRefHandler,
+};
+
+mod idle;
+use idle::Activity;
+pub use idle::IdleMonitor;
 
 mod signal_context;
 pub use signal_context::SignalContext;
 
+mod policy;
+pub use policy::{Access, Policy};
+
 /// Opaque structure that derefs to an `Interface` type.
 pub struct InterfaceDeref<'d, I> {
     iface: RwLockReadGuard<'d, dyn Interface>,
@@ -279,6 +288,13 @@ impl Node {
         })
     }
 
+    // The interfaces at this node, except for the standard ones every node gets implicitly.
+    fn registered_interfaces(&self) -> impl Iterator<Item = &InterfaceName<'static>> {
+        self.interfaces.keys().filter(|k| {
+            **k != Peer::name() && **k != Introspectable::name() && **k != Properties::name()
+        })
+    }
+
     fn remove_node(&mut self, node: &str) -> bool {
         self.children.remove(node).is_some()
     }
@@ -298,39 +314,29 @@ impl Node {
     }
 
     async fn introspect_to_writer<W: Write + Send>(&self, writer: &mut W) {
-        let mut node_list = VecDeque::new();
-        node_list.push_back((self, "", 0));
-        while let Some((node, path, level)) = node_list.pop_front() {
-            if level == 0 {
-                writeln!(
-                    writer,
-                    r#"
+        writeln!(
+            writer,
+            r#"
 <!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
  "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
 <node>"#
-                )
-                .unwrap();
-            } else {
-                writeln!(
-                    writer,
-                    "{:indent$}<node name=\"{}\">",
-                    "",
-                    path,
-                    indent = level
-                )
-                .unwrap();
-            }
-
-            for iface in node.interfaces.values() {
-                iface.read().await.introspect_to_writer(writer, level + 2);
-            }
+        )
+        .unwrap();
 
-            for (path, node) in &node.children {
-                node_list.push_front((node, path, level + 2));
-            }
+        let mut interfaces: Vec<_> = self.interfaces.iter().collect();
+        interfaces.sort_unstable_by_key(|(name, _)| *name);
+        for (_, iface) in interfaces {
+            iface.read().await.introspect_to_writer(writer, 2);
+        }
 
-            writeln!(writer, "{:indent$}</node>", "", indent = level).unwrap();
+        // Children are only listed by name. Their details are available by introspecting them.
+        let mut children: Vec<_> = self.children.keys().collect();
+        children.sort_unstable();
+        for name in children {
+            writeln!(writer, "  <node name=\"{name}\"/>").unwrap();
         }
+
+        writeln!(writer, "</node>").unwrap();
     }
 
     pub(crate) async fn introspect(&self) -> String {
@@ -385,6 +391,10 @@ impl Node {
 /// All object paths will have the standard interfaces implemented on your behalf, such as
 /// `org.freedesktop.DBus.Introspectable` or `org.freedesktop.DBus.Properties`.
 ///
+/// The introspection XML of an object describes its interfaces, sorted by name, followed by an
+/// empty `<node name="..."/>` element for each of its children, also sorted by name. The children
+/// need to be introspected themselves for their details.
+///
 /// # Example
 ///
 /// This example exposes the `org.myiface.Example.Quit` method on the `/org/zbus/path`
@@ -439,6 +449,7 @@ impl Node {
 pub struct ObjectServer {
     conn: WeakConnection,
     root: RwLock<Node>,
+    activity: Activity,
 }
 
 assert_impl_all!(ObjectServer: Send, Sync, Unpin);
@@ -449,6 +460,7 @@ impl ObjectServer {
         Self {
             conn: conn.into(),
             root: RwLock::new(Node::new("/".try_into().expect("zvariant bug"))),
+            activity: Activity::default(),
         }
     }
 
@@ -553,11 +565,8 @@ impl ObjectServer {
             ObjectManager::interfaces_removed(&ctxt, &path, &[I::name()]).await?;
         }
         if node.is_empty() {
-            let mut path_parts = path.rsplit('/').filter(|i| !i.is_empty());
-            let last_part = path_parts.next().unwrap();
-            let ppath = ObjectPath::from_string_unchecked(
-                path_parts.fold(String::new(), |a, p| format!("/{p}{a}")),
-            );
+            let last_part = path.components().next_back().unwrap();
+            let ppath = path.parent().unwrap();
             root.get_child_mut(&ppath, false)
                 .0
                 .unwrap()
@@ -567,6 +576,58 @@ impl ObjectServer {
         Ok(false)
     }
 
+    /// The paths of all the objects registered with this server, along with the names of the
+    /// interfaces at each path.
+    ///
+    /// The standard interfaces, implemented on your behalf at every path (e.g
+    /// `org.freedesktop.DBus.Introspectable`), are not included. Neither are the paths that only
+    /// exist because there are objects registered below them. The interface names are sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{Connection, dbus_interface, zvariant::OwnedObjectPath};
+    /// # use async_io::block_on;
+    /// #
+    /// struct MyIface;
+    ///
+    /// #[dbus_interface(name = "org.myiface.MyIface")]
+    /// impl MyIface {}
+    ///
+    /// # block_on(async {
+    /// let connection = Connection::session().await?;
+    /// connection.object_server().at("/org/zbus/path", MyIface).await?;
+    ///
+    /// let nodes = connection.object_server().nodes().await;
+    /// assert_eq!(nodes.len(), 1);
+    /// let interfaces = &nodes[&OwnedObjectPath::try_from("/org/zbus/path")?];
+    /// assert_eq!(interfaces, &["org.myiface.MyIface"]);
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// #
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub async fn nodes(&self) -> HashMap<OwnedObjectPath, Vec<OwnedInterfaceName>> {
+        let root = self.root.read().await;
+        let mut nodes = HashMap::new();
+
+        let mut node_list = vec![&*root];
+        while let Some(node) = node_list.pop() {
+            let mut interfaces: Vec<OwnedInterfaceName> = node
+                .registered_interfaces()
+                .map(|name| name.clone().into())
+                .collect();
+            if !interfaces.is_empty() {
+                interfaces.sort_unstable();
+                nodes.insert(node.path.clone(), interfaces);
+            }
+            node_list.extend(node.children.values());
+        }
+
+        nodes
+    }
+
     /// Get the interface at the given path.
     ///
     /// # Errors
@@ -641,6 +702,112 @@ impl ObjectServer {
         })
     }
 
+    /// Shut the object server, and its connection, down gracefully.
+    ///
+    /// This is meant to be called when the service is asked to stop (e.g by systemd), so that the
+    /// clients aren't cut off in the middle of a method call. The shutdown happens in this order:
+    ///
+    /// 1. New method calls are rejected with an `org.freedesktop.DBus.Error.Failed` error.
+    /// 2. The method calls being handled are given up to `timeout` to complete and reply.
+    /// 3. All the well-known names requested through the connection are released.
+    /// 4. The connection is closed, after any message being sent has been written out.
+    ///
+    /// # Errors
+    ///
+    /// Fails if releasing a name or closing the connection fails. Method calls still running after
+    /// `timeout` are not considered an error, they're just abandoned.
+    ///
+    /// # Calling from a method handler
+    ///
+    /// The method call being handled is one of the calls the shutdown waits for, so a handler must
+    /// not await the shutdown itself: it would wait for its own completion until `timeout`, and its
+    /// reply would then be lost with the connection. Spawn the shutdown instead, so the handler can
+    /// return and its caller get a reply first:
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use zbus::Connection;
+    ///
+    /// struct Service;
+    ///
+    /// #[zbus::dbus_interface(name = "org.zbus.Service1")]
+    /// impl Service {
+    ///     async fn quit(&self, #[zbus(connection)] conn: &Connection) {
+    ///         let conn_clone = conn.clone();
+    ///         conn.executor()
+    ///             .spawn(
+    ///                 async move {
+    ///                     let _ = conn_clone
+    ///                         .object_server()
+    ///                         .shutdown(Duration::from_secs(5))
+    ///                         .await;
+    ///                 },
+    ///                 "shutdown",
+    ///             )
+    ///             .detach();
+    ///     }
+    /// }
+    /// # zbus::block_on(async {
+    /// # let _conn = zbus::connection::Builder::session()?
+    /// #     .serve_at("/org/zbus/Service", Service)?
+    /// #     .build()
+    /// #     .await?;
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use std::time::Duration;
+    /// use zbus::connection;
+    ///
+    /// struct Service;
+    ///
+    /// #[zbus::dbus_interface(name = "org.zbus.Service1")]
+    /// impl Service {
+    ///     async fn work(&self) {}
+    /// }
+    ///
+    /// let conn = connection::Builder::session()?
+    ///     .name("org.zbus.Service")?
+    ///     .serve_at("/org/zbus/Service", Service)?
+    ///     .build()
+    ///     .await?;
+    ///
+    /// // .. wait for SIGTERM ..
+    ///
+    /// conn.object_server()
+    ///     .shutdown(Duration::from_secs(5))
+    ///     .await?;
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> Result<()> {
+        use futures_util::{future, pin_mut};
+
+        let conn = self.connection();
+        self.activity.start_shutdown();
+
+        let calls_done = self.activity.calls_done();
+        pin_mut!(calls_done);
+        let timer = crate::abstractions::timer::sleep(timeout);
+        pin_mut!(timer);
+        if let future::Either::Right(_) = future::select(calls_done, timer).await {
+            debug!(
+                "Method calls still running after {:?}, abandoning them",
+                timeout
+            );
+        }
+
+        for name in conn.registered_names().await {
+            conn.release_name(name).await?;
+        }
+
+        conn.close().await
+    }
+
     #[instrument(skip(self, connection))]
     async fn dispatch_method_call_try(
         &self,
@@ -648,6 +815,9 @@ impl ObjectServer {
         msg: &Message,
     ) -> fdo::Result<Result<()>> {
         let hdr = msg.header();
+        if self.activity.is_shutting_down() {
+            return Err(fdo::Error::Failed("Service is shutting down".into()));
+        }
         let path = hdr
             .path()
             .ok_or_else(|| fdo::Error::Failed("Missing object path".into()))?;
@@ -662,6 +832,13 @@ impl ObjectServer {
         let member = hdr
             .member()
             .ok_or_else(|| fdo::Error::Failed("Missing member".into()))?;
+        if let Some(policy) = connection.policy() {
+            if policy.check(msg) == Access::Deny {
+                return Err(fdo::Error::AccessDenied(format!(
+                    "Access to '{iface_name}.{member}' on '{path}' denied"
+                )));
+            }
+        }
 
         // Ensure the root lock isn't held while dispatching the message. That
         // way, the object server can be mutated during that time.
@@ -676,32 +853,51 @@ impl ObjectServer {
             })?
         };
 
-        trace!("acquiring read lock on interface `{}`", iface_name);
-        let read_lock = iface.read().await;
-        trace!("acquired read lock on interface `{}`", iface_name);
-        match read_lock.call(self, connection, msg, member.as_ref()) {
-            DispatchResult::NotFound => {
-                return Err(fdo::Error::UnknownMethod(format!(
-                    "Unknown method '{member}'"
-                )));
-            }
-            DispatchResult::Async(f) => {
-                return Ok(f.await);
+        let handler = {
+            trace!("acquiring read lock on interface `{}`", iface_name);
+            let read_lock = iface.read().await;
+            trace!("acquired read lock on interface `{}`", iface_name);
+            // Interfaces with a method table get their method handlers called directly.
+            let handler = match read_lock.methods() {
+                Some(methods) => match MethodEntry::find(methods, member.as_str()) {
+                    Some(entry) => Some(entry.handler()),
+                    None => {
+                        return Err(fdo::Error::UnknownMethod(format!(
+                            "Unknown method '{member}'"
+                        )));
+                    }
+                },
+                None => None,
+            };
+            let res = match handler {
+                Some(MethodHandler::Ref(handler)) => handler(&*read_lock, self, connection, msg),
+                Some(MethodHandler::Mut(_)) => DispatchResult::RequiresMut,
+                None => read_lock.call(self, connection, msg, member.as_ref()),
+            };
+            match res {
+                DispatchResult::NotFound => {
+                    return Err(fdo::Error::UnknownMethod(format!(
+                        "Unknown method '{member}'"
+                    )));
+                }
+                DispatchResult::Async(f) => {
+                    return Ok(f.await);
+                }
+                DispatchResult::RequiresMut => {}
             }
-            DispatchResult::RequiresMut => {}
-        }
-        drop(read_lock);
+
+            handler
+        };
         trace!("acquiring write lock on interface `{}`", iface_name);
         let mut write_lock = iface.write().await;
         trace!("acquired write lock on interface `{}`", iface_name);
-        match write_lock.call_mut(self, connection, msg, member.as_ref()) {
-            DispatchResult::NotFound => {}
-            DispatchResult::RequiresMut => {}
-            DispatchResult::Async(f) => {
-                return Ok(f.await);
-            }
+        let res = match handler {
+            Some(MethodHandler::Mut(handler)) => handler(&mut *write_lock, self, connection, msg),
+            _ => write_lock.call_mut(self, connection, msg, member.as_ref()),
+        };
+        if let DispatchResult::Async(f) = res {
+            return Ok(f.await);
         }
-        drop(write_lock);
         Err(fdo::Error::UnknownMethod(format!(
             "Unknown method '{member}'"
         )))
@@ -735,12 +931,17 @@ impl ObjectServer {
     #[instrument(skip(self))]
     pub(crate) async fn dispatch_message(&self, msg: &Message) -> Result<bool> {
         let conn = self.connection();
+        let _call = self.activity.call_started(msg);
         self.dispatch_method_call(&conn, msg).await?;
         trace!("Handled: {}", msg);
 
         Ok(true)
     }
 
+    pub(crate) fn activity(&self) -> &Activity {
+        &self.activity
+    }
+
     pub(crate) fn connection(&self) -> Connection {
         self.conn
             .upgrade()
@@ -754,6 +955,36 @@ impl From<crate::blocking::ObjectServer> for ObjectServer {
     }
 }
 
+/// The order in which method calls are dispatched by the [`ObjectServer`].
+///
+/// Set through [`zbus::connection::Builder::method_dispatch_order`].
+///
+/// Method calls a connection makes to itself (e.g from a method handler calling another method of
+/// its own object) are always dispatched right away, as they'd otherwise wait for the handler that
+/// made them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DispatchOrder {
+    /// All method calls are dispatched concurrently, as soon as they're received.
+    ///
+    /// Calls to methods taking `&mut self` still exclude any other call to the same interface of
+    /// the same object, but the order in which the calls are handled isn't guaranteed.
+    ///
+    /// This is the default.
+    #[default]
+    Concurrent,
+    /// Method calls to the same object are handled one at a time, in the order they're received.
+    ///
+    /// Calls to different objects are still dispatched concurrently, so a slow method only delays
+    /// the calls to its own object.
+    PerObject,
+    /// Method calls to the same interface of the same object are handled one at a time, in the
+    /// order they're received.
+    ///
+    /// Calls to different interfaces or objects are still dispatched concurrently.
+    PerInterface,
+}
+
 /// A response wrapper that notifies after response has been sent.
 ///
 /// Sometimes in [`dbus_interface`] method implemenations we need to do some other work after the
@@ -0,0 +1,197 @@
+use static_assertions::assert_impl_all;
+
+use crate::{message::Message, MatchRule, OwnedMatchRule};
+
+/// The decision of a [`Policy`] about a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Access {
+    /// The message is allowed.
+    Allow,
+    /// The message is denied.
+    Deny,
+}
+
+assert_impl_all!(Access: Send, Sync, Unpin);
+
+/// A policy restricting the messages exchanged with the peer of a connection.
+///
+/// This is mostly useful for peer-to-peer servers exposed to semi-trusted peers, as an in-process
+/// equivalent of the bus policy. The policy is set through
+/// [`zbus::connection::Builder::policy`] and applies to:
+///
+/// * the method calls received from the peer, which are checked before being dispatched by the
+///   [`ObjectServer`](super::ObjectServer). Denied calls get an
+///   `org.freedesktop.DBus.Error.AccessDenied` error reply.
+/// * the signals sent to the peer. Denied signals are silently dropped.
+///
+/// A policy is made of rules, each allowing or denying the messages matching a [`MatchRule`]. The
+/// last rule matching a message decides if it's allowed, the default access applying if no rule
+/// matches. Rules without a message type apply to both method calls and signals.
+///
+/// The calls to the standard `org.freedesktop.DBus.Properties` interface are checked twice: as is,
+/// and then as if they were made on the interface they refer to (with the same member and
+/// arguments). Both need to be allowed, so a policy restricting the interfaces of the peer also
+/// restricts access to their properties. E.g a call to
+/// `org.freedesktop.DBus.Properties.Get("org.zbus.Calculator", "Precision")` must be allowed by the
+/// policy, and also `org.zbus.Calculator.Get("org.zbus.Calculator", "Precision")`.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[cfg(unix)]
+/// # zbus::block_on(async {
+/// use zbus::{
+///     connection::Builder, message::Type, object_server::Policy, Guid, MatchRule,
+/// };
+/// # #[cfg(not(feature = "tokio"))]
+/// # use std::os::unix::net::UnixStream;
+/// # #[cfg(feature = "tokio")]
+/// # use tokio::net::UnixStream;
+///
+/// // Only allow calls to `org.zbus.Calculator` and `Introspect`, on any object under `/org/zbus`.
+/// let policy = Policy::deny_all()
+///     .allow(
+///         MatchRule::builder()
+///             .msg_type(Type::MethodCall)
+///             .interface("org.zbus.Calculator")?
+///             .path_namespace("/org/zbus")?
+///             .build(),
+///     )
+///     .allow(
+///         MatchRule::builder()
+///             .msg_type(Type::MethodCall)
+///             .interface("org.freedesktop.DBus.Introspectable")?
+///             .build(),
+///     )
+///     // Let the peer receive all signals, except `Secret`.
+///     .allow(MatchRule::builder().msg_type(Type::Signal).build())
+///     .deny(MatchRule::builder().member("Secret")?.build());
+///
+/// # let (stream, _) = UnixStream::pair().unwrap();
+/// let guid = Guid::generate();
+/// let conn = Builder::unix_stream(stream)
+///     .server(&guid)
+///     .p2p()
+///     .policy(policy)
+///     .build()
+///     .await?;
+/// # drop(conn);
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Policy {
+    default: Access,
+    rules: Vec<(Access, OwnedMatchRule)>,
+}
+
+assert_impl_all!(Policy: Send, Sync, Unpin);
+
+impl Policy {
+    /// Create a policy allowing all messages, unless denied by a rule.
+    pub fn allow_all() -> Self {
+        Self::new(Access::Allow)
+    }
+
+    /// Create a policy denying all messages, unless allowed by a rule.
+    pub fn deny_all() -> Self {
+        Self::new(Access::Deny)
+    }
+
+    /// Allow the messages matching `rule`.
+    #[must_use]
+    pub fn allow<R>(self, rule: R) -> Self
+    where
+        R: Into<OwnedMatchRule>,
+    {
+        self.rule(Access::Allow, rule)
+    }
+
+    /// Deny the messages matching `rule`.
+    #[must_use]
+    pub fn deny<R>(self, rule: R) -> Self
+    where
+        R: Into<OwnedMatchRule>,
+    {
+        self.rule(Access::Deny, rule)
+    }
+
+    /// The access given to `msg` by this policy.
+    pub fn check(&self, msg: &Message) -> Access {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(_, rule)| rule.matches(msg).unwrap_or(false))
+            .map_or(self.default, |(access, _)| *access)
+    }
+
+    /// The rules of this policy, in the order they were added.
+    pub fn rules(&self) -> impl Iterator<Item = (Access, &MatchRule<'static>)> {
+        self.rules
+            .iter()
+            .map(|(access, rule)| (*access, rule.inner()))
+    }
+
+    fn new(default: Access) -> Self {
+        Self {
+            default,
+            rules: vec![],
+        }
+    }
+
+    fn rule<R>(mut self, access: Access, rule: R) -> Self
+    where
+        R: Into<OwnedMatchRule>,
+    {
+        self.rules.push((access, rule.into()));
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Type;
+
+    #[test]
+    fn check() {
+        let policy = Policy::deny_all()
+            .allow(
+                MatchRule::builder()
+                    .interface("org.zbus.Allowed")
+                    .unwrap()
+                    .build(),
+            )
+            .deny(MatchRule::builder().member("Denied").unwrap().build());
+
+        let call = |iface: &str, member: &str| {
+            Message::method("/org/zbus", member)
+                .unwrap()
+                .interface(iface)
+                .unwrap()
+                .build(&())
+                .unwrap()
+        };
+        assert_eq!(
+            policy.check(&call("org.zbus.Allowed", "Method")),
+            Access::Allow
+        );
+        assert_eq!(
+            policy.check(&call("org.zbus.Allowed", "Denied")),
+            Access::Deny
+        );
+        assert_eq!(
+            policy.check(&call("org.zbus.Other", "Method")),
+            Access::Deny
+        );
+
+        let signal = Message::signal("/org/zbus", "org.zbus.Other", "Signal")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        assert_eq!(policy.check(&signal), Access::Deny);
+        let policy = policy.allow(MatchRule::builder().msg_type(Type::Signal).build());
+        assert_eq!(policy.check(&signal), Access::Allow);
+    }
+}
@@ -12,10 +12,19 @@ use crate::{proxy::ProxyInner, Connection, Error, Proxy, Result};
 pub enum CacheProperties {
     /// Cache properties. The properties will be cached upfront as part of the proxy
     /// creation.
+    ///
+    /// The cache is populated by a single `org.freedesktop.DBus.Properties.GetAll` call and kept
+    /// up to date through the `PropertiesChanged` signal, so reading any number of properties
+    /// doesn't cost any further round-trip.
     Yes,
     /// Don't cache properties.
+    ///
+    /// Each property read results in an `org.freedesktop.DBus.Properties.Get` call.
     No,
     /// Cache properties but only populate the cache on the first read of a property (default).
+    ///
+    /// Same as [`CacheProperties::Yes`], except that the `GetAll` call is deferred until the first
+    /// property read, sparing it entirely for proxies that are only used for method calls.
     #[default]
     Lazily,
 }
@@ -30,6 +39,7 @@ pub struct Builder<'a, T = ()> {
     proxy_type: PhantomData<T>,
     cache: CacheProperties,
     uncached_properties: Option<HashSet<Str<'a>>>,
+    activation_retry: bool,
 }
 
 impl<'a, T> Clone for Builder<'a, T> {
@@ -41,6 +51,7 @@ impl<'a, T> Clone for Builder<'a, T> {
             interface: self.interface.clone(),
             cache: self.cache,
             uncached_properties: self.uncached_properties.clone(),
+            activation_retry: self.activation_retry,
             proxy_type: PhantomData,
         }
     }
@@ -80,6 +91,9 @@ impl<'a, T> Builder<'a, T> {
     }
 
     /// Set the properties caching mode.
+    ///
+    /// See [`CacheProperties`] for details. Properties can be excluded from the cache with
+    /// [`Builder::uncached_properties`].
     #[must_use]
     pub fn cache_properties(mut self, cache: CacheProperties) -> Self {
         self.cache = cache;
@@ -95,6 +109,25 @@ impl<'a, T> Builder<'a, T> {
         self
     }
 
+    /// Retry method calls once the destination service is activated (default: `false`).
+    ///
+    /// When enabled, a method call failing with `org.freedesktop.DBus.Error.ServiceUnknown` or
+    /// `org.freedesktop.DBus.Error.NameHasNoOwner` results in a
+    /// [`StartServiceByName`](crate::fdo::DBusProxy::start_service_by_name) call for the
+    /// destination. Once the service owns its name (which is awaited for up to 25 seconds), the
+    /// method call is retried, once. This smooths over the races between a client and the
+    /// activation of the service it uses, e.g when the service is restarted or exits on idle.
+    ///
+    /// This only applies to proxies on a bus connection, with a well-known name as destination.
+    /// Calls made with the [`NoAutoStart`](super::MethodFlags::NoAutoStart) flag are never retried,
+    /// since they opted out of activation.
+    #[must_use]
+    pub fn activation_retry(mut self, retry: bool) -> Self {
+        self.activation_retry = retry;
+
+        self
+    }
+
     pub(crate) fn build_internal(self) -> Result<Proxy<'a>> {
         let conn = self.conn;
         let destination = self
@@ -104,6 +137,7 @@ impl<'a, T> Builder<'a, T> {
         let interface = self.interface.ok_or(Error::MissingParameter("interface"))?;
         let cache = self.cache;
         let uncached_properties = self.uncached_properties.unwrap_or_default();
+        let activation_retry = self.activation_retry;
 
         Ok(Proxy {
             inner: Arc::new(ProxyInner::new(
@@ -113,6 +147,7 @@ impl<'a, T> Builder<'a, T> {
                 interface,
                 cache,
                 uncached_properties,
+                activation_retry,
             )),
         })
     }
@@ -158,6 +193,7 @@ where
                 .map(|i| InterfaceName::from_static_str(i).expect("invalid interface name")),
             cache: CacheProperties::default(),
             uncached_properties: None,
+            activation_retry: false,
             proxy_type: PhantomData,
         }
     }
@@ -14,11 +14,12 @@ use std::{
     pin::Pin,
     sync::{Arc, RwLock, RwLockReadGuard},
     task::{Context, Poll},
+    time::Duration,
 };
 use tracing::{debug, info_span, instrument, trace, Instrument};
 
-use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
-use zvariant::{ObjectPath, OwnedValue, Str, Value};
+use zbus_names::{BusName, InterfaceName, MemberName, UniqueName, WellKnownName};
+use zvariant::{ObjectPath, OwnedValue, Signature, Str, Value};
 
 use crate::{
     fdo::{self, IntrospectableProxy, NameOwnerChanged, PropertiesChangedStream, PropertiesProxy},
@@ -29,6 +30,15 @@ use crate::{
 mod builder;
 pub use builder::{Builder, CacheProperties, ProxyDefault};
 
+#[cfg(feature = "xml")]
+mod namespace;
+#[cfg(feature = "xml")]
+pub use namespace::Namespace;
+#[cfg(feature = "xml")]
+mod tree;
+#[cfg(feature = "xml")]
+pub use tree::ObjectTree;
+
 /// A client-side interface proxy.
 ///
 /// A `Proxy` is a helper to interact with an interface on a remote object.
@@ -94,6 +104,8 @@ pub(crate) struct ProxyInner<'a> {
     /// Set of properties which do not get cached, by name.
     /// This overrides proxy-level caching behavior.
     uncached_properties: HashSet<Str<'a>>,
+    /// Whether to activate the destination and retry calls failing because it has no owner.
+    activation_retry: bool,
 }
 
 impl Drop for ProxyInnerStatic {
@@ -429,6 +441,8 @@ impl PropertiesCache {
 
             if let Some(entry) = values.get_mut(inval) {
                 entry.value = None;
+                entry.updates += 1;
+                entry.written = false;
                 entry.event.notify(usize::MAX);
             }
         }
@@ -444,12 +458,52 @@ impl PropertiesCache {
             trace!("Property `{interface}.{property_name}` updated");
 
             let entry = values.entry(property_name.to_string()).or_default();
+            let value = OwnedValue::from(value);
+            entry.updates += 1;
+            if std::mem::take(&mut entry.written) && entry.value.as_ref() == Some(&value) {
+                // Listeners were already notified of this value when we set it.
+                trace!("Property `{interface}.{property_name}` set value confirmed");
+
+                continue;
+            }
 
-            entry.value = Some(OwnedValue::from(value));
+            entry.value = Some(value);
             entry.event.notify(usize::MAX);
         }
     }
 
+    /// The number of updates received for the property `property_name` so far.
+    fn updates(&self, property_name: &str) -> u64 {
+        self.values
+            .read()
+            .expect("lock poisoned")
+            .get(property_name)
+            .map_or(0, |entry| entry.updates)
+    }
+
+    /// Cache `value`, just set by us, for the property `property_name`.
+    ///
+    /// Listeners are notified right away, so they don't miss the change if the peer doesn't emit
+    /// `PropertiesChanged` for the property. The notification of the first `PropertiesChanged`
+    /// signal carrying the same value is then skipped, to not notify the listeners twice.
+    ///
+    /// This is a no-op if the property received an update since `updates` was retrieved through
+    /// [`PropertiesCache::updates`].
+    fn write_through(&self, property_name: &str, value: &Value<'_>, updates: u64) {
+        let mut values = self.values.write().expect("lock poisoned");
+        let entry = values.entry(property_name.to_string()).or_default();
+        if entry.updates != updates {
+            trace!("Property `{property_name}` updated during `Set`, not caching the set value");
+
+            return;
+        }
+        trace!("Property `{property_name}` set");
+
+        entry.value = Some(OwnedValue::from(value));
+        entry.written = true;
+        entry.event.notify(usize::MAX);
+    }
+
     /// Wait for the cache to be populated and return any error encountered during population
     pub(crate) async fn ready(&self) -> Result<()> {
         let listener = match &*self.caching_result.read().expect("lock poisoned") {
@@ -476,6 +530,7 @@ impl<'a> ProxyInner<'a> {
         interface: InterfaceName<'a>,
         cache: CacheProperties,
         uncached_properties: HashSet<Str<'a>>,
+        activation_retry: bool,
     ) -> Self {
         let property_cache = match cache {
             CacheProperties::Yes | CacheProperties::Lazily => Some(OnceCell::new()),
@@ -491,6 +546,7 @@ impl<'a> ProxyInner<'a> {
             interface,
             property_cache,
             uncached_properties,
+            activation_retry,
         }
     }
 
@@ -664,6 +720,28 @@ impl<'a> Proxy<'a> {
             .into()
     }
 
+    /// Create a proxy for the interface of `T`, on the same destination and path.
+    ///
+    /// Used by the proxies generated by [`dbus_proxy`](macro@crate::dbus_proxy) to build the
+    /// proxies of their included interfaces. Properties of the included interfaces aren't cached.
+    #[doc(hidden)]
+    pub fn included<T>(&self) -> Proxy<'a>
+    where
+        T: ProxyDefault,
+    {
+        Builder::<T>::new(&self.inner.inner_without_borrows.conn)
+            // Safe because already checked earlier
+            .destination(self.inner.destination.clone())
+            .unwrap()
+            // Safe because already checked earlier
+            .path(self.inner.path.clone())
+            .unwrap()
+            .cache_properties(CacheProperties::No)
+            .build_internal()
+            // Safe because the interface of a generated proxy is always set
+            .unwrap()
+    }
+
     /// Get the cache, starting it in the background if needed.
     ///
     /// Use PropertiesCache::ready() to wait for the cache to be populated and to get any errors
@@ -785,13 +863,41 @@ impl<'a> Proxy<'a> {
     /// Set the property `property_name`.
     ///
     /// Effectively, call the `Set` method of the `org.freedesktop.DBus.Properties` interface.
+    ///
+    /// If the property is cached, the cache is updated with `value` once the call succeeds, so
+    /// reading the property right after doesn't return the old value, and the streams returned by
+    /// [`Proxy::receive_property_changed`] are notified. The cached value is then replaced by the
+    /// one from the `PropertiesChanged` signal emitted by the peer, if any (e.g if the peer
+    /// adjusted the value). If the property was updated by a signal during the call, the cache is
+    /// left as is, since the signal reflects the value set more accurately.
     pub async fn set_property<'t, T: 't>(&self, property_name: &str, value: T) -> fdo::Result<()>
     where
         T: Into<Value<'t>>,
     {
+        let value = value.into();
+        let cache = self
+            .inner
+            .property_cache
+            .as_ref()
+            .and_then(OnceCell::get)
+            .map(|(cache, _)| cache)
+            .filter(|_| {
+                !self
+                    .inner
+                    .uncached_properties
+                    .contains(&Str::from(property_name))
+            });
+        let updates = cache.map(|cache| cache.updates(property_name));
+
         self.properties_proxy()
-            .set(self.inner.interface.as_ref(), property_name, &value.into())
-            .await
+            .set(self.inner.interface.as_ref(), property_name, &value)
+            .await?;
+
+        if let (Some(cache), Some(updates)) = (cache, updates) {
+            cache.write_through(property_name, &value, updates);
+        }
+
+        Ok(())
     }
 
     /// Call a method and return the reply.
@@ -807,17 +913,20 @@ impl<'a> Proxy<'a> {
         M::Error: Into<Error>,
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
-        self.inner
-            .inner_without_borrows
-            .conn
-            .call_method(
+        let method_name = method_name.try_into().map_err(Into::into)?;
+        let call = || {
+            self.inner.inner_without_borrows.conn.call_method(
                 Some(&self.inner.destination),
                 self.inner.path.as_str(),
                 Some(&self.inner.interface),
-                method_name,
+                &method_name,
                 body,
             )
-            .await
+        };
+        match call().await {
+            Err(e) if self.activate_on(&e, false).await => call().await,
+            reply => reply,
+        }
     }
 
     /// Call a method and return the reply body.
@@ -837,6 +946,81 @@ impl<'a> Proxy<'a> {
         reply.body()
     }
 
+    /// Call a method and return the reply, after checking its body signature.
+    ///
+    /// Same as [`Proxy::call_method`], except that it returns [`Error::BodySignatureMismatch`] if
+    /// the signature of the reply body isn't `reply_signature`. See
+    /// [`Message::check_body_signature`] for details.
+    pub async fn call_method_checked<'m, 's, M, B, S>(
+        &self,
+        method_name: M,
+        body: &B,
+        reply_signature: S,
+    ) -> Result<Message>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<Error>,
+    {
+        // Validate the signature before making the call.
+        let reply_signature = reply_signature.try_into().map_err(Into::into)?;
+        let reply = self.call_method(method_name, body).await?;
+        reply.check_body_signature(reply_signature)?;
+
+        Ok(reply)
+    }
+
+    /// Call a method and return the reply body, after checking its signature.
+    ///
+    /// Same as [`Proxy::call`], except that it returns [`Error::BodySignatureMismatch`] if the
+    /// signature of the reply body isn't `reply_signature`, instead of attempting to deserialize
+    /// it. This allows to report changes in the API of the service clearly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{Connection, Error, Proxy};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let proxy = Proxy::new(
+    ///     &conn,
+    ///     "org.freedesktop.DBus",
+    ///     "/org/freedesktop/DBus",
+    ///     "org.freedesktop.DBus",
+    /// )
+    /// .await?;
+    /// match proxy.call_checked::<_, _, _, Vec<String>>("ListNames", &(), "as").await {
+    ///     Ok(names) => println!("{names:?}"),
+    ///     Err(Error::BodySignatureMismatch(_, found)) => eprintln!("unexpected reply: `{found}`"),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn call_checked<'m, 's, M, B, S, R>(
+        &self,
+        method_name: M,
+        body: &B,
+        reply_signature: S,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<Error>,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        let reply = self
+            .call_method_checked(method_name, body, reply_signature)
+            .await?;
+
+        reply.body()
+    }
+
     /// Call a method and return the reply body, optionally supplying a set of
     /// method flags to control the way the method call message is sent and handled.
     ///
@@ -858,24 +1042,83 @@ impl<'a> Proxy<'a> {
         B: serde::ser::Serialize + zvariant::DynamicType,
         R: serde::de::DeserializeOwned + zvariant::Type,
     {
+        let method_name = method_name.try_into().map_err(Into::into)?;
+        let method_name = &method_name;
+        let no_auto_start = flags.contains(MethodFlags::NoAutoStart);
         let flags = flags.iter().map(Flags::from).collect::<BitFlags<_>>();
-        match self
-            .inner
-            .inner_without_borrows
-            .conn
-            .call_method_raw(
-                Some(self.destination()),
-                self.path(),
-                Some(self.interface()),
-                method_name,
-                flags,
-                body,
-            )
-            .await?
-        {
-            Some(reply) => reply.await?.body().map(Some),
-            None => Ok(None),
-        }
+        let call = || async move {
+            match self
+                .inner
+                .inner_without_borrows
+                .conn
+                .call_method_raw(
+                    Some(self.destination()),
+                    self.path(),
+                    Some(self.interface()),
+                    method_name,
+                    flags,
+                    body,
+                )
+                .await?
+            {
+                Some(reply) => reply.await.map(Some),
+                None => Ok(None),
+            }
+        };
+        let reply = match call().await {
+            Err(e) if self.activate_on(&e, no_auto_start).await => call().await?,
+            reply => reply?,
+        };
+
+        reply.map(|reply| reply.body()).transpose()
+    }
+
+    /// Activate the destination if activation retry is enabled and `error` is caused by the
+    /// destination having no owner.
+    ///
+    /// Returns `true` if the call should be retried, i-e the destination was activated and now has
+    /// an owner. Calls made with `NoAutoStart` are never retried, since they explicitly opted out
+    /// of activation.
+    //
+    // The future is boxed since activation goes through `fdo::DBusProxy`, and hence back here.
+    fn activate_on<'p>(
+        &'p self,
+        error: &'p Error,
+        no_auto_start: bool,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'p>> {
+        Box::pin(async move {
+            if no_auto_start || !self.inner.activation_retry || !self.connection().is_bus() {
+                return false;
+            }
+            let name = match self.destination() {
+                BusName::WellKnown(name) => name,
+                BusName::Unique(_) => return false,
+            };
+            match error {
+                Error::MethodError(error_name, _, _)
+                    if error_name == "org.freedesktop.DBus.Error.ServiceUnknown"
+                        || error_name == "org.freedesktop.DBus.Error.NameHasNoOwner" => {}
+                _ => return false,
+            }
+
+            match activate(self.connection(), name).await {
+                Ok(true) => {
+                    debug!("Activated `{name}`, retrying the call");
+
+                    true
+                }
+                Ok(false) => {
+                    debug!("`{name}` got no owner after activation, not retrying the call");
+
+                    false
+                }
+                Err(e) => {
+                    debug!("Failed to activate `{name}`: {e}");
+
+                    false
+                }
+            }
+        })
     }
 
     /// Call a method without expecting a reply
@@ -977,8 +1220,14 @@ impl<'a> Proxy<'a> {
     ///
     /// Note that zbus doesn't queue the updates. If the listener is slower than the receiver, it
     /// will only receive the last update.
+    ///
+    /// Since there are no name owners outside the bus context, this method fails with
+    /// [`Error::NotABus`] on peer-to-peer connections.
     pub async fn receive_owner_changed(&self) -> Result<OwnerChangedStream<'_>> {
         use futures_util::StreamExt;
+        if !self.connection().is_bus() {
+            return Err(Error::NotABus);
+        }
         let dbus_proxy = fdo::DBusProxy::builder(self.connection())
             .cache_properties(CacheProperties::No)
             .build()
@@ -998,10 +1247,60 @@ impl<'a> Proxy<'a> {
     }
 }
 
+// How long to wait for an activated service to own its name, before giving up on retrying.
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(25);
+
+// Activate the service owning `name` and wait for it to own the name.
+//
+// Returns `false` if the name didn't get an owner within `ACTIVATION_TIMEOUT`.
+async fn activate(conn: &Connection, name: &WellKnownName<'_>) -> Result<bool> {
+    use futures_util::StreamExt;
+
+    let dbus_proxy = fdo::DBusProxy::builder(conn)
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?;
+    // Watch the name before activating the service, so its acquisition can't be missed.
+    let mut owner_changes = dbus_proxy
+        .receive_name_owner_changed_with_args(&[(0, name.as_str())])
+        .await?;
+    dbus_proxy.start_service_by_name(name.as_ref(), 0).await?;
+    // The bus usually replies once the service owns the name, but that's not guaranteed (e.g
+    // with systemd activation).
+    if dbus_proxy.name_has_owner(name.as_ref().into()).await? {
+        return Ok(true);
+    }
+
+    let owned = async {
+        while let Some(signal) = owner_changes.next().await {
+            if signal
+                .args()
+                .map_or(false, |args| args.new_owner().is_some())
+            {
+                return true;
+            }
+        }
+
+        false
+    };
+    futures_util::pin_mut!(owned);
+    let timeout = crate::abstractions::timer::sleep(ACTIVATION_TIMEOUT);
+    futures_util::pin_mut!(timeout);
+
+    Ok(match futures_util::future::select(owned, timeout).await {
+        Either::Left((owned, _)) => owned,
+        Either::Right(_) => false,
+    })
+}
+
 #[derive(Debug, Default)]
 struct PropertyValue {
     value: Option<OwnedValue>,
     event: Event,
+    // The number of updates received, to detect the ones made during a `Set` call.
+    updates: u64,
+    // If `value` was set by us and not confirmed by a `PropertiesChanged` signal yet.
+    written: bool,
 }
 
 /// Flags to use with [`Proxy::call_with_flags`].
@@ -1089,6 +1388,8 @@ impl<'a> stream::Stream for OwnerChangedStream<'a> {
 pub struct SignalStream<'a> {
     stream: Join<MessageStream, Option<MessageStream>>,
     src_unique_name: Option<UniqueName<'static>>,
+    // `false` for p2p connections, where the peer is the only possible sender.
+    match_sender: bool,
     signal_name: Option<MemberName<'a>>,
 }
 
@@ -1103,9 +1404,13 @@ impl<'a> SignalStream<'a> {
         signal_name: Option<MemberName<'a>>,
         args: &[(u8, &str)],
     ) -> Result<SignalStream<'a>> {
-        let mut rule_builder = MatchRule::builder()
-            .msg_type(Type::Signal)
-            .sender(proxy.destination())?
+        let conn = proxy.connection();
+        let mut rule_builder = MatchRule::builder().msg_type(Type::Signal);
+        if conn.is_bus() {
+            // Outside the bus context, the peer is the only possible sender.
+            rule_builder = rule_builder.sender(proxy.destination())?;
+        }
+        let mut rule_builder = rule_builder
             .path(proxy.path())?
             .interface(proxy.interface())?;
         if let Some(name) = &signal_name {
@@ -1115,9 +1420,15 @@ impl<'a> SignalStream<'a> {
             rule_builder = rule_builder.arg(*i, *arg)?;
         }
         let signal_rule: OwnedMatchRule = rule_builder.build().to_owned().into();
-        let conn = proxy.connection();
 
         let (src_unique_name, stream) = match proxy.destination().to_owned() {
+            _ if !conn.is_bus() => (
+                None,
+                join_streams(
+                    MessageStream::for_match_rule(signal_rule, conn, None).await?,
+                    None,
+                ),
+            ),
             BusName::Unique(name) => (
                 Some(name),
                 join_streams(
@@ -1227,6 +1538,7 @@ impl<'a> SignalStream<'a> {
         Ok(SignalStream {
             stream,
             src_unique_name,
+            match_sender: conn.is_bus(),
             signal_name,
         })
     }
@@ -1234,7 +1546,7 @@ impl<'a> SignalStream<'a> {
     fn filter(&mut self, msg: &Message) -> Result<bool> {
         let header = msg.header();
         let sender = header.sender();
-        if sender == self.src_unique_name.as_ref() {
+        if !self.match_sender || sender == self.src_unique_name.as_ref() {
             return Ok(true);
         }
 
@@ -1501,6 +1813,133 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[timeout(15000)]
+    fn activation_retry() {
+        block_on(test_activation_retry()).unwrap();
+    }
+
+    async fn test_activation_retry() -> Result<()> {
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = Builder::new(&conn)
+            .destination("org.freedesktop.zbus.NotActivatable")?
+            .path("/org/freedesktop/zbus/NotActivatable")?
+            .interface("org.freedesktop.zbus.NotActivatable")?
+            .activation_retry(true)
+            .build()
+            .await?;
+
+        // The name can't be activated so the error of the call is returned as is.
+        match proxy.call_method("Method", &()).await {
+            Err(Error::MethodError(name, _, _)) => {
+                assert_eq!(name, "org.freedesktop.DBus.Error.ServiceUnknown")
+            }
+            reply => panic!("unexpected reply: {reply:?}"),
+        }
+        let reply = proxy
+            .call_with_flags::<_, _, ()>("Method", BitFlags::empty(), &())
+            .await;
+        assert!(matches!(reply, Err(Error::MethodError(_, _, _))));
+
+        // Calls opting out of activation aren't retried.
+        struct Members(Arc<std::sync::Mutex<Vec<String>>>);
+
+        impl crate::connection::CallObserver for Members {
+            type Baggage = ();
+
+            fn call_sent(&self, call: &Message) {
+                let member = call.header().member().map(|m| m.to_string());
+                self.0.lock().unwrap().extend(member);
+            }
+
+            fn reply_received(&self, _call: &Message, _reply: &Message, _baggage: ()) {}
+        }
+
+        let members = Arc::new(std::sync::Mutex::new(vec![]));
+        let conn = crate::connection::Builder::session()?
+            .call_observer(Members(members.clone()))
+            .build()
+            .await?;
+        let proxy: Proxy<'_> = Builder::new(&conn)
+            .destination("org.freedesktop.zbus.NotActivatable")?
+            .path("/org/freedesktop/zbus/NotActivatable")?
+            .interface("org.freedesktop.zbus.NotActivatable")?
+            .activation_retry(true)
+            .build()
+            .await?;
+        let reply = proxy
+            .call_with_flags::<_, _, ()>("Method", MethodFlags::NoAutoStart.into(), &())
+            .await;
+        assert!(matches!(reply, Err(Error::MethodError(_, _, _))));
+        assert!(!members
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|m| m == "StartServiceByName"));
+        proxy.call_method("Method", &()).await.unwrap_err();
+        assert!(members
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|m| m == "StartServiceByName"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn call_checked() {
+        block_on(test_call_checked()).unwrap();
+    }
+
+    async fn test_call_checked() -> Result<()> {
+        let conn = Connection::session().await?;
+        let proxy = fdo::DBusProxy::new(&conn).await?;
+
+        let id: String = proxy.call_checked("GetId", &(), "s").await?;
+        assert_eq!(id.len(), 32);
+        let reply = proxy.call_checked::<_, _, _, u32>("GetId", &(), "u").await;
+        match reply {
+            Err(Error::BodySignatureMismatch(expected, found)) => {
+                assert_eq!(expected.as_str(), "u");
+                assert_eq!(found.as_str(), "s");
+            }
+            reply => panic!("unexpected reply: {reply:?}"),
+        }
+        // An invalid signature is reported before the call.
+        assert!(matches!(
+            proxy
+                .call_checked::<_, _, _, String>("GetId", &(), "a")
+                .await,
+            Err(Error::Variant(_))
+        ));
+
+        let reply = conn
+            .call_method_checked(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                Some("org.freedesktop.DBus"),
+                "GetId",
+                &(),
+                "s",
+            )
+            .await?;
+        assert_eq!(reply.body::<String>()?, id);
+        let reply = conn
+            .call_method_checked(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                Some("org.freedesktop.DBus"),
+                "GetId",
+                &(),
+                "",
+            )
+            .await;
+        assert!(matches!(reply, Err(Error::BodySignatureMismatch(_, _))));
+
+        Ok(())
+    }
 }
 
 /// This trait is implemented by all async proxies, which are generated with the
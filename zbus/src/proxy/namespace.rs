@@ -0,0 +1,227 @@
+use futures_util::{stream, StreamExt};
+use static_assertions::assert_impl_all;
+use std::future::Future;
+use zbus_names::{BusName, InterfaceName, MemberName};
+use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+use super::tree::introspect_node;
+use crate::{
+    proxy::{Builder, CacheProperties},
+    Connection, Error, Proxy, Result,
+};
+
+const DEFAULT_MAX_CONCURRENT: usize = 8;
+
+/// Batch operations on all the objects of a path namespace.
+///
+/// Services like BlueZ expose a tree of objects (e.g one per device), all implementing the same
+/// interface. `Namespace` helps with operating on all of them at once: it recursively introspects
+/// the tree rooted at the given path to find the objects implementing the given interface and then
+/// runs the requested operation on each of them, concurrently.
+///
+/// The number of requests in flight at any point (both while introspecting and while running the
+/// operation) is limited to 8 by default. Use [`Namespace::max_concurrent`] to change that.
+///
+/// This type is only available with the `xml` feature enabled.
+///
+/// # Example
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use zbus::{proxy::Namespace, Connection};
+///
+/// let conn = Connection::system().await?;
+/// let devices = Namespace::new(&conn, "org.bluez", "/org/bluez", "org.bluez.Device1")?
+///     .max_concurrent(4);
+///
+/// // Read a property of all devices.
+/// for (path, name) in devices.get_property::<String>("Name").await? {
+///     println!("{path}: {}", name.unwrap_or_else(|e| e.to_string()));
+/// }
+///
+/// // Disconnect all devices.
+/// for (path, res) in devices.call::<_, _, ()>("Disconnect", &()).await? {
+///     if let Err(e) = res {
+///         println!("Failed to disconnect {path}: {e}");
+///     }
+/// }
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Namespace<'a> {
+    conn: Connection,
+    destination: BusName<'a>,
+    root: ObjectPath<'a>,
+    interface: InterfaceName<'a>,
+    max_concurrent: usize,
+}
+
+assert_impl_all!(Namespace<'_>: Send, Sync, Unpin);
+
+impl<'a> Namespace<'a> {
+    /// Create a new `Namespace` for the objects implementing `interface` under `root` (including
+    /// `root` itself), on `destination`.
+    pub fn new<D, P, I>(conn: &Connection, destination: D, root: P, interface: I) -> Result<Self>
+    where
+        D: TryInto<BusName<'a>>,
+        P: TryInto<ObjectPath<'a>>,
+        I: TryInto<InterfaceName<'a>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+    {
+        Ok(Self {
+            conn: conn.clone(),
+            destination: destination.try_into().map_err(Into::into)?,
+            root: root.try_into().map_err(Into::into)?,
+            interface: interface.try_into().map_err(Into::into)?,
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+        })
+    }
+
+    /// Set the maximum number of requests in flight at any point.
+    ///
+    /// A value of `0` is treated as `1`.
+    #[must_use]
+    pub fn max_concurrent(mut self, max: usize) -> Self {
+        self.max_concurrent = max.max(1);
+
+        self
+    }
+
+    /// The paths of all the objects in the namespace implementing the interface, sorted.
+    pub async fn paths(&self) -> Result<Vec<OwnedObjectPath>> {
+        let mut paths = vec![];
+        let mut pending = vec![OwnedObjectPath::from(self.root.clone())];
+        while !pending.is_empty() {
+            let nodes: Vec<_> = stream::iter(pending.drain(..))
+                .map(|path| self.introspect(path))
+                .buffer_unordered(self.max_concurrent)
+                .collect()
+                .await;
+
+            for node in nodes {
+                let (path, implements, children) = node?;
+                if implements {
+                    paths.push(path.clone());
+                }
+                pending.extend(children);
+            }
+        }
+        paths.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        Ok(paths)
+    }
+
+    /// Run `op` on a [`Proxy`] for each of the objects in the namespace implementing the
+    /// interface.
+    ///
+    /// The result of each operation is returned along with the path of the object, sorted by the
+    /// path. An error is only returned directly if the objects couldn't be enumerated.
+    pub async fn for_each<F, Fut, T>(&self, op: F) -> Result<Vec<(OwnedObjectPath, Result<T>)>>
+    where
+        F: Fn(Proxy<'static>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let paths = self.paths().await?;
+        let op = &op;
+        let mut results: Vec<_> = stream::iter(paths)
+            .map(|path| async move {
+                let res = match self.proxy(&path).await {
+                    Ok(proxy) => op(proxy).await,
+                    Err(e) => Err(e),
+                };
+
+                (path, res)
+            })
+            .buffer_unordered(self.max_concurrent)
+            .collect()
+            .await;
+        results.sort_unstable_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+        Ok(results)
+    }
+
+    /// Call a method on each of the objects in the namespace implementing the interface.
+    ///
+    /// See [`Namespace::for_each`] for details on the return value.
+    pub async fn call<'m, M, B, R>(
+        &self,
+        method_name: M,
+        body: &B,
+    ) -> Result<Vec<(OwnedObjectPath, Result<R>)>>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        let method_name = method_name.try_into().map_err(Into::into)?;
+        let method_name = &method_name;
+
+        self.for_each(|proxy| async move { proxy.call(method_name, body).await })
+            .await
+    }
+
+    /// Get a property of each of the objects in the namespace implementing the interface.
+    ///
+    /// See [`Namespace::for_each`] for details on the return value.
+    pub async fn get_property<T>(
+        &self,
+        property_name: &str,
+    ) -> Result<Vec<(OwnedObjectPath, Result<T>)>>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<Error>,
+    {
+        self.for_each(|proxy| async move { proxy.get_property(property_name).await })
+            .await
+    }
+
+    /// Set a property of each of the objects in the namespace implementing the interface.
+    ///
+    /// See [`Namespace::for_each`] for details on the return value.
+    pub async fn set_property<'t, T>(
+        &self,
+        property_name: &str,
+        value: T,
+    ) -> Result<Vec<(OwnedObjectPath, Result<()>)>>
+    where
+        T: Into<Value<'t>> + Clone + 't,
+    {
+        let value = &value;
+
+        self.for_each(|proxy| async move {
+            proxy
+                .set_property(property_name, value.clone())
+                .await
+                .map_err(Into::into)
+        })
+        .await
+    }
+
+    async fn proxy(&self, path: &OwnedObjectPath) -> Result<Proxy<'static>> {
+        Builder::<Proxy<'static>>::new(&self.conn)
+            .destination(self.destination.to_owned())?
+            .path(path.clone())?
+            .interface(self.interface.to_owned())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+    }
+
+    // Returns the path back, whether the object implements the interface and the paths of its
+    // children.
+    async fn introspect(
+        &self,
+        path: OwnedObjectPath,
+    ) -> Result<(OwnedObjectPath, bool, Vec<OwnedObjectPath>)> {
+        let (interfaces, children) = introspect_node(&self.conn, &self.destination, &path).await?;
+        let implements = interfaces
+            .iter()
+            .any(|iface| iface.as_str() == self.interface.as_str());
+
+        Ok((path, implements, children))
+    }
+}
@@ -0,0 +1,328 @@
+use futures_util::{future::BoxFuture, stream, FutureExt, StreamExt};
+use static_assertions::assert_impl_all;
+use std::fmt;
+use tracing::warn;
+use zbus_names::{BusName, OwnedInterfaceName};
+use zvariant::{ObjectPath, OwnedObjectPath};
+
+use crate::{fdo::IntrospectableProxy, proxy::CacheProperties, Connection, Error, Result};
+
+const MAX_CONCURRENT: usize = 8;
+
+/// The tree of objects of a service, along with the interfaces they implement.
+///
+/// This is the equivalent of `busctl tree`: [`ObjectTree::introspect`] recursively introspects the
+/// objects of a destination, starting at the given path, and the [`Display`](fmt::Display)
+/// implementation prints the resulting tree.
+///
+/// Objects that fail to be introspected (e.g because of access restrictions or invalid
+/// introspection data) don't abort the whole introspection. They're kept in the tree, without
+/// interfaces or children, and the error is available through [`ObjectTree::error`].
+///
+/// This type is only available with the `xml` feature enabled.
+///
+/// # Example
+///
+/// ```no_run
+/// # zbus::block_on(async {
+/// use zbus::{proxy::ObjectTree, Connection};
+///
+/// let conn = Connection::system().await?;
+/// let tree = ObjectTree::introspect(&conn, "org.freedesktop.login1", "/").await?;
+/// print!("{tree}");
+///
+/// // Or walk the tree.
+/// for child in tree.children() {
+///     println!("{}: {:?}", child.path(), child.interfaces());
+/// }
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectTree {
+    path: OwnedObjectPath,
+    interfaces: Vec<OwnedInterfaceName>,
+    children: Vec<ObjectTree>,
+    error: Option<Error>,
+}
+
+assert_impl_all!(ObjectTree: Send, Sync, Unpin);
+
+impl ObjectTree {
+    /// Introspect the objects of `destination`, recursively, starting at `root`.
+    ///
+    /// # Errors
+    ///
+    /// If `root` itself can't be introspected. Errors introspecting its descendants are recorded in
+    /// the corresponding nodes instead.
+    pub async fn introspect<'a, D, P>(conn: &Connection, destination: D, root: P) -> Result<Self>
+    where
+        D: TryInto<BusName<'a>>,
+        P: TryInto<ObjectPath<'a>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+    {
+        let destination = destination.try_into().map_err(Into::into)?;
+        let root = root.try_into().map_err(Into::into)?;
+
+        let (interfaces, children) = introspect_node(conn, &destination, &root).await?;
+
+        Ok(introspect_children(conn, &destination, root.into(), interfaces, children).await)
+    }
+
+    /// The path of the object.
+    pub fn path(&self) -> &ObjectPath<'_> {
+        &self.path
+    }
+
+    /// The interfaces implemented by the object, in the order given by the service.
+    pub fn interfaces(&self) -> &[OwnedInterfaceName] {
+        &self.interfaces
+    }
+
+    /// The children of the object, sorted by path.
+    pub fn children(&self) -> &[ObjectTree] {
+        &self.children
+    }
+
+    /// The error introspecting the object, if it failed.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    fn fmt_subtree(&self, f: &mut fmt::Formatter<'_>, prefix: &str) -> fmt::Result {
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i == self.children.len() - 1;
+            let (branch, indent) = if last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            write!(f, "{prefix}{branch}")?;
+            child.fmt_node(f)?;
+            child.fmt_subtree(f, &format!("{prefix}{indent}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_node(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        if !self.interfaces.is_empty() {
+            let interfaces: Vec<_> = self.interfaces.iter().map(|i| i.as_str()).collect();
+            write!(f, " ({})", interfaces.join(", "))?;
+        }
+        if let Some(e) = &self.error {
+            write!(f, " [error: {e}]")?;
+        }
+
+        writeln!(f)
+    }
+}
+
+impl fmt::Display for ObjectTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_node(f)?;
+        self.fmt_subtree(f, "")
+    }
+}
+
+fn introspect_subtree<'a>(
+    conn: &'a Connection,
+    destination: &'a BusName<'a>,
+    path: OwnedObjectPath,
+) -> BoxFuture<'a, ObjectTree> {
+    async move {
+        match introspect_node(conn, destination, &path).await {
+            Ok((interfaces, children)) => {
+                introspect_children(conn, destination, path, interfaces, children).await
+            }
+            Err(e) => {
+                warn!("Failed to introspect `{path}` of `{destination}`: {e}");
+
+                ObjectTree {
+                    path,
+                    interfaces: vec![],
+                    children: vec![],
+                    error: Some(e),
+                }
+            }
+        }
+    }
+    .boxed()
+}
+
+async fn introspect_children<'a>(
+    conn: &'a Connection,
+    destination: &'a BusName<'a>,
+    path: OwnedObjectPath,
+    interfaces: Vec<OwnedInterfaceName>,
+    children: Vec<OwnedObjectPath>,
+) -> ObjectTree {
+    let mut children = stream::iter(children)
+        .map(|child| introspect_subtree(conn, destination, child))
+        .buffered(MAX_CONCURRENT)
+        .collect::<Vec<_>>()
+        .await;
+    children.sort_unstable_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
+
+    ObjectTree {
+        path,
+        interfaces,
+        children,
+        error: None,
+    }
+}
+
+// Introspect a single object, returning the interfaces it implements and the paths of its children.
+pub(super) async fn introspect_node(
+    conn: &Connection,
+    destination: &BusName<'_>,
+    path: &ObjectPath<'_>,
+) -> Result<(Vec<OwnedInterfaceName>, Vec<OwnedObjectPath>)> {
+    let xml = IntrospectableProxy::builder(conn)
+        .destination(destination)?
+        .path(path)?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?
+        .introspect()
+        .await?;
+    let node = zbus_xml::Node::from_reader(xml.as_bytes())
+        .map_err(|e| Error::Failure(format!("invalid introspection XML for `{path}`: {e}")))?;
+
+    let interfaces = node
+        .interfaces()
+        .iter()
+        .map(|iface| OwnedInterfaceName::from(iface.name()))
+        .collect();
+    let children = node
+        .nodes()
+        .iter()
+        .filter_map(|child| child.name())
+        .map(|name| name.trim_start_matches('/'))
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let child = match path.as_str() {
+                "/" => format!("/{name}"),
+                parent => format!("{parent}/{name}"),
+            };
+
+            OwnedObjectPath::try_from(child).map_err(Into::into)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((interfaces, children))
+}
+
+#[cfg(test)]
+mod tests {
+    use ntest::timeout;
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn display() {
+        let node = |path: &str, interfaces: &[&str], children| ObjectTree {
+            path: ObjectPath::try_from(path).unwrap().into(),
+            interfaces: interfaces
+                .iter()
+                .map(|i| OwnedInterfaceName::try_from(*i).unwrap())
+                .collect(),
+            children,
+            error: None,
+        };
+        let tree = node(
+            "/",
+            &[],
+            vec![node(
+                "/org",
+                &[],
+                vec![
+                    node("/org/a", &["org.zbus.A", "org.zbus.B"], vec![]),
+                    node(
+                        "/org/b",
+                        &[],
+                        vec![node("/org/b/c", &["org.zbus.C"], vec![])],
+                    ),
+                ],
+            )],
+        );
+
+        assert_eq!(
+            tree.to_string(),
+            "/\n\
+             └─ /org\n   \
+                ├─ /org/a (org.zbus.A, org.zbus.B)\n   \
+                └─ /org/b\n      \
+                   └─ /org/b/c (org.zbus.C)\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn failed_child() {
+        crate::utils::block_on(test_failed_child()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_failed_child() -> Result<()> {
+        use crate::{connection::Builder, message::Type, object_server::Policy, Guid, MatchRule};
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Object;
+
+        #[crate::dbus_interface(name = "org.zbus.Object")]
+        impl Object {}
+
+        // Introspecting the objects under `/secret` is denied.
+        let policy = Policy::allow_all().deny(
+            MatchRule::builder()
+                .msg_type(Type::MethodCall)
+                .interface("org.freedesktop.DBus.Introspectable")?
+                .path_namespace("/secret")?
+                .build(),
+        );
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (_server, client) = futures_util::try_join!(
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .policy(policy)
+                .serve_at("/public/a", Object)?
+                .serve_at("/secret/b", Object)?
+                .build(),
+            Builder::unix_stream(p1).p2p().build(),
+        )?;
+
+        let tree = ObjectTree::introspect(&client, "org.zbus.Test", "/").await?;
+        assert!(tree.error().is_none());
+        let children: Vec<_> = tree.children().iter().map(|c| c.path().as_str()).collect();
+        assert_eq!(children, ["/public", "/secret"]);
+
+        // The failed child doesn't prevent introspecting its siblings.
+        let public = &tree.children()[0];
+        assert!(public.error().is_none());
+        assert_eq!(public.children()[0].path().as_str(), "/public/a");
+        let secret = &tree.children()[1];
+        match secret.error() {
+            Some(Error::FDO(e)) => assert!(matches!(**e, crate::fdo::Error::AccessDenied(_))),
+            e => panic!("unexpected error: {e:?}"),
+        }
+        assert!(secret.children().is_empty());
+        assert!(tree.to_string().contains("└─ /secret [error: "));
+
+        // Unless it's the root.
+        ObjectTree::introspect(&client, "org.zbus.Test", "/secret")
+            .await
+            .unwrap_err();
+
+        Ok(())
+    }
+}
@@ -19,7 +19,9 @@ use zbus::{
     object_server::ResponseDispatchNotifier,
     DBusError, Error, Message, MessageStream,
 };
-use zvariant::{DeserializeDict, Optional, OwnedValue, SerializeDict, Str, Type, Value};
+use zvariant::{
+    DeserializeDict, Optional, OwnedObjectPath, OwnedValue, SerializeDict, Str, Type, Value,
+};
 
 use zbus::{
     connection, dbus_interface, dbus_proxy,
@@ -51,7 +53,7 @@ pub struct RefType<'a> {
     field1: Str<'a>,
 }
 
-#[dbus_proxy(assume_defaults = true, gen_blocking = true)]
+#[dbus_proxy(assume_defaults = true, gen_blocking = true, gen_with_flags = true)]
 trait MyIface {
     fn ping(&self) -> zbus::Result<u32>;
 
@@ -530,6 +532,14 @@ async fn my_iface_test(conn: Connection, event: Event) -> zbus::Result<u32> {
     proxy.test_no_reply().await?;
     proxy.test_no_autostart().await?;
     proxy.test_interactive_auth().await?;
+    // Flags declared on the method are kept when passing more on the call.
+    let reply = proxy
+        .test_no_autostart_with_flags(zbus::proxy::MethodFlags::AllowInteractiveAuth.into())
+        .await?;
+    assert_eq!(reply, Some(()));
+    proxy
+        .test_interactive_auth_with_flags(zbus::proxy::MethodFlags::NoAutoStart.into())
+        .await?;
 
     let err = proxy.fail_property().await;
     assert_eq!(
@@ -572,6 +582,20 @@ async fn my_iface_test(conn: Connection, event: Event) -> zbus::Result<u32> {
             assert_eq!(bar.ty().signature(), "s");
         }
     }
+    // The parent node only lists its children.
+    let xml = zbus::fdo::IntrospectableProxy::builder(&conn)
+        .destination("org.freedesktop.MyService")?
+        .path("/org/freedesktop")?
+        .build()
+        .await?
+        .introspect()
+        .await?;
+    let node =
+        zbus_xml::Node::from_reader(xml.as_bytes()).map_err(|e| Error::Failure(e.to_string()))?;
+    let children: Vec<_> = node.nodes().iter().map(|n| n.name()).collect();
+    assert_eq!(children, [Some("MyService")]);
+    assert!(node.nodes()[0].interfaces().is_empty());
+
     // build-time check to see if macro is doing the right thing.
     let _ = proxy.test_single_struct_ret().await?.foo;
     let _ = proxy.test_multi_ret().await?.1;
@@ -637,6 +661,43 @@ async fn my_iface_test(conn: Connection, event: Event) -> zbus::Result<u32> {
     );
     my_obj_proxy.ping().await?;
 
+    #[cfg(feature = "xml")]
+    {
+        let objects = zbus::proxy::Namespace::new(
+            &conn,
+            "org.freedesktop.MyService",
+            "/",
+            "org.freedesktop.MyIface",
+        )?
+        .max_concurrent(2);
+        let paths = objects.paths().await?;
+        let paths: Vec<_> = paths.iter().map(|p| p.as_str()).collect();
+        assert_eq!(paths, ["/org/freedesktop/MyService", "/zbus/test/MyObj"]);
+        let counts = objects.get_property::<u32>("Count").await?;
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[1].0.as_str(), "/zbus/test/MyObj");
+        // The `ping` call above incremented the count.
+        assert_eq!(*counts[1].1.as_ref().unwrap(), 1);
+
+        let tree =
+            zbus::proxy::ObjectTree::introspect(&conn, "org.freedesktop.MyService", "/").await?;
+        let children: Vec<_> = tree.children().iter().map(|c| c.path().as_str()).collect();
+        assert_eq!(children, ["/org", "/zbus"]);
+        let test = &tree.children()[1].children()[0];
+        assert_eq!(test.path().as_str(), "/zbus/test");
+        assert!(test
+            .interfaces()
+            .iter()
+            .any(|i| i.as_str() == "org.freedesktop.DBus.ObjectManager"));
+        let my_obj = &test.children()[0];
+        assert_eq!(my_obj.path().as_str(), "/zbus/test/MyObj");
+        assert!(my_obj
+            .interfaces()
+            .iter()
+            .any(|i| i.as_str() == "org.freedesktop.MyIface"));
+        assert!(tree.to_string().contains("└─ /zbus/test/MyObj ("));
+    }
+
     let mut ifaces_removed_stream = obj_manager_proxy.receive_interfaces_removed().await?;
     debug!("Created: {:?}", ifaces_removed_stream);
     // Must process in parallel, so the stream listener does not block receiving
@@ -690,6 +751,35 @@ fn iface_and_proxy_unix_p2p() {
     block_on(iface_and_proxy_(true));
 }
 
+#[test]
+fn iface_method_table() {
+    use zbus::object_server::{Interface, MethodEntry, MethodHandler};
+
+    let (next_tx, _next_rx) = channel(1);
+    let iface = MyIfaceImpl::new(next_tx);
+    let methods = iface.methods().unwrap();
+
+    // Sorted by name, properties and signals excluded.
+    assert!(methods.windows(2).all(|w| w[0].name() < w[1].name()));
+    assert!(methods
+        .iter()
+        .all(|m| m.name() != "Count" && m.name() != "AlertCount"));
+
+    let ping = methods.iter().find(|m| m.name() == "Ping").unwrap();
+    assert!(ping.requires_mut());
+    let quit = methods.iter().find(|m| m.name() == "Quit").unwrap();
+    assert!(!quit.requires_mut());
+    assert!(matches!(quit.handler(), MethodHandler::Ref(_)));
+
+    // Only the arguments read from the message body make the signature.
+    assert_eq!(ping.signature(), "");
+    let create_obj_inside = MethodEntry::find(methods, "CreateObjInside").unwrap();
+    assert_eq!(create_obj_inside.signature(), "s");
+    let test_single_struct_arg = MethodEntry::find(methods, "TestSingleStructArg").unwrap();
+    assert_eq!(test_single_struct_arg.signature(), "(is)");
+    assert!(MethodEntry::find(methods, "Count").is_none());
+}
+
 #[instrument]
 async fn iface_and_proxy_(p2p: bool) {
     let event = event_listener::Event::new();
@@ -770,6 +860,18 @@ async fn iface_and_proxy_(p2p: bool) {
     debug!("Client connection created: {:?}", client_conn);
     debug!("Service connection created: {:?}", service_conn);
 
+    let nodes = service_conn.object_server().nodes().await;
+    assert_eq!(nodes.len(), 2);
+    assert!(
+        nodes[&OwnedObjectPath::try_from("/org/freedesktop/MyService").unwrap()]
+            .iter()
+            .any(|iface| *iface == "org.freedesktop.MyIface")
+    );
+    assert_eq!(
+        nodes[&OwnedObjectPath::try_from("/zbus/test").unwrap()],
+        ["org.freedesktop.DBus.ObjectManager"]
+    );
+
     let listen = event.listen();
     let child = client_conn
         .executor()
@@ -2,10 +2,10 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::collections::BTreeMap;
 use syn::{
-    self, parse_quote, punctuated::Punctuated, spanned::Spanned, AngleBracketedGenericArguments,
-    AttributeArgs, Error, FnArg, GenericArgument, ImplItem, ItemImpl, Lit::Str, Meta,
-    Meta::NameValue, MetaList, MetaNameValue, NestedMeta, PatType, PathArguments, ReturnType,
-    Signature, Token, Type, TypePath,
+    self, ext::IdentExt, parse_quote, punctuated::Punctuated, spanned::Spanned,
+    AngleBracketedGenericArguments, AttributeArgs, Error, FnArg, GenericArgument, ImplItem,
+    ItemImpl, Lit::Str, Meta, Meta::NameValue, MetaList, MetaNameValue, NestedMeta, PatType,
+    PathArguments, ReturnType, Signature, Token, Type, TypePath,
 };
 use zvariant_utils::{case, def_attrs};
 
@@ -73,8 +73,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
     let mut set_mut_dispatch = quote!();
     let mut get_dispatch = quote!();
     let mut get_all = quote!();
-    let mut call_dispatch = quote!();
-    let mut call_mut_dispatch = quote!();
+    let mut method_entries = Vec::new();
     let mut introspect = quote!();
     let mut generated_signals = quote!();
 
@@ -193,7 +192,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         let is_result_output =
             introspect_add_output_args(&mut intro_args, output, out_args, &cfg_attrs)?;
 
-        let (args_from_msg, args_names) = get_args_from_inputs(&typed_inputs, &zbus)?;
+        let (args_from_msg, args_names, body_tys) = get_args_from_inputs(&typed_inputs, &zbus)?;
 
         clean_input_args(inputs);
 
@@ -434,34 +433,58 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
             introspect.extend(doc_comments);
             introspect.extend(introspect_method(&member_name, &intro_args));
 
-            let m = quote! {
-                #(#cfg_attrs)*
-                #member_name => {
-                    let future = async move {
-                        #args_from_msg
-                        let reply = self.#ident(#args_names)#method_await;
-                        #reply
-                    };
-                    #zbus::object_server::DispatchResult::Async(::std::boxed::Box::pin(async move {
-                        future.await
-                    }))
-                },
+            // The handler gets the interface as a trait object, being the same type for all the
+            // interfaces. It's only ever given the interface whose table it's in.
+            let (handler, this) = if is_mut {
+                (
+                    quote!(Mut),
+                    quote!(<dyn #zbus::object_server::Interface>::downcast_mut::<Self>(iface)),
+                )
+            } else {
+                (
+                    quote!(Ref),
+                    quote!(<dyn #zbus::object_server::Interface>::downcast_ref::<Self>(iface)),
+                )
             };
-
-            if is_mut {
-                call_dispatch.extend(quote! {
+            method_entries.push((
+                member_name.clone(),
+                quote! {
                     #(#cfg_attrs)*
-                    #member_name => #zbus::object_server::DispatchResult::RequiresMut,
-                });
-                call_mut_dispatch.extend(m);
-            } else {
-                call_dispatch.extend(m);
-            }
+                    #zbus::object_server::MethodEntry::new(
+                        #member_name,
+                        #zbus::object_server::MethodHandler::#handler(|iface, s, c, m| {
+                            let this = #this.expect("method handler called on another interface");
+                            let future = async move {
+                                #args_from_msg
+                                let reply = this.#ident(#args_names)#method_await;
+                                #reply
+                            };
+                            #zbus::object_server::DispatchResult::Async(::std::boxed::Box::pin(async move {
+                                future.await
+                            }))
+                        }),
+                        || {
+                            #[allow(unused_mut)]
+                            let mut signature = ::std::string::String::new();
+                            #(
+                                signature.push_str(
+                                    <#body_tys as #zbus::zvariant::Type>::signature().as_str(),
+                                );
+                            )*
+                            #zbus::zvariant::Signature::from_string_unchecked(signature)
+                        },
+                    ),
+                },
+            ));
         }
     }
 
     introspect_properties(&mut introspect, properties)?;
 
+    // Methods are looked up by binary search so the table needs to be sorted.
+    method_entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let method_entries = method_entries.into_iter().map(|(_, entry)| entry);
+
     let generics = &input.generics;
     let where_clause = &generics.where_clause;
 
@@ -472,6 +495,9 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         #where_clause
         {
             #generated_signals
+
+            #[doc(hidden)]
+            const __ZBUS_METHODS: &'static [#zbus::object_server::MethodEntry] = &[#(#method_entries)*];
         }
 
         #[#zbus::export::async_trait::async_trait]
@@ -530,6 +556,10 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                 }
             }
 
+            fn methods(&self) -> ::std::option::Option<&'static [#zbus::object_server::MethodEntry]> {
+                ::std::option::Option::Some(Self::__ZBUS_METHODS)
+            }
+
             fn call<'call>(
                 &'call self,
                 s: &'call #zbus::ObjectServer,
@@ -537,9 +567,16 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                 m: &'call #zbus::message::Message,
                 name: #zbus::names::MemberName<'call>,
             ) -> #zbus::object_server::DispatchResult<'call> {
-                match name.as_str() {
-                    #call_dispatch
-                    _ => #zbus::object_server::DispatchResult::NotFound,
+                match #zbus::object_server::MethodEntry::find(Self::__ZBUS_METHODS, name.as_str())
+                    .map(#zbus::object_server::MethodEntry::handler)
+                {
+                    ::std::option::Option::Some(#zbus::object_server::MethodHandler::Ref(handler)) => {
+                        handler(self, s, c, m)
+                    }
+                    ::std::option::Option::Some(#zbus::object_server::MethodHandler::Mut(_)) => {
+                        #zbus::object_server::DispatchResult::RequiresMut
+                    }
+                    ::std::option::Option::None => #zbus::object_server::DispatchResult::NotFound,
                 }
             }
 
@@ -550,8 +587,12 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                 m: &'call #zbus::message::Message,
                 name: #zbus::names::MemberName<'call>,
             ) -> #zbus::object_server::DispatchResult<'call> {
-                match name.as_str() {
-                    #call_mut_dispatch
+                match #zbus::object_server::MethodEntry::find(Self::__ZBUS_METHODS, name.as_str())
+                    .map(#zbus::object_server::MethodEntry::handler)
+                {
+                    ::std::option::Option::Some(#zbus::object_server::MethodHandler::Mut(handler)) => {
+                        handler(self, s, c, m)
+                    }
                     _ => #zbus::object_server::DispatchResult::NotFound,
                 }
             }
@@ -576,12 +617,14 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
     })
 }
 
-fn get_args_from_inputs(
-    inputs: &[PatType],
+// Returns the code extracting the arguments from the message, the names of all the arguments and
+// the types of the ones read from the message body.
+fn get_args_from_inputs<'i>(
+    inputs: &'i [PatType],
     zbus: &TokenStream,
-) -> syn::Result<(TokenStream, TokenStream)> {
+) -> syn::Result<(TokenStream, TokenStream, Vec<&'i Type>)> {
     if inputs.is_empty() {
-        Ok((quote!(), quote!()))
+        Ok((quote!(), quote!(), vec![]))
     } else {
         let mut server_arg_decl = None;
         let mut conn_arg_decl = None;
@@ -649,7 +692,7 @@ fn get_args_from_inputs(
                 });
             } else {
                 args_names.push(pat_ident(input).unwrap());
-                tys.push(&input.ty);
+                tys.push(&*input.ty);
             }
         }
 
@@ -677,7 +720,7 @@ fn get_args_from_inputs(
         let all_args_names = inputs.iter().filter_map(pat_ident);
         let all_args_names = quote! { #(#all_args_names,)* };
 
-        Ok((args_from_msg, all_args_names))
+        Ok((args_from_msg, all_args_names, tys))
     }
 }
 
@@ -748,8 +791,8 @@ fn introspect_input_args<'i>(
                 return None;
             }
 
-            let ident = pat_ident(pat_type).unwrap();
-            let arg_name = quote!(#ident).to_string();
+            // The name of the Rust parameter, without the `r#` prefix of raw identifiers.
+            let arg_name = pat_ident(pat_type).unwrap().unraw().to_string();
             let dir = if is_signal { "" } else { " direction=\"in\"" };
             Some(quote!(
                 #(#cfg_attrs)*
@@ -45,6 +45,38 @@ mod utils;
 ///
 /// * `blocking_name` - Specify the exact name of the blocking proxy type.
 ///
+/// * `include` - A list of other interfaces implemented by the same remote objects, e.g.
+///   `include("zbus::fdo::Properties", "zbus::fdo::Introspectable")`. Each item is the path of the
+///   trait the proxies for the interface were generated from and, just like for the `object`
+///   attribute, the proxies are assumed to be named `TraitNameProxy` and `TraitNameProxyBlocking`.
+///   The proxies of the included interfaces must have been generated with `includable`, and the
+///   methods and properties of these interfaces are then available on the proxies directly, through
+///   the `TraitNameProxyMethods` and `TraitNameProxyBlockingMethods` traits (which need to be in
+///   scope), on the same destination and path.
+///
+/// * `includable` - Whether or not the proxies can be included by other proxies, through their
+///   `include` attribute (default: `false`). If `true`, the `<ProxyName>Methods` trait is generated
+///   for each proxy, with the methods and properties of the interface, and implemented for any type
+///   giving access to the proxy through `AsRef`. Signals and `object` methods aren't part of it.
+///
+/// * `gen_with_flags` - Whether or not to generate the `<method_name>_with_flags` variant of the
+///   methods (default: `false`). Read the [Per-call flags](#per-call-flags) section below for
+///   details.
+///
+/// * `gen_server` - Whether or not to also generate the server side of the interface (default:
+///   `false`). Read the [Server](#server) section below for details.
+///
+/// * `server_name` - Specify the exact name of the server type, `TraitNameServer` by default.
+///
+/// * `keep_trait` - Whether or not to keep the trait itself (default: `false`), implemented by the
+///   asynchronous proxy (or the blocking one, if the asynchronous proxy isn't generated). This
+///   allows the application code to depend on `dyn TraitName` rather than the generated proxy, and
+///   use a different implementation (e.g a mock) in tests. The trait only contains the methods and
+///   properties declared (signals and `object` methods are left out) and, unless it's implemented
+///   by the blocking proxy, its methods are `async` and must be implemented using the
+///   [`async_trait`] attribute. Generic methods and methods with unnamed arguments are not
+///   supported. This can't be used together with `gen_server`.
+///
 /// * `assume_defaults` - whether to auto-generate values for `default_path` and `default_service`
 ///   if none are specified (default: `false`). `dbus_proxy` generates a warning if neither this
 ///   attribute nor one of the default values are specified. Please make sure to explicitly set
@@ -95,6 +127,73 @@ mod utils;
 ///
 ///   NB: Any doc comments provided shall be appended to the ones added by the macro.
 ///
+/// # Per-call flags
+///
+/// If the `gen_with_flags` attribute is set, for each regular method (i.e not a property, signal or
+/// `object` method), the macro also provides a method named `<method_name>_with_flags`, taking a
+/// set of [`zbus::proxy::MethodFlags`] as its first argument, followed by the arguments of the
+/// method. This allows setting flags like `NoAutoStart` or `AllowInteractiveAuth` on a specific
+/// call, without having to declare them on the method itself. Flags declared through the method
+/// attributes are always set. Since the `NoReplyExpected` flag may be passed, these methods return
+/// an `Option` of the reply, which is `None` if no reply was waited for.
+///
+/// # Server
+///
+/// If the `gen_server` attribute is set, the macro also generates the service side of the
+/// interface, so that both sides are guaranteed to agree on the names and types of its members:
+///
+/// * A trait, with the same name as the one the macro is applied on, with an `async` method for
+///   each method and property declared (signals excluded), taking the same arguments and returning
+///   the same type. Methods with the `object` attribute return a
+///   `zbus::fdo::Result<OwnedObjectPath>` instead. This trait is to be implemented by the service,
+///   using the [`async_trait`] attribute.
+/// * A `TraitNameServer<T>` tuple struct wrapping an implementation of this trait, which
+///   implements the D-Bus interface (as if it were generated by [`dbus_interface`]) and can be
+///   served through the [`zbus::ObjectServer`]. The errors returned by the implementation are
+///   converted to [`zbus::fdo::Error`]. Properties are read-only, unless a setter is declared.
+///   Signals are available as associated functions, taking a [`zbus::object_server::SignalContext`]
+///   and the signal arguments.
+///
+/// Generic methods and methods with unnamed arguments are not supported in this mode.
+///
+/// ```no_run
+/// use zbus::{dbus_proxy, Connection, Result};
+///
+/// #[dbus_proxy(
+///     interface = "org.zbus.Greeter",
+///     default_path = "/org/zbus/Greeter",
+///     gen_server = true
+/// )]
+/// trait Greeter {
+///     fn say_hello(&self, name: &str) -> Result<String>;
+/// }
+///
+/// struct Greeting;
+///
+/// #[async_trait::async_trait]
+/// impl Greeter for Greeting {
+///     async fn say_hello(&self, name: &str) -> Result<String> {
+///         Ok(format!("Hello {name}!"))
+///     }
+/// }
+///
+/// # zbus::block_on(async {
+/// let _service = zbus::connection::Builder::session()?
+///     .name("org.zbus.Greeter")?
+///     .serve_at("/org/zbus/Greeter", GreeterServer(Greeting))?
+///     .build()
+///     .await?;
+///
+/// let connection = Connection::session().await?;
+/// let proxy = GreeterProxy::builder(&connection)
+///     .destination("org.zbus.Greeter")?
+///     .build()
+///     .await?;
+/// println!("{}", proxy.say_hello("Maria").await?);
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
 /// # Signals
 ///
 /// For each signal method declared, this macro will provide a method, named `receive_<method_name>`
@@ -184,6 +283,10 @@ mod utils;
 ///
 /// [`zbus_polkit`]: https://docs.rs/zbus_polkit/1.0.0/zbus_polkit/policykit1/index.html
 /// [`zbus::Proxy`]: https://docs.rs/zbus/latest/zbus/proxy/struct.Proxy.html
+/// [`zbus::ObjectServer`]: https://docs.rs/zbus/latest/zbus/object_server/struct.ObjectServer.html
+/// [`zbus::fdo::Error`]: https://docs.rs/zbus/latest/zbus/fdo/enum.Error.html
+/// [`zbus::object_server::SignalContext`]: https://docs.rs/zbus/latest/zbus/object_server/struct.SignalContext.html
+/// [`async_trait`]: https://docs.rs/async-trait
 /// [`zbus::message::Message`]: https://docs.rs/zbus/latest/zbus/message/struct.Message.html
 /// [`zbus::blocking::Proxy`]: https://docs.rs/zbus/latest/zbus/blocking/proxy/struct.Proxy.html
 /// [`zbus::SignalStream`]: https://docs.rs/zbus/latest/zbus/proxy/struct.SignalStream.html
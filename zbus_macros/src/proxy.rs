@@ -1,10 +1,11 @@
-use crate::utils::{pat_ident, typed_arg, zbus_path};
+use crate::utils::{get_doc_attrs, pat_ident, typed_arg, zbus_path};
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use regex::Regex;
 use syn::{
-    self, fold::Fold, parse_quote, parse_str, spanned::Spanned, AttributeArgs, Error, FnArg, Ident,
-    ItemTrait, Path, ReturnType, TraitItemMethod,
+    self, fold::Fold, parse_quote, parse_str, punctuated::Punctuated, spanned::Spanned,
+    AttributeArgs, Error, FnArg, GenericArgument, Ident, ItemTrait, Path, PathArguments,
+    ReturnType, Token, TraitItemMethod, Type,
 };
 use zvariant_utils::{case, def_attrs};
 
@@ -21,7 +22,13 @@ def_attrs! {
         async_name str,
         blocking_name str,
         gen_async bool,
-        gen_blocking bool
+        gen_blocking bool,
+        gen_server bool,
+        server_name str,
+        keep_trait bool,
+        include [str],
+        includable bool,
+        gen_with_flags bool
     };
 
     pub MethodAttributes("method") {
@@ -73,7 +80,14 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         blocking_name,
         gen_async,
         gen_blocking,
+        gen_server,
+        server_name,
+        keep_trait,
+        include,
+        includable,
+        gen_with_flags,
     } = ImplAttributes::parse_nested_metas(&args)?;
+    let include = include.unwrap_or_default();
 
     let iface_name = match (interface, name) {
         (Some(name), None) | (None, Some(name)) => Ok(Some(name)),
@@ -85,6 +99,16 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
     }?;
     let gen_async = gen_async.unwrap_or(true);
     let gen_blocking = gen_blocking.unwrap_or(true);
+    let gen_server = gen_server.unwrap_or(false);
+    let keep_trait = keep_trait.unwrap_or(false);
+    let includable = includable.unwrap_or(false);
+    let gen_with_flags = gen_with_flags.unwrap_or(false);
+    if gen_server && keep_trait {
+        return Err(Error::new(
+            input.span(),
+            "`keep_trait` and `gen_server` can't be used together, as both define the trait",
+        ));
+    }
 
     // Some sanity checks
     assert!(
@@ -100,6 +124,8 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         "Can't set asynchronous proxy's name if you disabled it. 😸",
     );
 
+    // The trait is implemented by the asynchronous proxy, unless only the blocking one is generated.
+    let mut user_trait = quote! {};
     let blocking_proxy = if gen_blocking {
         let proxy_name = blocking_name.unwrap_or_else(|| {
             if gen_async {
@@ -109,33 +135,77 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
                 format!("{}Proxy", input.ident)
             }
         });
-        create_proxy(
+        if keep_trait && !gen_async {
+            user_trait = create_trait(&input, &proxy_name, true)?;
+        }
+        let methods_trait = if includable {
+            create_methods_trait(&input, &proxy_name, true)?
+        } else {
+            quote! {}
+        };
+        let proxy = create_proxy(
             &input,
             iface_name.as_deref(),
             assume_defaults,
             default_path.as_deref(),
             default_service.as_deref(),
             &proxy_name,
+            &include,
+            gen_with_flags,
             true,
             // Signal args structs are shared between the two proxies so always generate it for
             // async proxy only unless async proxy generation is disabled.
             !gen_async,
-        )?
+        )?;
+
+        quote! {
+            #proxy
+
+            #methods_trait
+        }
     } else {
         quote! {}
     };
     let async_proxy = if gen_async {
         let proxy_name = async_name.unwrap_or_else(|| format!("{}Proxy", input.ident));
-        create_proxy(
+        if keep_trait {
+            user_trait = create_trait(&input, &proxy_name, false)?;
+        }
+        let methods_trait = if includable {
+            create_methods_trait(&input, &proxy_name, false)?
+        } else {
+            quote! {}
+        };
+        let proxy = create_proxy(
             &input,
             iface_name.as_deref(),
             assume_defaults,
             default_path.as_deref(),
             default_service.as_deref(),
             &proxy_name,
+            &include,
+            gen_with_flags,
             false,
             true,
-        )?
+        )?;
+
+        quote! {
+            #proxy
+
+            #methods_trait
+        }
+    } else {
+        quote! {}
+    };
+
+    let server = if gen_server {
+        let server_name = server_name.unwrap_or_else(|| format!("{}Server", input.ident));
+        create_server(&input, iface_name.as_deref(), &server_name)?
+    } else if server_name.is_some() {
+        return Err(Error::new(
+            input.span(),
+            "`server_name` requires `gen_server` to be enabled",
+        ));
     } else {
         quote! {}
     };
@@ -144,9 +214,336 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         #blocking_proxy
 
         #async_proxy
+
+        #user_trait
+
+        #server
+    })
+}
+
+// Keep the trait, with the methods and properties declared (signals and `object` methods
+// excluded), and implement it for the given proxy by forwarding to its own methods.
+fn create_trait(input: &ItemTrait, proxy_name: &str, blocking: bool) -> Result<TokenStream, Error> {
+    let zbus = zbus_path();
+
+    let vis = &input.vis;
+    let trait_name = &input.ident;
+    let proxy_name = Ident::new(proxy_name, Span::call_site());
+    let attrs = input
+        .attrs
+        .iter()
+        .filter(|a| !a.path.is_ident("dbus_proxy"));
+    let async_trait = if blocking {
+        quote! {}
+    } else {
+        quote! { #[#zbus::export::async_trait::async_trait] }
+    };
+
+    let mut trait_methods = TokenStream::new();
+    let mut impl_methods = TokenStream::new();
+    let receiver = quote! { self };
+    for (sig, call) in forwarded_methods(input, &proxy_name, &receiver, blocking, "keep_trait")? {
+        trait_methods.extend(quote! { #sig; });
+        impl_methods.extend(quote! { #sig { #call } });
+    }
+
+    Ok(quote! {
+        #(#attrs)*
+        #async_trait
+        #vis trait #trait_name: ::std::marker::Send + ::std::marker::Sync {
+            #trait_methods
+        }
+
+        #async_trait
+        impl<'p> #trait_name for #proxy_name<'p> {
+            #impl_methods
+        }
+    })
+}
+
+// Generate the `<ProxyName>Methods` trait of an `includable` proxy: the methods and properties
+// declared (signals and `object` methods excluded), forwarding to the proxy, for any type giving
+// access to it. Proxies including the interface implement `AsRef` for the proxy, and so the trait.
+fn create_methods_trait(
+    input: &ItemTrait,
+    proxy_name: &str,
+    blocking: bool,
+) -> Result<TokenStream, Error> {
+    let zbus = zbus_path();
+
+    let trait_name = format_ident!("{proxy_name}Methods");
+    let proxy_name = Ident::new(proxy_name, Span::call_site());
+    let async_trait = if blocking {
+        quote! {}
+    } else {
+        quote! { #[#zbus::export::async_trait::async_trait] }
+    };
+    let doc = format!(
+        " The methods of [`{proxy_name}`], for the proxies including its interface.\n\n \
+        Implemented for any type giving access to a [`{proxy_name}`] through `AsRef`.",
+    );
+
+    let mut methods = TokenStream::new();
+    let receiver = quote! { ::std::convert::AsRef::<#proxy_name<'p>>::as_ref(self) };
+    for (sig, call) in forwarded_methods(input, &proxy_name, &receiver, blocking, "includable")? {
+        methods.extend(quote! { #sig { #call } });
+    }
+
+    Ok(quote! {
+        #[doc = #doc]
+        #async_trait
+        pub trait #trait_name<'p>:
+            ::std::convert::AsRef<#proxy_name<'p>> + ::std::marker::Send + ::std::marker::Sync
+        {
+            #methods
+        }
+
+        impl<'p, T> #trait_name<'p> for T
+        where
+            T: ::std::convert::AsRef<#proxy_name<'p>> + ::std::marker::Send + ::std::marker::Sync,
+        {
+        }
+    })
+}
+
+// The signatures of the methods and properties declared in the trait (signals and `object` methods
+// excluded), along with the call of the same method of the proxy given by `receiver`.
+fn forwarded_methods(
+    input: &ItemTrait,
+    proxy_name: &Ident,
+    receiver: &TokenStream,
+    blocking: bool,
+    attr: &str,
+) -> Result<Vec<(TokenStream, TokenStream)>, Error> {
+    let AsyncOpts { usage, wait, .. } = AsyncOpts::new(blocking);
+
+    let mut methods = vec![];
+    for i in input.items.iter() {
+        let m = match i {
+            syn::TraitItem::Method(m) => m,
+            _ => continue,
+        };
+        let attrs = MethodAttributes::parse(&m.attrs)?;
+        if attrs.signal || attrs.object.is_some() {
+            continue;
+        }
+        if !m.sig.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &m.sig.generics,
+                format!("generic methods are not supported with `{attr}`"),
+            ));
+        }
+
+        let method = &m.sig.ident;
+        let output = &m.sig.output;
+        let method_attrs: Vec<_> = m
+            .attrs
+            .iter()
+            .filter(|a| !a.path.is_ident("dbus_proxy"))
+            .collect();
+        let args: Vec<_> = m
+            .sig
+            .inputs
+            .iter()
+            .filter_map(typed_arg)
+            .map(|arg| {
+                let mut arg = arg.clone();
+                arg.attrs.clear();
+
+                arg
+            })
+            .collect();
+        let arg_names: Vec<_> = args.iter().filter_map(pat_ident).collect();
+        if arg_names.len() != args.len() {
+            return Err(Error::new_spanned(
+                &m.sig.inputs,
+                format!("only named arguments are supported with `{attr}`"),
+            ));
+        }
+
+        methods.push((
+            quote! {
+                #(#method_attrs)*
+                #usage fn #method(&self, #(#args),*) #output
+            },
+            quote! { #proxy_name::#method(#receiver, #(#arg_names),*)#wait },
+        ));
+    }
+
+    Ok(methods)
+}
+
+// Generate the server side of the interface: a trait with the same name and methods as the proxy
+// trait, for services to implement, and a generic wrapper type implementing the D-Bus interface on
+// top of any implementation of it.
+fn create_server(
+    input: &ItemTrait,
+    iface_name: Option<&str>,
+    server_name: &str,
+) -> Result<TokenStream, Error> {
+    let zbus = zbus_path();
+
+    let vis = &input.vis;
+    let trait_name = &input.ident;
+    let server_name = Ident::new(server_name, Span::call_site());
+    let iface_name = iface_name
+        .map(ToString::to_string)
+        .unwrap_or(format!("org.freedesktop.{trait_name}"));
+    let docs = get_doc_attrs(&input.attrs);
+
+    let mut trait_methods = TokenStream::new();
+    let mut impl_methods = TokenStream::new();
+    for i in input.items.iter() {
+        let m = match i {
+            syn::TraitItem::Method(m) => m,
+            _ => continue,
+        };
+        let attrs = MethodAttributes::parse(&m.attrs)?;
+        if !m.sig.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &m.sig.generics,
+                "generic methods are not supported with `gen_server`",
+            ));
+        }
+
+        let method = &m.sig.ident;
+        let method_name = method.to_string();
+        let is_property = attrs.property.is_some();
+        let is_setter = is_property && m.sig.inputs.len() > 1;
+        let member_name = attrs.name.clone().unwrap_or_else(|| {
+            case::pascal_or_camel_case(
+                if is_setter {
+                    method_name.trim_start_matches("set_")
+                } else {
+                    &method_name
+                },
+                true,
+            )
+        });
+        let docs = get_doc_attrs(&m.attrs);
+        let cfg_attrs: Vec<_> = m.attrs.iter().filter(|a| a.path.is_ident("cfg")).collect();
+
+        // The arguments, stripped of their attributes.
+        let args: Vec<_> = m
+            .sig
+            .inputs
+            .iter()
+            .filter_map(typed_arg)
+            .map(|arg| {
+                let mut arg = arg.clone();
+                arg.attrs.clear();
+
+                arg
+            })
+            .collect();
+        let arg_names: Vec<_> = args.iter().filter_map(pat_ident).collect();
+        if arg_names.len() != args.len() {
+            return Err(Error::new_spanned(
+                &m.sig.inputs,
+                "only named arguments are supported with `gen_server`",
+            ));
+        }
+
+        if attrs.signal {
+            impl_methods.extend(quote! {
+                #(#docs)*
+                #(#cfg_attrs)*
+                #[dbus_interface(signal, name = #member_name)]
+                #vis async fn #method(
+                    signal_context: &#zbus::object_server::SignalContext<'_>,
+                    #(#args),*
+                ) -> #zbus::Result<()> {}
+            });
+
+            continue;
+        }
+
+        let output = if attrs.object.is_some() {
+            parse_quote!(-> #zbus::fdo::Result<#zbus::zvariant::OwnedObjectPath>)
+        } else {
+            m.sig.output.clone()
+        };
+        let reply = match &output {
+            ReturnType::Type(_, ty) => reply_type(ty),
+            ReturnType::Default => None,
+        }
+        .ok_or_else(|| Error::new_spanned(&m.sig.output, "expected a `Result` return type"))?;
+        let iface_attrs = if is_property {
+            quote!(#[dbus_interface(property, name = #member_name)])
+        } else {
+            quote!(#[dbus_interface(name = #member_name)])
+        };
+
+        // `dbus_interface` expects setters taking `&self` to return a `zbus::Result`.
+        let impl_output = if is_setter {
+            quote!(#zbus::Result<#reply>)
+        } else {
+            quote!(#zbus::fdo::Result<#reply>)
+        };
+
+        trait_methods.extend(quote! {
+            #(#docs)*
+            #(#cfg_attrs)*
+            async fn #method(&self, #(#args),*) #output;
+        });
+        impl_methods.extend(quote! {
+            #(#docs)*
+            #(#cfg_attrs)*
+            #iface_attrs
+            async fn #method(&self, #(#args),*) -> #impl_output {
+                <T as #trait_name>::#method(&self.0, #(#arg_names),*)
+                    .await
+                    .map_err(::std::convert::Into::into)
+            }
+        });
+    }
+
+    let server_doc = format!(
+        " The D-Bus interface `{iface_name}`, served by an implementation of [`{trait_name}`]."
+    );
+    let iface_impl = crate::iface::expand(
+        vec![parse_quote!(name = #iface_name)],
+        parse_quote! {
+            impl<T> #server_name<T>
+            where
+                T: #trait_name,
+            {
+                #impl_methods
+            }
+        },
+    )?;
+
+    Ok(quote! {
+        #(#docs)*
+        #[#zbus::export::async_trait::async_trait]
+        #vis trait #trait_name: ::std::marker::Send + ::std::marker::Sync + 'static {
+            #trait_methods
+        }
+
+        #[doc = #server_doc]
+        #[derive(Debug, Clone, Default)]
+        #vis struct #server_name<T>(pub T);
+
+        #iface_impl
     })
 }
 
+// The type of the value in a `Result<T, ..>` type.
+fn reply_type(ty: &Type) -> Option<&Type> {
+    let args = match ty {
+        Type::Path(path) => match &path.path.segments.last()?.arguments {
+            PathArguments::AngleBracketed(args) => args,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(reply) => Some(reply),
+        _ => None,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_proxy(
     input: &ItemTrait,
@@ -155,6 +552,8 @@ pub fn create_proxy(
     default_path: Option<&str>,
     default_service: Option<&str>,
     proxy_name: &str,
+    include: &[String],
+    gen_with_flags: bool,
     blocking: bool,
     gen_sig_args: bool,
 ) -> Result<TokenStream, Error> {
@@ -247,12 +646,28 @@ pub fn create_proxy(
 
                 method
             } else {
-                gen_proxy_method_call(&member_name, &method_name, m, &attrs, &async_opts)?
+                gen_proxy_method_call(
+                    &member_name,
+                    &method_name,
+                    m,
+                    &attrs,
+                    &async_opts,
+                    gen_with_flags,
+                )?
             };
             methods.extend(m);
         }
     }
 
+    let included = include
+        .iter()
+        .map(|i| included_proxy(i, blocking))
+        .collect::<Result<Vec<_>, _>>()?;
+    let included_fields = (1..=included.len()).map(syn::Index::from);
+    let included_vars: Vec<_> = (0..included.len())
+        .map(|i| format_ident!("included{i}"))
+        .collect();
+
     let AsyncOpts { usage, wait, .. } = async_opts;
     let (proxy_struct, connection, builder, proxy_trait) = if blocking {
         let connection = quote! { #zbus::blocking::Connection };
@@ -347,7 +762,7 @@ pub fn create_proxy(
 
         #(#other_attrs)*
         #[derive(Clone, Debug)]
-        pub struct #proxy_name<'p>(#proxy_struct<'p>);
+        pub struct #proxy_name<'p>(#proxy_struct<'p> #(, #included<'p>)*);
 
         impl<'p> #proxy_name<'p> {
             #proxy_method_new
@@ -393,10 +808,23 @@ pub fn create_proxy(
 
         impl<'p> ::std::convert::From<#zbus::Proxy<'p>> for #proxy_name<'p> {
             fn from(proxy: #zbus::Proxy<'p>) -> Self {
-                #proxy_name(::std::convert::Into::into(proxy))
+                #(
+                    let #included_vars = ::std::convert::From::from(
+                        proxy.included::<#included<'p>>(),
+                    );
+                )*
+                #proxy_name(::std::convert::Into::into(proxy) #(, #included_vars)*)
             }
         }
 
+        #(
+            impl<'p> ::std::convert::AsRef<#included<'p>> for #proxy_name<'p> {
+                fn as_ref(&self) -> &#included<'p> {
+                    &self.#included_fields
+                }
+            }
+        )*
+
         impl<'p> ::std::ops::Deref for #proxy_name<'p> {
             type Target = #proxy_struct<'p>;
 
@@ -451,6 +879,7 @@ fn gen_proxy_method_call(
     m: &TraitItemMethod,
     attrs: &MethodAttributes,
     async_opts: &AsyncOpts,
+    gen_with_flags: bool,
 ) -> Result<TokenStream, Error> {
     let AsyncOpts {
         usage,
@@ -597,17 +1026,57 @@ fn gen_proxy_method_call(
             #where_clause
         };
 
-        if let Some(method_flags) = method_flags {
+        // The variant of the method taking additional flags for the call, if enabled.
+        let reply_type = optional_reply_type(output).filter(|_| gen_with_flags);
+        let with_flags_method = reply_type.map(|output| {
+            let with_flags = format_ident!("{snake_case_name}_with_flags");
+            let mut inputs = inputs.clone().into_iter();
+            let receiver = inputs.next();
+            let inputs: Punctuated<FnArg, Token![,]> = inputs.collect();
+            let flags = match &method_flags {
+                Some(method_flags) => quote! {{
+                    let method_flags: #zbus::export::enumflags2::BitFlags<
+                        #zbus::proxy::MethodFlags,
+                    > = #method_flags;
+
+                    call_flags | method_flags
+                }},
+                None => quote!(call_flags),
+            };
+            let doc = format!(
+                " Same as [`Self::{snake_case_name}`], with additional flags for the call.\n\n \
+                 Returns `None` if the `NoReplyExpected` flag is set."
+            );
+
+            let attrs = other_attrs.iter().filter(|a| !a.path.is_ident("doc"));
+
+            quote! {
+                #[doc = #doc]
+                #(#attrs)*
+                pub #usage fn #with_flags #ty_generics(
+                    #receiver,
+                    call_flags: #zbus::export::enumflags2::BitFlags<#zbus::proxy::MethodFlags>,
+                    #inputs
+                ) -> #output
+                #where_clause
+                {
+                    let reply = self.0.call_with_flags(#method_name, #flags, #body)#wait?;
+                    ::std::result::Result::Ok(reply)
+                }
+            }
+        });
+
+        let method_impl = if let Some(method_flags) = method_flags {
             if no_reply {
-                Ok(quote! {
+                quote! {
                     #(#other_attrs)*
                     pub #usage #signature {
                         self.0.call_with_flags::<_, _, ()>(#method_name, #method_flags, #body)#wait?;
                         ::std::result::Result::Ok(())
                     }
-                })
+                }
             } else {
-                Ok(quote! {
+                quote! {
                     #(#other_attrs)*
                     pub #usage #signature {
                         let reply = self.0.call_with_flags(#method_name, #method_flags, #body)#wait?;
@@ -620,20 +1089,56 @@ fn gen_proxy_method_call(
                         // unwrap
                         ::std::result::Result::Ok(reply.unwrap())
                     }
-                })
+                }
             }
         } else {
-            Ok(quote! {
+            quote! {
                 #(#other_attrs)*
                 pub #usage #signature {
                     let reply = self.0.call(#method_name, #body)#wait?;
                     ::std::result::Result::Ok(reply)
                 }
-            })
-        }
+            }
+        };
+
+        Ok(quote! {
+            #method_impl
+            #with_flags_method
+        })
     }
 }
 
+// The path of the proxy generated for an included interface, from the path of its trait.
+fn included_proxy(included: &str, blocking: bool) -> Result<Path, Error> {
+    if blocking {
+        parse_str(&format!("{included}ProxyBlocking"))
+    } else {
+        parse_str(&format!("{included}Proxy"))
+    }
+}
+
+// Turn the `Result<T, ..>` output type of a method into `Result<Option<T>, ..>`, for calls that
+// might not wait for a reply.
+fn optional_reply_type(output: &ReturnType) -> Option<Type> {
+    let mut ty = match output {
+        ReturnType::Type(_, ty) => (**ty).clone(),
+        ReturnType::Default => return None,
+    };
+    let args = match &mut ty {
+        Type::Path(path) => match &mut path.path.segments.last_mut()?.arguments {
+            PathArguments::AngleBracketed(args) => args,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match args.args.first_mut()? {
+        GenericArgument::Type(reply) => *reply = parse_quote!(::std::option::Option<#reply>),
+        _ => return None,
+    }
+
+    Some(ty)
+}
+
 /// Standard annotation `org.freedesktop.DBus.Property.EmitsChangedSignal`.
 ///
 /// See <https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format>.
@@ -98,6 +98,53 @@ fn test_proxy() {
     });
 }
 
+#[test]
+fn test_proxy_keep_trait() {
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.Greeter",
+        default_service = "org.freedesktop.zbus_macros",
+        default_path = "/org/freedesktop/zbus_macros/greeter",
+        keep_trait = true
+    )]
+    trait Greeter {
+        fn greet(&self, name: &str) -> zbus::Result<String>;
+
+        #[dbus_proxy(property)]
+        fn greeting(&self) -> zbus::Result<String>;
+
+        #[dbus_proxy(signal)]
+        fn greeted(&self, name: &str) -> zbus::Result<()>;
+    }
+
+    struct MockGreeter;
+
+    #[async_trait::async_trait]
+    impl Greeter for MockGreeter {
+        async fn greet(&self, name: &str) -> zbus::Result<String> {
+            Ok(format!("{}, {name}!", self.greeting().await?))
+        }
+
+        async fn greeting(&self) -> zbus::Result<String> {
+            Ok("Hello".into())
+        }
+    }
+
+    async fn greet_all(greeter: &dyn Greeter, names: &[&str]) -> zbus::Result<Vec<String>> {
+        let mut greetings = vec![];
+        for name in names {
+            greetings.push(greeter.greet(name).await?);
+        }
+
+        Ok(greetings)
+    }
+
+    fn assert_greeter<T: Greeter>() {}
+    assert_greeter::<GreeterProxy<'static>>();
+
+    let greetings = block_on(greet_all(&MockGreeter, &["Maria", "Zeeshan"])).unwrap();
+    assert_eq!(greetings, ["Hello, Maria!", "Hello, Zeeshan!"]);
+}
+
 #[test]
 fn test_derive_error() {
     #[derive(Debug, DBusError)]
@@ -150,6 +197,10 @@ fn test_interface() {
             res
         }
 
+        fn arg_names(&self, r#type: &str, _unused: u32) -> String {
+            r#type.to_string()
+        }
+
         // TODO: naming output arguments after "RFC: Structural Records #2584"
         fn many_output(&self) -> zbus::fdo::Result<(&T, String)> {
             Ok((&self.generic, self.something.clone()))
@@ -203,6 +254,11 @@ fn test_interface() {
     <arg name="val" type="s" direction="in"/>
     <arg type="u" direction="out"/>
   </method>
+  <method name="ArgNames">
+    <arg name="type" type="s" direction="in"/>
+    <arg name="_unused" type="u" direction="in"/>
+    <arg type="s" direction="out"/>
+  </method>
   <method name="ManyOutput">
     <arg type="u" direction="out"/>
     <arg type="s" direction="out"/>
@@ -374,6 +374,12 @@ impl Borrow<str> for OwnedBusName {
     }
 }
 
+impl AsRef<str> for OwnedBusName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl Debug for OwnedBusName {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match &self.0 {
@@ -416,6 +422,24 @@ impl From<BusName<'_>> for OwnedBusName {
     }
 }
 
+impl From<&BusName<'_>> for OwnedBusName {
+    fn from(name: &BusName<'_>) -> Self {
+        OwnedBusName(name.to_owned())
+    }
+}
+
+impl From<OwnedUniqueName> for OwnedBusName {
+    fn from(name: OwnedUniqueName) -> Self {
+        OwnedBusName(BusName::Unique(name.into_inner()))
+    }
+}
+
+impl From<OwnedWellKnownName> for OwnedBusName {
+    fn from(name: OwnedWellKnownName) -> Self {
+        OwnedBusName(BusName::WellKnown(name.into_inner()))
+    }
+}
+
 impl TryFrom<&'_ str> for OwnedBusName {
     type Error = Error;
 
@@ -488,6 +512,12 @@ impl<'de> Deserialize<'de> for OwnedBusName {
     }
 }
 
+impl PartialEq<str> for OwnedBusName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedBusName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
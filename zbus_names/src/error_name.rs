@@ -150,62 +150,7 @@ impl_try_from! {
 }
 
 fn ensure_correct_error_name(name: &str) -> Result<()> {
-    // Rules
-    //
-    // * Only ASCII alphanumeric or `_`.
-    // * Must not begin with a `.`.
-    // * Must contain at least one `.`.
-    // * Each element must:
-    //   * not begin with a digit.
-    //   * be 1 character (so name must be minimum 3 characters long).
-    // * <= 255 characters.
-    if name.len() < 3 {
-        return Err(Error::InvalidErrorName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (3)",
-            name,
-            name.len(),
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidErrorName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    let mut prev = None;
-    let mut no_dot = true;
-    for c in name.chars() {
-        if c == '.' {
-            if prev.is_none() || prev == Some('.') {
-                return Err(Error::InvalidErrorName(String::from(
-                    "must not contain a double `.`",
-                )));
-            }
-
-            if no_dot {
-                no_dot = false;
-            }
-        } else if c.is_ascii_digit() && (prev.is_none() || prev == Some('.')) {
-            return Err(Error::InvalidErrorName(String::from(
-                "each element must not start with a digit",
-            )));
-        } else if !c.is_ascii_alphanumeric() && c != '_' {
-            return Err(Error::InvalidErrorName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-
-        prev = Some(c);
-    }
-
-    if no_dot {
-        return Err(Error::InvalidErrorName(String::from(
-            "must contain at least 1 `.`",
-        )));
-    }
-
-    Ok(())
+    crate::validate_error_name(name).map_err(|e| Error::InvalidErrorName(e.to_string()))
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -270,6 +215,12 @@ impl Borrow<str> for OwnedErrorName {
     }
 }
 
+impl AsRef<str> for OwnedErrorName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<OwnedErrorName> for ErrorName<'static> {
     fn from(o: OwnedErrorName) -> Self {
         o.into_inner()
@@ -288,6 +239,12 @@ impl From<ErrorName<'_>> for OwnedErrorName {
     }
 }
 
+impl From<&ErrorName<'_>> for OwnedErrorName {
+    fn from(name: &ErrorName<'_>) -> Self {
+        OwnedErrorName(name.to_owned())
+    }
+}
+
 impl From<OwnedErrorName> for Str<'static> {
     fn from(value: OwnedErrorName) -> Self {
         value.into_inner().0
@@ -305,6 +262,12 @@ impl<'de> Deserialize<'de> for OwnedErrorName {
     }
 }
 
+impl PartialEq<str> for OwnedErrorName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedErrorName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -154,62 +154,7 @@ impl<'name> From<InterfaceName<'name>> for Str<'name> {
 }
 
 fn ensure_correct_interface_name(name: &str) -> Result<()> {
-    // Rules
-    //
-    // * Only ASCII alphanumeric or `_`.
-    // * Must not begin with a `.`.
-    // * Must contain at least one `.`.
-    // * Each element must:
-    //   * not begin with a digit.
-    //   * be 1 character (so name must be minimum 3 characters long).
-    // * <= 255 characters.
-    if name.len() < 3 {
-        return Err(Error::InvalidInterfaceName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (3)",
-            name,
-            name.len(),
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidInterfaceName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    let mut prev = None;
-    let mut no_dot = true;
-    for c in name.chars() {
-        if c == '.' {
-            if prev.is_none() || prev == Some('.') {
-                return Err(Error::InvalidInterfaceName(String::from(
-                    "must not contain a double `.`",
-                )));
-            }
-
-            if no_dot {
-                no_dot = false;
-            }
-        } else if c.is_ascii_digit() && (prev.is_none() || prev == Some('.')) {
-            return Err(Error::InvalidInterfaceName(String::from(
-                "each element must not start with a digit",
-            )));
-        } else if !c.is_ascii_alphanumeric() && c != '_' {
-            return Err(Error::InvalidInterfaceName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-
-        prev = Some(c);
-    }
-
-    if no_dot {
-        return Err(Error::InvalidInterfaceName(String::from(
-            "must contain at least 1 `.`",
-        )));
-    }
-
-    Ok(())
+    crate::validate_interface_name(name).map_err(|e| Error::InvalidInterfaceName(e.to_string()))
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -268,6 +213,12 @@ impl Borrow<str> for OwnedInterfaceName {
     }
 }
 
+impl AsRef<str> for OwnedInterfaceName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<OwnedInterfaceName> for InterfaceName<'static> {
     fn from(o: OwnedInterfaceName) -> Self {
         o.into_inner()
@@ -286,6 +237,12 @@ impl From<InterfaceName<'_>> for OwnedInterfaceName {
     }
 }
 
+impl From<&InterfaceName<'_>> for OwnedInterfaceName {
+    fn from(name: &InterfaceName<'_>) -> Self {
+        OwnedInterfaceName(name.to_owned())
+    }
+}
+
 impl From<OwnedInterfaceName> for Str<'static> {
     fn from(value: OwnedInterfaceName) -> Self {
         value.into_inner().0
@@ -303,6 +260,12 @@ impl<'de> Deserialize<'de> for OwnedInterfaceName {
     }
 }
 
+impl PartialEq<str> for OwnedInterfaceName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedInterfaceName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
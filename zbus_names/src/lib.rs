@@ -31,4 +31,7 @@ pub use error::*;
 mod error_name;
 pub use error_name::*;
 
+mod validate;
+pub use validate::*;
+
 mod utils;
@@ -152,42 +152,7 @@ impl_try_from! {
 }
 
 fn ensure_correct_member_name(name: &str) -> Result<()> {
-    // Rules
-    //
-    // * Only ASCII alphanumeric or `_`.
-    // * Must not begin with a digit.
-    // * Must contain at least 1 character.
-    // * <= 255 characters.
-    if name.is_empty() {
-        return Err(Error::InvalidMemberName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (1)",
-            name,
-            name.len(),
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidMemberName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    // SAFETY: We established above that there is at least 1 character so unwrap is fine.
-    if name.chars().next().unwrap().is_ascii_digit() {
-        return Err(Error::InvalidMemberName(String::from(
-            "must not start with a digit",
-        )));
-    }
-
-    for c in name.chars() {
-        if !c.is_ascii_alphanumeric() && c != '_' {
-            return Err(Error::InvalidMemberName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-    }
-
-    Ok(())
+    crate::validate_member_name(name).map_err(|e| Error::InvalidMemberName(e.to_string()))
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -246,6 +211,12 @@ impl Borrow<str> for OwnedMemberName {
     }
 }
 
+impl AsRef<str> for OwnedMemberName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<OwnedMemberName> for MemberName<'static> {
     fn from(o: OwnedMemberName) -> Self {
         o.into_inner()
@@ -264,6 +235,12 @@ impl From<MemberName<'_>> for OwnedMemberName {
     }
 }
 
+impl From<&MemberName<'_>> for OwnedMemberName {
+    fn from(name: &MemberName<'_>) -> Self {
+        OwnedMemberName(name.to_owned())
+    }
+}
+
 impl From<OwnedMemberName> for Str<'static> {
     fn from(value: OwnedMemberName) -> Self {
         value.into_inner().0
@@ -281,6 +258,12 @@ impl<'de> Deserialize<'de> for OwnedMemberName {
     }
 }
 
+impl PartialEq<str> for OwnedMemberName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedMemberName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -138,66 +138,7 @@ impl<'de: 'name, 'name> Deserialize<'de> for UniqueName<'name> {
 }
 
 fn ensure_correct_unique_name(name: &str) -> Result<()> {
-    // Rules
-    //
-    // * Only ASCII alphanumeric, `_` or '-'
-    // * Must begin with a `:`.
-    // * Must contain at least one `.`.
-    // * <= 255 characters.
-    if name.is_empty() {
-        return Err(Error::InvalidUniqueName(String::from(
-            "must contain at least 4 characters",
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidUniqueName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    } else if name == "org.freedesktop.DBus" {
-        // Bus itself uses its well-known name as its unique name.
-        return Ok(());
-    }
-
-    // SAFETY: Just checked above that we've at least 1 character.
-    let mut chars = name.chars();
-    let mut prev = match chars.next().expect("no first char") {
-        first @ ':' => first,
-        _ => {
-            return Err(Error::InvalidUniqueName(String::from(
-                "must start with a `:`",
-            )));
-        }
-    };
-
-    let mut no_dot = true;
-    for c in chars {
-        if c == '.' {
-            if prev == '.' {
-                return Err(Error::InvalidUniqueName(String::from(
-                    "must not contain a double `.`",
-                )));
-            }
-
-            if no_dot {
-                no_dot = false;
-            }
-        } else if !c.is_ascii_alphanumeric() && c != '_' && c != '-' {
-            return Err(Error::InvalidUniqueName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-
-        prev = c;
-    }
-
-    if no_dot {
-        return Err(Error::InvalidUniqueName(String::from(
-            "must contain at least 1 `.`",
-        )));
-    }
-
-    Ok(())
+    crate::validate_unique_name(name).map_err(|e| Error::InvalidUniqueName(e.to_string()))
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -262,6 +203,12 @@ impl Borrow<str> for OwnedUniqueName {
     }
 }
 
+impl AsRef<str> for OwnedUniqueName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<OwnedUniqueName> for UniqueName<'static> {
     fn from(o: OwnedUniqueName) -> Self {
         o.into_inner()
@@ -280,6 +227,12 @@ impl From<UniqueName<'_>> for OwnedUniqueName {
     }
 }
 
+impl From<&UniqueName<'_>> for OwnedUniqueName {
+    fn from(name: &UniqueName<'_>) -> Self {
+        OwnedUniqueName(name.to_owned())
+    }
+}
+
 impl_try_from! {
     ty: UniqueName<'s>,
     owned_ty: OwnedUniqueName,
@@ -304,6 +257,12 @@ impl<'de> Deserialize<'de> for OwnedUniqueName {
     }
 }
 
+impl PartialEq<str> for OwnedUniqueName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedUniqueName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
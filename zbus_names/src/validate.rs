@@ -0,0 +1,498 @@
+use static_assertions::assert_impl_all;
+use std::{error, fmt};
+
+/// The maximum length of bus, interface, member and error names.
+const MAX_NAME_LEN: usize = 255;
+
+/// Why a string is not a valid D-Bus identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Reason {
+    /// The string is shorter than the minimum length allowed.
+    TooShort {
+        /// The minimum length allowed.
+        min: usize,
+    },
+    /// The string is longer than the maximum length allowed.
+    TooLong {
+        /// The maximum length allowed.
+        max: usize,
+    },
+    /// The character is not allowed.
+    InvalidChar(char),
+    /// An element (the part between two separators) is empty, e.g because of a leading, trailing
+    /// or double separator.
+    EmptyElement,
+    /// An element starts with a digit.
+    StartsWithDigit,
+    /// The name doesn't contain any `.`.
+    MissingDot,
+    /// A unique name doesn't start with `:`.
+    MissingColon,
+    /// An object path doesn't start with `/`.
+    MissingSlash,
+}
+
+assert_impl_all!(Reason: Send, Sync, Unpin);
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::TooShort { min } => write!(f, "must be at least {min} characters long"),
+            Reason::TooLong { max } => write!(f, "must be at most {max} characters long"),
+            Reason::InvalidChar(c) => write!(f, "`{c}` character not allowed"),
+            Reason::EmptyElement => f.write_str("elements must not be empty"),
+            Reason::StartsWithDigit => f.write_str("elements must not start with a digit"),
+            Reason::MissingDot => f.write_str("must contain at least 1 `.`"),
+            Reason::MissingColon => f.write_str("must start with a `:`"),
+            Reason::MissingSlash => f.write_str("must start with a `/`"),
+        }
+    }
+}
+
+/// The error returned by the `validate_*` functions.
+///
+/// Unlike [`Error`](crate::Error), this gives the exact [`Reason`] the string was rejected and,
+/// where it applies, the position of the offending character. This makes it suitable for reporting
+/// invalid user input, e.g in command line tools or configuration files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    reason: Reason,
+    position: Option<usize>,
+}
+
+assert_impl_all!(ValidationError: Send, Sync, Unpin);
+
+impl ValidationError {
+    fn new(reason: Reason, position: Option<usize>) -> Self {
+        Self { reason, position }
+    }
+
+    fn at(reason: Reason, position: usize) -> Self {
+        Self::new(reason, Some(position))
+    }
+
+    /// Why the string is not valid.
+    pub fn reason(&self) -> Reason {
+        self.reason
+    }
+
+    /// The byte offset of the offending character in the string, if the error is about a specific
+    /// character.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{} (at position {position})", self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+type Result = std::result::Result<(), ValidationError>;
+
+/// Check if `name` is a valid [bus name], either unique or well-known.
+///
+/// Names starting with a `:` are validated as unique names, others as well-known names.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{validate_bus_name, Reason};
+///
+/// validate_bus_name("org.freedesktop.DBus").unwrap();
+/// validate_bus_name(":1.42").unwrap();
+///
+/// let e = validate_bus_name("org.freedesktop.DBus!").unwrap_err();
+/// assert_eq!(e.reason(), Reason::InvalidChar('!'));
+/// assert_eq!(e.position(), Some(20));
+/// ```
+///
+/// [bus name]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-names-bus
+pub fn validate_bus_name(name: &str) -> Result {
+    if name.starts_with(':') {
+        validate_unique_name(name)
+    } else {
+        validate_well_known_name(name)
+    }
+}
+
+/// Check if `name` is a valid unique bus name.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{validate_unique_name, Reason};
+///
+/// validate_unique_name(":1.42").unwrap();
+///
+/// let e = validate_unique_name("1.42").unwrap_err();
+/// assert_eq!(e.reason(), Reason::MissingColon);
+/// assert_eq!(e.position(), Some(0));
+/// ```
+pub fn validate_unique_name(name: &str) -> Result {
+    // Rules
+    //
+    // * Only ASCII alphanumeric, `_` or '-'
+    // * Must begin with a `:`.
+    // * Must contain at least one `.`.
+    // * Each element must be at least 1 character.
+    // * <= 255 characters.
+    if name == "org.freedesktop.DBus" {
+        // Bus itself uses its well-known name as its unique name.
+        return Ok(());
+    }
+    check_len(name, 4, MAX_NAME_LEN)?;
+
+    match name.strip_prefix(':') {
+        Some(elements) => check_elements(elements, 1, |c| {
+            c.is_ascii_alphanumeric() || c == '_' || c == '-'
+        }),
+        None => Err(ValidationError::at(Reason::MissingColon, 0)),
+    }
+}
+
+/// Check if `name` is a valid well-known bus name.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{validate_well_known_name, Reason};
+///
+/// validate_well_known_name("org.gnome.Service-for_you").unwrap();
+///
+/// let e = validate_well_known_name("org.2nd.Element").unwrap_err();
+/// assert_eq!(e.reason(), Reason::StartsWithDigit);
+/// assert_eq!(e.position(), Some(4));
+/// ```
+pub fn validate_well_known_name(name: &str) -> Result {
+    // Rules
+    //
+    // * Only ASCII alphanumeric, `_` or '-'.
+    // * Must contain at least one `.`.
+    // * Each element must:
+    //   * not begin with a digit.
+    //   * be at least 1 character (so name must be minimum 3 characters long).
+    // * <= 255 characters.
+    check_len(name, 3, MAX_NAME_LEN)?;
+    check_no_leading_digit(name, '.')?;
+
+    check_elements(name, 0, |c| {
+        c.is_ascii_alphanumeric() || c == '_' || c == '-'
+    })
+}
+
+/// Check if `name` is a valid [interface name].
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{validate_interface_name, Reason};
+///
+/// validate_interface_name("org.gnome.Interface_for_you").unwrap();
+///
+/// let e = validate_interface_name("double..dots").unwrap_err();
+/// assert_eq!(e.reason(), Reason::EmptyElement);
+/// assert_eq!(e.position(), Some(7));
+///
+/// let e = validate_interface_name("no-dots").unwrap_err();
+/// assert_eq!(e.reason(), Reason::InvalidChar('-'));
+/// assert_eq!(e.position(), Some(2));
+/// ```
+///
+/// [interface name]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-names-interface
+pub fn validate_interface_name(name: &str) -> Result {
+    // Rules
+    //
+    // * Only ASCII alphanumeric or `_`.
+    // * Must contain at least one `.`.
+    // * Each element must:
+    //   * not begin with a digit.
+    //   * be at least 1 character (so name must be minimum 3 characters long).
+    // * <= 255 characters.
+    check_len(name, 3, MAX_NAME_LEN)?;
+    check_no_leading_digit(name, '.')?;
+
+    check_elements(name, 0, |c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Check if `name` is a valid [error name].
+///
+/// Error names follow the same rules as interface names.
+///
+/// [error name]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-names-error
+pub fn validate_error_name(name: &str) -> Result {
+    validate_interface_name(name)
+}
+
+/// Check if `name` is a valid [member name], i.e a method or signal name.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{validate_member_name, Reason};
+///
+/// validate_member_name("Method_for_you").unwrap();
+///
+/// let e = validate_member_name("").unwrap_err();
+/// assert_eq!(e.reason(), Reason::TooShort { min: 1 });
+/// assert_eq!(e.position(), None);
+///
+/// let e = validate_member_name("contains.dots").unwrap_err();
+/// assert_eq!(e.reason(), Reason::InvalidChar('.'));
+/// assert_eq!(e.position(), Some(8));
+/// ```
+///
+/// [member name]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-names-member
+pub fn validate_member_name(name: &str) -> Result {
+    // Rules
+    //
+    // * Only ASCII alphanumeric or `_`.
+    // * Must not begin with a digit.
+    // * Must contain at least 1 character.
+    // * <= 255 characters.
+    check_len(name, 1, MAX_NAME_LEN)?;
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(ValidationError::at(Reason::StartsWithDigit, 0));
+    }
+
+    check_chars(name, 0, |c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Check if `path` is a valid [object path].
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{validate_object_path, Reason};
+///
+/// validate_object_path("/").unwrap();
+/// validate_object_path("/org/freedesktop/DBus").unwrap();
+///
+/// let e = validate_object_path("/org/freedesktop/").unwrap_err();
+/// assert_eq!(e.reason(), Reason::EmptyElement);
+/// assert_eq!(e.position(), Some(16));
+///
+/// let e = validate_object_path("/org/ünicode").unwrap_err();
+/// assert_eq!(e.reason(), Reason::InvalidChar('ü'));
+/// assert_eq!(e.position(), Some(5));
+/// ```
+///
+/// [object path]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-marshaling-object-path
+pub fn validate_object_path(path: &str) -> Result {
+    use zvariant::{check_object_path, ObjectPathError};
+
+    // The rules are the ones of `zvariant::ObjectPath`, which does the actual checking.
+    check_object_path(path.as_bytes()).map_err(|e| match e {
+        ObjectPathError::Empty => ValidationError::new(Reason::TooShort { min: 1 }, None),
+        ObjectPathError::MissingSlash => ValidationError::at(Reason::MissingSlash, 0),
+        ObjectPathError::EmptyElement(i) => ValidationError::at(Reason::EmptyElement, i),
+        ObjectPathError::InvalidByte(i) => {
+            // All the bytes before are ASCII, so `i` is at a character boundary.
+            let c = path[i..]
+                .chars()
+                .next()
+                .expect("invalid byte out of bounds");
+
+            ValidationError::at(Reason::InvalidChar(c), i)
+        }
+    })
+}
+
+fn check_len(s: &str, min: usize, max: usize) -> Result {
+    if s.len() < min {
+        Err(ValidationError::new(Reason::TooShort { min }, None))
+    } else if s.len() > max {
+        Err(ValidationError::new(Reason::TooLong { max }, None))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_chars<F>(s: &str, offset: usize, allowed: F) -> Result
+where
+    F: Fn(char) -> bool,
+{
+    match s.char_indices().find(|(_, c)| !allowed(*c)) {
+        Some((i, c)) => Err(ValidationError::at(Reason::InvalidChar(c), offset + i)),
+        None => Ok(()),
+    }
+}
+
+fn check_no_leading_digit(s: &str, separator: char) -> Result {
+    let mut offset = 0;
+    for element in s.split(separator) {
+        if element.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(ValidationError::at(Reason::StartsWithDigit, offset));
+        }
+        offset += element.len() + separator.len_utf8();
+    }
+
+    Ok(())
+}
+
+// Check the `.`-separated elements of `s`, which starts at `offset` in the whole string. There must
+// be at least 2 elements.
+fn check_elements<F>(s: &str, offset: usize, allowed: F) -> Result
+where
+    F: Fn(char) -> bool,
+{
+    let mut position = offset;
+    let mut elements = 0;
+    for element in s.split('.') {
+        if element.is_empty() {
+            // Report the separator following the empty element, or the last one if it's trailing.
+            let position = position.min((offset + s.len()).saturating_sub(1));
+
+            return Err(ValidationError::at(Reason::EmptyElement, position));
+        }
+        check_chars(element, position, &allowed)?;
+
+        position += element.len() + 1;
+        elements += 1;
+    }
+
+    if elements < 2 {
+        return Err(ValidationError::new(Reason::MissingDot, None));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(result: Result) -> (Reason, Option<usize>) {
+        let e = result.unwrap_err();
+
+        (e.reason(), e.position())
+    }
+
+    #[test]
+    fn too_short() {
+        let short = (Reason::TooShort { min: 3 }, None);
+        assert_eq!(error(validate_bus_name("")), short);
+        assert_eq!(error(validate_well_known_name("a.")), short);
+        assert_eq!(error(validate_interface_name("ab")), short);
+        assert_eq!(error(validate_error_name("")), short);
+        assert_eq!(
+            error(validate_unique_name(":1.")),
+            (Reason::TooShort { min: 4 }, None)
+        );
+        assert_eq!(
+            error(validate_member_name("")),
+            (Reason::TooShort { min: 1 }, None)
+        );
+        assert_eq!(
+            error(validate_object_path("")),
+            (Reason::TooShort { min: 1 }, None)
+        );
+    }
+
+    #[test]
+    fn too_long() {
+        let long = (Reason::TooLong { max: 255 }, None);
+        let name = format!("org.{}", "a".repeat(252));
+        assert_eq!(error(validate_interface_name(&name)), long);
+        assert_eq!(error(validate_well_known_name(&name)), long);
+        assert_eq!(error(validate_unique_name(&format!(":{name}"))), long);
+        assert_eq!(error(validate_member_name(&"a".repeat(256))), long);
+        validate_interface_name(&name[1..]).unwrap();
+    }
+
+    #[test]
+    fn invalid_char() {
+        assert_eq!(
+            error(validate_interface_name("org.foo$")),
+            (Reason::InvalidChar('$'), Some(7))
+        );
+        assert_eq!(
+            error(validate_interface_name("org.foo-bar")),
+            (Reason::InvalidChar('-'), Some(7))
+        );
+        validate_well_known_name("org.foo-bar").unwrap();
+        assert_eq!(
+            error(validate_well_known_name("org.fö.bar")),
+            (Reason::InvalidChar('ö'), Some(5))
+        );
+        assert_eq!(
+            error(validate_unique_name(":1.4!")),
+            (Reason::InvalidChar('!'), Some(4))
+        );
+        assert_eq!(
+            error(validate_member_name("Get.All")),
+            (Reason::InvalidChar('.'), Some(3))
+        );
+        assert_eq!(
+            error(validate_object_path("/a b")),
+            (Reason::InvalidChar(' '), Some(2))
+        );
+        // Positions are byte offsets, so they can be used to slice the input.
+        let path = "/föo/bär";
+        let (_, position) = error(validate_object_path(path));
+        assert!(path[position.unwrap()..].starts_with('ö'));
+    }
+
+    #[test]
+    fn empty_element() {
+        let empty = |position| (Reason::EmptyElement, Some(position));
+        assert_eq!(error(validate_interface_name("org..foo")), empty(4));
+        assert_eq!(error(validate_interface_name(".org.foo")), empty(0));
+        assert_eq!(error(validate_interface_name("org.foo.")), empty(7));
+        assert_eq!(error(validate_well_known_name("org..foo")), empty(4));
+        assert_eq!(error(validate_unique_name(":1..2")), empty(3));
+        assert_eq!(error(validate_unique_name(":.1.2")), empty(1));
+        assert_eq!(error(validate_object_path("/a//b")), empty(3));
+        assert_eq!(error(validate_object_path("/a/")), empty(2));
+    }
+
+    #[test]
+    fn starts_with_digit() {
+        let digit = |position| (Reason::StartsWithDigit, Some(position));
+        assert_eq!(error(validate_interface_name("org.foo.3bar")), digit(8));
+        assert_eq!(error(validate_interface_name("1org.foo")), digit(0));
+        assert_eq!(error(validate_well_known_name("org.2nd.Element")), digit(4));
+        assert_eq!(error(validate_member_name("3Method")), digit(0));
+        // Unique names may have elements starting with digits.
+        validate_unique_name(":1.42").unwrap();
+    }
+
+    #[test]
+    fn missing_separator() {
+        assert_eq!(
+            error(validate_interface_name("orgfoo")),
+            (Reason::MissingDot, None)
+        );
+        assert_eq!(
+            error(validate_well_known_name("orgfoo")),
+            (Reason::MissingDot, None)
+        );
+        assert_eq!(
+            error(validate_unique_name(":1234")),
+            (Reason::MissingDot, None)
+        );
+        assert_eq!(
+            error(validate_unique_name("1.42")),
+            (Reason::MissingColon, Some(0))
+        );
+        assert_eq!(
+            error(validate_object_path("org/foo")),
+            (Reason::MissingSlash, Some(0))
+        );
+    }
+
+    #[test]
+    fn display() {
+        let e = validate_interface_name("org.foo$").unwrap_err();
+        assert_eq!(e.to_string(), "`$` character not allowed (at position 7)");
+        let e = validate_member_name("").unwrap_err();
+        assert_eq!(e.to_string(), "must be at least 1 characters long");
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Write;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use zbus_xml::Node;
+
+// An introspection document of a few hundred kilobytes, like the ones of the systemd manager.
+fn big_document() -> String {
+    let mut xml = String::from(
+        r#"<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node name="/org/example/Manager">
+"#,
+    );
+    for i in 0..40 {
+        writeln!(xml, r#"  <interface name="org.example.Interface{i}">"#).unwrap();
+        for m in 0..20 {
+            writeln!(
+                xml,
+                r#"    <method name="Method{m}">
+      <arg type="s" name="name" direction="in"/>
+      <arg type="a(sasbttttuii)" name="changes" direction="in"/>
+      <arg type="o" name="job" direction="out"/>
+      <annotation name="org.freedesktop.systemd1.Privileged" value="true"/>
+    </method>"#
+            )
+            .unwrap();
+        }
+        for s in 0..5 {
+            writeln!(
+                xml,
+                r#"    <signal name="Signal{s}">
+      <arg type="u" name="id"/>
+      <arg type="a{{sv}}" name="properties"/>
+    </signal>"#
+            )
+            .unwrap();
+        }
+        for p in 0..30 {
+            writeln!(
+                xml,
+                r#"    <property name="Property{p}" type="a(sbbst)" access="read">
+      <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="const"/>
+    </property>"#
+            )
+            .unwrap();
+        }
+        xml.push_str("  </interface>\n");
+    }
+    for n in 0..200 {
+        writeln!(xml, r#"  <node name="unit{n}"/>"#).unwrap();
+    }
+    xml.push_str("</node>\n");
+
+    xml
+}
+
+fn parse(c: &mut Criterion) {
+    let xml = big_document();
+    c.bench_function("parse_big_document", |b| {
+        b.iter(|| {
+            let node = Node::try_from(black_box(xml.as_str())).unwrap();
+            black_box(node);
+        })
+    });
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
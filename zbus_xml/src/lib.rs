@@ -12,11 +12,12 @@
 
 mod error;
 pub use error::{Error, Result};
+mod parser;
 
-use quick_xml::{de::Deserializer, se::to_writer};
+use quick_xml::{de::DeError, se::to_writer};
 use serde::{Deserialize, Serialize};
 use static_assertions::assert_impl_all;
-use std::io::{BufReader, Read, Write};
+use std::io::{Read, Write};
 
 use zbus_names::{InterfaceName, MemberName};
 use zvariant::CompleteType;
@@ -272,10 +273,13 @@ assert_impl_all!(Node<'_>: Send, Sync, Unpin);
 
 impl<'a> Node<'a> {
     /// Parse the introspection XML document from reader.
-    pub fn from_reader<R: Read>(reader: R) -> Result<Node<'a>> {
-        let mut deserializer = Deserializer::from_reader(BufReader::new(reader));
-        deserializer.event_buffer_size(Some(1024_usize.try_into().unwrap()));
-        Ok(Node::deserialize(&mut deserializer)?)
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Node<'a>> {
+        let mut xml = String::new();
+        reader
+            .read_to_string(&mut xml)
+            .map_err(|e| DeError::from(quick_xml::Error::from(e)))?;
+
+        parser::parse(&xml)
     }
 
     /// Write the XML document to writer.
@@ -318,8 +322,6 @@ impl<'a> TryFrom<&'a str> for Node<'a> {
 
     /// Parse the introspection XML document from `s`.
     fn try_from(s: &'a str) -> Result<Node<'a>> {
-        let mut deserializer = Deserializer::from_str(s);
-        deserializer.event_buffer_size(Some(1024_usize.try_into().unwrap()));
-        Ok(Node::deserialize(&mut deserializer)?)
+        parser::parse(s)
     }
 }
@@ -0,0 +1,294 @@
+//! A streaming parser for introspection XML documents.
+//!
+//! Rather than going through serde, the document is read with the low-level quick-xml reader,
+//! building the typed model as elements are encountered. This avoids buffering events and is
+//! significantly faster on large documents.
+//!
+//! Unknown elements and attributes are ignored, as are the document type declaration, comments
+//! and processing instructions. Entities declared in the document type are never expanded: only the
+//! predefined XML entities and character references are allowed in attribute values.
+//!
+//! Since `node` elements are parsed recursively, their nesting is limited to [`MAX_DEPTH`] levels.
+
+use quick_xml::{
+    de::DeError,
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::{borrow::Cow, fmt::Display};
+use zvariant::{CompleteType, Signature};
+
+use crate::{
+    Annotation, Arg, ArgDirection, Error, Interface, Method, Node, Property, PropertyAccess,
+    Result, Signal,
+};
+
+/// The maximum nesting depth of `node` elements.
+const MAX_DEPTH: usize = 128;
+
+/// Parse the introspection XML document in `xml`.
+pub(crate) fn parse(xml: &str) -> Result<Node<'static>> {
+    let mut parser = Parser::new(xml);
+
+    loop {
+        match parser.next()? {
+            Event::Start(e) if e.local_name().as_ref() == b"node" => return parser.node(&e, false),
+            Event::Empty(e) if e.local_name().as_ref() == b"node" => return parser.node(&e, true),
+            Event::Start(e) | Event::Empty(e) => {
+                return Err(invalid(format!(
+                    "unexpected root element `{}`",
+                    String::from_utf8_lossy(e.name().as_ref()),
+                )))
+            }
+            Event::Eof => return Err(invalid("missing `node` element")),
+            _ => (),
+        }
+    }
+}
+
+struct Parser<'x> {
+    reader: Reader<&'x [u8]>,
+    // The number of `node` elements being parsed.
+    depth: usize,
+}
+
+impl<'x> Parser<'x> {
+    fn new(xml: &'x str) -> Self {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+
+        Self { reader, depth: 0 }
+    }
+
+    fn next(&mut self) -> Result<Event<'x>> {
+        self.reader
+            .read_event()
+            .map_err(|e| DeError::from(e).into())
+    }
+
+    // Call `f` for each child element of the current element, until its end.
+    fn children<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, &BytesStart<'x>, bool) -> Result<()>,
+    {
+        loop {
+            match self.next()? {
+                Event::Start(e) => f(self, &e, false)?,
+                Event::Empty(e) => f(self, &e, true)?,
+                Event::End(_) => return Ok(()),
+                Event::Eof => return Err(invalid("unexpected end of document")),
+                _ => (),
+            }
+        }
+    }
+
+    // Skip the element `e` and all its children.
+    fn skip(&mut self, e: &BytesStart<'x>, empty: bool) -> Result<()> {
+        if !empty {
+            self.reader
+                .read_to_end(e.name())
+                .map_err(|e| Error::from(DeError::from(e)))?;
+        }
+
+        Ok(())
+    }
+
+    fn node(&mut self, e: &BytesStart<'x>, empty: bool) -> Result<Node<'static>> {
+        if self.depth == MAX_DEPTH {
+            return Err(invalid(format!(
+                "`node` elements nested more than {MAX_DEPTH} levels deep"
+            )));
+        }
+        self.depth += 1;
+
+        let mut node = Node {
+            name: attribute(e, b"name")?.map(Cow::into_owned),
+            interfaces: vec![],
+            nodes: vec![],
+        };
+        if !empty {
+            self.children(|p, e, empty| {
+                match e.local_name().as_ref() {
+                    b"interface" => node.interfaces.push(p.interface(e, empty)?),
+                    b"node" => node.nodes.push(p.node(e, empty)?),
+                    _ => p.skip(e, empty)?,
+                }
+
+                Ok(())
+            })?;
+        }
+        self.depth -= 1;
+
+        Ok(node)
+    }
+
+    fn interface(&mut self, e: &BytesStart<'x>, empty: bool) -> Result<Interface<'static>> {
+        let mut iface = Interface {
+            name: convert(required_attribute(e, b"name")?)?,
+            methods: vec![],
+            properties: vec![],
+            signals: vec![],
+            annotations: vec![],
+        };
+        if !empty {
+            self.children(|p, e, empty| {
+                match e.local_name().as_ref() {
+                    b"method" => {
+                        let (name, args, annotations) = p.member(e, empty)?;
+                        iface.methods.push(Method {
+                            name,
+                            args,
+                            annotations,
+                        });
+                    }
+                    b"signal" => {
+                        let (name, args, annotations) = p.member(e, empty)?;
+                        iface.signals.push(Signal {
+                            name,
+                            args,
+                            annotations,
+                        });
+                    }
+                    b"property" => iface.properties.push(p.property(e, empty)?),
+                    b"annotation" => iface.annotations.push(p.annotation(e, empty)?),
+                    _ => p.skip(e, empty)?,
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(iface)
+    }
+
+    // The contents of a method or a signal.
+    fn member(
+        &mut self,
+        e: &BytesStart<'x>,
+        empty: bool,
+    ) -> Result<(
+        zbus_names::MemberName<'static>,
+        Vec<Arg<'static>>,
+        Vec<Annotation>,
+    )> {
+        let name = convert(required_attribute(e, b"name")?)?;
+        let mut args = vec![];
+        let mut annotations = vec![];
+        if !empty {
+            self.children(|p, e, empty| {
+                match e.local_name().as_ref() {
+                    b"arg" => args.push(p.arg(e, empty)?),
+                    b"annotation" => annotations.push(p.annotation(e, empty)?),
+                    _ => p.skip(e, empty)?,
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok((name, args, annotations))
+    }
+
+    fn arg(&mut self, e: &BytesStart<'x>, empty: bool) -> Result<Arg<'static>> {
+        let direction = match attribute(e, b"direction")?.as_deref() {
+            Some("in") => Some(ArgDirection::In),
+            Some("out") => Some(ArgDirection::Out),
+            Some(d) => return Err(invalid(format!("invalid argument direction `{d}`"))),
+            None => None,
+        };
+
+        Ok(Arg {
+            name: attribute(e, b"name")?.map(Cow::into_owned),
+            ty: complete_type(required_attribute(e, b"type")?)?,
+            direction,
+            annotations: self.annotations(empty)?,
+        })
+    }
+
+    fn property(&mut self, e: &BytesStart<'x>, empty: bool) -> Result<Property<'static>> {
+        let access = match &*required_attribute(e, b"access")? {
+            "read" => PropertyAccess::Read,
+            "write" => PropertyAccess::Write,
+            "readwrite" => PropertyAccess::ReadWrite,
+            a => return Err(invalid(format!("invalid property access `{a}`"))),
+        };
+
+        Ok(Property {
+            name: convert(required_attribute(e, b"name")?)?,
+            ty: complete_type(required_attribute(e, b"type")?)?,
+            access,
+            annotations: self.annotations(empty)?,
+        })
+    }
+
+    fn annotation(&mut self, e: &BytesStart<'x>, empty: bool) -> Result<Annotation> {
+        let annotation = Annotation {
+            name: required_attribute(e, b"name")?.into_owned(),
+            value: required_attribute(e, b"value")?.into_owned(),
+        };
+        self.skip(e, empty)?;
+
+        Ok(annotation)
+    }
+
+    // The annotations of an element without any other children.
+    fn annotations(&mut self, empty: bool) -> Result<Vec<Annotation>> {
+        let mut annotations = vec![];
+        if !empty {
+            self.children(|p, e, empty| {
+                match e.local_name().as_ref() {
+                    b"annotation" => annotations.push(p.annotation(e, empty)?),
+                    _ => p.skip(e, empty)?,
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(annotations)
+    }
+}
+
+fn attribute<'x>(e: &'x BytesStart<'_>, name: &[u8]) -> Result<Option<Cow<'x, str>>> {
+    for attr in e.attributes() {
+        let attr = attr.map_err(|e| Error::from(DeError::from(quick_xml::Error::from(e))))?;
+        if attr.key.as_ref() == name {
+            // Only the predefined entities and character references are unescaped.
+            let value = attr
+                .unescape_value()
+                .map_err(|e| Error::from(DeError::from(e)))?;
+            return Ok(Some(value));
+        }
+    }
+
+    Ok(None)
+}
+
+fn required_attribute<'x>(e: &'x BytesStart<'_>, name: &[u8]) -> Result<Cow<'x, str>> {
+    attribute(e, name)?.ok_or_else(|| {
+        invalid(format!(
+            "missing `{}` attribute on `{}` element",
+            String::from_utf8_lossy(name),
+            String::from_utf8_lossy(e.name().as_ref()),
+        ))
+    })
+}
+
+fn convert<T>(value: Cow<'_, str>) -> Result<T>
+where
+    T: TryFrom<String>,
+    T::Error: Display,
+{
+    T::try_from(value.into_owned()).map_err(invalid)
+}
+
+fn complete_type(value: Cow<'_, str>) -> Result<CompleteType<'static>> {
+    let signature: Signature<'static> = convert(value)?;
+
+    CompleteType::try_from(signature).map_err(invalid)
+}
+
+// Errors in the contents of the document are reported the same way the serde deserializer does.
+fn invalid<E: Display>(e: E) -> Error {
+    Error::QuickXml(DeError::Custom(e.to_string()))
+}
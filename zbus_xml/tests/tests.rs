@@ -23,6 +23,10 @@ fn serde() -> Result<(), Box<dyn Error>> {
     assert_eq!(node_str.interfaces().len(), 1);
     assert_eq!(node_str.nodes().len(), 3);
 
+    // The types can also be deserialized with serde, giving the same result.
+    let node_de: Node<'_> = quick_xml::de::from_str(example)?;
+    assert_eq!(node_de, node);
+
     let mut writer = Vec::with_capacity(128);
     node.to_writer(&mut writer).unwrap();
     Ok(())
@@ -36,3 +40,75 @@ fn invalid_arg_type() {
         Err(zbus_xml::Error::QuickXml(DeError::Custom(_)))
     ));
 }
+
+#[test]
+fn annotations_and_nested_nodes() -> Result<(), Box<dyn Error>> {
+    let input = r#"<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<!-- A comment -->
+<node>
+  <interface name="org.example.Outer">
+    <annotation name="org.example.Note" value="fish &amp; chips &#x263A;"/>
+    <method name="Frob">
+      <arg name="x" type="i" direction="in">
+        <annotation name="org.qtproject.QtDBus.QtTypeName.In0" value="int"/>
+      </arg>
+    </method>
+    <property name="Count" type="u" access="read">
+      <annotation name="org.freedesktop.DBus.Property.EmitsChangedSignal" value="const"/>
+    </property>
+    <unknown><whatever/></unknown>
+  </interface>
+  <node name="child">
+    <interface name="org.example.Inner">
+      <signal name="Changed"><arg type="b"/></signal>
+    </interface>
+  </node>
+</node>
+"#;
+    let node = Node::try_from(input)?;
+    assert_eq!(node, Node::from_reader(input.as_bytes())?);
+    assert_eq!(node.name(), None);
+
+    let outer = &node.interfaces()[0];
+    assert_eq!(outer.annotations()[0].value(), "fish & chips \u{263A}");
+    let arg = &outer.methods()[0].args()[0];
+    assert_eq!(arg.direction(), Some(ArgDirection::In));
+    assert_eq!(arg.annotations()[0].value(), "int");
+    assert_eq!(outer.properties()[0].annotations()[0].value(), "const");
+
+    let child = &node.nodes()[0];
+    assert_eq!(child.name(), Some("child"));
+    let signal = &child.interfaces()[0].signals()[0];
+    assert_eq!(signal.name(), "Changed");
+    assert_eq!(signal.args()[0].name(), None);
+
+    Ok(())
+}
+
+#[test]
+fn entities_not_expanded() {
+    let input = r#"<!DOCTYPE node [
+  <!ENTITY boom "org.example.Boom">
+]>
+<node>
+  <interface name="&boom;"/>
+</node>
+"#;
+    assert!(Node::try_from(input).is_err());
+}
+
+#[test]
+fn nesting_depth_limit() {
+    let nested = |depth| format!("{}{}", "<node>".repeat(depth), "</node>".repeat(depth));
+
+    let xml = nested(128);
+    let node = Node::try_from(xml.as_str()).unwrap();
+    assert_eq!(node.nodes().len(), 1);
+    assert!(matches!(
+        Node::try_from(nested(129).as_str()),
+        Err(zbus_xml::Error::QuickXml(DeError::Custom(_)))
+    ));
+    // Deep enough to overflow the stack without the limit.
+    assert!(Node::try_from(nested(100_000).as_str()).is_err());
+}
@@ -8,10 +8,56 @@ use zvariant::{
     DICT_ENTRY_SIG_START_CHAR, STRUCT_SIG_END_CHAR, STRUCT_SIG_START_CHAR, VARIANT_SIGNATURE_CHAR,
 };
 
+/// The generator of the proxy trait for an interface.
+///
+/// Create it with [`GenTrait::new`] and configure it through its setters. The generated code is
+/// given by the `Display` implementation.
+///
+/// This struct is non-exhaustive, so it can't be created with a struct literal outside of this
+/// crate anymore. This is a breaking change: code creating it with a struct literal needs to use
+/// [`GenTrait::new`] and the setters instead.
+#[non_exhaustive]
 pub struct GenTrait<'i> {
     pub interface: &'i Interface<'i>,
     pub service: Option<&'i BusName<'i>>,
     pub path: Option<&'i ObjectPath<'i>>,
+    /// The cargo feature of the target crate the blocking proxy is gated by, if any.
+    ///
+    /// If `None`, both the asynchronous and the blocking proxies are always generated.
+    pub blocking_feature: Option<&'i str>,
+}
+
+impl<'i> GenTrait<'i> {
+    /// Create a generator for `interface`, without default service and path.
+    pub fn new(interface: &'i Interface<'i>) -> Self {
+        Self {
+            interface,
+            service: None,
+            path: None,
+            blocking_feature: None,
+        }
+    }
+
+    /// Set the default service of the generated proxy.
+    pub fn service(mut self, service: Option<&'i BusName<'i>>) -> Self {
+        self.service = service;
+
+        self
+    }
+
+    /// Set the default path of the generated proxy.
+    pub fn path(mut self, path: Option<&'i ObjectPath<'i>>) -> Self {
+        self.path = path;
+
+        self
+    }
+
+    /// Set the cargo feature of the target crate the blocking proxy is gated by.
+    pub fn blocking_feature(mut self, feature: Option<&'i str>) -> Self {
+        self.blocking_feature = feature;
+
+        self
+    }
 }
 
 impl<'i> Display for GenTrait<'i> {
@@ -20,17 +66,29 @@ impl<'i> Display for GenTrait<'i> {
         let idx = iface.name().rfind('.').unwrap() + 1;
         let name = &iface.name()[idx..];
 
-        write!(f, "#[dbus_proxy(interface = \"{}\"", iface.name())?;
+        let mut proxy_attrs = format!("interface = \"{}\"", iface.name());
         if let Some(service) = self.service {
-            write!(f, ", default_service = \"{service}\"")?;
+            proxy_attrs.push_str(&format!(", default_service = \"{service}\""));
         }
         if let Some(path) = self.path {
-            write!(f, ", default_path = \"{path}\"")?;
+            proxy_attrs.push_str(&format!(", default_path = \"{path}\""));
         }
         if self.path.is_none() || self.service.is_none() {
-            write!(f, ", assume_defaults = true")?;
+            proxy_attrs.push_str(", assume_defaults = true");
+        }
+        match self.blocking_feature {
+            Some(feature) => {
+                writeln!(
+                    f,
+                    "#[cfg_attr(feature = \"{feature}\", dbus_proxy({proxy_attrs}))]"
+                )?;
+                writeln!(
+                    f,
+                    "#[cfg_attr(not(feature = \"{feature}\"), dbus_proxy({proxy_attrs}, gen_blocking = false))]"
+                )?;
+            }
+            None => writeln!(f, "#[dbus_proxy({proxy_attrs})]")?,
         }
-        writeln!(f, ")]")?;
         writeln!(f, "trait {name} {{")?;
 
         let mut methods = iface.methods().to_vec();
@@ -149,6 +207,44 @@ fn parse_signal_args(args: &[Arg]) -> String {
     inputs.join(", ")
 }
 
+/// How a D-Bus type is used in the generated code, which affects the Rust type it maps to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TypeConfig {
+    /// Whether the type is used as an input (e.g a method argument), in which case borrowed types
+    /// are used where possible instead of owned ones.
+    pub input: bool,
+    /// Whether the top-level type should be a reference.
+    pub as_ref: bool,
+}
+
+/// Get the Rust type zbus conventionally uses for the D-Bus type `ty`.
+///
+/// This is the mapping used by the generated proxy code, exposed so other code generators can
+/// produce types consistent with it.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_xmlgen::{rust_type_for, TypeConfig};
+/// use zvariant::{CompleteType, Signature};
+///
+/// let ty = CompleteType::try_from(Signature::try_from("a{sv}").unwrap()).unwrap();
+/// assert_eq!(
+///     rust_type_for(&ty, TypeConfig::default()),
+///     "std::collections::HashMap<String, zbus::zvariant::OwnedValue>",
+/// );
+///
+/// let ty = CompleteType::try_from(Signature::try_from("as").unwrap()).unwrap();
+/// let config = TypeConfig {
+///     input: true,
+///     as_ref: true,
+/// };
+/// assert_eq!(rust_type_for(&ty, config), "&[&str]");
+/// ```
+pub fn rust_type_for(ty: &CompleteType<'_>, config: TypeConfig) -> String {
+    to_rust_type(ty, config.input, config.as_ref)
+}
+
 fn to_rust_type(ty: &CompleteType, input: bool, as_ref: bool) -> String {
     // can't haz recursive closure, yet
     fn iter_to_rust_type(
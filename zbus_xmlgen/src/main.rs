@@ -13,6 +13,7 @@ use std::{
 use zbus::{
     blocking::{connection, fdo::IntrospectableProxy, Connection},
     names::BusName,
+    proxy::ObjectTree,
 };
 use zbus_xml::{Interface, Node};
 
@@ -22,9 +23,19 @@ use zvariant::ObjectPath;
 fn usage() {
     eprintln!(
         r#"Usage:
-  zbus-xmlgen <interface.xml>
-  zbus-xmlgen --system|--session <service> <object_path>
-  zbus-xmlgen --address <address> <service> <object_path>
+  zbus-xmlgen [--blocking-feature <feature>] <interface.xml>
+  zbus-xmlgen [--blocking-feature <feature>] --system|--session <service> <object_path>
+  zbus-xmlgen [--blocking-feature <feature>] --address <address> <service> <object_path>
+  zbus-xmlgen tree --system|--session <service> [<object_path>]
+  zbus-xmlgen tree --address <address> <service> [<object_path>]
+
+Options:
+  --blocking-feature <feature>  Only generate the blocking proxies if the given cargo feature of
+                                the target crate is enabled. Both the asynchronous and the blocking
+                                proxies are always generated otherwise.
+
+The `tree` command prints the tree of objects of the service, starting at the given object path
+(`/` by default), along with the interfaces each object implements.
 "#
     );
 }
@@ -32,6 +43,24 @@ fn usage() {
 fn main() -> Result<(), Box<dyn Error>> {
     let input_src;
 
+    let mut args: Vec<String> = args().collect();
+    let blocking_feature = match args.iter().position(|a| a == "--blocking-feature") {
+        Some(i) => {
+            let feature = args
+                .get(i + 1)
+                .cloned()
+                .expect("Missing param for blocking feature");
+            args.drain(i..=i + 1);
+
+            Some(feature)
+        }
+        None => None,
+    };
+
+    if args.get(1).map(|a| a == "tree").unwrap_or(false) {
+        return tree(&args[2..]);
+    }
+
     let proxy = |conn: Connection, service, path| -> IntrospectableProxy<'_> {
         IntrospectableProxy::builder(&conn)
             .destination(service)
@@ -42,19 +71,21 @@ fn main() -> Result<(), Box<dyn Error>> {
             .unwrap()
     };
 
-    let (node, service, path) = match args().nth(1) {
+    let (node, service, path) = match args.get(1).cloned() {
         Some(bus) if bus == "--system" || bus == "--session" => {
             let connection = if bus == "--system" {
                 Connection::system()?
             } else {
                 Connection::session()?
             };
-            let service: BusName<'_> = args()
-                .nth(2)
+            let service: BusName<'_> = args
+                .get(2)
+                .cloned()
                 .expect("Missing param for service")
                 .try_into()?;
-            let path: ObjectPath<'_> = args()
-                .nth(3)
+            let path: ObjectPath<'_> = args
+                .get(3)
+                .cloned()
                 .expect("Missing param for object path")
                 .try_into()?;
 
@@ -73,13 +104,18 @@ fn main() -> Result<(), Box<dyn Error>> {
             )
         }
         Some(address) if address == "--address" => {
-            let address = args().nth(2).expect("Missing param for address path");
-            let service: BusName<'_> = args()
-                .nth(3)
+            let address = args
+                .get(2)
+                .cloned()
+                .expect("Missing param for address path");
+            let service: BusName<'_> = args
+                .get(3)
+                .cloned()
                 .expect("Missing param for service")
                 .try_into()?;
-            let path: ObjectPath<'_> = args()
-                .nth(4)
+            let path: ObjectPath<'_> = args
+                .get(4)
+                .cloned()
                 .expect("Missing param for object path")
                 .try_into()?;
 
@@ -189,14 +225,44 @@ fn main() -> Result<(), Box<dyn Error>> {
     )?;
     for iface in &needed_ifaces {
         writeln!(rustfmt_stdin)?;
-        let gen = GenTrait {
-            interface: iface,
-            service: service.as_ref(),
-            path: path.as_ref(),
-        }
-        .to_string();
+        let gen = GenTrait::new(iface)
+            .service(service.as_ref())
+            .path(path.as_ref())
+            .blocking_feature(blocking_feature.as_deref())
+            .to_string();
         rustfmt_stdin.write_all(gen.as_bytes())?;
     }
     process.wait()?;
     Ok(())
 }
+
+fn tree(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (connection, args) = match args.first().map(|a| a.as_str()) {
+        Some("--system") => (Connection::system()?, &args[1..]),
+        Some("--session") => (Connection::session()?, &args[1..]),
+        Some("--address") => {
+            let address = args.get(1).expect("Missing param for address path");
+            let connection = connection::Builder::address(&**address)?.build()?;
+
+            (connection, &args[2..])
+        }
+        _ => {
+            usage();
+            return Ok(());
+        }
+    };
+    let service: BusName<'_> = args
+        .first()
+        .cloned()
+        .expect("Missing param for service")
+        .try_into()?;
+    let path: ObjectPath<'_> = match args.get(1) {
+        Some(path) => path.clone().try_into()?,
+        None => ObjectPath::from_static_str_unchecked("/"),
+    };
+
+    let tree = zbus::block_on(ObjectTree::introspect(connection.inner(), service, path))?;
+    print!("{tree}");
+
+    Ok(())
+}
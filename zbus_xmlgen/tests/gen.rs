@@ -5,18 +5,18 @@ use zbus_xml::Node;
 use zbus_xmlgen::GenTrait;
 
 macro_rules! gen_diff {
-    ($infile:literal, $outfile:literal) => {{
+    ($infile:literal, $outfile:literal) => {
+        gen_diff!($infile, $outfile, None)
+    };
+    ($infile:literal, $outfile:literal, $blocking_feature:expr) => {{
         let input = include_str!(concat!("data/", $infile));
         let expected = include_str!(concat!("data/", $outfile));
         #[cfg(windows)]
         let expected = expected.replace("\r\n", "\n");
         let node = Node::from_reader(input.as_bytes())?;
-        let gen = GenTrait {
-            interface: &node.interfaces()[0],
-            path: None,
-            service: None,
-        }
-        .to_string();
+        let gen = GenTrait::new(&node.interfaces()[0])
+            .blocking_feature($blocking_feature)
+            .to_string();
 
         if env::var("TEST_OVERWRITE").is_ok() {
             let path = Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -41,3 +41,12 @@ macro_rules! gen_diff {
 fn sample_object0() -> Result<(), Box<dyn Error>> {
     gen_diff!("sample_object0.xml", "sample_object0.rs")
 }
+
+#[test]
+fn sample_object0_blocking_feature() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "sample_object0.xml",
+        "sample_object0_blocking_feature.rs",
+        Some("blocking")
+    )
+}
@@ -0,0 +1,15 @@
+#![no_main]
+use byteorder::ByteOrder;
+use zvariant::{from_slice, to_bytes, EncodingContext as Context, Value};
+
+// `Value` can hold GVariant-only types (e.g `Maybe`) so only the GVariant format is used here.
+libfuzzer_sys::fuzz_target!(|value: Value<'_>| {
+    roundtrip(&value, Context::<byteorder::LE>::new_gvariant(0));
+    roundtrip(&value, Context::<byteorder::BE>::new_gvariant(0));
+});
+
+fn roundtrip<B: ByteOrder>(value: &Value<'_>, ctxt: Context<B>) {
+    let encoded = to_bytes(ctxt, value).unwrap();
+    let (decoded, _) = from_slice::<_, Value<'_>>(&encoded, ctxt).unwrap();
+    assert_eq!(&decoded, value);
+}
@@ -0,0 +1,252 @@
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+#[cfg(feature = "gvariant")]
+use crate::Maybe;
+use crate::{
+    signature_parser::SignatureParser, Array, Dict, ObjectPath, OwnedValue, Signature, Structure,
+    StructureBuilder, Value,
+};
+
+// How deep the generated types are allowed to nest. Way below the default encoding limits.
+const MAX_DEPTH: u8 = 8;
+// The maximum number of elements in generated arrays and dictionaries.
+const MAX_ELEMENTS: usize = 16;
+// The maximum number of fields in generated structures.
+const MAX_FIELDS: usize = 4;
+// Beyond this length, only basic types are added to a signature. Keeps the signatures well within
+// the 255 bytes allowed by D-Bus.
+const MAX_SIGNATURE_LEN: usize = 128;
+
+// File descriptors are left out since they're meaningless without the actual descriptors.
+const BASIC_TYPES: &[char] = &['y', 'b', 'n', 'q', 'i', 'u', 'x', 't', 'd', 's', 'o', 'g'];
+
+const OBJECT_PATH_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+
+/// Generates a signature of up to 4 complete types.
+///
+/// This implementation is only available with the `arbitrary` feature enabled.
+impl<'a> Arbitrary<'a> for Signature<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut signature = String::new();
+        for _ in 0..u.int_in_range(0..=4)? {
+            arbitrary_type(u, MAX_DEPTH, &mut signature)?;
+        }
+
+        Ok(Signature::from_string_unchecked(signature))
+    }
+}
+
+/// Generates an object path of up to 4 elements.
+///
+/// This implementation is only available with the `arbitrary` feature enabled.
+impl<'a> Arbitrary<'a> for ObjectPath<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut path = String::new();
+        for _ in 0..u.int_in_range(0..=4)? {
+            path.push('/');
+            for _ in 0..u.int_in_range(1..=8)? {
+                path.push(*u.choose(OBJECT_PATH_CHARS)? as char);
+            }
+        }
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        Ok(ObjectPath::from_string_unchecked(path))
+    }
+}
+
+/// Generates a value of an arbitrary type, containing no file descriptors.
+///
+/// Note that values of GVariant-only types (i.e `Maybe`) are also generated when the `gvariant`
+/// feature is enabled. This implementation is only available with the `arbitrary` feature
+/// enabled.
+impl<'a> Arbitrary<'a> for Value<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_value_of_any_type(u, MAX_DEPTH)
+    }
+}
+
+/// See the [`Value`] implementation for details.
+///
+/// [`Value`]: enum.Value.html
+impl<'a> Arbitrary<'a> for OwnedValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Value::arbitrary(u).map(Into::into)
+    }
+}
+
+/// This implementation is only available with the `arbitrary` feature enabled.
+impl<'a> Arbitrary<'a> for Array<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut signature = String::from("a");
+        arbitrary_type(u, MAX_DEPTH - 1, &mut signature)?;
+
+        match arbitrary_value(u, Signature::from_string_unchecked(signature), MAX_DEPTH)? {
+            Value::Array(array) => Ok(array),
+            _ => unreachable!("array signature must give an array"),
+        }
+    }
+}
+
+/// This implementation is only available with the `arbitrary` feature enabled.
+impl<'a> Arbitrary<'a> for Dict<'a, 'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut signature = String::from("a{");
+        signature.push(*u.choose(BASIC_TYPES)?);
+        arbitrary_type(u, MAX_DEPTH - 1, &mut signature)?;
+        signature.push('}');
+
+        match arbitrary_value(u, Signature::from_string_unchecked(signature), MAX_DEPTH)? {
+            Value::Dict(dict) => Ok(dict),
+            _ => unreachable!("dict signature must give a dict"),
+        }
+    }
+}
+
+/// This implementation is only available with the `arbitrary` feature enabled.
+impl<'a> Arbitrary<'a> for Structure<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut builder = StructureBuilder::new();
+        for _ in 0..u.int_in_range(1..=MAX_FIELDS)? {
+            builder = builder.append_field(arbitrary_value_of_any_type(u, MAX_DEPTH - 1)?);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+// Append a single complete type to `signature`.
+fn arbitrary_type(u: &mut Unstructured<'_>, depth: u8, signature: &mut String) -> Result<()> {
+    // Once out of depth, length (or data), only basic types are generated.
+    let kinds = if depth == 0 || signature.len() >= MAX_SIGNATURE_LEN {
+        1
+    } else if cfg!(feature = "gvariant") {
+        6
+    } else {
+        5
+    };
+
+    match u.choose_index(kinds)? {
+        0 => signature.push(*u.choose(BASIC_TYPES)?),
+        1 => signature.push('v'),
+        2 => {
+            signature.push('a');
+            arbitrary_type(u, depth - 1, signature)?;
+        }
+        3 => {
+            signature.push_str("a{");
+            signature.push(*u.choose(BASIC_TYPES)?);
+            arbitrary_type(u, depth - 1, signature)?;
+            signature.push('}');
+        }
+        4 => {
+            signature.push('(');
+            for _ in 0..u.int_in_range(1..=MAX_FIELDS)? {
+                arbitrary_type(u, depth - 1, signature)?;
+            }
+            signature.push(')');
+        }
+        _ => {
+            signature.push('m');
+            arbitrary_type(u, depth - 1, signature)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn arbitrary_value_of_any_type(u: &mut Unstructured<'_>, depth: u8) -> Result<Value<'static>> {
+    let mut signature = String::new();
+    arbitrary_type(u, depth, &mut signature)?;
+
+    arbitrary_value(u, Signature::from_string_unchecked(signature), depth)
+}
+
+// Generate a value for the given single complete type signature.
+fn arbitrary_value(
+    u: &mut Unstructured<'_>,
+    signature: Signature<'_>,
+    depth: u8,
+) -> Result<Value<'static>> {
+    let parser = SignatureParser::new(signature.clone());
+    let value = match parser.next_char().map_err(|_| Error::IncorrectFormat)? {
+        'y' => Value::U8(u.arbitrary()?),
+        'b' => Value::Bool(u.arbitrary()?),
+        'n' => Value::I16(u.arbitrary()?),
+        'q' => Value::U16(u.arbitrary()?),
+        'i' => Value::I32(u.arbitrary()?),
+        'u' => Value::U32(u.arbitrary()?),
+        'x' => Value::I64(u.arbitrary()?),
+        't' => Value::U64(u.arbitrary()?),
+        'd' => Value::F64(u.arbitrary()?),
+        // Strings can't contain nul bytes.
+        's' => Value::from(String::arbitrary(u)?.replace('\0', "")),
+        'o' => Value::ObjectPath(ObjectPath::arbitrary(u)?.to_owned()),
+        'g' => {
+            let mut signature = String::new();
+            arbitrary_type(u, depth.saturating_sub(1), &mut signature)?;
+
+            Value::Signature(Signature::from_string_unchecked(signature))
+        }
+        'v' => Value::Value(Box::new(arbitrary_value_of_any_type(
+            u,
+            depth.saturating_sub(1),
+        )?)),
+        'a' if signature.as_bytes().get(1) == Some(&b'{') => {
+            let key_signature = signature.slice(2..3);
+            let value_signature = signature.slice(3..signature.len() - 1);
+            let mut dict = Dict::new(key_signature.to_owned(), value_signature.to_owned());
+            for _ in 0..u.int_in_range(0..=MAX_ELEMENTS)? {
+                let key = arbitrary_value(u, key_signature.clone(), depth.saturating_sub(1))?;
+                let value = arbitrary_value(u, value_signature.clone(), depth.saturating_sub(1))?;
+                dict.append(key, value)
+                    .map_err(|_| Error::IncorrectFormat)?;
+            }
+
+            Value::Dict(dict)
+        }
+        'a' => {
+            let element_signature = signature.slice(1..);
+            let mut array = Array::new(element_signature.to_owned());
+            for _ in 0..u.int_in_range(0..=MAX_ELEMENTS)? {
+                let element =
+                    arbitrary_value(u, element_signature.clone(), depth.saturating_sub(1))?;
+                array.append(element).map_err(|_| Error::IncorrectFormat)?;
+            }
+
+            Value::Array(array)
+        }
+        '(' => {
+            let mut fields = parser.slice(1..signature.len() - 1);
+            let mut builder = StructureBuilder::new();
+            while !fields.done() {
+                let field_signature = fields
+                    .next_signature()
+                    .map_err(|_| Error::IncorrectFormat)?
+                    .to_owned();
+                fields
+                    .skip_chars(field_signature.len())
+                    .map_err(|_| Error::IncorrectFormat)?;
+                let field = arbitrary_value(u, field_signature, depth.saturating_sub(1))?;
+                builder = builder.append_field(field);
+            }
+
+            Value::Structure(builder.build())
+        }
+        #[cfg(feature = "gvariant")]
+        'm' => {
+            let value_signature = signature.slice(1..);
+            if u.arbitrary()? {
+                let value = arbitrary_value(u, value_signature, depth.saturating_sub(1))?;
+
+                Value::Maybe(Maybe::just(value))
+            } else {
+                Value::Maybe(Maybe::nothing(value_signature.to_owned()))
+            }
+        }
+        _ => return Err(Error::IncorrectFormat),
+    };
+
+    Ok(value)
+}
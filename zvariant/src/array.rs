@@ -1,6 +1,6 @@
 #![allow(unknown_lints)]
 use serde::{
-    de::{DeserializeSeed, Deserializer, SeqAccess, Visitor},
+    de::{DeserializeSeed, Deserializer, SeqAccess, Unexpected, Visitor},
     ser::{Serialize, SerializeSeq, Serializer},
 };
 use static_assertions::assert_impl_all;
@@ -13,11 +13,24 @@ use crate::{
 
 /// A helper type to wrap arrays in a [`Value`].
 ///
-/// API is provided to convert from, and to a [`Vec`].
+/// API is provided to convert from, and to a [`Vec`]. An `Array` can also be collected from, and
+/// extended with, an iterator.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{Array, Value};
+///
+/// let mut array: Array<'_> = (1..=3_u32).map(|i| i * 10).collect();
+/// array.extend([40_u32, 50]);
+/// assert_eq!(array.element_signature().as_str(), "u");
+/// assert_eq!(array.len(), 5);
+/// assert_eq!(Value::from(array).to_string(), "[uint32 10, 20, 30, 40, 50]");
+/// ```
 ///
 /// [`Value`]: enum.Value.html#variant.Array
 /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Array<'a> {
     element_signature: Signature<'a>,
     elements: Vec<Value<'a>>,
@@ -64,6 +77,68 @@ impl<'a> Array<'a> {
         &self.elements
     }
 
+    /// Get a reference to the element at `index`.
+    ///
+    /// Returns `Ok(None)` if `index` is out of bounds, and an error if the element is not a `T`.
+    /// Elements wrapped in a [`Value::Value`] (e.g in an `av` array) are looked through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Array;
+    ///
+    /// let array = Array::from(vec!["hello", "world"]);
+    /// assert_eq!(array.element::<str>(1).unwrap(), Some("world"));
+    /// assert_eq!(array.element::<str>(2).unwrap(), None);
+    /// assert!(array.element::<u32>(0).is_err());
+    /// ```
+    ///
+    /// [`Value::Value`]: enum.Value.html#variant.Value
+    pub fn element<'s, T>(&'s self, index: usize) -> Result<Option<&'s T>>
+    where
+        T: ?Sized,
+        &'s T: TryFrom<&'s Value<'s>>,
+    {
+        match self.elements.get(index) {
+            Some(element) => element
+                .downcast_ref()
+                .ok_or_else(|| Error::type_mismatch_for::<T>(element).in_path(format!("[{index}]")))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// An iterator over references to the elements, as `T`.
+    ///
+    /// Each element is downcast when the iterator gets to it, producing an error if it's not a
+    /// `T`. Like [`Array::element`], elements wrapped in a [`Value::Value`] are looked through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Array, Value};
+    ///
+    /// let array = Array::from(vec![Value::new("hello"), Value::new("world")]);
+    /// let strings = array.iter_as::<str>().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(strings, ["hello", "world"]);
+    ///
+    /// let mut numbers = array.iter_as::<u32>();
+    /// assert!(numbers.next().unwrap().is_err());
+    /// ```
+    ///
+    /// [`Value::Value`]: enum.Value.html#variant.Value
+    pub fn iter_as<'s, T>(&'s self) -> impl Iterator<Item = Result<&'s T>>
+    where
+        T: ?Sized + 's,
+        &'s T: TryFrom<&'s Value<'s>>,
+    {
+        self.elements.iter().enumerate().map(|(i, element)| {
+            element
+                .downcast_ref::<T>()
+                .ok_or_else(|| Error::type_mismatch_for::<T>(element).in_path(format!("[{i}]")))
+        })
+    }
+
     /// Get the number of elements.
     pub fn len(&self) -> usize {
         self.elements.len()
@@ -100,6 +175,14 @@ impl<'a> Array<'a> {
             signature: self.signature.to_owned(),
         }
     }
+
+    pub(crate) fn byteswap(&self) -> Array<'static> {
+        Array {
+            element_signature: self.element_signature.to_owned(),
+            elements: self.elements.iter().map(Value::byteswap).collect(),
+            signature: self.signature.to_owned(),
+        }
+    }
 }
 
 impl Display for Array<'_> {
@@ -262,6 +345,45 @@ where
     }
 }
 
+impl<'a, T> FromIterator<T> for Array<'a>
+where
+    T: Type + Into<Value<'a>>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let element_signature = T::signature();
+        let elements = iter.into_iter().map(Value::new).collect();
+        let signature = create_signature(&element_signature);
+
+        Self {
+            element_signature,
+            elements,
+            signature,
+        }
+    }
+}
+
+/// # Panics
+///
+/// If the signature of an element doesn't match the element signature of the `Array`. Use
+/// [`Array::append`] to handle that case gracefully.
+impl<'a, T> Extend<T> for Array<'a>
+where
+    T: DynamicType + Into<Value<'a>>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for element in iter {
+            self.append(Value::new(element))
+                .expect("mismatched `Array` element signature");
+        }
+    }
+}
+
 impl<'a, T> TryFrom<Array<'a>> for Vec<T>
 where
     T: TryFrom<Value<'a>>,
@@ -272,13 +394,13 @@ where
     fn try_from(v: Array<'a>) -> core::result::Result<Self, Self::Error> {
         // there is no try_map yet..
         let mut res = vec![];
-        for e in v.elements.into_iter() {
+        for (i, e) in v.elements.into_iter().enumerate() {
             let value = if let Value::Value(v) = e {
                 T::try_from(*v)
             } else {
                 T::try_from(e)
             }
-            .map_err(Into::into)?;
+            .map_err(|e| Into::<Error>::into(e).in_path(format!("[{i}]")))?;
 
             res.push(value);
         }
@@ -294,6 +416,20 @@ impl<'a> Serialize for Array<'a> {
     where
         S: Serializer,
     {
+        // Let the serializer write byte arrays in one go.
+        if self.element_signature == "y" {
+            let bytes: Vec<u8> = self
+                .elements
+                .iter()
+                .filter_map(|element| match element {
+                    Value::U8(b) => Some(*b),
+                    _ => None,
+                })
+                .collect();
+
+            return serializer.serialize_bytes(&bytes);
+        }
+
         let mut seq = serializer.serialize_seq(Some(self.elements.len()))?;
         for element in &self.elements {
             element.serialize_value_as_seq_element(&mut seq)?;
@@ -309,9 +445,14 @@ impl<'de> DeserializeSeed<'de> for ArraySeed<'de> {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_seq(ArrayVisitor {
+        let visitor = ArrayVisitor {
             signature: self.signature,
-        })
+        };
+        if visitor.signature == "ay" {
+            deserializer.deserialize_bytes(visitor)
+        } else {
+            deserializer.deserialize_seq(visitor)
+        }
     }
 }
 
@@ -336,8 +477,78 @@ impl<'de> Visitor<'de> for ArrayVisitor<'de> {
         }
         .visit_array(visitor)
     }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> std::result::Result<Array<'de>, E>
+    where
+        E: serde::de::Error,
+    {
+        if self.signature != "ay" {
+            return Err(E::invalid_type(Unexpected::Bytes(v), &self));
+        }
+
+        Ok(Array::from(v))
+    }
 }
 
 fn create_signature(element_signature: &Signature<'_>) -> Signature<'static> {
     Signature::from_string_unchecked(format!("a{element_signature}"))
 }
+
+/// Owned [`Array`](struct.Array.html)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedArray(Array<'static>);
+
+assert_impl_all!(OwnedArray: Send, Sync, Unpin);
+
+impl OwnedArray {
+    pub fn into_inner(self) -> Array<'static> {
+        self.0
+    }
+}
+
+impl std::ops::Deref for OwnedArray {
+    type Target = Array<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<OwnedArray> for Array<'static> {
+    fn from(o: OwnedArray) -> Self {
+        o.into_inner()
+    }
+}
+
+impl<'a> From<Array<'a>> for OwnedArray {
+    fn from(o: Array<'a>) -> Self {
+        OwnedArray(o.to_owned())
+    }
+}
+
+impl From<OwnedArray> for Value<'static> {
+    fn from(o: OwnedArray) -> Self {
+        o.into_inner().into()
+    }
+}
+
+impl DynamicType for OwnedArray {
+    fn dynamic_signature(&self) -> Signature<'_> {
+        self.0.dynamic_signature()
+    }
+}
+
+impl Serialize for OwnedArray {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Display for OwnedArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
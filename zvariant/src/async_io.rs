@@ -0,0 +1,148 @@
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    from_slice_for_signature,
+    reader::{check_read_len, DBusValueScanner},
+    ser::ChunkedEncoding,
+    DynamicType, EncodingContext, EncodingFormat, Error, Result, Signature, Type,
+};
+
+/// Serialize `T` to the given async `writer`.
+///
+/// The value is serialized in chunks, each written to the `writer` before the next one is
+/// serialized, so its complete encoding is never kept in memory. Like [`to_stream`], this takes a
+/// pass over the value to find out where array lengths go, followed by a pass per chunk.
+///
+/// This function returns the number of bytes written to the given `writer`.
+///
+/// This function is only available with the `async` feature enabled.
+///
+/// # Panics
+///
+/// This function will panic if the value to serialize contains file descriptors.
+///
+/// [`to_stream`]: fn.to_stream.html
+pub async fn to_async_writer<B, W, T>(
+    writer: &mut W,
+    ctxt: EncodingContext<B>,
+    value: &T,
+) -> Result<usize>
+where
+    B: byteorder::ByteOrder,
+    W: AsyncWrite + Unpin,
+    T: ?Sized + Serialize + DynamicType,
+{
+    let signature = value.dynamic_signature();
+
+    to_async_writer_for_signature(writer, ctxt, &signature, value).await
+}
+
+/// Serialize `T` that has the given signature, to the given async `writer`.
+///
+/// Use this function instead of [`to_async_writer`] if the value being serialized does not
+/// implement [`Type`].
+///
+/// This function is only available with the `async` feature enabled.
+///
+/// [`to_async_writer`]: fn.to_async_writer.html
+/// [`Type`]: trait.Type.html
+pub async fn to_async_writer_for_signature<'s, B, W, S, T>(
+    writer: &mut W,
+    ctxt: EncodingContext<B>,
+    signature: S,
+    value: &T,
+) -> Result<usize>
+where
+    B: byteorder::ByteOrder,
+    W: AsyncWrite + Unpin,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: ?Sized + Serialize,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+    let mut encoding = ChunkedEncoding::new(ctxt, signature, value)?;
+    while let Some(chunk) = encoding.next_chunk()? {
+        writer
+            .write_all(&chunk)
+            .await
+            .map_err(|e| Error::InputOutput(e.into()))?;
+    }
+
+    Ok(encoding.len())
+}
+
+/// Deserialize `T` from the given async `reader`.
+///
+/// See [`from_async_reader_for_signature`] for details.
+///
+/// This function is only available with the `async` feature enabled.
+///
+/// [`from_async_reader_for_signature`]: fn.from_async_reader_for_signature.html
+pub async fn from_async_reader<B, R, T>(
+    reader: &mut R,
+    ctxt: EncodingContext<B>,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned + Type,
+{
+    let signature = T::signature();
+
+    from_async_reader_for_signature(reader, ctxt, &signature).await
+}
+
+/// Deserialize `T` with the given signature from the given async `reader`.
+///
+/// This is the async equivalent of [`from_reader_for_signature`]. Since deserializers can't wait on
+/// the `reader`, the encoded value is read into memory before being deserialized. In the D-Bus
+/// format, only the bytes making up the value are read from the `reader` while in the GVariant
+/// format, the `reader` is read to the end.
+///
+/// This function is only available with the `async` feature enabled.
+///
+/// # Return value
+///
+/// A tuple containing the deserialized value and the number of bytes read from `reader`.
+///
+/// [`from_reader_for_signature`]: fn.from_reader_for_signature.html
+pub async fn from_async_reader_for_signature<'s, B, R, S, T>(
+    reader: &mut R,
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    R: AsyncRead + Unpin,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: DeserializeOwned,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+    let mut bytes = vec![];
+    match ctxt.format() {
+        EncodingFormat::DBus => {
+            let mut scanner = DBusValueScanner::new(ctxt, &signature)?;
+            while let Some(len) = scanner.next_read(&bytes)? {
+                let read = (&mut *reader)
+                    .take(len)
+                    .read_to_end(&mut bytes)
+                    .await
+                    .map_err(|e| Error::InputOutput(e.into()))?;
+                check_read_len(read, len)?;
+            }
+        }
+        #[cfg(feature = "gvariant")]
+        EncodingFormat::GVariant => {
+            // Reading one byte beyond the limit is enough for the deserializer to reject it.
+            (&mut *reader)
+                .take(ctxt.limits().max_size() as u64 + 1)
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(|e| Error::InputOutput(e.into()))?;
+        }
+    }
+
+    from_slice_for_signature(&bytes, ctxt, signature)
+}
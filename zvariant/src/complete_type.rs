@@ -28,7 +28,10 @@ impl<'a> TryFrom<Signature<'a>> for CompleteType<'a> {
 
     fn try_from(sig: Signature<'a>) -> Result<Self> {
         if sig.n_complete_types() != Ok(1) {
-            return Err(Error::IncorrectType);
+            return Err(Error::SignatureMismatch(
+                sig.to_owned(),
+                "a single complete type".to_string(),
+            ));
         }
         Ok(Self(sig))
     }
@@ -1,27 +1,25 @@
-use crate::{Error, MaxDepthExceeded, Result};
-
-// We take the limits from the D-Bus specification for gvariant as well.
-//
-// The GVariant specification removed all the limits, from the D-Bus specification but that turned
-// out to be a [mistake]. Although glib went for a higher limit (128) but we'll stick to the D-Bus
-// limits and expand if/when needed.
-//
-// [mistake]: https://gitlab.gnome.org/GNOME/glib/-/commit/7c4e6e9fbe473de0401c778c6b0c4aad27d5145a
-const MAX_STRUCT_DEPTH: u8 = 32;
-const MAX_ARRAY_DEPTH: u8 = 32;
-const MAX_TOTAL_DEPTH: u8 = 64;
+use crate::{Error, Limits, MaxDepthExceeded, Result};
 
 // Represents the current depth of all container being (de)serialized.
 #[derive(Debug, Default, Clone, Copy)]
 pub(crate) struct ContainerDepths {
-    structure: u8,
-    array: u8,
-    variant: u8,
+    // Wider than the limits, so incrementing a depth that is at its limit can't overflow.
+    structure: u16,
+    array: u16,
+    variant: u16,
     #[cfg(feature = "gvariant")]
-    maybe: u8,
+    maybe: u16,
+    limits: Limits,
 }
 
 impl ContainerDepths {
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
     pub fn inc_structure(mut self) -> Result<Self> {
         self.structure += 1;
         self.check()
@@ -60,11 +58,11 @@ impl ContainerDepths {
     }
 
     fn check(self) -> Result<Self> {
-        if self.structure > MAX_STRUCT_DEPTH {
+        if self.structure > u16::from(self.limits.max_struct_depth()) {
             return Err(Error::MaxDepthExceeded(MaxDepthExceeded::Structure));
         }
 
-        if self.array > MAX_ARRAY_DEPTH {
+        if self.array > u16::from(self.limits.max_array_depth()) {
             return Err(Error::MaxDepthExceeded(MaxDepthExceeded::Array));
         }
 
@@ -73,7 +71,7 @@ impl ContainerDepths {
         #[cfg(feature = "gvariant")]
         let total = self.structure + self.array + self.variant + self.maybe;
 
-        if total > MAX_TOTAL_DEPTH {
+        if total > u16::from(self.limits.max_container_depth()) {
             return Err(Error::MaxDepthExceeded(MaxDepthExceeded::Container));
         }
 
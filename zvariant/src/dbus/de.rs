@@ -7,12 +7,13 @@ use std::{marker::PhantomData, str};
 use std::os::unix::io::RawFd;
 
 use crate::{
-    de::ValueParseStage, signature_parser::SignatureParser, utils::*, Basic, EncodingContext,
-    EncodingFormat, Error, ObjectPath, Result, Signature,
+    container_depths::ContainerDepths, de::ValueParseStage, signature_parser::SignatureParser,
+    utils::*, Basic, EncodingContext, EncodingFormat, Error, MaxSizeExceeded, ObjectPath, Result,
+    Signature,
 };
 
 #[cfg(unix)]
-use crate::Fd;
+use crate::{fd::OWNED_FD_HANDOVER_NAME, Fd};
 
 /// Our D-Bus deserialization implementation.
 #[derive(Debug)]
@@ -38,6 +39,9 @@ where
         S::Error: Into<Error>,
     {
         assert_eq!(ctxt.format(), EncodingFormat::DBus);
+        if bytes.len() > ctxt.limits().max_size() {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Total));
+        }
 
         let signature = signature.try_into().map_err(Into::into)?;
         let sig_parser = SignatureParser::new(signature);
@@ -46,14 +50,25 @@ where
             sig_parser,
             bytes,
             #[cfg(unix)]
-            fds,
+            fds: crate::FdList::new(fds),
             #[cfg(not(unix))]
             fds: PhantomData,
             pos: 0,
-            container_depths: Default::default(),
+            value_start: 0,
+            container_depths: ContainerDepths::new(ctxt.limits()),
+            error_location: None,
             b: PhantomData,
         }))
     }
+
+    #[cfg(unix)]
+    fn parse_fd_index(&mut self) -> Result<u32> {
+        self.0.sig_parser.skip_char()?;
+        let alignment = u32::alignment(EncodingFormat::DBus);
+        self.0.parse_padding(alignment)?;
+
+        Ok(B::read_u32(self.0.next_slice(alignment)?))
+    }
 }
 
 macro_rules! deserialize_basic {
@@ -133,6 +148,27 @@ where
     deserialize_basic!(deserialize_u64 read_u64 visit_u64(u64));
     deserialize_basic!(deserialize_f64 read_f64 visit_f64(f64));
 
+    // Encoded as a `(tt)` structure of the high and low 64 bits.
+    #[cfg(feature = "int128")]
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let (high, low) = <(u64, u64) as serde::Deserialize>::deserialize(self)?;
+
+        visitor.visit_i128(((high as u128) << 64 | low as u128) as i128)
+    }
+
+    #[cfg(feature = "int128")]
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let (high, low) = <(u64, u64) as serde::Deserialize>::deserialize(self)?;
+
+        visitor.visit_u128((high as u128) << 64 | low as u128)
+    }
+
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
@@ -164,10 +200,7 @@ where
         let v = match self.0.sig_parser.next_char()? {
             #[cfg(unix)]
             Fd::SIGNATURE_CHAR => {
-                self.0.sig_parser.skip_char()?;
-                let alignment = u32::alignment(EncodingFormat::DBus);
-                self.0.parse_padding(alignment)?;
-                let idx = B::read_u32(self.0.next_slice(alignment)?);
+                let idx = self.parse_fd_index()?;
                 self.0.get_fd(idx)?
             }
             _ => B::read_i32(self.0.next_const_size_slice::<i32>()?),
@@ -199,6 +232,7 @@ where
     {
         let len = match self.0.sig_parser.next_char()? {
             Signature::SIGNATURE_CHAR | VARIANT_SIGNATURE_CHAR => {
+                self.0.value_starts_here();
                 let len_slice = self.0.next_slice(1)?;
 
                 len_slice[0] as usize
@@ -263,9 +297,11 @@ where
             }
         }
 
+        // Maybe types can only be decoded from arrays in D-Bus format.
         #[cfg(not(feature = "option-as-array"))]
-        Err(de::Error::custom(
-            "Can only decode Option<T> from D-Bus format if `option-as-array` feature is enabled",
+        Err(Error::IncompatibleFormat(
+            self.0.sig_parser.next_signature()?.to_owned(),
+            EncodingFormat::DBus,
         ))
     }
 
@@ -283,10 +319,27 @@ where
         visitor.visit_unit()
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        #[cfg(unix)]
+        if name == OWNED_FD_HANDOVER_NAME && self.0.sig_parser.next_char()? == Fd::SIGNATURE_CHAR {
+            let idx = self.parse_fd_index()?;
+
+            return self.0.deserialize_owned_fd(idx, visitor);
+        }
+        if let Some(element) = crate::fixed_array::fixed_array_element(name) {
+            let signature = self.0.sig_parser.next_signature()?;
+            crate::fixed_array::check_fixed_array_signature(&signature, element)?;
+            // Wider elements can only be borrowed if they're encoded in native byte order.
+            if element != u8::SIGNATURE_CHAR && !is_native_endian::<B>() {
+                return self.deserialize_seq(visitor);
+            }
+
+            return visitor.visit_borrowed_bytes(deserialize_fixed_basic_array(self)?);
+        }
+
         visitor.visit_newtype_struct(self)
     }
 
@@ -319,7 +372,7 @@ where
                 self.0.sig_parser.skip_char()?;
 
                 self.0.container_depths = self.0.container_depths.inc_structure()?;
-                let v = visitor.visit_seq(StructureDeserializer { de: self });
+                let v = visitor.visit_seq(StructureDeserializer { de: self, index: 0 });
                 self.0.container_depths = self.0.container_depths.dec_structure();
 
                 v
@@ -328,7 +381,7 @@ where
                 // Empty struct: encoded as a `0u8`.
                 let _: u8 = serde::Deserialize::deserialize(&mut *self)?;
 
-                visitor.visit_seq(StructureDeserializer { de: self })
+                visitor.visit_seq(StructureDeserializer { de: self, index: 0 })
             }
             c => Err(de::Error::invalid_type(
                 de::Unexpected::Char(c),
@@ -400,6 +453,10 @@ struct ArrayDeserializer<'d, 'de, 'sig, 'f, B> {
     element_alignment: usize,
     // where value signature starts
     element_signature_len: usize,
+    // index of the next element
+    index: usize,
+    // where the last element (or dict entry key) started
+    element_start: usize,
 }
 
 impl<'d, 'de, 'sig, 'f, B> ArrayDeserializer<'d, 'de, 'sig, 'f, B>
@@ -411,6 +468,9 @@ where
         de.0.container_depths = de.0.container_depths.inc_array()?;
 
         let len = B::read_u32(de.0.next_slice(4)?) as usize;
+        if len > de.0.ctxt.limits().max_array_size() {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Array));
+        }
         let element_signature = de.0.sig_parser.next_signature()?;
         let element_alignment = alignment_for_signature(&element_signature, EncodingFormat::DBus)?;
         let mut element_signature_len = element_signature.len();
@@ -431,6 +491,8 @@ where
             start,
             element_alignment,
             element_signature_len,
+            index: 0,
+            element_start: start,
         })
     }
 
@@ -438,19 +500,28 @@ where
     where
         T: DeserializeSeed<'de>,
     {
-        let ctxt = EncodingContext::new_dbus(self.de.0.ctxt.position() + self.de.0.pos);
+        let ctxt = self.de.0.ctxt.nested(
+            EncodingFormat::DBus,
+            self.de.0.ctxt.position() + self.de.0.pos,
+        );
 
+        self.element_start = self.de.0.pos;
         let mut de = Deserializer::<B>(crate::DeserializerCommon {
             ctxt,
             sig_parser,
             bytes: subslice(self.de.0.bytes, self.de.0.pos..)?,
             fds: self.de.0.fds,
             pos: 0,
+            value_start: 0,
             container_depths: self.de.0.container_depths,
+            error_location: None,
             b: PhantomData,
         });
         let v = seed.deserialize(&mut de);
         self.de.0.pos += de.0.pos;
+        if v.is_err() {
+            self.de.0.error_location = Some(de.0.take_error_location());
+        }
         // No need for retaking the container depths as the child can't be incomplete.
 
         if self.de.0.pos > self.start + self.len {
@@ -499,6 +570,14 @@ where
         return Err(de::Error::invalid_type(de::Unexpected::Seq, &"ay"));
     }
 
+    deserialize_fixed_basic_array(de)
+}
+
+// Deserialize an array of `FixedBasic` elements as its encoded bytes.
+fn deserialize_fixed_basic_array<'de, B>(de: &mut Deserializer<'de, '_, '_, B>) -> Result<&'de [u8]>
+where
+    B: byteorder::ByteOrder,
+{
     de.0.sig_parser.skip_char()?;
     let ad = ArrayDeserializer::new(de)?;
     let len = ad.len;
@@ -519,7 +598,13 @@ where
         T: DeserializeSeed<'de>,
     {
         let sig_parser = self.0.de.0.sig_parser.clone();
-        self.0.next_element(seed, sig_parser)
+        let v = self.0.next_element(seed, sig_parser);
+        if v.is_err() {
+            self.0.de.0.error_in_path(format!("[{}]", self.0.index));
+        }
+        self.0.index += 1;
+
+        v
     }
 }
 
@@ -544,15 +629,33 @@ where
         V: DeserializeSeed<'de>,
     {
         let mut sig_parser = self.0.de.0.sig_parser.clone();
+        let key_signature = sig_parser.next_char()?;
         // Skip key signature (always 1 char)
         sig_parser.skip_char()?;
-        self.0.next(seed, sig_parser)
+        let key_start = self.0.element_start;
+        let v = self.0.next(seed, sig_parser);
+        if v.is_err() {
+            let de = &mut self.0.de.0;
+            let ctxt = de
+                .ctxt
+                .nested(EncodingFormat::DBus, de.ctxt.position() + key_start);
+            let segment = crate::de::dict_value_path_segment(
+                subslice(de.bytes, key_start..)?,
+                ctxt,
+                key_signature,
+            );
+            de.error_in_path(segment);
+        }
+
+        v
     }
 }
 
 #[derive(Debug)]
 struct StructureDeserializer<'d, 'de, 'sig, 'f, B> {
     de: &'d mut Deserializer<'de, 'sig, 'f, B>,
+    // index of the next field
+    index: usize,
 }
 
 impl<'d, 'de, 'sig, 'f, B> SeqAccess<'de> for StructureDeserializer<'d, 'de, 'sig, 'f, B>
@@ -566,6 +669,10 @@ where
         T: DeserializeSeed<'de>,
     {
         let v = seed.deserialize(&mut *self.de).map(Some);
+        if v.is_err() {
+            self.de.0.error_in_path(format!(".{}", self.index));
+        }
+        self.index += 1;
 
         if self.de.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
             // Last item in the struct
@@ -627,7 +734,7 @@ where
                 let signature = Signature::try_from(slice)?;
                 let sig_parser = SignatureParser::new(signature);
 
-                let ctxt = EncodingContext::new(
+                let ctxt = self.de.0.ctxt.nested(
                     EncodingFormat::DBus,
                     self.de.0.ctxt.position() + value_start,
                 );
@@ -637,12 +744,17 @@ where
                     bytes: subslice(self.de.0.bytes, value_start..)?,
                     fds: self.de.0.fds,
                     pos: 0,
+                    value_start: 0,
                     container_depths: self.de.0.container_depths.inc_variant()?,
+                    error_location: None,
                     b: PhantomData,
                 });
 
                 let v = seed.deserialize(&mut de).map(Some);
                 self.de.0.pos += de.0.pos;
+                if v.is_err() {
+                    self.de.0.error_location = Some(de.0.take_error_location());
+                }
 
                 v
             }
@@ -1,4 +1,6 @@
 mod de;
 pub use de::*;
+mod reader;
+pub(crate) use reader::*;
 mod ser;
 pub use ser::*;
@@ -0,0 +1,745 @@
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+
+use std::{io::Read, mem, str};
+
+#[cfg(unix)]
+use crate::Fd;
+use crate::{
+    container_depths::ContainerDepths, reader::check_read_len, signature_parser::SignatureParser,
+    utils::*, Basic, EncodingContext, EncodingFormat, Error, MaxSizeExceeded, ObjectPath, Result,
+    Signature,
+};
+
+/// Our D-Bus deserialization implementation reading from an [`io::Read`].
+///
+/// Unlike [`Deserializer`], the value is deserialized as it's read and only the encoding of the
+/// basic value being deserialized (e.g a string) is kept in memory. Since the data doesn't outlive
+/// the read, nothing can be borrowed from it.
+///
+/// [`io::Read`]: std::io::Read
+/// [`Deserializer`]: struct.Deserializer.html
+pub(crate) struct ReaderDeserializer<'r, B, R> {
+    ctxt: EncodingContext<B>,
+    reader: &'r mut R,
+    // The number of bytes read so far.
+    pos: usize,
+    sig_parser: SignatureParser<'static>,
+    container_depths: ContainerDepths,
+    // The bytes last read.
+    buf: Vec<u8>,
+}
+
+impl<'r, B, R> ReaderDeserializer<'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    pub(crate) fn new(
+        reader: &'r mut R,
+        signature: Signature<'_>,
+        ctxt: EncodingContext<B>,
+    ) -> Self {
+        assert_eq!(ctxt.format(), EncodingFormat::DBus);
+
+        Self {
+            ctxt,
+            reader,
+            pos: 0,
+            sig_parser: SignatureParser::new(signature.to_owned()),
+            container_depths: ContainerDepths::new(ctxt.limits()),
+            buf: vec![],
+        }
+    }
+
+    /// The number of bytes read so far.
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    // Read the next `len` bytes into `buf`.
+    fn read(&mut self, len: usize) -> Result<()> {
+        if self.pos + len > self.ctxt.limits().max_size() {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Total));
+        }
+
+        self.buf.clear();
+        // Not allocating `len` bytes upfront, so a bogus length can't exhaust the memory before
+        // the data is actually there.
+        let read = (&mut *self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.buf)
+            .map_err(|e| Error::InputOutput(e.into()))?;
+        check_read_len(read, len as u64)?;
+        self.pos += len;
+
+        Ok(())
+    }
+
+    fn parse_padding(&mut self, alignment: usize) -> Result<usize> {
+        let padding = padding_for_n_bytes(self.ctxt.position() + self.pos, alignment);
+        if padding > 0 {
+            self.read(padding)?;
+            if let Some(byte) = self.buf.iter().find(|b| **b != 0) {
+                return Err(Error::PaddingNot0(*byte));
+            }
+        }
+
+        Ok(padding)
+    }
+
+    // Read the next basic value into `buf`.
+    fn read_basic<T>(&mut self) -> Result<()>
+    where
+        T: Basic,
+    {
+        self.sig_parser.skip_char()?;
+        let alignment = T::alignment(EncodingFormat::DBus);
+        self.parse_padding(alignment)?;
+
+        self.read(alignment)
+    }
+
+    // Read the next array of `FixedBasic` elements into `buf`.
+    fn read_fixed_basic_array(&mut self) -> Result<()> {
+        self.sig_parser.skip_char()?;
+        let len = ArrayDeserializer::new(self)?.len;
+        self.sig_parser.skip_char()?;
+        self.container_depths = self.container_depths.dec_array();
+
+        self.read(len)
+    }
+}
+
+macro_rules! deserialize_basic {
+    ($method:ident $read_method:ident $visitor_method:ident($type:ty)) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value>
+        where
+            V: Visitor<'de>,
+        {
+            self.read_basic::<$type>()?;
+
+            visitor.$visitor_method(B::$read_method(&self.buf))
+        }
+    };
+}
+
+macro_rules! deserialize_as {
+    ($method:ident => $as:ident) => {
+        deserialize_as!($method() => $as());
+    };
+    ($method:ident($($in_arg:ident: $type:ty),*) => $as:ident($($as_arg:expr),*)) => {
+        #[inline]
+        fn $method<V>(self, $($in_arg: $type,)* visitor: V) -> Result<V::Value>
+        where
+            V: Visitor<'de>,
+        {
+            self.$as($($as_arg,)* visitor)
+        }
+    }
+}
+
+impl<'de, 'd, 'r, B, R> de::Deserializer<'de> for &'d mut ReaderDeserializer<'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let c = self.sig_parser.next_char()?;
+
+        crate::de::deserialize_any::<Self, V>(self, c, visitor)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.read_basic::<bool>()?;
+        let v = B::read_u32(&self.buf);
+        let b = match v {
+            1 => true,
+            0 => false,
+            // As per D-Bus spec, only 0 and 1 values are allowed
+            _ => {
+                return Err(de::Error::invalid_value(
+                    de::Unexpected::Unsigned(v as u64),
+                    &"0 or 1",
+                ))
+            }
+        };
+
+        visitor.visit_bool(b)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i16(visitor)
+    }
+
+    deserialize_basic!(deserialize_i16 read_i16 visit_i16(i16));
+    deserialize_basic!(deserialize_i64 read_i64 visit_i64(i64));
+    deserialize_basic!(deserialize_u16 read_u16 visit_u16(u16));
+    deserialize_basic!(deserialize_u32 read_u32 visit_u32(u32));
+    deserialize_basic!(deserialize_u64 read_u64 visit_u64(u64));
+    deserialize_basic!(deserialize_f64 read_f64 visit_f64(f64));
+
+    // Encoded as a `(tt)` structure of the high and low 64 bits.
+    #[cfg(feature = "int128")]
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let (high, low) = <(u64, u64) as serde::Deserialize>::deserialize(self)?;
+
+        visitor.visit_i128(((high as u128) << 64 | low as u128) as i128)
+    }
+
+    #[cfg(feature = "int128")]
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let (high, low) = <(u64, u64) as serde::Deserialize>::deserialize(self)?;
+
+        visitor.visit_u128((high as u128) << 64 | low as u128)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.sig_parser.next_signature()? != "ay" {
+            return Err(de::Error::invalid_type(de::Unexpected::Seq, &"ay"));
+        }
+        self.read_fixed_basic_array()?;
+
+        visitor.visit_bytes(&self.buf)
+    }
+
+    deserialize_as!(deserialize_char => deserialize_str);
+    deserialize_as!(deserialize_string => deserialize_str);
+    deserialize_as!(deserialize_tuple(_l: usize) => deserialize_struct("", &[]));
+    deserialize_as!(deserialize_tuple_struct(n: &'static str, _l: usize) => deserialize_struct(n, &[]));
+    deserialize_as!(deserialize_struct(_n: &'static str, _f: &'static [&'static str]) => deserialize_seq());
+    deserialize_as!(deserialize_map => deserialize_seq);
+    deserialize_as!(deserialize_ignored_any => deserialize_any);
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // File descriptors are passed out-of-band, so there are none along with a reader.
+        #[cfg(unix)]
+        if self.sig_parser.next_char()? == Fd::SIGNATURE_CHAR {
+            return Err(Error::UnknownFd);
+        }
+        self.read_basic::<i32>()?;
+
+        visitor.visit_i32(B::read_i32(&self.buf))
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.read_basic::<u8>()?;
+
+        visitor.visit_u8(self.buf[0])
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.read_basic::<f64>()?;
+
+        visitor.visit_f32(f64_to_f32(B::read_f64(&self.buf)))
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let len = match self.sig_parser.next_char()? {
+            Signature::SIGNATURE_CHAR | VARIANT_SIGNATURE_CHAR => {
+                self.read(1)?;
+
+                self.buf[0] as usize
+            }
+            <&str>::SIGNATURE_CHAR | ObjectPath::SIGNATURE_CHAR => {
+                let alignment = u32::alignment(EncodingFormat::DBus);
+                self.parse_padding(alignment)?;
+                self.read(alignment)?;
+
+                B::read_u32(&self.buf) as usize
+            }
+            c => {
+                let expected = format!(
+                    "`{}`, `{}`, `{}` or `{}`",
+                    <&str>::SIGNATURE_STR,
+                    Signature::SIGNATURE_STR,
+                    ObjectPath::SIGNATURE_STR,
+                    VARIANT_SIGNATURE_CHAR,
+                );
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Char(c),
+                    &expected.as_str(),
+                ));
+            }
+        };
+        // Including the trailing null byte.
+        self.read(len + 1)?;
+        let slice = &self.buf[..len];
+        if slice.contains(&0) {
+            return Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Char('\0'),
+                &"D-Bus string type must not contain interior null bytes",
+            ));
+        }
+        let s = str::from_utf8(slice).map_err(Error::Utf8)?;
+        self.sig_parser.skip_char()?;
+
+        visitor.visit_str(s)
+    }
+
+    fn deserialize_option<V>(self, #[allow(unused)] visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        #[cfg(feature = "option-as-array")]
+        {
+            let c = self.sig_parser.next_char()?;
+            if c != ARRAY_SIGNATURE_CHAR {
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Char(c),
+                    &ARRAY_SIGNATURE_STR,
+                ));
+            }
+            self.sig_parser.skip_char()?;
+            // This takes care of parsing all the padding and getting the byte length.
+            let len = ArrayDeserializer::new(self)?.len;
+            let v = if len == 0 {
+                self.sig_parser.parse_next_signature()?;
+
+                visitor.visit_none()
+            } else {
+                visitor.visit_some(&mut *self)
+            };
+            self.container_depths = self.container_depths.dec_array();
+
+            v
+        }
+
+        // Maybe types can only be decoded from arrays in D-Bus format.
+        #[cfg(not(feature = "option-as-array"))]
+        Err(Error::IncompatibleFormat(
+            self.sig_parser.next_signature()?.to_owned(),
+            EncodingFormat::DBus,
+        ))
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(element) = crate::fixed_array::fixed_array_element(name) {
+            let signature = self.sig_parser.next_signature()?;
+            crate::fixed_array::check_fixed_array_signature(&signature, element)?;
+
+            // There is nothing to borrow from.
+            return self.deserialize_seq(visitor);
+        }
+
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.sig_parser.next_char()? {
+            VARIANT_SIGNATURE_CHAR => visitor.visit_seq(ValueDeserializer {
+                de: self,
+                signature: None,
+            }),
+            ARRAY_SIGNATURE_CHAR => {
+                self.sig_parser.skip_char()?;
+                let next_signature_char = self.sig_parser.next_char()?;
+                let array_de = ArrayDeserializer::new(self)?;
+
+                if next_signature_char == DICT_ENTRY_SIG_START_CHAR {
+                    visitor.visit_map(ArrayMapDeserializer(array_de))
+                } else {
+                    visitor.visit_seq(ArraySeqDeserializer(array_de))
+                }
+            }
+            STRUCT_SIG_START_CHAR => {
+                let signature = self.sig_parser.next_signature()?;
+                let alignment = alignment_for_signature(&signature, EncodingFormat::DBus)?;
+                self.parse_padding(alignment)?;
+
+                self.sig_parser.skip_char()?;
+
+                self.container_depths = self.container_depths.inc_structure()?;
+                let v = visitor.visit_seq(StructureDeserializer { de: self });
+                self.container_depths = self.container_depths.dec_structure();
+
+                v
+            }
+            u8::SIGNATURE_CHAR => {
+                // Empty struct: encoded as a `0u8`.
+                let _: u8 = serde::Deserialize::deserialize(&mut *self)?;
+
+                visitor.visit_seq(StructureDeserializer { de: self })
+            }
+            c => Err(de::Error::invalid_type(
+                de::Unexpected::Char(c),
+                &format!(
+                    "`{VARIANT_SIGNATURE_CHAR}`, `{ARRAY_SIGNATURE_CHAR}` or `{STRUCT_SIG_START_CHAR}`",
+                )
+                .as_str(),
+            )),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let signature = self.sig_parser.next_signature()?;
+        let alignment = alignment_for_signature(&signature, EncodingFormat::DBus)?;
+        self.parse_padding(alignment)?;
+
+        let non_unit = if self.sig_parser.next_char()? == STRUCT_SIG_START_CHAR {
+            // This means we've a non-unit enum. Let's skip the `(`.
+            self.sig_parser.skip_char()?;
+
+            true
+        } else {
+            false
+        };
+
+        let v = visitor.visit_enum(Enum { de: &mut *self })?;
+
+        if non_unit {
+            // For non-unit enum, we need to skip the closing paren.
+            self.sig_parser.skip_char()?;
+        }
+
+        Ok(v)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.sig_parser.next_char()? == <&str>::SIGNATURE_CHAR {
+            self.deserialize_str(visitor)
+        } else {
+            self.deserialize_u32(visitor)
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct ArrayDeserializer<'d, 'r, B, R> {
+    de: &'d mut ReaderDeserializer<'r, B, R>,
+    len: usize,
+    start: usize,
+    // alignment of element
+    element_alignment: usize,
+    // where value signature starts
+    element_signature_len: usize,
+}
+
+impl<'d, 'r, B, R> ArrayDeserializer<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    fn new(de: &'d mut ReaderDeserializer<'r, B, R>) -> Result<Self> {
+        de.parse_padding(ARRAY_ALIGNMENT_DBUS)?;
+        de.container_depths = de.container_depths.inc_array()?;
+
+        de.read(4)?;
+        let len = B::read_u32(&de.buf) as usize;
+        if len > de.ctxt.limits().max_array_size() {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Array));
+        }
+        let element_signature = de.sig_parser.next_signature()?;
+        let element_alignment = alignment_for_signature(&element_signature, EncodingFormat::DBus)?;
+        let mut element_signature_len = element_signature.len();
+
+        // D-Bus requires padding for the first element even when there is no first element
+        // (i-e empty array) so we parse padding already.
+        de.parse_padding(element_alignment)?;
+        let start = de.pos;
+
+        if de.sig_parser.next_char()? == DICT_ENTRY_SIG_START_CHAR {
+            de.sig_parser.skip_char()?;
+            element_signature_len -= 1;
+        }
+
+        Ok(Self {
+            de,
+            len,
+            start,
+            element_alignment,
+            element_signature_len,
+        })
+    }
+
+    fn next<'de, T>(&mut self, seed: T, sig_parser: SignatureParser<'static>) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let sig_parser = mem::replace(&mut self.de.sig_parser, sig_parser);
+        let container_depths = self.de.container_depths;
+        let v = seed.deserialize(&mut *self.de);
+        self.de.sig_parser = sig_parser;
+        self.de.container_depths = container_depths;
+
+        if self.de.pos > self.start + self.len {
+            return Err(serde::de::Error::invalid_length(
+                self.len,
+                &format!(">= {}", self.de.pos - self.start).as_str(),
+            ));
+        }
+
+        v
+    }
+
+    fn next_element<'de, T>(
+        &mut self,
+        seed: T,
+        sig_parser: SignatureParser<'static>,
+    ) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.done() {
+            self.de.sig_parser.skip_chars(self.element_signature_len)?;
+            self.de.container_depths = self.de.container_depths.dec_array();
+
+            return Ok(None);
+        }
+
+        self.de.parse_padding(self.element_alignment)?;
+
+        self.next(seed, sig_parser).map(Some)
+    }
+
+    fn done(&self) -> bool {
+        self.de.pos == self.start + self.len
+    }
+}
+
+struct ArraySeqDeserializer<'d, 'r, B, R>(ArrayDeserializer<'d, 'r, B, R>);
+
+impl<'de, 'd, 'r, B, R> SeqAccess<'de> for ArraySeqDeserializer<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let sig_parser = self.0.de.sig_parser.clone();
+        self.0.next_element(seed, sig_parser)
+    }
+}
+
+struct ArrayMapDeserializer<'d, 'r, B, R>(ArrayDeserializer<'d, 'r, B, R>);
+
+impl<'de, 'd, 'r, B, R> MapAccess<'de> for ArrayMapDeserializer<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let sig_parser = self.0.de.sig_parser.clone();
+        self.0.next_element(seed, sig_parser)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let mut sig_parser = self.0.de.sig_parser.clone();
+        // Skip key signature (always 1 char)
+        sig_parser.skip_char()?;
+        self.0.next(seed, sig_parser)
+    }
+}
+
+struct StructureDeserializer<'d, 'r, B, R> {
+    de: &'d mut ReaderDeserializer<'r, B, R>,
+}
+
+impl<'de, 'd, 'r, B, R> SeqAccess<'de> for StructureDeserializer<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let v = seed.deserialize(&mut *self.de).map(Some);
+
+        if self.de.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
+            // Last item in the struct
+            self.de.sig_parser.skip_char()?;
+        }
+
+        v
+    }
+}
+
+struct ValueDeserializer<'d, 'r, B, R> {
+    de: &'d mut ReaderDeserializer<'r, B, R>,
+    // The signature of the value, once read.
+    signature: Option<Signature<'static>>,
+}
+
+impl<'de, 'd, 'r, B, R> SeqAccess<'de> for ValueDeserializer<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.signature.take() {
+            None => {
+                self.de.read(1)?;
+                let len = self.de.buf[0] as usize;
+                // Including the trailing null byte.
+                self.de.read(len + 1)?;
+                let signature = str::from_utf8(&self.de.buf[..len]).map_err(Error::Utf8)?;
+                let signature = Signature::try_from(signature)?.to_owned();
+                self.de.sig_parser.skip_char()?;
+
+                let v = seed.deserialize(signature.as_str().into_deserializer());
+                self.signature = Some(signature);
+
+                v.map(Some)
+            }
+            Some(signature) => {
+                let sig_parser =
+                    mem::replace(&mut self.de.sig_parser, SignatureParser::new(signature));
+                let container_depths = self.de.container_depths;
+                self.de.container_depths = container_depths.inc_variant()?;
+                let v = seed.deserialize(&mut *self.de);
+                self.de.sig_parser = sig_parser;
+                self.de.container_depths = container_depths;
+
+                v.map(Some)
+            }
+        }
+    }
+}
+
+struct Enum<'d, 'r, B, R> {
+    de: &'d mut ReaderDeserializer<'r, B, R>,
+}
+
+impl<'de, 'd, 'r, B, R> EnumAccess<'de> for Enum<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de).map(|v| (v, self))
+    }
+}
+
+impl<'de, 'd, 'r, B, R> VariantAccess<'de> for Enum<'d, 'r, B, R>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+{
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self.de, "", &[], visitor)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self.de, "", fields, visitor)
+    }
+}
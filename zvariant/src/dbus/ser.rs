@@ -53,7 +53,7 @@ where
             fds,
             bytes_written: 0,
             value_sign: None,
-            container_depths: Default::default(),
+            container_depths: ContainerDepths::new(ctxt.limits()),
             b: PhantomData,
         }))
     }
@@ -126,6 +126,17 @@ where
     serialize_basic!(serialize_f32(f32) write_f64(f64));
     serialize_basic!(serialize_f64(f64) write_f64);
 
+    // Encoded as a `(tt)` structure of the high and low 64 bits.
+    #[cfg(feature = "int128")]
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        self.serialize_u128(v as u128)
+    }
+
+    #[cfg(feature = "int128")]
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        ((v >> 64) as u64, v as u64).serialize(self)
+    }
+
     fn serialize_char(self, v: char) -> Result<()> {
         // No char type in D-Bus, let's pretend it's a string
         self.serialize_str(&v.to_string())
@@ -198,10 +209,12 @@ where
             seq.end()
         }
 
+        // Maybe types can only be encoded as arrays in D-Bus format.
         #[cfg(not(feature = "option-as-array"))]
-        unreachable!(
-            "Can only encode Option<T> in D-Bus format if `option-as-array` feature is enabled",
-        );
+        Err(Error::IncompatibleFormat(
+            self.0.sig_parser.next_signature()?.to_owned(),
+            EncodingFormat::DBus,
+        ))
     }
 
     fn serialize_some<T>(self, #[allow(unused)] value: &T) -> Result<()>
@@ -215,10 +228,12 @@ where
             seq.end()
         }
 
+        // Maybe types can only be encoded as arrays in D-Bus format.
         #[cfg(not(feature = "option-as-array"))]
-        unreachable!(
-            "Can only encode Option<T> in D-Bus format if `option-as-array` feature is enabled",
-        );
+        Err(Error::IncompatibleFormat(
+            self.0.sig_parser.next_signature()?.to_owned(),
+            EncodingFormat::DBus,
+        ))
     }
 
     fn serialize_unit(self) -> Result<()> {
@@ -7,14 +7,17 @@ use static_assertions::assert_impl_all;
 use std::{marker::PhantomData, str};
 
 #[cfg(unix)]
-use std::os::unix::io::RawFd;
+use std::{
+    os::unix::io::{AsRawFd, BorrowedFd, IntoRawFd, OwnedFd as StdOwnedFd, RawFd},
+    sync::{Mutex, PoisonError},
+};
 
 #[cfg(feature = "gvariant")]
 use crate::gvariant::Deserializer as GVDeserializer;
 use crate::{
     container_depths::ContainerDepths, dbus::Deserializer as DBusDeserializer,
     signature_parser::SignatureParser, utils::*, Basic, DynamicDeserialize, DynamicType,
-    EncodingContext, EncodingFormat, Error, ObjectPath, Result, Signature, Type,
+    EncodingContext, EncodingFormat, Error, LocatedError, ObjectPath, Result, Signature, Type,
 };
 
 #[cfg(unix)]
@@ -59,6 +62,84 @@ where
     from_slice_fds_for_signature(bytes, fds, ctxt, &signature)
 }
 
+/// Deserialize `T` from a given slice of bytes containing file descriptor indices, taking the
+/// ownership of the file descriptors.
+///
+/// Unlike with [`from_slice_fds`], the [`OwnedFd`]s in `T` take over the file descriptors they
+/// refer to, instead of duplicating them. If multiple `OwnedFd`s refer to the same file
+/// descriptor, all but the first get a duplicate. File descriptors that no `OwnedFd` refers to
+/// are closed once the deserialization is done.
+///
+/// This function is not available on Windows.
+///
+/// # Examples
+///
+/// ```
+/// use std::os::unix::io::{AsRawFd, OwnedFd as StdOwnedFd};
+/// use zvariant::{from_slice_owned_fds, to_bytes_fds, EncodingContext, Fd, OwnedFd};
+///
+/// let file = std::fs::File::open("Cargo.toml").unwrap();
+/// let raw_fd = file.as_raw_fd();
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let (encoded, _) = to_bytes_fds(ctxt, &Fd::from(&file)).unwrap();
+///
+/// // E.g the file descriptors received along with a message.
+/// let fds = vec![StdOwnedFd::from(file)];
+/// let decoded: OwnedFd = from_slice_owned_fds(&encoded, fds, ctxt).unwrap().0;
+/// assert_eq!(decoded.as_raw_fd(), raw_fd);
+/// ```
+///
+/// # Return value
+///
+/// A tuple containing the deserialized value and the number of bytes parsed from `bytes`.
+///
+/// [`from_slice_fds`]: fn.from_slice_fds.html
+/// [`OwnedFd`]: struct.OwnedFd.html
+#[cfg(unix)]
+pub fn from_slice_owned_fds<'d, 'r: 'd, B, T>(
+    bytes: &'r [u8],
+    fds: Vec<StdOwnedFd>,
+    ctxt: EncodingContext<B>,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    T: Deserialize<'d> + Type,
+{
+    let signature = T::signature();
+    from_slice_owned_fds_for_signature(bytes, fds, ctxt, &signature)
+}
+
+/// Deserialize `T` from a given slice of bytes containing file descriptor indices, with the given
+/// signature, taking the ownership of the file descriptors.
+///
+/// See [`from_slice_owned_fds`] for details.
+///
+/// This function is not available on Windows.
+///
+/// [`from_slice_owned_fds`]: fn.from_slice_owned_fds.html
+#[cfg(unix)]
+pub fn from_slice_owned_fds_for_signature<'d, 'r: 'd, B, S, T>(
+    bytes: &'r [u8],
+    fds: Vec<StdOwnedFd>,
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    T: Deserialize<'d>,
+    S: TryInto<Signature<'d>>,
+    S::Error: Into<Error>,
+{
+    let raw_fds: Vec<_> = fds.iter().map(AsRawFd::as_raw_fd).collect();
+    let owned_fds = Mutex::new(fds.into_iter().map(Some).collect());
+    let fds = FdList {
+        raw: Some(&raw_fds),
+        owned: Some(&owned_fds),
+    };
+
+    _from_slice_fds_for_signature(bytes, fds, ctxt, signature).map_err(Into::into)
+}
+
 /// Deserialize `T` from a given slice of bytes.
 ///
 /// If `T` is an, or (potentially) contains an [`Fd`], use [`from_slice_fds`] instead.
@@ -177,12 +258,250 @@ where
     _from_slice_fds_for_signature(
         bytes,
         #[cfg(unix)]
-        None,
+        FdList::default(),
+        ctxt,
+        signature,
+    )
+    .map_err(Into::into)
+}
+
+/// Same as [`from_slice`], except that the error tells where the deserialization failed.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{from_slice_located, to_bytes, EncodingContext, Value};
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let props = HashMap::from([("Devices", Value::from(vec![(true, "lo"), (false, "eth0")]))]);
+/// let mut encoded = to_bytes(ctxt, &props).unwrap();
+/// // Corrupt the boolean of the second device.
+/// encoded[48] = 2;
+///
+/// let err = from_slice_located::<_, HashMap<&str, Value<'_>>>(&encoded, ctxt).unwrap_err();
+/// assert_eq!(err.path(), r#"["Devices"][1].0"#);
+/// assert_eq!(err.offset(), 48);
+/// ```
+///
+/// [`from_slice`]: fn.from_slice.html
+pub fn from_slice_located<'d, 'r: 'd, B, T>(
+    bytes: &'r [u8],
+    ctxt: EncodingContext<B>,
+) -> std::result::Result<(T, usize), LocatedError>
+where
+    B: byteorder::ByteOrder,
+    T: Deserialize<'d> + Type,
+{
+    let signature = T::signature();
+    from_slice_for_signature_located(bytes, ctxt, &signature)
+}
+
+/// Same as [`from_slice_for_signature`], except that the error tells where the deserialization
+/// failed.
+///
+/// [`from_slice_for_signature`]: fn.from_slice_for_signature.html
+pub fn from_slice_for_signature_located<'d, 'r: 'd, B, S, T>(
+    bytes: &'r [u8],
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> std::result::Result<(T, usize), LocatedError>
+where
+    B: byteorder::ByteOrder,
+    T: Deserialize<'d>,
+    S: TryInto<Signature<'d>>,
+    S::Error: Into<Error>,
+{
+    _from_slice_fds_for_signature(
+        bytes,
+        #[cfg(unix)]
+        FdList::default(),
         ctxt,
         signature,
     )
 }
 
+/// Same as [`from_slice_fds_for_signature`], except that the error tells where the
+/// deserialization failed.
+///
+/// This function is not available on Windows.
+///
+/// [`from_slice_fds_for_signature`]: fn.from_slice_fds_for_signature.html
+#[cfg(unix)]
+pub fn from_slice_fds_for_signature_located<'d, 'r: 'd, B, S, T>(
+    bytes: &'r [u8],
+    fds: Option<&[RawFd]>,
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> std::result::Result<(T, usize), LocatedError>
+where
+    B: byteorder::ByteOrder,
+    T: Deserialize<'d>,
+    S: TryInto<Signature<'d>>,
+    S::Error: Into<Error>,
+{
+    _from_slice_fds_for_signature(bytes, FdList::new(fds), ctxt, signature)
+}
+
+/// Deserialize `T` from a given slice of bytes, after checking that its signature is compatible
+/// with the given one.
+///
+/// Unlike [`from_slice_for_signature`], which trusts the caller that `signature` is one `T` can be
+/// deserialized from, this function first compares it against `T::signature()`. Just as with
+/// [`DynamicDeserialize`], a structure signature is considered compatible with the signature of
+/// its fields (e.g `(su)` and `su`). If the signatures are incompatible,
+/// [`Error::SignatureMismatch`] is returned, with details on where exactly they diverge, without
+/// attempting to deserialize anything.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{from_slice_with_signature, to_bytes, EncodingContext};
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &("hello", 42u32)).unwrap();
+///
+/// let decoded: (String, u32) = from_slice_with_signature(&encoded, ctxt, "(su)").unwrap().0;
+/// assert_eq!(decoded, ("hello".to_string(), 42));
+///
+/// let err = from_slice_with_signature::<_, _, (String, i32)>(&encoded, ctxt, "(su)").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Signature mismatch: got `(su)`, expected `(si)` (differing at character 2, in field 1)",
+/// );
+/// ```
+///
+/// # Return value
+///
+/// A tuple containing the deserialized value and the number of bytes parsed from `bytes`.
+///
+/// [`from_slice_for_signature`]: fn.from_slice_for_signature.html
+pub fn from_slice_with_signature<'d, 'r: 'd, B, S, T>(
+    bytes: &'r [u8],
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    T: Deserialize<'d> + Type,
+    S: TryInto<Signature<'d>>,
+    S::Error: Into<Error>,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+    let expected = T::signature();
+    check_signature_compatibility(&expected, &signature)?;
+
+    // `signature` may lack the parentheses of a structure, which the deserializer needs.
+    from_slice_for_signature(bytes, ctxt, expected)
+}
+
+// Check if data encoded with the `actual` signature can be decoded as the `expected` signature.
+fn check_signature_compatibility(expected: &Signature<'_>, actual: &Signature<'_>) -> Result<()> {
+    if expected == actual {
+        return Ok(());
+    }
+
+    let mut unwrapped_actual = actual.as_ref();
+    let mut unwrapped_expected = expected.as_ref();
+    while unwrapped_expected.len() < unwrapped_actual.len()
+        && unwrapped_actual.starts_with(STRUCT_SIG_START_CHAR)
+        && unwrapped_actual.ends_with(STRUCT_SIG_END_CHAR)
+    {
+        unwrapped_actual = unwrapped_actual.slice(1..unwrapped_actual.len() - 1);
+    }
+    while unwrapped_actual.len() < unwrapped_expected.len()
+        && unwrapped_expected.starts_with(STRUCT_SIG_START_CHAR)
+        && unwrapped_expected.ends_with(STRUCT_SIG_END_CHAR)
+    {
+        unwrapped_expected = unwrapped_expected.slice(1..unwrapped_expected.len() - 1);
+    }
+    if unwrapped_expected == unwrapped_actual {
+        return Ok(());
+    }
+
+    let pos = expected
+        .as_bytes()
+        .iter()
+        .zip(actual.as_bytes())
+        .position(|(e, a)| e != a)
+        .unwrap_or_else(|| expected.len().min(actual.len()));
+    let location = signature_location(&expected.as_bytes()[..pos]);
+    let location = if location.is_empty() {
+        String::new()
+    } else {
+        format!(", in {location}")
+    };
+
+    Err(Error::SignatureMismatch(
+        actual.to_owned(),
+        format!("`{expected}` (differing at character {pos}{location})"),
+    ))
+}
+
+// Describe the location in a signature right after the given (possibly incomplete) prefix of it.
+fn signature_location(prefix: &[u8]) -> String {
+    // The container being walked and the index of the current child type in it.
+    let mut containers: Vec<(char, usize)> = vec![];
+    let mut top_level = 0;
+    for c in prefix.iter().map(|b| *b as char) {
+        let completed = match c {
+            ARRAY_SIGNATURE_CHAR | STRUCT_SIG_START_CHAR | DICT_ENTRY_SIG_START_CHAR => {
+                containers.push((c, 0));
+                false
+            }
+            #[cfg(feature = "gvariant")]
+            MAYBE_SIGNATURE_CHAR => {
+                containers.push((c, 0));
+                false
+            }
+            STRUCT_SIG_END_CHAR | DICT_ENTRY_SIG_END_CHAR => {
+                containers.pop();
+                true
+            }
+            _ => true,
+        };
+        if !completed {
+            continue;
+        }
+
+        // A complete type also completes all the arrays (and maybes) it's the element type of.
+        loop {
+            match containers.last_mut() {
+                Some((ARRAY_SIGNATURE_CHAR, _)) => {
+                    containers.pop();
+                }
+                #[cfg(feature = "gvariant")]
+                Some((MAYBE_SIGNATURE_CHAR, _)) => {
+                    containers.pop();
+                }
+                Some((_, i)) => {
+                    *i += 1;
+                    break;
+                }
+                None => {
+                    top_level += 1;
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut location = vec![];
+    if top_level > 0 {
+        location.push(format!("field {top_level}"));
+    }
+    location.extend(containers.iter().map(|(c, i)| match (*c, i) {
+        (ARRAY_SIGNATURE_CHAR, _) => "array element".to_string(),
+        (DICT_ENTRY_SIG_START_CHAR, 0) => "dict key".to_string(),
+        (DICT_ENTRY_SIG_START_CHAR, _) => "dict value".to_string(),
+        (STRUCT_SIG_START_CHAR, i) => format!("field {i}"),
+        _ => "maybe value".to_string(),
+    }));
+    location.reverse();
+
+    location.join(" of ")
+}
+
 /// Deserialize `T` from a given slice of bytes containing file descriptor indices, with the given
 /// signature.
 ///
@@ -211,48 +530,68 @@ where
     S: TryInto<Signature<'d>>,
     S::Error: Into<Error>,
 {
-    _from_slice_fds_for_signature(bytes, fds, ctxt, signature)
+    _from_slice_fds_for_signature(bytes, FdList::new(fds), ctxt, signature).map_err(Into::into)
 }
 
 fn _from_slice_fds_for_signature<'d, 'r: 'd, B, S, T: ?Sized>(
     bytes: &'r [u8],
-    #[cfg(unix)] fds: Option<&[RawFd]>,
+    #[cfg(unix)] fds: FdList<'_>,
     ctxt: EncodingContext<B>,
     signature: S,
-) -> Result<(T, usize)>
+) -> std::result::Result<(T, usize), LocatedError>
 where
     B: byteorder::ByteOrder,
     T: Deserialize<'d>,
     S: TryInto<Signature<'d>>,
     S::Error: Into<Error>,
 {
-    let signature = signature.try_into().map_err(Into::into)?;
+    let before_data = |e: Error| LocatedError::new(e, 0, String::new());
+    let signature = signature.try_into().map_err(|e| before_data(e.into()))?;
 
     let mut de = match ctxt.format() {
         #[cfg(feature = "gvariant")]
         EncodingFormat::GVariant => GVDeserializer::new(
             bytes,
             #[cfg(unix)]
-            fds,
+            fds.raw,
             signature,
             ctxt,
         )
-        .map(Deserializer::GVariant)?,
+        .map(Deserializer::GVariant)
+        .map_err(before_data)?,
         EncodingFormat::DBus => DBusDeserializer::new(
             bytes,
             #[cfg(unix)]
-            fds,
+            fds.raw,
             signature,
             ctxt,
         )
-        .map(Deserializer::DBus)?,
+        .map(Deserializer::DBus)
+        .map_err(before_data)?,
     };
+    #[cfg(unix)]
+    match &mut de {
+        #[cfg(feature = "gvariant")]
+        Deserializer::GVariant(de) => de.0.fds = fds,
+        Deserializer::DBus(de) => de.0.fds = fds,
+    }
 
-    T::deserialize(&mut de).map(|t| match de {
+    let res = T::deserialize(&mut de);
+    let common = match &mut de {
         #[cfg(feature = "gvariant")]
-        Deserializer::GVariant(de) => (t, de.0.pos),
-        Deserializer::DBus(de) => (t, de.0.pos),
-    })
+        Deserializer::GVariant(de) => &mut de.0,
+        Deserializer::DBus(de) => &mut de.0,
+    };
+
+    match res {
+        Ok(t) => Ok((t, common.pos)),
+        Err(e) => {
+            let location = common.take_error_location();
+            let offset = location.abs_pos.saturating_sub(ctxt.position());
+
+            Err(LocatedError::new(e, offset, location.path))
+        }
+    }
 }
 
 /// Deserialize `T` from a given slice of bytes containing file descriptor indices, with the given
@@ -311,6 +650,38 @@ where
     from_slice_fds_with_seed(bytes, fds, ctxt, seed)
 }
 
+/// Deserialize `T` from a given slice of bytes containing file descriptor indices, with the given
+/// signature, taking the ownership of the file descriptors.
+///
+/// See [`from_slice_owned_fds`] for details.
+///
+/// This function is not available on Windows.
+///
+/// [`from_slice_owned_fds`]: fn.from_slice_owned_fds.html
+#[cfg(unix)]
+pub fn from_slice_owned_fds_for_dynamic_signature<'d, B, S, T>(
+    bytes: &'d [u8],
+    fds: Vec<StdOwnedFd>,
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    T: DynamicDeserialize<'d>,
+    S: TryInto<Signature<'d>>,
+    S::Error: Into<Error>,
+{
+    let seed = T::deserializer_for_signature(signature)?;
+    let raw_fds: Vec<_> = fds.iter().map(AsRawFd::as_raw_fd).collect();
+    let owned_fds = Mutex::new(fds.into_iter().map(Some).collect());
+    let fds = FdList {
+        raw: Some(&raw_fds),
+        owned: Some(&owned_fds),
+    };
+
+    _from_slice_fds_with_seed(bytes, fds, ctxt, seed)
+}
+
 /// Deserialize `T` from a given slice of bytes containing file descriptor indices, using the given
 /// seed.
 ///
@@ -333,7 +704,7 @@ where
     _from_slice_fds_with_seed(
         bytes,
         #[cfg(unix)]
-        None,
+        FdList::default(),
         ctxt,
         seed,
     )
@@ -362,12 +733,12 @@ where
     B: byteorder::ByteOrder,
     S: DeserializeSeed<'d> + DynamicType,
 {
-    _from_slice_fds_with_seed(bytes, fds, ctxt, seed)
+    _from_slice_fds_with_seed(bytes, FdList::new(fds), ctxt, seed)
 }
 
 fn _from_slice_fds_with_seed<'d, B, S>(
     bytes: &'d [u8],
-    #[cfg(unix)] fds: Option<&[RawFd]>,
+    #[cfg(unix)] fds: FdList<'_>,
     ctxt: EncodingContext<B>,
     seed: S,
 ) -> Result<(S::Value, usize)>
@@ -382,7 +753,7 @@ where
         EncodingFormat::GVariant => GVDeserializer::new(
             bytes,
             #[cfg(unix)]
-            fds,
+            fds.raw,
             signature,
             ctxt,
         )
@@ -390,12 +761,18 @@ where
         EncodingFormat::DBus => DBusDeserializer::new(
             bytes,
             #[cfg(unix)]
-            fds,
+            fds.raw,
             signature,
             ctxt,
         )
         .map(Deserializer::DBus)?,
     };
+    #[cfg(unix)]
+    match &mut de {
+        #[cfg(feature = "gvariant")]
+        Deserializer::GVariant(de) => de.0.fds = fds,
+        Deserializer::DBus(de) => de.0.fds = fds,
+    }
 
     seed.deserialize(&mut de).map(|t| match de {
         #[cfg(feature = "gvariant")]
@@ -404,6 +781,23 @@ where
     })
 }
 
+// The file descriptors available to a deserializer.
+#[cfg(unix)]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct FdList<'f> {
+    raw: Option<&'f [RawFd]>,
+    // Only set if the caller handed over the ownership of the file descriptors, in which case
+    // `OwnedFd`s take them from here instead of duplicating them.
+    owned: Option<&'f Mutex<Vec<Option<StdOwnedFd>>>>,
+}
+
+#[cfg(unix)]
+impl<'f> FdList<'f> {
+    pub(crate) fn new(raw: Option<&'f [RawFd]>) -> Self {
+        Self { raw, owned: None }
+    }
+}
+
 /// Our deserialization implementation.
 #[derive(Debug)]
 pub(crate) struct DeserializerCommon<'de, 'sig, 'f, B> {
@@ -411,19 +805,46 @@ pub(crate) struct DeserializerCommon<'de, 'sig, 'f, B> {
     pub(crate) bytes: &'de [u8],
 
     #[cfg(unix)]
-    pub(crate) fds: Option<&'f [RawFd]>,
+    pub(crate) fds: FdList<'f>,
     #[cfg(not(unix))]
     pub(crate) fds: PhantomData<&'f ()>,
 
     pub(crate) pos: usize,
 
+    // Where the value being deserialized starts, relative to `bytes`.
+    pub(crate) value_start: usize,
+
     pub(crate) sig_parser: SignatureParser<'sig>,
 
     pub(crate) container_depths: ContainerDepths,
 
+    // Where the error being returned happened, recorded as it propagates out of containers.
+    pub(crate) error_location: Option<ErrorLocation>,
+
     pub(crate) b: PhantomData<B>,
 }
 
+// Where a deserialization error happened.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorLocation {
+    // The absolute position, i-e including that of the encoding context.
+    pub(crate) abs_pos: usize,
+    pub(crate) path: String,
+}
+
+impl ErrorLocation {
+    // Prepend `segment` to the path, as the error propagates out of the container element it
+    // designates.
+    pub(crate) fn in_path<S>(mut self, segment: S) -> Self
+    where
+        S: std::fmt::Display,
+    {
+        self.path.insert_str(0, &segment.to_string());
+
+        self
+    }
+}
+
 /// Our deserialization implementation.
 ///
 /// Using this deserializer involves an redirection to the actual deserializer. It's best
@@ -483,12 +904,42 @@ where
     #[cfg(unix)]
     pub fn get_fd(&self, idx: u32) -> Result<i32> {
         self.fds
+            .raw
             .and_then(|fds| fds.get(idx as usize))
             .copied()
             .ok_or(Error::UnknownFd)
     }
 
+    // Hand the file descriptor at `idx` over to the `OwnedFd` visitor: the one we own, unless it
+    // was already handed over, or a duplicate.
+    #[cfg(unix)]
+    pub(crate) fn deserialize_owned_fd<V>(&self, idx: u32, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let owned = self.fds.owned.and_then(|owned| {
+            owned
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get_mut(idx as usize)
+                .and_then(Option::take)
+        });
+        let fd = match owned {
+            Some(fd) => fd,
+            None => {
+                // SAFETY: The file descriptors we're given stay open during the deserialization.
+                let fd = unsafe { BorrowedFd::borrow_raw(self.get_fd(idx)?) };
+
+                fd.try_clone_to_owned()
+                    .map_err(|e| Error::InputOutput(e.into()))?
+            }
+        };
+
+        visitor.visit_i32(fd.into_raw_fd())
+    }
+
     pub fn parse_padding(&mut self, alignment: usize) -> Result<usize> {
+        self.value_starts_here();
         let padding = padding_for_n_bytes(self.abs_pos(), alignment);
         if padding > 0 {
             if self.pos + padding > self.bytes.len() {
@@ -506,10 +957,16 @@ where
             }
             self.pos += padding;
         }
+        self.value_starts_here();
 
         Ok(padding)
     }
 
+    // Record that the next value starts at the current position, for errors to point at it.
+    pub(crate) fn value_starts_here(&mut self) {
+        self.value_start = self.pos;
+    }
+
     pub fn prep_deserialize_basic<T>(&mut self) -> Result<()>
     where
         T: Basic,
@@ -546,6 +1003,42 @@ where
     pub fn abs_pos(&self) -> usize {
         self.ctxt.position() + self.pos
     }
+
+    // Where the error just returned happened: as recorded while it propagated out of the
+    // containers it happened in or, if it happened right here, the start of the failing value.
+    pub(crate) fn take_error_location(&mut self) -> ErrorLocation {
+        self.error_location.take().unwrap_or_else(|| ErrorLocation {
+            abs_pos: self.ctxt.position() + self.value_start,
+            path: String::new(),
+        })
+    }
+
+    // Prepend `segment` to the path of the error just returned, as it propagates out of the
+    // element of the container being deserialized it designates.
+    pub(crate) fn error_in_path<S>(&mut self, segment: S)
+    where
+        S: std::fmt::Display,
+    {
+        let location = self.take_error_location().in_path(segment);
+        self.error_location = Some(location);
+    }
+}
+
+// The path segment of a dictionary value, given the encoded key of its entry. Only used on
+// errors, hence the key is only decoded then.
+pub(crate) fn dict_value_path_segment<B>(
+    key: &[u8],
+    ctxt: EncodingContext<B>,
+    signature: char,
+) -> String
+where
+    B: byteorder::ByteOrder,
+{
+    let signature = Signature::from_string_unchecked(signature.to_string());
+    match crate::Value::deserialize_for_signature(key, ctxt, signature) {
+        Ok((key, _)) => format!("[{}]", crate::dict::KeyDisplay(&key)),
+        Err(_) => "[?]".to_string(),
+    }
 }
 
 macro_rules! deserialize_method {
@@ -584,6 +1077,10 @@ where
     deserialize_method!(deserialize_u16());
     deserialize_method!(deserialize_u32());
     deserialize_method!(deserialize_u64());
+    #[cfg(feature = "int128")]
+    deserialize_method!(deserialize_i128());
+    #[cfg(feature = "int128")]
+    deserialize_method!(deserialize_u128());
     deserialize_method!(deserialize_f32());
     deserialize_method!(deserialize_f64());
     deserialize_method!(deserialize_char());
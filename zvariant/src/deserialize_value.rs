@@ -1,7 +1,7 @@
 use core::str;
 use std::marker::PhantomData;
 
-use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::de::{Deserialize, Deserializer, IgnoredAny, SeqAccess, Visitor};
 use static_assertions::assert_impl_all;
 
 use crate::{Signature, Type, Value};
@@ -80,3 +80,66 @@ impl<'de, T: Type + Deserialize<'de>> Type for DeserializeValue<'de, T> {
         Value::signature()
     }
 }
+
+/// Same as [`DeserializeValue`] but gives `None` if the value is not of type `T`, instead of
+/// failing.
+///
+/// The value is skipped in that case, so the deserializer can carry on with the data that follows.
+/// This is used by the `DeserializeDict` derive for lenient dictionary decoding.
+#[doc(hidden)]
+pub struct DeserializeValueIfType<'de, T: Type + Deserialize<'de>>(
+    pub Option<T>,
+    std::marker::PhantomData<&'de T>,
+);
+
+impl<'de, T: Type + Deserialize<'de>> Deserialize<'de> for DeserializeValueIfType<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["zvariant::Value::Signature", "zvariant::Value::Value"];
+        Ok(DeserializeValueIfType(
+            deserializer.deserialize_struct(
+                "zvariant::Value",
+                FIELDS,
+                DeserializeValueIfTypeVisitor(PhantomData),
+            )?,
+            PhantomData,
+        ))
+    }
+}
+
+struct DeserializeValueIfTypeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Type + Deserialize<'de>> Visitor<'de> for DeserializeValueIfTypeVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("zvariant::Value")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let sig: Signature<'_> = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        if sig != T::signature() {
+            seq.next_element::<IgnoredAny>()?
+                .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+
+            return Ok(None);
+        }
+
+        seq.next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(1, &self))
+            .map(Some)
+    }
+}
+
+impl<'de, T: Type + Deserialize<'de>> Type for DeserializeValueIfType<'de, T> {
+    fn signature() -> Signature<'static> {
+        Value::signature()
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
     fmt::{Display, Write},
     hash::BuildHasher,
 };
@@ -11,11 +12,30 @@ use crate::{value_display_fmt, Basic, DynamicType, Error, Signature, Type, Value
 
 /// A helper type to wrap dictionaries in a [`Value`].
 ///
-/// API is provided to convert from, and to a [`HashMap`].
+/// API is provided to convert from, and to a [`HashMap`] or a [`BTreeMap`]. The latter keeps the
+/// entries sorted by key, so it's useful when a deterministic order is needed.
+///
+/// A `&Dict` can also be converted to a [`HashMap`] of references to its keys and values, avoiding
+/// copies:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{Dict, Value};
+///
+/// let mut props = HashMap::new();
+/// props.insert("Name", Value::from("zbus"));
+/// props.insert("Version", Value::from(4_u32));
+/// let props = Dict::from(props);
+///
+/// let view = HashMap::<&str, &Value<'_>>::try_from(&props).unwrap();
+/// assert_eq!(view["Name"], &Value::from("zbus"));
+/// assert_eq!(view["Version"], &Value::from(4_u32));
+/// ```
 ///
 /// [`Value`]: enum.Value.html#variant.Dict
 /// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
-#[derive(Debug, Clone, PartialEq)]
+/// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Dict<'k, 'v> {
     entries: Vec<DictEntry<'k, 'v>>,
     key_signature: Signature<'k>,
@@ -61,6 +81,41 @@ impl<'k, 'v> Dict<'k, 'v> {
         Ok(())
     }
 
+    /// Append `key` and `value` as a new entry, unless an entry with the same key already exists.
+    ///
+    /// Unlike [`append`], this keeps the keys of the dictionary unique, as required by the
+    /// GVariant canonical form and expected by most peers. Note that this is a linear search over
+    /// the existing entries.
+    ///
+    /// # Errors
+    ///
+    /// * if an entry for `key` already exists.
+    /// * any of the errors that [`append`] returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Dict, Type, Value};
+    ///
+    /// let mut dict = Dict::new(<&str>::signature(), u32::signature());
+    /// dict.append_unique(Value::from("a"), Value::U32(1)).unwrap();
+    /// assert!(dict.append_unique(Value::from("a"), Value::U32(2)).is_err());
+    /// assert_eq!(dict.len(), 1);
+    /// ```
+    ///
+    /// [`append`]: #method.append
+    pub fn append_unique<'kv: 'k, 'vv: 'v>(
+        &mut self,
+        key: Value<'kv>,
+        value: Value<'vv>,
+    ) -> Result<(), Error> {
+        if self.entries.iter().any(|e| e.key == key) {
+            return Err(Error::Message(format!("duplicate dict key `{key}`")));
+        }
+
+        self.append(key, value)
+    }
+
     /// Add a new entry.
     pub fn add<K, V>(&mut self, key: K, value: V) -> Result<(), Error>
     where
@@ -88,12 +143,18 @@ impl<'k, 'v> Dict<'k, 'v> {
         &'v V: TryFrom<&'v Value<'v>>,
     {
         for entry in &self.entries {
-            let entry_key = entry.key.downcast_ref::<K>().ok_or(Error::IncorrectType)?;
+            let entry_key = entry
+                .key
+                .downcast_ref::<K>()
+                .ok_or_else(|| Error::type_mismatch_for::<K>(&entry.key))?;
             if *entry_key == *key {
                 return entry
                     .value
                     .downcast_ref()
-                    .ok_or(Error::IncorrectType)
+                    .ok_or_else(|| {
+                        Error::type_mismatch_for::<V>(&entry.value)
+                            .in_path(format!("[{}]", KeyDisplay(&entry.key)))
+                    })
                     .map(Some);
             }
         }
@@ -101,6 +162,203 @@ impl<'k, 'v> Dict<'k, 'v> {
         Ok(None)
     }
 
+    /// Remove the entry for the given key, returning its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Dict, Value};
+    ///
+    /// let mut dict: Dict<'_, '_> = [("a", 1_u32), ("b", 2)].into_iter().collect();
+    /// assert_eq!(dict.remove("a"), Some(Value::U32(1)));
+    /// assert_eq!(dict.remove("a"), None);
+    /// assert_eq!(dict.len(), 1);
+    /// ```
+    pub fn remove<'a, K>(&mut self, key: K) -> Option<Value<'v>>
+    where
+        K: Into<Value<'a>>,
+    {
+        let key = key.into();
+        let i = self.entries.iter().position(|e| e.key == key)?;
+
+        Some(self.entries.remove(i).value)
+    }
+
+    /// Extend the dictionary with the entries of `iter`, replacing the value of existing keys.
+    ///
+    /// This is the fallible counterpart of the [`Extend`] implementation. Like the extension of
+    /// std maps, an entry whose key already exists replaces the value of that entry in place,
+    /// rather than being appended as a duplicate.
+    ///
+    /// # Errors
+    ///
+    /// If the signature of a key or a value doesn't match the one `self` was created for. The
+    /// entries preceding the mismatched one are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Dict;
+    ///
+    /// let mut dict: Dict<'_, '_> = [("a", 1_u32)].into_iter().collect();
+    /// dict.try_extend([("a", 2_u32), ("b", 3)]).unwrap();
+    /// assert_eq!(dict.len(), 2);
+    /// assert_eq!(dict.get::<_, u32>("a").unwrap(), Some(&2));
+    ///
+    /// assert!(dict.try_extend([("c", "not a u32")]).is_err());
+    /// ```
+    pub fn try_extend<K, V, I>(&mut self, iter: I) -> Result<(), Error>
+    where
+        K: Basic + Into<Value<'k>>,
+        V: Into<Value<'v>> + DynamicType,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            check_child_value_signature!(self.key_signature, K::signature(), "key");
+            check_child_value_signature!(self.value_signature, value.dynamic_signature(), "value");
+
+            let key = Value::new(key);
+            let value = Value::new(value);
+            match self.entries.iter_mut().find(|e| e.key == key) {
+                Some(entry) => entry.value = value,
+                None => self.entries.push(DictEntry { key, value }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// If there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// An iterator over the entries, in order of insertion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zvariant::{Dict, Value};
+    ///
+    /// let value = Value::from(HashMap::from([("id", 42_u32)]));
+    /// let dict: &Dict<'_, '_> = (&value).try_into().unwrap();
+    /// for (key, value) in dict.iter() {
+    ///     assert_eq!(key, &Value::from("id"));
+    ///     assert_eq!(value, &Value::U32(42));
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&Value<'k>, &Value<'v>)> {
+        self.entries.iter().map(|e| (&e.key, &e.value))
+    }
+
+    /// An iterator over the keys, in order of insertion.
+    pub fn keys(&self) -> impl Iterator<Item = &Value<'k>> {
+        self.entries.iter().map(|e| &e.key)
+    }
+
+    /// An iterator over the values, in order of insertion.
+    pub fn values(&self) -> impl Iterator<Item = &Value<'v>> {
+        self.entries.iter().map(|e| &e.value)
+    }
+
+    /// Sort the entries by key.
+    ///
+    /// Entries are serialized (and iterated over) in order of insertion, so the encoding of a
+    /// `Dict` created from a [`HashMap`] is not deterministic. Sorting the entries first gives a
+    /// stable encoding, as needed for the GVariant canonical form or golden tests. Entries with
+    /// equal keys keep their relative order. Dictionaries nested in the values are not sorted.
+    ///
+    /// There is no option to sort the entries while serializing instead: the serializers write the
+    /// entries of a map in the order they're given, and the `Serialize` implementation of `Dict`
+    /// would have to sort them all over again each time it's serialized. For maps serialized
+    /// through serde directly, use a [`BTreeMap`], which is serialized in key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zvariant::{Dict, Value};
+    ///
+    /// let mut dict = Dict::from(HashMap::from([("b", 2_u32), ("c", 3), ("a", 1)]));
+    /// dict.sort();
+    /// let keys: Vec<_> = dict.keys().cloned().collect();
+    /// assert_eq!(keys, [Value::from("a"), Value::from("b"), Value::from("c")]);
+    /// ```
+    ///
+    /// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+    /// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+    pub fn sort(&mut self) {
+        self.entries.sort_by(|e1, e2| e1.key.cmp(&e2.key));
+    }
+
+    /// Consume `self` and return it with its entries sorted by key.
+    ///
+    /// See [`sort`] for details.
+    ///
+    /// [`sort`]: #method.sort
+    pub fn sorted(mut self) -> Self {
+        self.sort();
+
+        self
+    }
+
+    /// Merge `other` into `self`.
+    ///
+    /// Entries of `other` override the entries of `self` with the same key, except when both values
+    /// are dictionaries, in which case they're merged recursively (see [`Value::patch`]). Entries
+    /// of `other` with a new key are appended.
+    ///
+    /// # Errors
+    ///
+    /// if the signature of `other` doesn't match that of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zvariant::{Dict, Value};
+    ///
+    /// let mut ipv4 = HashMap::new();
+    /// ipv4.insert("method", Value::from("auto"));
+    /// ipv4.insert("may-fail", Value::from(true));
+    /// let mut profile = HashMap::new();
+    /// profile.insert("id", Value::from("Home"));
+    /// profile.insert("ipv4", Value::from(ipv4));
+    /// let mut profile = Dict::from(profile);
+    ///
+    /// let mut ipv4 = HashMap::new();
+    /// ipv4.insert("method", Value::from("manual"));
+    /// let mut patch = HashMap::new();
+    /// patch.insert("ipv4", Value::from(ipv4));
+    /// patch.insert("autoconnect", Value::from(false));
+    ///
+    /// profile.merge(&Dict::from(patch)).unwrap();
+    ///
+    /// assert_eq!(profile.get::<_, str>("id").unwrap(), Some("Home"));
+    /// assert_eq!(profile.get::<_, bool>("autoconnect").unwrap(), Some(&false));
+    /// let ipv4 = profile.get::<_, Dict<'_, '_>>("ipv4").unwrap().unwrap();
+    /// assert_eq!(ipv4.get::<_, str>("method").unwrap(), Some("manual"));
+    /// assert_eq!(ipv4.get::<_, bool>("may-fail").unwrap(), Some(&true));
+    /// ```
+    pub fn merge(&mut self, other: &Dict<'_, '_>) -> Result<(), Error> {
+        check_child_value_signature!(self.signature, other.signature, "dict");
+
+        for other_entry in &other.entries {
+            match self.entries.iter_mut().find(|e| e.key == other_entry.key) {
+                Some(entry) => entry.value.patch(&other_entry.value)?,
+                None => self.entries.push(other_entry.to_owned()),
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the signature of this `Dict`.
     ///
     /// NB: This method potentially allocates and copies. Use [`full_signature`] if you'd like to
@@ -125,6 +383,15 @@ impl<'k, 'v> Dict<'k, 'v> {
         }
     }
 
+    pub(crate) fn byteswap(&self) -> Dict<'static, 'static> {
+        Dict {
+            key_signature: self.key_signature.to_owned(),
+            value_signature: self.value_signature.to_owned(),
+            signature: self.signature.to_owned(),
+            entries: self.entries.iter().map(|v| v.byteswap()).collect(),
+        }
+    }
+
     /// Create a new empty `Dict`, given the complete signature.
     pub(crate) fn new_full_signature<'s: 'k + 'v>(signature: Signature<'s>) -> Self {
         let key_signature = signature.slice(2..3);
@@ -137,8 +404,27 @@ impl<'k, 'v> Dict<'k, 'v> {
             signature,
         }
     }
+}
+
+impl<'k, 'v> DynamicType for Dict<'k, 'v> {
+    fn dynamic_signature(&self) -> Signature<'_> {
+        self.signature.clone()
+    }
+}
+
+// Containers of different signatures are ordered by their signatures, so compare those first.
+impl PartialOrd for Dict<'_, '_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    // TODO: Provide more API like https://docs.rs/toml/0.5.5/toml/map/struct.Map.html
+impl Ord for Dict<'_, '_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.signature
+            .cmp(&other.signature)
+            .then_with(|| self.entries.cmp(&other.entries))
+    }
 }
 
 impl Display for Dict<'_, '_> {
@@ -194,6 +480,39 @@ impl<'k, 'v> Serialize for Dict<'k, 'v> {
     }
 }
 
+impl<'k, 'v, K, V> FromIterator<(K, V)> for Dict<'k, 'v>
+where
+    K: Basic + Into<Value<'k>>,
+    V: Type + Into<Value<'v>>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        Self::from_pairs(iter)
+    }
+}
+
+/// Entries whose key already exists replace the value of the existing entry.
+///
+/// # Panics
+///
+/// If the signature of the keys or the values doesn't match that of the `Dict`. Use
+/// [`Dict::try_extend`] to handle that case gracefully.
+impl<'k, 'v, K, V> Extend<(K, V)> for Dict<'k, 'v>
+where
+    K: Basic + Into<Value<'k>>,
+    V: Into<Value<'v>> + DynamicType,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.try_extend(iter)
+            .expect("mismatched `Dict` entry signature");
+    }
+}
+
 // Conversion of Dict to HashMap
 impl<'k, 'v, K, V, H> TryFrom<Dict<'k, 'v>> for HashMap<K, V, H>
 where
@@ -206,31 +525,46 @@ where
     type Error = Error;
 
     fn try_from(v: Dict<'k, 'v>) -> Result<Self, Self::Error> {
-        let mut map = HashMap::default();
-        for e in v.entries.into_iter() {
-            let key = if let Value::Value(v) = e.key {
-                K::try_from(*v)
-            } else {
-                K::try_from(e.key)
-            }
-            .map_err(Into::into)?;
+        v.entries
+            .into_iter()
+            .map(DictEntry::try_into_pair)
+            .collect()
+    }
+}
 
-            let value = if let Value::Value(v) = e.value {
-                V::try_from(*v)
-            } else {
-                V::try_from(e.value)
-            }
-            .map_err(Into::into)?;
+// Conversion of a Dict reference to a HashMap of references
+impl<'d, 'k, 'v, K, V, H> TryFrom<&'d Dict<'k, 'v>> for HashMap<&'d K, &'d V, H>
+where
+    'k: 'd,
+    'v: 'd,
+    K: ?Sized + std::hash::Hash + std::cmp::Eq,
+    V: ?Sized,
+    &'d K: TryFrom<&'d Value<'d>>,
+    &'d V: TryFrom<&'d Value<'d>>,
+    H: BuildHasher + Default,
+{
+    type Error = Error;
 
-            map.insert(key, value);
-        }
-        Ok(map)
+    fn try_from(v: &'d Dict<'k, 'v>) -> Result<Self, Self::Error> {
+        v.entries
+            .iter()
+            .map(|e| {
+                let key: &'d Value<'d> = &e.key;
+                let value: &'d Value<'d> = &e.value;
+
+                Ok((
+                    key.downcast_ref()
+                        .ok_or_else(|| Error::type_mismatch_for::<K>(key))?,
+                    value.downcast_ref().ok_or_else(|| {
+                        Error::type_mismatch_for::<V>(value)
+                            .in_path(format!("[{}]", KeyDisplay(key)))
+                    })?,
+                ))
+            })
+            .collect()
     }
 }
 
-// TODO: this could be useful
-// impl<'d, 'k, 'v, K, V, H> TryFrom<&'d Dict<'k, 'v>> for HashMap<&'k K, &'v V, H>
-
 // Conversion of Hashmap to Dict
 impl<'k, 'v, K, V, H> From<HashMap<K, V, H>> for Dict<'k, 'v>
 where
@@ -239,7 +573,47 @@ where
     H: BuildHasher + Default,
 {
     fn from(value: HashMap<K, V, H>) -> Self {
-        let entries = value
+        Self::from_pairs(value)
+    }
+}
+
+// Conversion of Dict to BTreeMap
+impl<'k, 'v, K, V> TryFrom<Dict<'k, 'v>> for BTreeMap<K, V>
+where
+    K: Basic + TryFrom<Value<'k>> + std::cmp::Ord,
+    V: TryFrom<Value<'v>>,
+    K::Error: Into<crate::Error>,
+    V::Error: Into<crate::Error>,
+{
+    type Error = Error;
+
+    fn try_from(v: Dict<'k, 'v>) -> Result<Self, Self::Error> {
+        v.entries
+            .into_iter()
+            .map(DictEntry::try_into_pair)
+            .collect()
+    }
+}
+
+// Conversion of BTreeMap to Dict
+impl<'k, 'v, K, V> From<BTreeMap<K, V>> for Dict<'k, 'v>
+where
+    K: Type + Into<Value<'k>> + std::cmp::Ord,
+    V: Type + Into<Value<'v>>,
+{
+    fn from(value: BTreeMap<K, V>) -> Self {
+        Self::from_pairs(value)
+    }
+}
+
+impl<'k, 'v> Dict<'k, 'v> {
+    fn from_pairs<K, V, I>(pairs: I) -> Self
+    where
+        K: Type + Into<Value<'k>>,
+        V: Type + Into<Value<'v>>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let entries = pairs
             .into_iter()
             .map(|(key, value)| DictEntry {
                 key: Value::new(key),
@@ -259,9 +633,7 @@ where
     }
 }
 
-// TODO: Conversion of Dict from/to BTreeMap
-
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct DictEntry<'k, 'v> {
     key: Value<'k>,
     value: Value<'v>,
@@ -274,6 +646,49 @@ impl<'k, 'v> DictEntry<'k, 'v> {
             value: self.value.to_owned().into(),
         }
     }
+
+    fn byteswap(&self) -> DictEntry<'static, 'static> {
+        DictEntry {
+            key: self.key.byteswap(),
+            value: self.value.byteswap(),
+        }
+    }
+
+    fn try_into_pair<K, V>(self) -> Result<(K, V), Error>
+    where
+        K: TryFrom<Value<'k>>,
+        V: TryFrom<Value<'v>>,
+        K::Error: Into<crate::Error>,
+        V::Error: Into<crate::Error>,
+    {
+        let DictEntry { key, value } = self;
+        // The value is converted first, so the key is still around to locate the mismatch.
+        let value = if let Value::Value(v) = value {
+            V::try_from(*v)
+        } else {
+            V::try_from(value)
+        }
+        .map_err(|e| Into::<Error>::into(e).in_path(format!("[{}]", KeyDisplay(&key))))?;
+
+        let key = if let Value::Value(v) = key {
+            K::try_from(*v)
+        } else {
+            K::try_from(key)
+        }
+        .map_err(Into::into)?;
+
+        Ok((key, value))
+    }
+}
+
+// Displays a dict key the way it's written in a `TypeMismatch` path, i.e in GVariant text format
+// without type annotations.
+pub(crate) struct KeyDisplay<'a, 'k>(pub(crate) &'a Value<'k>);
+
+impl Display for KeyDisplay<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        value_display_fmt(self.0, f, false)
+    }
 }
 
 impl<'k, 'v> Serialize for DictEntry<'k, 'v> {
@@ -297,3 +712,62 @@ fn create_signature(
 ) -> Signature<'static> {
     Signature::from_string_unchecked(format!("a{{{key_signature}{value_signature}}}",))
 }
+
+/// Owned [`Dict`](struct.Dict.html)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedDict(Dict<'static, 'static>);
+
+assert_impl_all!(OwnedDict: Send, Sync, Unpin);
+
+impl OwnedDict {
+    pub fn into_inner(self) -> Dict<'static, 'static> {
+        self.0
+    }
+}
+
+impl std::ops::Deref for OwnedDict {
+    type Target = Dict<'static, 'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<OwnedDict> for Dict<'static, 'static> {
+    fn from(o: OwnedDict) -> Self {
+        o.into_inner()
+    }
+}
+
+impl<'a> From<Dict<'a, 'a>> for OwnedDict {
+    fn from(o: Dict<'a, 'a>) -> Self {
+        OwnedDict(o.to_owned())
+    }
+}
+
+impl From<OwnedDict> for Value<'static> {
+    fn from(o: OwnedDict) -> Self {
+        o.into_inner().into()
+    }
+}
+
+impl DynamicType for OwnedDict {
+    fn dynamic_signature(&self) -> Signature<'_> {
+        self.0.dynamic_signature()
+    }
+}
+
+impl Serialize for OwnedDict {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Display for OwnedDict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    ops::{Deref, DerefMut},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Signature, Type};
+
+macro_rules! duration_type {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $repr:ty, $as_unit:ident, $from_unit:ident, $unit:literal
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(Duration);
+
+        impl $name {
+            #[doc = concat!("Create a new `", stringify!($name), "` from a number of ", $unit, ".")]
+            pub fn new(value: $repr) -> Option<Self> {
+                u64::try_from(value)
+                    .ok()
+                    .map(|v| Self(Duration::$from_unit(v)))
+            }
+
+            #[doc = concat!("The duration as a number of ", $unit, ".")]
+            ///
+            /// Returns `None` if the duration doesn't fit in the underlying integer type.
+            pub fn get(&self) -> Option<$repr> {
+                <$repr>::try_from(self.0.$as_unit()).ok()
+            }
+
+            /// The inner [`Duration`].
+            pub fn into_inner(self) -> Duration {
+                self.0
+            }
+        }
+
+        impl Type for $name {
+            fn signature() -> Signature<'static> {
+                <$repr>::signature()
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.get()
+                    .ok_or_else(|| {
+                        <S::Error as ser::Error>::custom(concat!(
+                            "duration too large to be represented as ",
+                            stringify!($repr),
+                            " ",
+                            $unit,
+                        ))
+                    })?
+                    .serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let value = <$repr>::deserialize(deserializer)?;
+
+                Self::new(value)
+                    .ok_or_else(|| de::Error::custom(concat!("negative duration in ", $unit)))
+            }
+        }
+
+        impl From<Duration> for $name {
+            fn from(value: Duration) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Duration {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = Duration;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
+}
+
+duration_type!(
+    /// A [`Duration`] encoded as an unsigned number of microseconds (`t`).
+    ///
+    /// [`Duration`] itself is encoded as a `(tu)` structure of seconds and nanoseconds, which is
+    /// rarely what D-Bus interfaces use. Many of them (e.g logind and systemd) instead define
+    /// timestamps and timeouts as a plain integer in a specific unit. This type, and its siblings,
+    /// allow you to use [`Duration`] in your code for these, without having to convert manually.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zvariant::{from_slice, to_bytes, EncodingContext, MicrosU64, Type};
+    /// use byteorder::LE;
+    ///
+    /// assert_eq!(MicrosU64::signature(), "t");
+    ///
+    /// let ctxt = EncodingContext::<LE>::new_dbus(0);
+    /// let timeout = MicrosU64::from(Duration::from_millis(1500));
+    /// let encoded = to_bytes(ctxt, &timeout).unwrap();
+    /// assert_eq!(from_slice::<_, u64>(&encoded, ctxt).unwrap().0, 1_500_000);
+    ///
+    /// let decoded: MicrosU64 = from_slice(&encoded, ctxt).unwrap().0;
+    /// assert_eq!(Duration::from(decoded), Duration::from_millis(1500));
+    /// ```
+    MicrosU64,
+    u64,
+    as_micros,
+    from_micros,
+    "microseconds"
+);
+
+duration_type!(
+    /// A [`Duration`] encoded as a signed number of microseconds (`x`).
+    ///
+    /// Negative values are rejected on deserialization. See [`MicrosU64`] for details.
+    MicrosI64,
+    i64,
+    as_micros,
+    from_micros,
+    "microseconds"
+);
+
+duration_type!(
+    /// A [`Duration`] encoded as an unsigned number of milliseconds (`t`).
+    ///
+    /// See [`MicrosU64`] for details.
+    MillisU64,
+    u64,
+    as_millis,
+    from_millis,
+    "milliseconds"
+);
+
+duration_type!(
+    /// A [`Duration`] encoded as a signed number of milliseconds (`x`).
+    ///
+    /// Negative values are rejected on deserialization. See [`MicrosU64`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zvariant::{from_slice, to_bytes, EncodingContext, MillisI64, Type};
+    /// use byteorder::LE;
+    ///
+    /// assert_eq!(MillisI64::signature(), "x");
+    ///
+    /// let ctxt = EncodingContext::<LE>::new_dbus(0);
+    /// let encoded = to_bytes(ctxt, &-1_i64).unwrap();
+    /// assert!(from_slice::<_, MillisI64>(&encoded, ctxt).is_err());
+    ///
+    /// let encoded = to_bytes(ctxt, &250_i64).unwrap();
+    /// let decoded: MillisI64 = from_slice(&encoded, ctxt).unwrap().0;
+    /// assert_eq!(*decoded, Duration::from_millis(250));
+    /// ```
+    MillisI64,
+    i64,
+    as_millis,
+    from_millis,
+    "milliseconds"
+);
+
+/// A [`SystemTime`] encoded as an unsigned number of microseconds since the Unix epoch (`t`).
+///
+/// [`SystemTime`] itself is encoded as a `(tu)` structure of seconds and nanoseconds since the
+/// epoch but wall clock timestamps on D-Bus (e.g systemd's `*Timestamp` properties) are typically
+/// microseconds since the epoch instead. Times before the epoch can't be represented in this
+/// encoding and fail to serialize.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use zvariant::{from_slice, to_bytes, EncodingContext, Type, UnixMicros};
+/// use byteorder::LE;
+///
+/// assert_eq!(UnixMicros::signature(), "t");
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+/// let encoded = to_bytes(ctxt, &UnixMicros::from(time)).unwrap();
+/// assert_eq!(from_slice::<_, u64>(&encoded, ctxt).unwrap().0, 1_700_000_000_000_000);
+///
+/// let decoded: UnixMicros = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(*decoded, time);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixMicros(SystemTime);
+
+impl UnixMicros {
+    /// Create a new `UnixMicros` from a number of microseconds since the Unix epoch.
+    ///
+    /// Returns `None` if the time can't be represented by [`SystemTime`] on this platform.
+    pub fn new(value: u64) -> Option<Self> {
+        UNIX_EPOCH
+            .checked_add(Duration::from_micros(value))
+            .map(Self)
+    }
+
+    /// The time as a number of microseconds since the Unix epoch.
+    ///
+    /// Returns `None` if the time is before the epoch, or too far after it to fit in a `u64`.
+    pub fn get(&self) -> Option<u64> {
+        let since_epoch = self.0.duration_since(UNIX_EPOCH).ok()?;
+
+        u64::try_from(since_epoch.as_micros()).ok()
+    }
+
+    /// The inner [`SystemTime`].
+    pub fn into_inner(self) -> SystemTime {
+        self.0
+    }
+}
+
+impl Type for UnixMicros {
+    fn signature() -> Signature<'static> {
+        u64::signature()
+    }
+}
+
+impl Serialize for UnixMicros {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.get()
+            .ok_or_else(|| {
+                <S::Error as ser::Error>::custom(
+                    "time not representable as u64 microseconds since the epoch",
+                )
+            })?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnixMicros {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = u64::deserialize(deserializer)?;
+
+        Self::new(value).ok_or_else(|| de::Error::custom("time out of range"))
+    }
+}
+
+impl From<SystemTime> for UnixMicros {
+    fn from(value: SystemTime) -> Self {
+        Self(value)
+    }
+}
+
+impl From<UnixMicros> for SystemTime {
+    fn from(value: UnixMicros) -> Self {
+        value.0
+    }
+}
+
+impl Deref for UnixMicros {
+    type Target = SystemTime;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for UnixMicros {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -59,6 +59,7 @@ impl std::fmt::Display for EncodingFormat {
 pub struct EncodingContext<B> {
     format: EncodingFormat,
     position: usize,
+    limits: Limits,
 
     b: PhantomData<B>,
 }
@@ -74,10 +75,35 @@ where
         Self {
             format,
             position,
+            limits: Limits::default(),
             b: PhantomData,
         }
     }
 
+    /// Set the [`Limits`] to enforce when deserializing with this context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{from_slice, to_bytes, EncodingContext, Error, Limits, MaxDepthExceeded};
+    ///
+    /// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+    /// let encoded = to_bytes(ctxt, &vec![vec![42u8]]).unwrap();
+    ///
+    /// let ctxt = ctxt.with_limits(Limits::default().with_max_array_depth(1));
+    /// assert_eq!(
+    ///     from_slice::<_, Vec<Vec<u8>>>(&encoded, ctxt).unwrap_err(),
+    ///     Error::MaxDepthExceeded(MaxDepthExceeded::Array),
+    /// );
+    /// ```
+    ///
+    /// [`Limits`]: struct.Limits.html
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+
+        self
+    }
+
     /// Convenient wrapper for [`new`] to create a context for D-Bus format.
     ///
     /// [`new`]: #method.new
@@ -104,4 +130,152 @@ where
     pub fn position(self) -> usize {
         self.position
     }
+
+    /// The [`Limits`] enforced when deserializing with this context.
+    ///
+    /// [`Limits`]: struct.Limits.html
+    pub fn limits(self) -> Limits {
+        self.limits
+    }
+
+    // A context for the data at `position` in the same message, keeping the limits.
+    pub(crate) fn nested(self, format: EncodingFormat, position: usize) -> Self {
+        Self {
+            format,
+            position,
+            ..self
+        }
+    }
+}
+
+// The limits of the D-Bus specification. We use them for GVariant as well.
+//
+// The GVariant specification removed all the limits, from the D-Bus specification but that turned
+// out to be a [mistake]. Although glib went for a higher limit (128) but we'll stick to the D-Bus
+// limits and expand if/when needed.
+//
+// [mistake]: https://gitlab.gnome.org/GNOME/glib/-/commit/7c4e6e9fbe473de0401c778c6b0c4aad27d5145a
+const MAX_STRUCT_DEPTH: u8 = 32;
+const MAX_ARRAY_DEPTH: u8 = 32;
+const MAX_CONTAINER_DEPTH: u8 = 64;
+const MAX_ARRAY_SIZE: usize = 64 * 1024 * 1024;
+const MAX_SIZE: usize = 128 * 1024 * 1024;
+
+/// Limits to enforce when deserializing, to protect against malicious input.
+///
+/// The defaults are the limits set by the [D-Bus specification]: structures and arrays can each
+/// be nested 32 levels deep and all containers (including variants) 64 levels deep, arrays can't
+/// be larger than 64 MiB and messages can't be larger than 128 MiB. The same defaults apply to
+/// the GVariant format.
+///
+/// The depth limits also apply to serialization. Note that signatures are always validated against
+/// the D-Bus specification limits, so raising the structure or array depth limits has no effect
+/// on values with a signature nesting deeper than those, only on nested variants.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{from_slice, to_bytes, EncodingContext, Error, Limits, MaxSizeExceeded};
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &(vec![0u8; 1024], "hello")).unwrap();
+///
+/// let limits = Limits::default().with_max_array_size(512);
+/// let ctxt = ctxt.with_limits(limits);
+/// assert_eq!(
+///     from_slice::<_, (Vec<u8>, &str)>(&encoded, ctxt).unwrap_err(),
+///     Error::MaxSizeExceeded(MaxSizeExceeded::Array),
+/// );
+///
+/// let ctxt = ctxt.with_limits(limits.with_max_size(1024));
+/// assert_eq!(
+///     from_slice::<_, (Vec<u8>, &str)>(&encoded, ctxt).unwrap_err(),
+///     Error::MaxSizeExceeded(MaxSizeExceeded::Total),
+/// );
+/// ```
+///
+/// [D-Bus specification]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-marshaling
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Limits {
+    max_struct_depth: u8,
+    max_array_depth: u8,
+    max_container_depth: u8,
+    max_array_size: usize,
+    max_size: usize,
+}
+
+assert_impl_all!(Limits: Send, Sync, Unpin);
+
+impl Limits {
+    /// The maximum nesting depth of structures.
+    pub fn max_struct_depth(self) -> u8 {
+        self.max_struct_depth
+    }
+
+    /// Set the maximum nesting depth of structures.
+    pub fn with_max_struct_depth(mut self, depth: u8) -> Self {
+        self.max_struct_depth = depth;
+
+        self
+    }
+
+    /// The maximum nesting depth of arrays.
+    pub fn max_array_depth(self) -> u8 {
+        self.max_array_depth
+    }
+
+    /// Set the maximum nesting depth of arrays.
+    pub fn with_max_array_depth(mut self, depth: u8) -> Self {
+        self.max_array_depth = depth;
+
+        self
+    }
+
+    /// The maximum nesting depth of all containers combined, including variants.
+    pub fn max_container_depth(self) -> u8 {
+        self.max_container_depth
+    }
+
+    /// Set the maximum nesting depth of all containers combined, including variants.
+    pub fn with_max_container_depth(mut self, depth: u8) -> Self {
+        self.max_container_depth = depth;
+
+        self
+    }
+
+    /// The maximum size of an array, in bytes.
+    pub fn max_array_size(self) -> usize {
+        self.max_array_size
+    }
+
+    /// Set the maximum size of an array, in bytes.
+    pub fn with_max_array_size(mut self, size: usize) -> Self {
+        self.max_array_size = size;
+
+        self
+    }
+
+    /// The maximum size of the data to deserialize, in bytes.
+    pub fn max_size(self) -> usize {
+        self.max_size
+    }
+
+    /// Set the maximum size of the data to deserialize, in bytes.
+    pub fn with_max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+
+        self
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_struct_depth: MAX_STRUCT_DEPTH,
+            max_array_depth: MAX_ARRAY_DEPTH,
+            max_container_depth: MAX_CONTAINER_DEPTH,
+            max_array_size: MAX_ARRAY_SIZE,
+            max_size: MAX_SIZE,
+        }
+    }
 }
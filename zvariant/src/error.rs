@@ -32,6 +32,129 @@ impl fmt::Display for MaxDepthExceeded {
     }
 }
 
+/// Enum representing the max size exceeded error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxSizeExceeded {
+    /// The maximum allowed size of arrays in encoding was exceeded.
+    Array,
+    /// The maximum allowed size of the entire encoding was exceeded.
+    Total,
+}
+
+impl fmt::Display for MaxSizeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Array => write!(f, "Maximum allowed size of arrays in encoding was exceeded"),
+            Self::Total => write!(f, "Maximum allowed size of the encoding was exceeded"),
+        }
+    }
+}
+
+/// The reason a signature is invalid.
+///
+/// All positions are byte offsets into the signature string. See [`Signature::validate`].
+///
+/// [`Signature::validate`]: struct.Signature.html#method.validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureError {
+    /// The signature is longer than the maximum of 255 characters (the actual length).
+    TooLong(usize),
+    /// The character at `position` is not a valid signature character.
+    InvalidChar { position: usize, character: char },
+    /// The array (or maybe) type at `position` has no element type.
+    MissingElementType { position: usize },
+    /// The structure at `position` has no fields.
+    EmptyStructure { position: usize },
+    /// The parenthesis or brace at `position` is never closed.
+    Unclosed { position: usize },
+    /// The closing parenthesis or brace at `position` doesn't close anything.
+    UnexpectedClose { position: usize, character: char },
+    /// The dict-entry key at `position` is not of a basic type.
+    NonBasicDictKey { position: usize },
+    /// The dict entry at `position` doesn't consist of exactly one key and one value type.
+    InvalidDictEntry { position: usize },
+    /// The container at `position` is nested too deep.
+    MaxDepthExceeded {
+        position: usize,
+        kind: MaxDepthExceeded,
+    },
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong(len) => write!(f, "signature of {len} characters exceeds 255 characters"),
+            Self::InvalidChar {
+                position,
+                character,
+            } => write!(f, "invalid character `{character}` at position {position}"),
+            Self::MissingElementType { position } => {
+                write!(
+                    f,
+                    "missing element type for container at position {position}"
+                )
+            }
+            Self::EmptyStructure { position } => {
+                write!(f, "structure at position {position} has no fields")
+            }
+            Self::Unclosed { position } => {
+                write!(f, "unclosed container at position {position}")
+            }
+            Self::UnexpectedClose {
+                position,
+                character,
+            } => write!(f, "unexpected `{character}` at position {position}"),
+            Self::NonBasicDictKey { position } => {
+                write!(
+                    f,
+                    "dict-entry key at position {position} is not a basic type"
+                )
+            }
+            Self::InvalidDictEntry { position } => write!(
+                f,
+                "dict entry at position {position} must have exactly one key and one value type"
+            ),
+            Self::MaxDepthExceeded { position, kind } => write!(f, "{kind} at position {position}"),
+        }
+    }
+}
+
+impl error::Error for SignatureError {}
+
+/// The error returned by [`Value::try_downcast`].
+///
+/// [`Value::try_downcast`]: enum.Value.html#method.try_downcast
+#[derive(Debug)]
+pub enum TryDowncastError<'a, E> {
+    /// The value doesn't hold the requested type. The value is given back.
+    Mismatch(crate::Value<'a>),
+    /// The value holds the requested type but converting it failed.
+    Conversion(E),
+}
+
+impl<E: fmt::Display> fmt::Display for TryDowncastError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch(value) => write!(
+                f,
+                "value of type `{}` doesn't hold the requested type",
+                value.value_signature()
+            ),
+            Self::Conversion(e) => write!(f, "conversion failed: {e}"),
+        }
+    }
+}
+
+impl<E: error::Error + 'static> error::Error for TryDowncastError<'_, E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Mismatch(_) => None,
+            Self::Conversion(e) => Some(e),
+        }
+    }
+}
+
 /// Error type used by zvariant API.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -42,6 +165,20 @@ pub enum Error {
     /// Wrapper for [`std::io::Error`](https://doc.rust-lang.org/std/io/struct.Error.html)
     InputOutput(Arc<std::io::Error>),
     /// Type conversions errors.
+    ///
+    /// Not returned by zvariant since 4.1.0: conversions from [`Value`] return
+    /// [`Error::TypeMismatch`] and signature checks return [`Error::SignatureMismatch`] instead.
+    /// Code matching on this variant still builds but the match never succeeds anymore, so it
+    /// should match on those variants instead.
+    ///
+    /// [`Value`]: enum.Value.html
+    /// [`Error::TypeMismatch`]: enum.Error.html#variant.TypeMismatch
+    /// [`Error::SignatureMismatch`]: enum.Error.html#variant.SignatureMismatch
+    #[deprecated(
+        since = "4.1.0",
+        note = "Not returned by zvariant anymore, see `Error::TypeMismatch` and \
+                `Error::SignatureMismatch` instead"
+    )]
     IncorrectType,
     /// Wrapper for [`std::str::Utf8Error`](https://doc.rust-lang.org/std/str/struct.Utf8Error.html)
     Utf8(std::str::Utf8Error),
@@ -60,6 +197,25 @@ pub enum Error {
     OutOfBounds,
     /// The maximum allowed depth for containers in encoding was exceeded.
     MaxDepthExceeded(MaxDepthExceeded),
+    /// The maximum allowed size of arrays or the entire encoding was exceeded.
+    MaxSizeExceeded(MaxSizeExceeded),
+    /// Invalid signature.
+    InvalidSignature(SignatureError),
+    /// A value of an unexpected type was encountered while converting from a [`Value`].
+    ///
+    /// [`Value`]: enum.Value.html
+    TypeMismatch {
+        /// The expected type: its signature, a description (e.g. "an array") for containers or
+        /// the name of the Rust type if neither is known.
+        expected: String,
+        /// The signature of the value encountered.
+        found: crate::Signature<'static>,
+        /// The location of the value, relative to the value being converted. It's made of `[i]`
+        /// for array elements, `[key]` for dictionary values (with `key` in the GVariant text
+        /// format, e.g. `["Flags"]`) and `.i` for structure fields, e.g. `["Devices"][2].1`. It's
+        /// empty when the value being converted is itself of an unexpected type.
+        path: String,
+    },
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -69,11 +225,27 @@ impl PartialEq for Error {
         match (self, other) {
             (Error::Message(msg), Error::Message(other)) => msg == other,
             // Io is false
+            #[allow(deprecated)]
             (Error::IncorrectType, Error::IncorrectType) => true,
             (Error::Utf8(msg), Error::Utf8(other)) => msg == other,
             (Error::PaddingNot0(p), Error::PaddingNot0(other)) => p == other,
             (Error::UnknownFd, Error::UnknownFd) => true,
+            (Error::OutOfBounds, Error::OutOfBounds) => true,
             (Error::MaxDepthExceeded(max1), Error::MaxDepthExceeded(max2)) => max1 == max2,
+            (Error::MaxSizeExceeded(max1), Error::MaxSizeExceeded(max2)) => max1 == max2,
+            (Error::InvalidSignature(e1), Error::InvalidSignature(e2)) => e1 == e2,
+            (
+                Error::TypeMismatch {
+                    expected: e1,
+                    found: f1,
+                    path: p1,
+                },
+                Error::TypeMismatch {
+                    expected: e2,
+                    found: f2,
+                    path: p2,
+                },
+            ) => e1 == e2 && f1 == f2 && p1 == p2,
             (_, _) => false,
         }
     }
@@ -84,6 +256,7 @@ impl error::Error for Error {
         match self {
             Error::InputOutput(e) => Some(e),
             Error::Utf8(e) => Some(e),
+            Error::InvalidSignature(e) => Some(e),
             _ => None,
         }
     }
@@ -94,6 +267,7 @@ impl fmt::Display for Error {
         match self {
             Error::Message(s) => write!(f, "{s}"),
             Error::InputOutput(e) => e.fmt(f),
+            #[allow(deprecated)]
             Error::IncorrectType => write!(f, "incorrect type"),
             Error::Utf8(e) => write!(f, "{e}"),
             Error::PaddingNot0(b) => write!(f, "Unexpected non-0 padding byte `{b}`"),
@@ -115,6 +289,20 @@ impl fmt::Display for Error {
                 "Out of bounds range specified",
             ),
             Error::MaxDepthExceeded(max) => write!(f, "{max}"),
+            Error::MaxSizeExceeded(max) => write!(f, "{max}"),
+            Error::InvalidSignature(e) => write!(f, "Invalid signature: {e}"),
+            Error::TypeMismatch {
+                expected,
+                found,
+                path,
+            } => {
+                write!(f, "Type mismatch: expected {expected}, found `{found}`")?;
+                if !path.is_empty() {
+                    write!(f, " at `{path}`")?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
@@ -124,6 +312,7 @@ impl Clone for Error {
         match self {
             Error::Message(s) => Error::Message(s.clone()),
             Error::InputOutput(e) => Error::InputOutput(e.clone()),
+            #[allow(deprecated)]
             Error::IncorrectType => Error::IncorrectType,
             Error::Utf8(e) => Error::Utf8(*e),
             Error::PaddingNot0(b) => Error::PaddingNot0(*b),
@@ -137,7 +326,64 @@ impl Clone for Error {
             }
             Error::OutOfBounds => Error::OutOfBounds,
             Error::MaxDepthExceeded(max) => Error::MaxDepthExceeded(*max),
+            Error::MaxSizeExceeded(max) => Error::MaxSizeExceeded(*max),
+            Error::InvalidSignature(e) => Error::InvalidSignature(*e),
+            Error::TypeMismatch {
+                expected,
+                found,
+                path,
+            } => Error::TypeMismatch {
+                expected: expected.clone(),
+                found: found.clone(),
+                path: path.clone(),
+            },
+        }
+    }
+}
+
+impl Error {
+    // A `TypeMismatch` error for `found`, at the root of the conversion.
+    pub(crate) fn type_mismatch<E>(expected: E, found: &crate::Value<'_>) -> Self
+    where
+        E: Into<String>,
+    {
+        Error::TypeMismatch {
+            expected: expected.into(),
+            found: found.value_signature().to_owned(),
+            path: String::new(),
+        }
+    }
+
+    // Same as `type_mismatch` but with the name of `T` as the expected type. Like the `downcast`
+    // API of `Value`, this looks through `Value::Value`.
+    pub(crate) fn type_mismatch_for<T>(found: &crate::Value<'_>) -> Self
+    where
+        T: ?Sized,
+    {
+        let found = match found {
+            crate::Value::Value(value) => &**value,
+            value => value,
+        };
+
+        Self::type_mismatch(std::any::type_name::<T>(), found)
+    }
+
+    // Prepend `segment` to the path of a `TypeMismatch` error. Other errors are left untouched.
+    pub(crate) fn in_path<S>(mut self, segment: S) -> Self
+    where
+        S: fmt::Display,
+    {
+        if let Error::TypeMismatch { path, .. } = &mut self {
+            path.insert_str(0, &segment.to_string());
         }
+
+        self
+    }
+}
+
+impl From<SignatureError> for Error {
+    fn from(e: SignatureError) -> Self {
+        Error::InvalidSignature(e)
     }
 }
 
@@ -167,5 +413,83 @@ impl ser::Error for Error {
     }
 }
 
+/// An [`Error`] returned by a deserializer, along with where in the data it happened.
+///
+/// Returned by [`from_slice_located`] and its variants, so that a failure deep down in e.g. a
+/// dictionary of properties can be tracked down without trial and error. Converts into the
+/// [`Error`] it carries, for `?` to work in functions returning [`Result`].
+///
+/// [`Error`]: enum.Error.html
+/// [`Result`]: type.Result.html
+/// [`from_slice_located`]: fn.from_slice_located.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedError {
+    error: Error,
+    offset: usize,
+    path: String,
+}
+
+assert_impl_all!(LocatedError: Send, Sync, Unpin);
+
+impl LocatedError {
+    pub(crate) fn new(error: Error, offset: usize, path: String) -> Self {
+        Self {
+            error,
+            offset,
+            path,
+        }
+    }
+
+    /// The error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Consume `self`, returning the error.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+
+    /// The offset, in the bytes being deserialized, of the value that failed to deserialize.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The location of the value that failed to deserialize, relative to the value being
+    /// deserialized.
+    ///
+    /// It follows the same syntax as the path of [`Error::TypeMismatch`]: `[i]` for array
+    /// elements, `[key]` for dictionary values and `.i` for structure fields, e.g.
+    /// `["Devices"][2].1`. It's empty if the value being deserialized failed itself.
+    ///
+    /// [`Error::TypeMismatch`]: enum.Error.html#variant.TypeMismatch
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for LocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {}", self.error, self.offset)?;
+        if !self.path.is_empty() {
+            write!(f, ", in `{}`", self.path)?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+impl error::Error for LocatedError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<LocatedError> for Error {
+    fn from(e: LocatedError) -> Self {
+        e.error
+    }
+}
+
 /// Alias for a `Result` with the error type `zvariant::Error`.
 pub type Result<T> = result::Result<T, Error>;
@@ -1,6 +1,12 @@
-use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use serde::{
+    de::{Error, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 use static_assertions::assert_impl_all;
-use std::os::unix::io;
+use std::{
+    fmt,
+    os::unix::io::{self, FromRawFd, IntoRawFd},
+};
 
 use crate::{Basic, EncodingFormat, Signature, Type};
 
@@ -16,7 +22,7 @@ use crate::{Basic, EncodingFormat, Signature, Type};
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 /// [deserializer]: fn.from_slice_fds.html
 /// [serializer]: fn.to_bytes_fds.html
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Fd(io::RawFd);
 
 macro_rules! fd_impl {
@@ -90,12 +96,34 @@ impl std::fmt::Display for Fd {
 /// An owned [`RawFd`](https://doc.rust-lang.org/std/os/unix/io/type.RawFd.html) wrapper.
 ///
 /// See also [`Fd`]. This type owns the file and will close it on drop. On deserialize, it will
-/// duplicate the file descriptor.
+/// duplicate the file descriptor, unless the deserializer was given the ownership of the file
+/// descriptors (see [`from_slice_owned_fds`]), in which case it takes over the file descriptor.
+///
+/// It can be converted from and into the standard library's [`OwnedFd`][std_owned_fd].
+///
+/// [`from_slice_owned_fds`]: fn.from_slice_owned_fds.html
+/// [std_owned_fd]: https://doc.rust-lang.org/std/os/unix/io/struct.OwnedFd.html
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct OwnedFd {
     inner: io::RawFd,
 }
 
+impl OwnedFd {
+    /// Create a new `OwnedFd` for the same file, by duplicating the file descriptor.
+    pub fn try_clone(&self) -> std::io::Result<Self> {
+        Self::dup(self.inner)
+    }
+
+    fn dup(fd: io::RawFd) -> std::io::Result<Self> {
+        let fd = unsafe { libc::dup(fd) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(OwnedFd { inner: fd })
+    }
+}
+
 impl Drop for OwnedFd {
     fn drop(&mut self) {
         unsafe {
@@ -115,16 +143,66 @@ impl Serialize for OwnedFd {
     }
 }
 
+// The names of the outer and inner newtype structs `OwnedFd` deserializes as. See `Stage` for
+// details.
+const OWNED_FD_NAME: &str = "zvariant::OwnedFd";
+pub(crate) const OWNED_FD_HANDOVER_NAME: &str = "zvariant::OwnedFd::HandOver";
+
 impl<'de> Deserialize<'de> for OwnedFd {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let fd = unsafe { libc::dup(i32::deserialize(deserializer)?) };
-        if fd < 0 {
-            return Err(D::Error::custom(std::io::Error::last_os_error()));
+        deserializer.deserialize_newtype_struct(OWNED_FD_NAME, OwnedFdVisitor(Stage::Outer))
+    }
+}
+
+// Where an `OwnedFdVisitor` is in the deserialization.
+//
+// Our deserializers hand over the ownership of the file descriptors they're given the ownership
+// of (see `from_slice_owned_fds`) and duplicates of the others. Other deserializers only give us
+// file descriptors we need to duplicate. Hence an `OwnedFd` is deserialized as a newtype struct,
+// whose deserializer is asked for another newtype struct, for which only our deserializers hand
+// over a file descriptor. Foreign ones either visit the newtype struct again or hand out the file
+// descriptor right away (e.g because they deserialize any newtype struct as its content).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Outer,
+    HandOver,
+}
+
+struct OwnedFdVisitor(Stage);
+
+impl<'de> Visitor<'de> for OwnedFdVisitor {
+    type Value = OwnedFd;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a file descriptor")
+    }
+
+    fn visit_i32<E>(self, fd: i32) -> Result<OwnedFd, E>
+    where
+        E: Error,
+    {
+        match self.0 {
+            // SAFETY: Our deserializers give up the ownership of the file descriptor.
+            Stage::HandOver => Ok(unsafe { OwnedFd::from_raw_fd(fd) }),
+            Stage::Outer => OwnedFd::dup(fd).map_err(E::custom),
+        }
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<OwnedFd, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.0 {
+            Stage::Outer => deserializer.deserialize_newtype_struct(
+                OWNED_FD_HANDOVER_NAME,
+                OwnedFdVisitor(Stage::HandOver),
+            ),
+            Stage::HandOver => i32::deserialize(deserializer)
+                .and_then(|fd| OwnedFd::dup(fd).map_err(D::Error::custom)),
         }
-        Ok(OwnedFd { inner: fd })
     }
 }
 
@@ -148,6 +226,28 @@ impl io::IntoRawFd for OwnedFd {
     }
 }
 
+impl From<io::OwnedFd> for OwnedFd {
+    fn from(fd: io::OwnedFd) -> Self {
+        Self {
+            inner: fd.into_raw_fd(),
+        }
+    }
+}
+
+impl From<OwnedFd> for io::OwnedFd {
+    fn from(fd: OwnedFd) -> Self {
+        // SAFETY: `fd` owns the file descriptor and gives it up.
+        unsafe { io::OwnedFd::from_raw_fd(fd.into_raw_fd()) }
+    }
+}
+
+impl io::AsFd for OwnedFd {
+    fn as_fd(&self) -> io::BorrowedFd<'_> {
+        // SAFETY: The file descriptor stays open as long as `self` is alive.
+        unsafe { io::BorrowedFd::borrow_raw(self.inner) }
+    }
+}
+
 impl std::fmt::Display for OwnedFd {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.inner.fmt(f)
@@ -0,0 +1,273 @@
+use std::{
+    borrow::Cow,
+    ffi::{OsStr, OsString},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use static_assertions::assert_impl_all;
+
+use crate::{Signature, Type};
+
+/// A file path, encoded as a byte array (`ay`).
+///
+/// File paths are not guaranteed to be valid UTF-8, so encoding them as D-Bus strings (which is
+/// what the [`Type`] implementation of [`Path`] and [`PathBuf`] does, for compatibility with their
+/// serde implementation) is lossy at best and fails at worst. Hence many D-Bus APIs (e.g the
+/// document portal) use byte arrays for file paths instead. This type allows you to do that.
+///
+/// The encoded byte array holds the bytes of the path as is, without any nul byte. Paths containing
+/// a nul byte can't be encoded. For interoperability with GLib-based peers, which nul-terminate
+/// their byte array paths, a trailing nul byte is stripped on decoding while an interior nul byte
+/// results in an error.
+///
+/// If you need the string (`s`) encoding instead, simply use [`Path`] or [`PathBuf`] directly.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use zvariant::{from_slice, to_bytes, EncodingContext, FilePath, Type};
+/// use byteorder::LE;
+///
+/// assert_eq!(FilePath::signature(), "ay");
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let path = FilePath::from(Path::new("/tmp/file"));
+/// let encoded = to_bytes(ctxt, &path).unwrap();
+/// assert_eq!(encoded.len(), 13);
+///
+/// let decoded: FilePath<'_> = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded.as_path(), Path::new("/tmp/file"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FilePath<'f>(Cow<'f, OsStr>);
+
+assert_impl_all!(FilePath<'_>: Send, Sync, Unpin);
+
+impl<'f> FilePath<'f> {
+    /// Create a new `FilePath`.
+    pub fn new(path: impl Into<Cow<'f, OsStr>>) -> Self {
+        Self(path.into())
+    }
+
+    /// The path as a [`Path`].
+    pub fn as_path(&self) -> &Path {
+        Path::new(&self.0)
+    }
+
+    /// The path as an [`OsStr`].
+    pub fn as_os_str(&self) -> &OsStr {
+        &self.0
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn to_owned(&self) -> FilePath<'static> {
+        FilePath(Cow::Owned(self.0.clone().into_owned()))
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn into_owned(self) -> FilePath<'static> {
+        FilePath(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Convert `self` into a [`PathBuf`].
+    pub fn into_path_buf(self) -> PathBuf {
+        self.0.into_owned().into()
+    }
+}
+
+impl Type for FilePath<'_> {
+    fn signature() -> Signature<'static> {
+        Signature::from_static_str_unchecked("ay")
+    }
+}
+
+impl AsRef<Path> for FilePath<'_> {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl AsRef<OsStr> for FilePath<'_> {
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl<'f> From<&'f Path> for FilePath<'f> {
+    fn from(path: &'f Path) -> Self {
+        Self(Cow::Borrowed(path.as_os_str()))
+    }
+}
+
+impl<'f> From<&'f PathBuf> for FilePath<'f> {
+    fn from(path: &'f PathBuf) -> Self {
+        Self(Cow::Borrowed(path.as_os_str()))
+    }
+}
+
+impl From<PathBuf> for FilePath<'_> {
+    fn from(path: PathBuf) -> Self {
+        Self(Cow::Owned(path.into_os_string()))
+    }
+}
+
+impl<'f> From<&'f OsStr> for FilePath<'f> {
+    fn from(path: &'f OsStr) -> Self {
+        Self(Cow::Borrowed(path))
+    }
+}
+
+impl From<OsString> for FilePath<'_> {
+    fn from(path: OsString) -> Self {
+        Self(Cow::Owned(path))
+    }
+}
+
+impl<'f> From<&'f str> for FilePath<'f> {
+    fn from(path: &'f str) -> Self {
+        Self(Cow::Borrowed(OsStr::new(path)))
+    }
+}
+
+impl From<FilePath<'_>> for PathBuf {
+    fn from(path: FilePath<'_>) -> Self {
+        path.into_path_buf()
+    }
+}
+
+impl From<FilePath<'_>> for OsString {
+    fn from(path: FilePath<'_>) -> Self {
+        path.0.into_owned()
+    }
+}
+
+impl Serialize for FilePath<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = os_str_as_bytes(&self.0).map_err(serde::ser::Error::custom)?;
+        if bytes.contains(&0) {
+            return Err(serde::ser::Error::custom("file path contains a nul byte"));
+        }
+
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de: 'f, 'f> Deserialize<'de> for FilePath<'f> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(FilePathVisitor)
+    }
+}
+
+struct FilePathVisitor;
+
+impl<'de> Visitor<'de> for FilePathVisitor {
+    type Value = FilePath<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte array without interior nul bytes")
+    }
+
+    fn visit_borrowed_bytes<E>(self, bytes: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        strip_nul(bytes)
+            .and_then(bytes_as_os_str)
+            .map(FilePath)
+            .map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_byte_buf(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, mut bytes: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let len = strip_nul(&bytes).map_err(E::custom)?.len();
+        bytes.truncate(len);
+
+        bytes_into_os_string(bytes)
+            .map(|p| FilePath(Cow::Owned(p)))
+            .map_err(E::custom)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        self.visit_byte_buf(bytes)
+    }
+}
+
+fn strip_nul(bytes: &[u8]) -> Result<&[u8], &'static str> {
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    if bytes.contains(&0) {
+        return Err("file path contains a nul byte");
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(unix)]
+fn os_str_as_bytes(s: &OsStr) -> Result<Cow<'_, [u8]>, &'static str> {
+    use std::os::unix::ffi::OsStrExt;
+
+    Ok(Cow::Borrowed(s.as_bytes()))
+}
+
+#[cfg(not(unix))]
+fn os_str_as_bytes(s: &OsStr) -> Result<Cow<'_, [u8]>, &'static str> {
+    s.to_str()
+        .map(|s| Cow::Borrowed(s.as_bytes()))
+        .ok_or("file path is not valid UTF-8")
+}
+
+#[cfg(unix)]
+fn bytes_as_os_str(bytes: &[u8]) -> Result<Cow<'_, OsStr>, &'static str> {
+    use std::os::unix::ffi::OsStrExt;
+
+    Ok(Cow::Borrowed(OsStr::from_bytes(bytes)))
+}
+
+#[cfg(not(unix))]
+fn bytes_as_os_str(bytes: &[u8]) -> Result<Cow<'_, OsStr>, &'static str> {
+    std::str::from_utf8(bytes)
+        .map(|s| Cow::Borrowed(OsStr::new(s)))
+        .map_err(|_| "file path is not valid UTF-8")
+}
+
+#[cfg(unix)]
+fn bytes_into_os_string(bytes: Vec<u8>) -> Result<OsString, &'static str> {
+    use std::os::unix::ffi::OsStringExt;
+
+    Ok(OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn bytes_into_os_string(bytes: Vec<u8>) -> Result<OsString, &'static str> {
+    String::from_utf8(bytes)
+        .map(Into::into)
+        .map_err(|_| "file path is not valid UTF-8")
+}
@@ -0,0 +1,314 @@
+use std::{borrow::Cow, fmt, marker::PhantomData, mem, ops::Deref, slice};
+
+use serde::{
+    de::{self, DeserializeOwned, SeqAccess, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use static_assertions::assert_impl_all;
+
+use crate::{Basic, Error, Signature, Type};
+
+mod private {
+    pub trait Sealed {
+        // The name of the inner newtype struct `FixedArray`s of this type are deserialized as,
+        // i-e `FIXED_ARRAY_NAME_PREFIX` followed by the signature of the type.
+        const FIXED_ARRAY_NAME: &'static str;
+    }
+}
+
+/// Basic types that are encoded as their in-memory representation.
+///
+/// This is implemented for all the fixed-sized basic types whose alignment matches their size and
+/// for which any bit pattern is valid, i-e `u8`, `i16`, `u16`, `i32`, `u32`, `i64`, `u64` and
+/// `f64`. This trait is sealed and can not be implemented outside of this crate.
+pub trait FixedBasic: Basic + Copy + Serialize + DeserializeOwned + private::Sealed {}
+
+macro_rules! fixed_basic_impl {
+    ($($t:ty => $signature:literal)*) => {
+        $(
+            impl private::Sealed for $t {
+                const FIXED_ARRAY_NAME: &'static str = concat!("zvariant::FixedArray::", $signature);
+            }
+            impl FixedBasic for $t {}
+        )*
+    };
+}
+
+fixed_basic_impl!(
+    u8 => "y" i16 => "n" u16 => "q" i32 => "i" u32 => "u" i64 => "x" u64 => "t" f64 => "d"
+);
+
+// The names of the outer and (the prefix of) the inner newtype structs `FixedArray`s are
+// deserialized as. See `Stage` for details.
+const FIXED_ARRAY_NAME: &str = "zvariant::FixedArray";
+const FIXED_ARRAY_NAME_PREFIX: &str = "zvariant::FixedArray::";
+
+/// An array of fixed-sized basic elements (e.g `ay` or `au`) that borrows from the input on
+/// decoding.
+///
+/// Deserializing a [`Vec`] builds it element by element. This type instead borrows the elements
+/// directly from the encoded data when possible, which makes decoding large arrays (e.g icon data
+/// or firmware blobs) practically free. Borrowing requires the data to be encoded in the native
+/// byte order and to be suitably aligned in memory. If that's not the case, the elements are
+/// copied into an owned buffer.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{from_slice, to_bytes, EncodingContext, FixedArray, Type};
+/// use byteorder::NativeEndian;
+///
+/// assert_eq!(FixedArray::<u32>::signature(), "au");
+///
+/// let ctxt = EncodingContext::<NativeEndian>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &vec![1u32, 2, 3]).unwrap();
+///
+/// let decoded: FixedArray<'_, u32> = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(&*decoded, &[1, 2, 3]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedArray<'a, T: FixedBasic>(Cow<'a, [T]>);
+
+assert_impl_all!(FixedArray<'_, u32>: Send, Sync, Unpin);
+
+impl<'a, T: FixedBasic> FixedArray<'a, T> {
+    /// Create a new `FixedArray`.
+    pub fn new(elements: impl Into<Cow<'a, [T]>>) -> Self {
+        Self(elements.into())
+    }
+
+    /// The elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Whether the elements are borrowed.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn to_owned(&self) -> FixedArray<'static, T> {
+        FixedArray(Cow::Owned(self.0.to_vec()))
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn into_owned(self) -> FixedArray<'static, T> {
+        FixedArray(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Convert `self` into a [`Vec`].
+    pub fn into_vec(self) -> Vec<T> {
+        self.0.into_owned()
+    }
+
+    // Reinterpret native-endian encoded bytes as elements, if they're suitably aligned.
+    fn borrow_bytes(bytes: &'a [u8]) -> Option<&'a [T]> {
+        let size = mem::size_of::<T>();
+        if bytes.len() % size != 0 || bytes.as_ptr() as usize % mem::align_of::<T>() != 0 {
+            return None;
+        }
+
+        // SAFETY: The pointer is aligned for `T`, the length is a multiple of its size and all bit
+        // patterns are valid for `FixedBasic` types.
+        Some(unsafe { slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / size) })
+    }
+
+    // Copy native-endian encoded bytes into elements.
+    fn copy_bytes(bytes: &[u8]) -> Option<Vec<T>> {
+        let size = mem::size_of::<T>();
+        if bytes.len() % size != 0 {
+            return None;
+        }
+
+        let elements = bytes
+            .chunks_exact(size)
+            // SAFETY: Each chunk has the size of `T` and all bit patterns are valid for
+            // `FixedBasic` types.
+            .map(|chunk| unsafe { chunk.as_ptr().cast::<T>().read_unaligned() })
+            .collect();
+
+        Some(elements)
+    }
+}
+
+impl<T: FixedBasic> Deref for FixedArray<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: FixedBasic> AsRef<[T]> for FixedArray<'_, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T: FixedBasic> From<&'a [T]> for FixedArray<'a, T> {
+    fn from(elements: &'a [T]) -> Self {
+        Self(Cow::Borrowed(elements))
+    }
+}
+
+impl<T: FixedBasic> From<Vec<T>> for FixedArray<'_, T> {
+    fn from(elements: Vec<T>) -> Self {
+        Self(Cow::Owned(elements))
+    }
+}
+
+impl<T: FixedBasic> From<FixedArray<'_, T>> for Vec<T> {
+    fn from(array: FixedArray<'_, T>) -> Self {
+        array.into_vec()
+    }
+}
+
+impl<T: FixedBasic> Type for FixedArray<'_, T> {
+    fn signature() -> Signature<'static> {
+        <[T]>::signature()
+    }
+}
+
+impl<T: FixedBasic> Serialize for FixedArray<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+// The signature of the elements of the `FixedArray` deserialized as a newtype struct of the given
+// name, if it is one.
+pub(crate) fn fixed_array_element(name: &str) -> Option<char> {
+    name.strip_prefix(FIXED_ARRAY_NAME_PREFIX)
+        .and_then(|signature| signature.chars().next())
+}
+
+// Check the encoded `signature` against the `element` signature of the `FixedArray` being
+// deserialized.
+pub(crate) fn check_fixed_array_signature(
+    signature: &Signature<'_>,
+    element: char,
+) -> crate::Result<()> {
+    if signature.as_bytes() != [b'a', element as u8] {
+        return Err(Error::SignatureMismatch(
+            signature.to_owned(),
+            format!("`a{element}`"),
+        ));
+    }
+
+    Ok(())
+}
+
+impl<'de: 'a, 'a, T: FixedBasic + 'de> Deserialize<'de> for FixedArray<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(
+            FIXED_ARRAY_NAME,
+            FixedArrayVisitor {
+                stage: Stage::Outer,
+                phantom: PhantomData,
+            },
+        )
+    }
+}
+
+// Where a `FixedArrayVisitor` is in the deserialization.
+//
+// Only our deserializers can be trusted to hand out the encoded bytes of elements wider than
+// bytes, as they check the signature of the elements. Hence a `FixedArray` is deserialized as a
+// newtype struct, whose deserializer is asked for another newtype struct, named after the
+// signature of the elements. Only our deserializers hand out the elements then, foreign ones
+// either visit the newtype struct again or hand out whatever they have right away (e.g because
+// they deserialize any newtype struct as its content).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Outer,
+    Inner,
+    Content,
+}
+
+struct FixedArrayVisitor<T> {
+    stage: Stage,
+    phantom: PhantomData<T>,
+}
+
+impl<T: FixedBasic> FixedArrayVisitor<T> {
+    fn check_bytes<E>(&self, bytes: &[u8]) -> Result<(), E>
+    where
+        E: de::Error,
+    {
+        if T::SIGNATURE_CHAR != u8::SIGNATURE_CHAR && self.stage != Stage::Inner {
+            return Err(E::invalid_type(Unexpected::Bytes(bytes), self));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, T: FixedBasic + 'de> Visitor<'de> for FixedArrayVisitor<T> {
+    type Value = FixedArray<'de, T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "an array of `{}`", T::SIGNATURE_CHAR)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.stage {
+            Stage::Outer => deserializer.deserialize_newtype_struct(
+                T::FIXED_ARRAY_NAME,
+                FixedArrayVisitor {
+                    stage: Stage::Inner,
+                    phantom: PhantomData,
+                },
+            ),
+            Stage::Inner => deserializer.deserialize_bytes(FixedArrayVisitor {
+                stage: Stage::Content,
+                phantom: PhantomData,
+            }),
+            Stage::Content => Err(de::Error::invalid_type(Unexpected::NewtypeStruct, &self)),
+        }
+    }
+
+    fn visit_borrowed_bytes<E>(self, bytes: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.check_bytes(bytes)?;
+
+        match FixedArray::borrow_bytes(bytes) {
+            Some(elements) => Ok(FixedArray(Cow::Borrowed(elements))),
+            None => self.visit_bytes(bytes),
+        }
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.check_bytes(bytes)?;
+
+        FixedArray::copy_bytes(bytes)
+            .map(|elements| FixedArray(Cow::Owned(elements)))
+            .ok_or_else(|| E::invalid_length(bytes.len(), &self))
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+
+        Ok(FixedArray(Cow::Owned(elements)))
+    }
+}
@@ -1,17 +1,21 @@
 #[cfg(feature = "gvariant")]
 use crate::Maybe;
 use crate::{
-    Array, Dict, Error, NoneValue, ObjectPath, Optional, OwnedObjectPath, OwnedSignature,
-    Signature, Str, Structure, Value,
+    Array, Dict, Error, NoneValue, ObjectPath, Optional, OwnedArray, OwnedDict, OwnedObjectPath,
+    OwnedSignature, OwnedStr, OwnedStructure, Signature, Str, Structure, Value,
 };
 
 #[cfg(unix)]
 use crate::Fd;
 
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::CString,
+    hash::BuildHasher,
+};
 
 macro_rules! value_try_from {
-    ($kind:ident, $to:ty) => {
+    ($kind:ident, $to:ty, $expected:literal) => {
         impl<'a> TryFrom<Value<'a>> for $to {
             type Error = Error;
 
@@ -19,7 +23,7 @@ macro_rules! value_try_from {
                 if let Value::$kind(value) = value {
                     Ok(value.into())
                 } else {
-                    Err(Error::IncorrectType)
+                    Err(Error::type_mismatch($expected, &value))
                 }
             }
         }
@@ -27,7 +31,7 @@ macro_rules! value_try_from {
 }
 
 macro_rules! value_try_from_ref {
-    ($kind:ident, $to:ty) => {
+    ($kind:ident, $to:ty, $expected:literal) => {
         impl<'a> TryFrom<&'a Value<'a>> for &'a $to {
             type Error = Error;
 
@@ -35,7 +39,7 @@ macro_rules! value_try_from_ref {
                 if let Value::$kind(value) = value {
                     Ok(value)
                 } else {
-                    Err(Error::IncorrectType)
+                    Err(Error::type_mismatch($expected, &value))
                 }
             }
         }
@@ -43,7 +47,7 @@ macro_rules! value_try_from_ref {
 }
 
 macro_rules! value_try_from_ref_clone {
-    ($kind:ident, $to:ty) => {
+    ($kind:ident, $to:ty, $expected:literal) => {
         impl<'a> TryFrom<&'a Value<'a>> for $to {
             type Error = Error;
 
@@ -51,7 +55,7 @@ macro_rules! value_try_from_ref_clone {
                 if let Value::$kind(value) = value {
                     Ok(value.clone().into())
                 } else {
-                    Err(Error::IncorrectType)
+                    Err(Error::type_mismatch($expected, &value))
                 }
             }
         }
@@ -59,36 +63,36 @@ macro_rules! value_try_from_ref_clone {
 }
 
 macro_rules! value_try_from_all {
-    ($from:ident, $to:ty) => {
-        value_try_from!($from, $to);
-        value_try_from_ref!($from, $to);
-        value_try_from_ref_clone!($from, $to);
+    ($from:ident, $to:ty, $expected:literal) => {
+        value_try_from!($from, $to, $expected);
+        value_try_from_ref!($from, $to, $expected);
+        value_try_from_ref_clone!($from, $to, $expected);
     };
 }
 
-value_try_from_all!(U8, u8);
-value_try_from_all!(Bool, bool);
-value_try_from_all!(I16, i16);
-value_try_from_all!(U16, u16);
-value_try_from_all!(I32, i32);
-value_try_from_all!(U32, u32);
-value_try_from_all!(I64, i64);
-value_try_from_all!(U64, u64);
-value_try_from_all!(F64, f64);
+value_try_from_all!(U8, u8, "`y`");
+value_try_from_all!(Bool, bool, "`b`");
+value_try_from_all!(I16, i16, "`n`");
+value_try_from_all!(U16, u16, "`q`");
+value_try_from_all!(I32, i32, "`i`");
+value_try_from_all!(U32, u32, "`u`");
+value_try_from_all!(I64, i64, "`x`");
+value_try_from_all!(U64, u64, "`t`");
+value_try_from_all!(F64, f64, "`d`");
 #[cfg(unix)]
-value_try_from_all!(Fd, Fd);
-
-value_try_from_all!(Str, Str<'a>);
-value_try_from_all!(Signature, Signature<'a>);
-value_try_from_all!(ObjectPath, ObjectPath<'a>);
-value_try_from_all!(Structure, Structure<'a>);
-value_try_from_all!(Dict, Dict<'a, 'a>);
-value_try_from_all!(Array, Array<'a>);
+value_try_from_all!(Fd, Fd, "`h`");
+
+value_try_from_all!(Str, Str<'a>, "`s`");
+value_try_from_all!(Signature, Signature<'a>, "`g`");
+value_try_from_all!(ObjectPath, ObjectPath<'a>, "`o`");
+value_try_from_all!(Structure, Structure<'a>, "a structure");
+value_try_from_all!(Dict, Dict<'a, 'a>, "a dict");
+value_try_from_all!(Array, Array<'a>, "an array");
 #[cfg(feature = "gvariant")]
-value_try_from_all!(Maybe, Maybe<'a>);
+value_try_from_all!(Maybe, Maybe<'a>, "a maybe");
 
-value_try_from!(Str, String);
-value_try_from_ref!(Str, str);
+value_try_from!(Str, String, "`s`");
+value_try_from_ref!(Str, str, "`s`");
 
 impl<'a> TryFrom<&'a Value<'a>> for String {
     type Error = Error;
@@ -109,7 +113,7 @@ where
         if let Value::Array(v) = value {
             Self::try_from(v)
         } else {
-            Err(Error::IncorrectType)
+            Err(Error::type_mismatch("an array", &value))
         }
     }
 }
@@ -130,6 +134,61 @@ impl TryFrom<Value<'_>> for OwnedSignature {
     }
 }
 
+impl TryFrom<Value<'_>> for OwnedStr {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Str::try_from(value).map(OwnedStr::from)
+    }
+}
+
+impl TryFrom<Value<'_>> for OwnedArray {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Array::try_from(value).map(OwnedArray::from)
+    }
+}
+
+impl TryFrom<Value<'_>> for OwnedDict {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Dict::try_from(value).map(OwnedDict::from)
+    }
+}
+
+impl TryFrom<Value<'_>> for OwnedStructure {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Structure::try_from(value).map(OwnedStructure::from)
+    }
+}
+
+/// Converts from either a byte array or a string, as long as it doesn't contain any nul bytes.
+impl TryFrom<Value<'_>> for CString {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        let bytes = match value {
+            Value::Array(array) => Vec::<u8>::try_from(array)?,
+            Value::Str(s) => String::from(s).into_bytes(),
+            _ => return Err(Error::type_mismatch("`ay` or `s`", &value)),
+        };
+
+        CString::new(bytes).map_err(|e| Error::Message(e.to_string()))
+    }
+}
+
+impl TryFrom<&Value<'_>> for CString {
+    type Error = Error;
+
+    fn try_from(value: &Value<'_>) -> Result<Self, Self::Error> {
+        Self::try_from(value.clone())
+    }
+}
+
 // tuple conversions in `structure` module for avoiding code-duplication.
 
 #[cfg(feature = "enumflags2")]
@@ -160,7 +219,25 @@ where
         if let Value::Dict(v) = value {
             Self::try_from(v)
         } else {
-            Err(crate::Error::IncorrectType)
+            Err(crate::Error::type_mismatch("a dict", &value))
+        }
+    }
+}
+
+impl<'a, K, V> TryFrom<Value<'a>> for BTreeMap<K, V>
+where
+    K: crate::Basic + TryFrom<Value<'a>> + std::cmp::Ord,
+    V: TryFrom<Value<'a>>,
+    K::Error: Into<crate::Error>,
+    V::Error: Into<crate::Error>,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        if let Value::Dict(v) = value {
+            Self::try_from(v)
+        } else {
+            Err(crate::Error::type_mismatch("a dict", &value))
         }
     }
 }
@@ -7,11 +7,14 @@ use std::{ffi::CStr, marker::PhantomData, str};
 use std::os::unix::io::RawFd;
 
 use crate::{
-    de::ValueParseStage, framing_offset_size::FramingOffsetSize, framing_offsets::FramingOffsets,
-    signature_parser::SignatureParser, utils::*, Basic, EncodingContext, EncodingFormat, Error,
-    Result, Signature,
+    container_depths::ContainerDepths, de::ValueParseStage, framing_offset_size::FramingOffsetSize,
+    framing_offsets::FramingOffsets, signature_parser::SignatureParser, utils::*, Basic,
+    EncodingContext, EncodingFormat, Error, MaxSizeExceeded, Result, Signature,
 };
 
+#[cfg(unix)]
+use crate::{fd::OWNED_FD_HANDOVER_NAME, Fd};
+
 /// Our GVariant deserialization implementation.
 #[derive(Debug)]
 pub struct Deserializer<'de, 'sig, 'f, B>(pub(crate) crate::DeserializerCommon<'de, 'sig, 'f, B>);
@@ -36,6 +39,9 @@ where
         S::Error: Into<Error>,
     {
         assert_eq!(ctxt.format(), EncodingFormat::GVariant);
+        if bytes.len() > ctxt.limits().max_size() {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Total));
+        }
 
         let signature = signature.try_into().map_err(Into::into)?;
         let sig_parser = SignatureParser::new(signature);
@@ -44,11 +50,13 @@ where
             sig_parser,
             bytes,
             #[cfg(unix)]
-            fds,
+            fds: crate::FdList::new(fds),
             #[cfg(not(unix))]
             fds: PhantomData,
             pos: 0,
-            container_depths: Default::default(),
+            value_start: 0,
+            container_depths: ContainerDepths::new(ctxt.limits()),
+            error_location: None,
             b: PhantomData,
         }))
     }
@@ -60,7 +68,10 @@ macro_rules! deserialize_basic {
         where
             V: Visitor<'de>,
         {
-            let ctxt = EncodingContext::new_dbus(self.0.ctxt.position() + self.0.pos);
+            let ctxt = self
+                .0
+                .ctxt
+                .nested(EncodingFormat::DBus, self.0.ctxt.position() + self.0.pos);
 
             let mut dbus_de = crate::dbus::Deserializer::<B>(crate::DeserializerCommon::<B> {
                 ctxt,
@@ -68,11 +79,17 @@ macro_rules! deserialize_basic {
                 bytes: subslice(self.0.bytes, self.0.pos..)?,
                 fds: self.0.fds,
                 pos: 0,
+                value_start: 0,
                 container_depths: self.0.container_depths,
+                error_location: None,
                 b: PhantomData,
             });
 
-            let v = dbus_de.$method(visitor)?;
+            let v = dbus_de.$method(visitor);
+            if v.is_err() {
+                self.0.error_location = Some(dbus_de.0.take_error_location());
+            }
+            let v = v?;
             self.0.sig_parser = dbus_de.0.sig_parser;
             self.0.pos += dbus_de.0.pos;
             // Basic types don't have anything to do with container depths so not updating it here.
@@ -125,6 +142,27 @@ where
     deserialize_basic!(deserialize_f64);
     deserialize_basic!(deserialize_identifier);
 
+    // Encoded as a `(tt)` structure of the high and low 64 bits.
+    #[cfg(feature = "int128")]
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let (high, low) = <(u64, u64) as serde::Deserialize>::deserialize(self)?;
+
+        visitor.visit_i128(((high as u128) << 64 | low as u128) as i128)
+    }
+
+    #[cfg(feature = "int128")]
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let (high, low) = <(u64, u64) as serde::Deserialize>::deserialize(self)?;
+
+        visitor.visit_u128((high as u128) << 64 | low as u128)
+    }
+
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
@@ -153,6 +191,7 @@ where
     where
         V: Visitor<'de>,
     {
+        self.0.value_starts_here();
         let slice = subslice(self.0.bytes, self.0.pos..)?;
         let s = if self.0.sig_parser.next_char()? == VARIANT_SIGNATURE_CHAR {
             if slice.contains(&0) {
@@ -214,8 +253,10 @@ where
 
             visitor.visit_none()
         } else {
-            let ctxt =
-                EncodingContext::new(self.0.ctxt.format(), self.0.ctxt.position() + self.0.pos);
+            let ctxt = self
+                .0
+                .ctxt
+                .nested(self.0.ctxt.format(), self.0.ctxt.position() + self.0.pos);
             let end = if fixed_sized_child {
                 self.0.bytes.len()
             } else {
@@ -228,12 +269,18 @@ where
                 bytes: subslice(self.0.bytes, self.0.pos..end)?,
                 fds: self.0.fds,
                 pos: 0,
+                value_start: 0,
                 container_depths: self.0.container_depths.inc_maybe()?,
+                error_location: None,
                 b: PhantomData,
             });
 
-            let v = visitor.visit_some(&mut de)?;
+            let v = visitor.visit_some(&mut de);
             self.0.pos += de.0.pos;
+            if v.is_err() {
+                self.0.error_location = Some(de.0.take_error_location());
+            }
+            let v = v?;
             // No need for retaking the container depths as the underlying type can't be incomplete.
 
             if !fixed_sized_child {
@@ -277,10 +324,50 @@ where
         visitor.visit_unit()
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        #[cfg(unix)]
+        if name == OWNED_FD_HANDOVER_NAME && self.0.sig_parser.next_char()? == Fd::SIGNATURE_CHAR {
+            // File descriptor indices are encoded just like in the D-Bus format.
+            let ctxt = self
+                .0
+                .ctxt
+                .nested(EncodingFormat::DBus, self.0.ctxt.position() + self.0.pos);
+            let mut dbus_de = crate::dbus::Deserializer::<B>(crate::DeserializerCommon::<B> {
+                ctxt,
+                sig_parser: self.0.sig_parser.clone(),
+                bytes: subslice(self.0.bytes, self.0.pos..)?,
+                fds: self.0.fds,
+                pos: 0,
+                value_start: 0,
+                container_depths: self.0.container_depths,
+                error_location: None,
+                b: PhantomData,
+            });
+
+            let v = de::Deserializer::deserialize_newtype_struct(&mut dbus_de, name, visitor);
+            if v.is_err() {
+                self.0.error_location = Some(dbus_de.0.take_error_location());
+            }
+            let v = v?;
+            self.0.sig_parser = dbus_de.0.sig_parser;
+            self.0.pos += dbus_de.0.pos;
+
+            return Ok(v);
+        }
+        if let Some(element) = crate::fixed_array::fixed_array_element(name) {
+            let signature = self.0.sig_parser.next_signature()?;
+            crate::fixed_array::check_fixed_array_signature(&signature, element)?;
+            // Wider elements can only be borrowed if they're encoded in native byte order.
+            if element != u8::SIGNATURE_CHAR && !is_native_endian::<B>() {
+                return self.deserialize_seq(visitor);
+            }
+
+            return visitor.visit_borrowed_bytes(deserialize_fixed_basic_array(self)?);
+        }
+
         visitor.visit_newtype_struct(self)
     }
 
@@ -324,6 +411,7 @@ where
                     end,
                     offsets_len: 0,
                     offset_size,
+                    index: 0,
                 });
                 self.0.container_depths = self.0.container_depths.dec_structure();
 
@@ -341,6 +429,7 @@ where
                     end,
                     offsets_len: 0,
                     offset_size: FramingOffsetSize::U8,
+                    index: 0,
                 })
             }
             c => Err(de::Error::invalid_type(
@@ -402,9 +491,17 @@ where
         return Err(de::Error::invalid_type(de::Unexpected::Seq, &"ay"));
     }
 
+    deserialize_fixed_basic_array(de)
+}
+
+// Deserialize an array of `FixedBasic` elements as its encoded bytes.
+fn deserialize_fixed_basic_array<'de, B>(de: &mut Deserializer<'de, '_, '_, B>) -> Result<&'de [u8]>
+where
+    B: byteorder::ByteOrder,
+{
     de.0.sig_parser.skip_char()?;
     let ad = ArrayDeserializer::new(de)?;
-    let len = dbg!(ad.len);
+    let len = ad.len;
     de.0.next_slice(len)
 }
 
@@ -422,6 +519,10 @@ struct ArrayDeserializer<'d, 'de, 'sig, 'f, B> {
     offsets_len: usize,
     // size of the framing offset of last dict-entry key read (GVariant-specific)
     key_offset_size: Option<FramingOffsetSize>,
+    // index of the next element
+    index: usize,
+    // where the last dict-entry key read starts and ends
+    key_range: (usize, usize),
 }
 
 impl<'d, 'de, 'sig, 'f, B> ArrayDeserializer<'d, 'de, 'sig, 'f, B>
@@ -468,6 +569,9 @@ where
         } else {
             (None, 0, None)
         };
+        if len > de.0.ctxt.limits().max_array_size() {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Array));
+        }
         let start = de.0.pos;
 
         if de.0.sig_parser.next_char()? == DICT_ENTRY_SIG_START_CHAR {
@@ -483,6 +587,8 @@ where
             offsets,
             offsets_len,
             key_offset_size,
+            index: 0,
+            key_range: (start, start),
         })
     }
 
@@ -531,7 +637,7 @@ where
             return Ok(None);
         }
 
-        let ctxt = EncodingContext::new(
+        let ctxt = self.de.0.ctxt.nested(
             self.de.0.ctxt.format(),
             self.de.0.ctxt.position() + self.de.0.pos,
         );
@@ -543,13 +649,22 @@ where
             bytes: subslice(self.de.0.bytes, self.de.0.pos..end)?,
             fds: self.de.0.fds,
             pos: 0,
+            value_start: 0,
             container_depths: self.de.0.container_depths,
+            error_location: None,
             b: PhantomData,
         });
 
         let v = seed.deserialize(&mut de).map(Some);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the child can't be incomplete.
+        if v.is_err() {
+            let location =
+                de.0.take_error_location()
+                    .in_path(format!("[{}]", self.index));
+            self.de.0.error_location = Some(location);
+        }
+        self.index += 1;
 
         if self.de.0.pos > self.start + self.len {
             return Err(serde::de::Error::invalid_length(
@@ -585,7 +700,7 @@ where
 
         self.de.0.parse_padding(self.element_alignment)?;
 
-        let ctxt = EncodingContext::new(
+        let ctxt = self.de.0.ctxt.nested(
             self.de.0.ctxt.format(),
             self.de.0.ctxt.position() + self.de.0.pos,
         );
@@ -603,6 +718,7 @@ where
             }
             None => element_end,
         };
+        self.key_range = (self.de.0.pos, key_end);
 
         let mut de = Deserializer::<B>(crate::DeserializerCommon {
             ctxt,
@@ -610,12 +726,17 @@ where
             bytes: subslice(self.de.0.bytes, self.de.0.pos..key_end)?,
             fds: self.de.0.fds,
             pos: 0,
+            value_start: 0,
             container_depths: self.de.0.container_depths,
+            error_location: None,
             b: PhantomData,
         });
         let v = seed.deserialize(&mut de).map(Some);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the key can't be incomplete.
+        if v.is_err() {
+            self.de.0.error_location = Some(de.0.take_error_location());
+        }
 
         if self.de.0.pos > self.start + self.len {
             return Err(serde::de::Error::invalid_length(
@@ -631,7 +752,7 @@ where
     where
         V: DeserializeSeed<'de>,
     {
-        let ctxt = EncodingContext::new(
+        let ctxt = self.de.0.ctxt.nested(
             self.de.0.ctxt.format(),
             self.de.0.ctxt.position() + self.de.0.pos,
         );
@@ -641,6 +762,7 @@ where
             None => element_end,
         };
         let mut sig_parser = self.de.0.sig_parser.clone();
+        let key_signature = sig_parser.next_char()?;
         // Skip key signature (always 1 char)
         sig_parser.skip_char()?;
 
@@ -650,12 +772,28 @@ where
             bytes: subslice(self.de.0.bytes, self.de.0.pos..value_end)?,
             fds: self.de.0.fds,
             pos: 0,
+            value_start: 0,
             container_depths: self.de.0.container_depths,
+            error_location: None,
             b: PhantomData,
         });
         let v = seed.deserialize(&mut de);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the value can't be incomplete.
+        if v.is_err() {
+            let (key_start, key_end) = self.key_range;
+            let ctxt = self.de.0.ctxt.nested(
+                self.de.0.ctxt.format(),
+                self.de.0.ctxt.position() + key_start,
+            );
+            let segment = crate::de::dict_value_path_segment(
+                subslice(self.de.0.bytes, key_start..key_end)?,
+                ctxt,
+                key_signature,
+            );
+            let location = de.0.take_error_location().in_path(segment);
+            self.de.0.error_location = Some(location);
+        }
 
         if let Some(key_offset_size) = self.key_offset_size {
             self.de.0.pos += key_offset_size as usize;
@@ -681,6 +819,8 @@ struct StructureDeserializer<'d, 'de, 'sig, 'f, B> {
     offsets_len: usize,
     // size of the framing offset
     offset_size: FramingOffsetSize,
+    // index of the next field
+    index: usize,
 }
 
 impl<'d, 'de, 'sig, 'f, B> SeqAccess<'de> for StructureDeserializer<'d, 'de, 'sig, 'f, B>
@@ -693,7 +833,7 @@ where
     where
         T: DeserializeSeed<'de>,
     {
-        let ctxt = EncodingContext::new(
+        let ctxt = self.de.0.ctxt.nested(
             self.de.0.ctxt.format(),
             self.de.0.ctxt.position() + self.de.0.pos,
         );
@@ -735,12 +875,21 @@ where
             bytes: subslice(self.de.0.bytes, self.de.0.pos..element_end)?,
             fds: self.de.0.fds,
             pos: 0,
+            value_start: 0,
             container_depths: self.de.0.container_depths,
+            error_location: None,
             b: PhantomData,
         });
         let v = seed.deserialize(&mut de).map(Some);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the field can't be incomplete.
+        if v.is_err() {
+            let location =
+                de.0.take_error_location()
+                    .in_path(format!(".{}", self.index));
+            self.de.0.error_location = Some(location);
+        }
+        self.index += 1;
 
         if de.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
             // Last item in the struct
@@ -835,7 +984,9 @@ where
                     bytes: subslice(self.de.0.bytes, self.sig_start..self.sig_end)?,
                     fds: self.de.0.fds,
                     pos: 0,
+                    value_start: 0,
                     container_depths: self.de.0.container_depths,
+                    error_location: None,
                     b: PhantomData,
                 });
 
@@ -849,7 +1000,7 @@ where
                 let signature = Signature::try_from(slice)?;
                 let sig_parser = SignatureParser::new(signature);
 
-                let ctxt = EncodingContext::new(
+                let ctxt = self.de.0.ctxt.nested(
                     self.de.0.ctxt.format(),
                     self.de.0.ctxt.position() + self.value_start,
                 );
@@ -859,11 +1010,16 @@ where
                     bytes: subslice(self.de.0.bytes, self.value_start..self.value_end)?,
                     fds: self.de.0.fds,
                     pos: 0,
+                    value_start: 0,
                     container_depths: self.de.0.container_depths.inc_variant()?,
+                    error_location: None,
                     b: PhantomData,
                 });
 
                 let v = seed.deserialize(&mut de).map(Some);
+                if v.is_err() {
+                    self.de.0.error_location = Some(de.0.take_error_location());
+                }
 
                 self.de.0.pos = self.sig_end;
 
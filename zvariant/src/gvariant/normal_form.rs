@@ -0,0 +1,62 @@
+use serde::{Serialize, Serializer};
+
+use crate::{
+    to_bytes_for_signature, EncodingContext, EncodingFormat, Error, Result, Signature, Value,
+};
+
+/// Convert GVariant-encoded data of the given signature to its normal form.
+///
+/// The GVariant specification allows for multiple encodings of the same value, e.g with unused
+/// bytes after the value in a variant. Only one of these encodings, the normal form, is suitable
+/// for comparing, hashing or signing the encoded data. This is the equivalent of GLib's
+/// `g_variant_get_normal_form` and is typically used to canonicalize data coming from untrusted
+/// sources, such as dconf databases or ostree repositories.
+///
+/// Unlike GLib, which silently replaces malformed values with default ones, this function returns
+/// an error if `bytes` can't be decoded. The encoded data must not contain any file descriptors.
+///
+/// # Panics
+///
+/// This function will panic if `ctxt` is not for the GVariant format.
+///
+/// # Examples
+///
+/// ```
+/// use byteorder::LE;
+/// use zvariant::{gvariant::to_normal_form, EncodingContext};
+///
+/// let ctxt = EncodingContext::<LE>::new_gvariant(0);
+/// // A variant holding `42_u32`, with 4 unused bytes after the value.
+/// let encoded = b"\x2a\0\0\0\xff\xff\xff\xff\0u";
+/// let normal = to_normal_form(encoded, ctxt, "v").unwrap();
+/// assert_eq!(normal, b"\x2a\0\0\0\0u");
+/// ```
+pub fn to_normal_form<'s, B, S>(
+    bytes: &[u8],
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> Result<Vec<u8>>
+where
+    B: byteorder::ByteOrder,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+{
+    assert_eq!(ctxt.format(), EncodingFormat::GVariant);
+
+    let signature = signature.try_into().map_err(Into::into)?;
+    let (value, _) = Value::deserialize_for_signature(bytes, ctxt, signature.clone())?;
+
+    to_bytes_for_signature(ctxt, signature, &Content(value))
+}
+
+// Serializes a `Value` as its contents, rather than as a variant.
+struct Content<'a>(Value<'a>);
+
+impl Serialize for Content<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_value_as_newtype_struct(serializer)
+    }
+}
@@ -50,7 +50,7 @@ where
             fds,
             bytes_written: 0,
             value_sign: None,
-            container_depths: Default::default(),
+            container_depths: ContainerDepths::new(ctxt.limits()),
             b: PhantomData,
         }))
     }
@@ -95,7 +95,10 @@ where
 macro_rules! serialize_basic {
     ($method:ident, $type:ty) => {
         fn $method(self, v: $type) -> Result<()> {
-            let ctxt = EncodingContext::new_dbus(self.0.ctxt.position());
+            let ctxt = self
+                .0
+                .ctxt
+                .nested(EncodingFormat::DBus, self.0.ctxt.position());
             let bytes_written = self.0.bytes_written;
             let mut dbus_ser = crate::dbus::Serializer(crate::SerializerCommon::<B, W> {
                 ctxt,
@@ -147,6 +150,17 @@ where
 
     serialize_basic!(serialize_f64, f64);
 
+    // Encoded as a `(tt)` structure of the high and low 64 bits.
+    #[cfg(feature = "int128")]
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        self.serialize_u128(v as u128)
+    }
+
+    #[cfg(feature = "int128")]
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        ((v >> 64) as u64, v as u64).serialize(self)
+    }
+
     fn serialize_i8(self, v: i8) -> Result<()> {
         // No i8 type in GVariant, let's pretend it's i16
         self.serialize_i16(v as i16)
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::{CStr, CString},
+    hash::BuildHasher,
+};
 
 #[cfg(feature = "gvariant")]
 use crate::Maybe;
@@ -104,12 +108,36 @@ where
     }
 }
 
+impl<'a, 'k, 'v, K, V> From<BTreeMap<K, V>> for Value<'a>
+where
+    'k: 'a,
+    'v: 'a,
+    K: Type + Into<Value<'k>> + std::cmp::Ord,
+    V: Type + Into<Value<'v>>,
+{
+    fn from(value: BTreeMap<K, V>) -> Self {
+        Self::Dict(value.into())
+    }
+}
+
 impl<'v> From<&'v String> for Value<'v> {
     fn from(v: &'v String) -> Value<'v> {
         Value::Str(v.into())
     }
 }
 
+impl From<CString> for Value<'static> {
+    fn from(v: CString) -> Self {
+        Value::Array(v.into_bytes().into())
+    }
+}
+
+impl<'v> From<&'v CStr> for Value<'v> {
+    fn from(v: &'v CStr) -> Value<'v> {
+        Value::Array(v.to_bytes().into())
+    }
+}
+
 impl<'v, V> From<Optional<V>> for Value<'v>
 where
     V: Into<Value<'v>> + NoneValue<NoneType = V>,
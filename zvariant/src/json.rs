@@ -0,0 +1,327 @@
+use serde::de::{Error as _, Unexpected};
+use serde_json::{Map, Number, Value as JsonValue};
+
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+use crate::{Array, Dict, Error, OwnedValue, Result, Signature, Str, Value};
+
+// The largest integer magnitude that all JSON consumers (notably JavaScript) can represent exactly.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// How to handle data that doesn't map cleanly between [`Value`] and JSON.
+///
+/// JSON has far fewer types than D-Bus, so some information is necessarily lost in translation:
+/// all integer and floating point types become JSON numbers, object paths and signatures become
+/// JSON strings, structures become JSON arrays and variants are transparent. Byte arrays (`ay`)
+/// are mapped to JSON arrays of numbers. File descriptors are never valid outside the process.
+///
+/// Converting JSON into a [`Value`], JSON objects become `a{sv}` dictionaries, JSON arrays whose
+/// elements all have the same type become typed arrays (e.g `ax` or `as`) and others become `av`.
+/// Integers become `x` (or `t` if too large for `i64`), other numbers become `d`.
+///
+/// This type is only available with the `json` feature enabled.
+///
+/// [`Value`]: enum.Value.html
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonMode {
+    /// Fail rather than produce data that a JSON consumer could misinterpret.
+    ///
+    /// Converting to JSON, non-finite floating point numbers, 64-bit integers with a magnitude
+    /// larger than 2<sup>53</sup> - 1 (which JavaScript can't represent exactly), dictionary keys
+    /// that aren't strings, object paths or signatures, and file descriptors are rejected.
+    ///
+    /// Converting from JSON, `null` and arrays with elements of different types are rejected.
+    #[default]
+    Strict,
+    /// Always convert, approximating where needed.
+    ///
+    /// Converting to JSON, non-finite floating point numbers become `null`, all integers are
+    /// written as is, dictionary keys are converted to strings and file descriptors become their
+    /// number.
+    ///
+    /// Converting from JSON, `null` elements of arrays and members of objects are skipped and arrays
+    /// with elements of different types become `av`.
+    Lossy,
+}
+
+impl<'a> Value<'a> {
+    /// Convert `self` to a JSON value.
+    ///
+    /// See [`JsonMode`] for details on the mapping. This method is only available with the `json`
+    /// feature enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zvariant::{JsonMode, Value};
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert("Name", Value::from("zbus"));
+    /// map.insert("Versions", Value::from(vec![1u32, 2, 3]));
+    /// let value = Value::from(map);
+    ///
+    /// let json = value.to_json(JsonMode::Strict).unwrap();
+    /// assert_eq!(
+    ///     json,
+    ///     serde_json::json!({ "Name": "zbus", "Versions": [1, 2, 3] }),
+    /// );
+    ///
+    /// // Larger than JavaScript can represent exactly.
+    /// let value = Value::from(u64::MAX);
+    /// assert!(value.to_json(JsonMode::Strict).is_err());
+    /// assert_eq!(value.to_json(JsonMode::Lossy).unwrap(), serde_json::json!(u64::MAX));
+    /// ```
+    ///
+    /// [`JsonMode`]: enum.JsonMode.html
+    pub fn to_json(&self, mode: JsonMode) -> Result<JsonValue> {
+        let json = match self {
+            Value::U8(v) => (*v).into(),
+            Value::Bool(v) => (*v).into(),
+            Value::I16(v) => (*v).into(),
+            Value::U16(v) => (*v).into(),
+            Value::I32(v) => (*v).into(),
+            Value::U32(v) => (*v).into(),
+            Value::I64(v) => {
+                if mode == JsonMode::Strict && v.unsigned_abs() > MAX_SAFE_INTEGER {
+                    return Err(Error::invalid_value(
+                        Unexpected::Signed(*v),
+                        &"an integer that can be exactly represented in JSON",
+                    ));
+                }
+
+                (*v).into()
+            }
+            Value::U64(v) => {
+                if mode == JsonMode::Strict && *v > MAX_SAFE_INTEGER {
+                    return Err(Error::invalid_value(
+                        Unexpected::Unsigned(*v),
+                        &"an integer that can be exactly represented in JSON",
+                    ));
+                }
+
+                (*v).into()
+            }
+            Value::F64(v) => match Number::from_f64(*v) {
+                Some(n) => JsonValue::Number(n),
+                None if mode == JsonMode::Lossy => JsonValue::Null,
+                None => {
+                    return Err(Error::invalid_value(
+                        Unexpected::Float(*v),
+                        &"a finite number",
+                    ))
+                }
+            },
+            Value::Str(v) => v.as_str().into(),
+            Value::Signature(v) => v.as_str().into(),
+            Value::ObjectPath(v) => v.as_str().into(),
+            Value::Value(v) => v.to_json(mode)?,
+            Value::Array(array) => JsonValue::Array(
+                array
+                    .get()
+                    .iter()
+                    .map(|v| v.to_json(mode))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::Dict(dict) => {
+                let mut object = Map::new();
+                for (k, v) in dict.iter() {
+                    object.insert(json_key(k, mode)?, v.to_json(mode)?);
+                }
+
+                JsonValue::Object(object)
+            }
+            Value::Structure(structure) => JsonValue::Array(
+                structure
+                    .fields()
+                    .iter()
+                    .map(|v| v.to_json(mode))
+                    .collect::<Result<_>>()?,
+            ),
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(maybe) => match maybe.inner() {
+                Some(v) => v.to_json(mode)?,
+                None => JsonValue::Null,
+            },
+            #[cfg(unix)]
+            Value::Fd(fd) => match mode {
+                JsonMode::Lossy => fd.as_raw_fd().into(),
+                JsonMode::Strict => {
+                    return Err(Error::invalid_type(
+                        Unexpected::Other("file descriptor"),
+                        &"a value representable in JSON",
+                    ))
+                }
+            },
+        };
+
+        Ok(json)
+    }
+
+    /// Create a `Value` from a JSON value.
+    ///
+    /// See [`JsonMode`] for details on the mapping. This method is only available with the `json`
+    /// feature enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{JsonMode, Value};
+    ///
+    /// let json = serde_json::json!({ "Name": "zbus", "Versions": [1, 2, 3] });
+    /// let value = Value::from_json(&json, JsonMode::Strict).unwrap();
+    /// assert_eq!(value.value_signature(), "a{sv}");
+    ///
+    /// let json = serde_json::json!([1, "two", null]);
+    /// assert!(Value::from_json(&json, JsonMode::Strict).is_err());
+    /// let value = Value::from_json(&json, JsonMode::Lossy).unwrap();
+    /// assert_eq!(value.value_signature(), "av");
+    /// ```
+    ///
+    /// [`JsonMode`]: enum.JsonMode.html
+    pub fn from_json(json: &JsonValue, mode: JsonMode) -> Result<Value<'static>> {
+        let value = match json {
+            JsonValue::Null => {
+                return Err(Error::invalid_type(
+                    Unexpected::Unit,
+                    &"a value representable in D-Bus",
+                ))
+            }
+            JsonValue::Bool(b) => Value::Bool(*b),
+            JsonValue::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                (Some(i), _, _) => Value::I64(i),
+                (None, Some(u), _) => Value::U64(u),
+                (None, None, Some(f)) => Value::F64(f),
+                // Only possible with serde_json's `arbitrary_precision` feature.
+                (None, None, None) => {
+                    return Err(Error::invalid_value(
+                        Unexpected::Other("number"),
+                        &"a number that fits in 64 bits",
+                    ))
+                }
+            },
+            JsonValue::String(s) => Value::Str(Str::from(s.clone())),
+            JsonValue::Array(elements) => {
+                let elements = from_json_elements(elements, mode)?;
+                let signatures_match = elements
+                    .windows(2)
+                    .all(|w| w[0].value_signature() == w[1].value_signature());
+                let mut array = match elements.first() {
+                    Some(first) if signatures_match => {
+                        Array::new(first.value_signature().to_owned())
+                    }
+                    Some(_) if mode == JsonMode::Strict => {
+                        return Err(Error::invalid_value(
+                            Unexpected::Seq,
+                            &"an array with elements of the same type",
+                        ))
+                    }
+                    _ => Array::new(Signature::from_static_str_unchecked("v")),
+                };
+                let variant = array.element_signature() == "v";
+                for element in elements {
+                    if variant {
+                        array.append(Value::Value(Box::new(element)))?;
+                    } else {
+                        array.append(element)?;
+                    }
+                }
+
+                Value::Array(array)
+            }
+            JsonValue::Object(members) => {
+                let mut dict = Dict::new(
+                    Signature::from_static_str_unchecked("s"),
+                    Signature::from_static_str_unchecked("v"),
+                );
+                for (k, v) in members {
+                    if v.is_null() && mode == JsonMode::Lossy {
+                        continue;
+                    }
+                    let v = Value::from_json(v, mode)?;
+                    dict.append(Value::from(k.clone()), Value::Value(Box::new(v)))?;
+                }
+
+                Value::Dict(dict)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+fn from_json_elements(elements: &[JsonValue], mode: JsonMode) -> Result<Vec<Value<'static>>> {
+    elements
+        .iter()
+        .filter(|e| !(e.is_null() && mode == JsonMode::Lossy))
+        .map(|e| Value::from_json(e, mode))
+        .collect()
+}
+
+fn json_key(key: &Value<'_>, mode: JsonMode) -> Result<String> {
+    match (key, mode) {
+        (Value::Str(s), _) => Ok(s.to_string()),
+        (Value::ObjectPath(p), _) => Ok(p.to_string()),
+        (Value::Signature(s), _) => Ok(s.to_string()),
+        (Value::U8(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::Bool(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::I16(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::U16(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::I32(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::U32(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::I64(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::U64(v), JsonMode::Lossy) => Ok(v.to_string()),
+        (Value::F64(v), JsonMode::Lossy) => Ok(v.to_string()),
+        #[cfg(unix)]
+        (Value::Fd(fd), JsonMode::Lossy) => Ok(fd.as_raw_fd().to_string()),
+        _ => Err(Error::invalid_type(
+            Unexpected::Other(&format!("`{}` key", key.value_signature())),
+            &"a string, object path or signature key",
+        )),
+    }
+}
+
+/// Converts in the [`JsonMode::Strict`] mode.
+///
+/// [`JsonMode::Strict`]: enum.JsonMode.html#variant.Strict
+impl TryFrom<&Value<'_>> for JsonValue {
+    type Error = Error;
+
+    fn try_from(value: &Value<'_>) -> Result<Self> {
+        value.to_json(JsonMode::Strict)
+    }
+}
+
+/// Converts in the [`JsonMode::Strict`] mode.
+///
+/// [`JsonMode::Strict`]: enum.JsonMode.html#variant.Strict
+impl TryFrom<&JsonValue> for Value<'static> {
+    type Error = Error;
+
+    fn try_from(json: &JsonValue) -> Result<Self> {
+        Value::from_json(json, JsonMode::Strict)
+    }
+}
+
+/// Converts in the [`JsonMode::Strict`] mode.
+///
+/// [`JsonMode::Strict`]: enum.JsonMode.html#variant.Strict
+impl TryFrom<&OwnedValue> for JsonValue {
+    type Error = Error;
+
+    fn try_from(value: &OwnedValue) -> Result<Self> {
+        value.to_json(JsonMode::Strict)
+    }
+}
+
+/// Converts in the [`JsonMode::Strict`] mode.
+///
+/// [`JsonMode::Strict`]: enum.JsonMode.html#variant.Strict
+impl TryFrom<&JsonValue> for OwnedValue {
+    type Error = Error;
+
+    fn try_from(json: &JsonValue) -> Result<Self> {
+        Value::from_json(json, JsonMode::Strict).map(OwnedValue)
+    }
+}
@@ -41,6 +41,14 @@ pub use ser::*;
 mod de;
 pub use de::*;
 
+mod reader;
+pub use reader::*;
+
+#[cfg(feature = "async")]
+mod async_io;
+#[cfg(feature = "async")]
+pub use async_io::*;
+
 pub mod dbus;
 #[cfg(feature = "gvariant")]
 pub mod gvariant;
@@ -48,6 +56,9 @@ pub mod gvariant;
 mod signature;
 pub use crate::signature::*;
 
+mod signature_node;
+pub use crate::signature_node::*;
+
 mod complete_type;
 pub use complete_type::*;
 
@@ -65,9 +76,37 @@ pub use crate::maybe::*;
 mod optional;
 pub use crate::optional::*;
 
+mod duration;
+pub use crate::duration::*;
+
+mod file_path;
+pub use crate::file_path::*;
+
+mod fixed_array;
+pub use crate::fixed_array::*;
+
 mod value;
 pub use value::*;
 
+mod value_parser;
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "json")]
+pub use json::*;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+mod value_visitor;
+pub use value_visitor::*;
+
+mod pretty;
+pub use pretty::*;
+
+mod schema;
+pub use schema::*;
+
 mod serialize_value;
 pub use serialize_value::*;
 
@@ -98,7 +137,9 @@ mod signature_parser;
 
 mod container_depths;
 
-pub use zvariant_derive::{DeserializeDict, OwnedValue, SerializeDict, Type, Value};
+pub use zvariant_derive::{
+    DeserializeDict, DeserializeRepr, OwnedValue, SerializeDict, SerializeRepr, Type, Value,
+};
 
 // Required for the macros to function within this crate.
 extern crate self as zvariant;
@@ -106,7 +147,14 @@ extern crate self as zvariant;
 // Macro support module, not part of the public API.
 #[doc(hidden)]
 pub mod export {
+    #[cfg(feature = "bitflags")]
+    pub use bitflags;
+    pub use once_cell;
     pub use serde;
+
+    /// Implemented by the `SerializeRepr` derive, so the `Type` derive can ensure the enums given
+    /// a `#[zvariant(repr = "...")]` attribute are actually encoded as that integer type.
+    pub trait ReprEncoded {}
 }
 
 #[cfg(test)]
@@ -364,6 +412,58 @@ mod tests {
         basic_type_test!(LE, GVariant, Fd::from(42), 4, Fd, 4, Fd, 6);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn owned_fds() {
+        use crate::{from_slice_owned_fds, OwnedFd};
+        use std::{
+            fs::File,
+            os::unix::io::{AsRawFd, OwnedFd as StdOwnedFd},
+        };
+
+        fn check(format: EncodingFormat) {
+            let file1 = File::open("Cargo.toml").unwrap();
+            let file2 = File::open("Cargo.toml").unwrap();
+            let (raw1, raw2) = (file1.as_raw_fd(), file2.as_raw_fd());
+
+            let ctxt = Context::<LE>::new(format, 0);
+            // The first file descriptor is referred to twice.
+            let value = (Fd::from(&file1), Fd::from(&file2), Fd::from(&file1));
+            let (encoded, fds) = to_bytes_fds(ctxt, &value).unwrap();
+            assert_eq!(fds, [raw1, raw2]);
+
+            let fds = vec![StdOwnedFd::from(file1), StdOwnedFd::from(file2)];
+            let (first, fd, second): (OwnedFd, Fd, OwnedFd) =
+                from_slice_owned_fds(&encoded, fds, ctxt).unwrap().0;
+            // Taken over, rather than duplicated, unless already taken.
+            assert_eq!(first.as_raw_fd(), raw1);
+            assert_eq!(fd.as_raw_fd(), raw2);
+            assert_ne!(second.as_raw_fd(), raw1);
+
+            // Conversions with the standard library types.
+            let std_fd = StdOwnedFd::from(first);
+            assert_eq!(std_fd.as_raw_fd(), raw1);
+            let owned = OwnedFd::from(std_fd);
+            assert_eq!(owned.as_raw_fd(), raw1);
+            assert_ne!(owned.try_clone().unwrap().as_raw_fd(), raw1);
+
+            // Without the ownership, file descriptors are duplicated.
+            let (encoded, fds) = to_bytes_fds(ctxt, &Fd::from(&owned)).unwrap();
+            let dup: OwnedFd = from_slice_fds(&encoded, Some(&fds), ctxt).unwrap().0;
+            assert_ne!(dup.as_raw_fd(), raw1);
+
+            // Other deserializers can't hand over file descriptors they don't own.
+            let json = format!(r#"{{"$zvariant::private::OwnedFd": {raw1}}}"#);
+            serde_json::from_str::<OwnedFd>(&json).unwrap_err();
+            let dup: OwnedFd = serde_json::from_str(&raw1.to_string()).unwrap();
+            assert_ne!(dup.as_raw_fd(), raw1);
+        }
+
+        check(EncodingFormat::DBus);
+        #[cfg(feature = "gvariant")]
+        check(EncodingFormat::GVariant);
+    }
+
     #[test]
     fn u16_value() {
         let encoded = basic_type_test!(BE, DBus, 0xABBA_u16, 2, u16, 2, U16, 6);
@@ -502,6 +602,124 @@ mod tests {
         assert_eq!(v, Value::new("c"));
     }
 
+    #[test]
+    fn borrowed_strings() {
+        use std::borrow::Cow;
+
+        fn check(ctxt: Context<LE>) {
+            let names = vec!["org.freedesktop.DBus", "org.freedesktop.zbus"];
+            let encoded = to_bytes(ctxt, &names).unwrap();
+            let within = |s: &str| encoded.as_ptr_range().contains(&s.as_ptr());
+
+            let decoded: Vec<&str> = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, names);
+            assert!(decoded.iter().all(|s| within(s)));
+
+            let decoded: Vec<Str<'_>> = from_slice(&encoded, ctxt).unwrap().0;
+            assert!(decoded.into_iter().all(|s| match Cow::from(s) {
+                Cow::Borrowed(s) => within(s),
+                Cow::Owned(_) => false,
+            }));
+
+            let value = Value::from(names[0]);
+            let encoded = to_bytes(ctxt, &value).unwrap();
+            let decoded: Value<'_> = from_slice(&encoded, ctxt).unwrap().0;
+            match decoded {
+                Value::Str(s) => {
+                    assert!(matches!(Cow::from(s), Cow::Borrowed(s) if s == names[0]))
+                }
+                _ => panic!("unexpected value: {decoded:?}"),
+            }
+        }
+
+        check(Context::<LE>::new_dbus(0));
+        #[cfg(feature = "gvariant")]
+        check(Context::<LE>::new_gvariant(0));
+    }
+
+    #[test]
+    fn byte_swapping() {
+        #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+        struct All<'a> {
+            y: u8,
+            b: bool,
+            n: i16,
+            q: u16,
+            i: i32,
+            u: u32,
+            x: i64,
+            t: u64,
+            d: f64,
+            s: &'a str,
+            o: ObjectPath<'a>,
+            g: Signature<'a>,
+            an: Vec<i16>,
+            at: Vec<u64>,
+            ad: Vec<f64>,
+            dict: HashMap<u16, Vec<u32>>,
+            v: Value<'a>,
+        }
+
+        fn check(format: EncodingFormat, value: &All<'_>) {
+            let le = Context::<LE>::new(format, 0);
+            let be = Context::<BE>::new(format, 0);
+            let le_encoded = to_bytes(le, value).unwrap();
+            let be_encoded = to_bytes(be, value).unwrap();
+            assert_eq!(le_encoded.len(), be_encoded.len());
+            assert_ne!(le_encoded, be_encoded);
+
+            for (encoded, decoded) in [
+                (
+                    &le_encoded,
+                    from_slice::<_, All<'_>>(&le_encoded, le).unwrap().0,
+                ),
+                (
+                    &be_encoded,
+                    from_slice::<_, All<'_>>(&be_encoded, be).unwrap().0,
+                ),
+            ] {
+                assert_eq!(&decoded, value, "{encoded:?}");
+            }
+
+            // Through `Value` as well, which takes different code paths.
+            let le_decoded: Structure<'_> =
+                crate::from_slice_for_dynamic_signature(&le_encoded, le, All::signature())
+                    .unwrap()
+                    .0;
+            let be_decoded: Structure<'_> =
+                crate::from_slice_for_dynamic_signature(&be_encoded, be, All::signature())
+                    .unwrap()
+                    .0;
+            assert_eq!(le_decoded, be_decoded);
+            assert_eq!(to_bytes(be, &be_decoded).unwrap(), be_encoded);
+        }
+
+        let mut dict = HashMap::new();
+        dict.insert(0xABCD, vec![0x0102_0304, 0xA0B0_C0D0]);
+        let value = All {
+            y: 0xAB,
+            b: true,
+            n: -0x0102,
+            q: 0xABCD,
+            i: -0x0102_0304,
+            u: 0xABCD_EF01,
+            x: -0x0102_0304_0506_0708,
+            t: 0xABCD_EF01_2345_6789,
+            d: -1.0e-10,
+            s: "hello",
+            o: ObjectPath::try_from("/hello/world").unwrap(),
+            g: Signature::try_from("a{sv}").unwrap(),
+            an: vec![-0x0102, 0x0304],
+            at: vec![0x0102_0304_0506_0708, 0xABCD_EF01_2345_6789],
+            ad: vec![1.5, -2.25e100],
+            dict,
+            v: Value::from(vec![0xABCD_u16, 0x0102]),
+        };
+        check(EncodingFormat::DBus, &value);
+        #[cfg(feature = "gvariant")]
+        check(EncodingFormat::GVariant, &value);
+    }
+
     #[cfg(feature = "arrayvec")]
     #[test]
     fn array_string_value() {
@@ -1151,6 +1369,35 @@ mod tests {
         }
         let ctxt = Context::<LE>::new_dbus(0);
 
+        // Sorted dicts encode the same, whatever the insertion order.
+        let mut dict1 = Dict::new(<&str>::signature(), u32::signature());
+        dict1
+            .append_unique(Value::from("b"), Value::U32(2))
+            .unwrap();
+        dict1
+            .append_unique(Value::from("a"), Value::U32(1))
+            .unwrap();
+        assert!(dict1
+            .append_unique(Value::from("a"), Value::U32(3))
+            .is_err());
+        let dict2: Dict<'_, '_> = [("a", 1_u32), ("b", 2)].into_iter().collect();
+        assert_ne!(dict1, dict2);
+        let dict1 = dict1.sorted();
+        assert_eq!(dict1, dict2.clone().sorted());
+        assert_eq!(
+            to_bytes(ctxt, &dict1).unwrap(),
+            to_bytes(ctxt, &dict2).unwrap()
+        );
+
+        // Extending replaces the values of existing keys, rejecting mismatched signatures.
+        let mut dict = dict2;
+        dict.extend([("b", 3_u32), ("c", 4)]);
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict.get::<_, u32>("b").unwrap(), Some(&3));
+        assert!(dict.try_extend([("d", "four")]).is_err());
+        assert!(dict.try_extend([(4_u8, 4_u32)]).is_err());
+        assert_eq!(dict.len(), 3);
+
         // Now a hand-crafted Dict Value but with a Value as value
         let mut dict = Dict::new(<&str>::signature(), Value::signature());
         dict.add("hello", Value::new("there")).unwrap();
@@ -1216,6 +1463,35 @@ mod tests {
             Error::Message("missing field `quota`".to_string())
         );
 
+        fn default_quota() -> u8 {
+            10
+        }
+
+        #[derive(SerializeDict, DeserializeDict, Type, PartialEq, Debug)]
+        #[zvariant(signature = "a{sv}")]
+        struct TestDefault {
+            process_id: Option<u32>,
+            #[zvariant(default = "Default::default")]
+            group_id: Option<u32>,
+            #[zvariant(default)]
+            user: String,
+            #[zvariant(default)]
+            admin: bool,
+            #[zvariant(default = "default_quota")]
+            quota: u8,
+        }
+        let decoded: TestDefault = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(
+            decoded,
+            TestDefault {
+                process_id: Some(42),
+                group_id: None,
+                user: "me".to_string(),
+                admin: false,
+                quota: 10,
+            }
+        );
+
         #[derive(SerializeDict, DeserializeDict, Type, PartialEq, Debug)]
         #[zvariant(signature = "a{sv}")]
         struct TestSkipUnknown {
@@ -1235,6 +1511,32 @@ mod tests {
             decoded.unwrap_err(),
             Error::Message("unknown field `user`, expected `process_id` or `group_id`".to_string())
         );
+
+        #[derive(SerializeDict, DeserializeDict, Type, PartialEq, Debug)]
+        #[zvariant(deny_unknown_fields, signature = "a{sv}")]
+        struct TestStrictType {
+            process_id: Option<u32>,
+            group_id: Option<u32>,
+            user: u32,
+        }
+        let decoded: Result<(TestStrictType, _)> = from_slice(&encoded, ctxt);
+        assert!(decoded.is_err());
+
+        // Lenient mode silently skips the entry with an unexpected type.
+        #[derive(SerializeDict, DeserializeDict, Type, PartialEq, Debug)]
+        #[zvariant(signature = "a{sv}")]
+        struct TestLenientType {
+            process_id: Option<u32>,
+            user: Option<u32>,
+        }
+        let decoded: TestLenientType = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(
+            decoded,
+            TestLenientType {
+                process_id: Some(42),
+                user: None,
+            }
+        );
     }
 
     #[test]
@@ -1281,6 +1583,192 @@ mod tests {
         assert_eq!(v, Value::U64(0xFEFE));
     }
 
+    #[test]
+    fn value_hash_ord() {
+        use std::collections::{BTreeSet, HashSet};
+
+        // Kinds are ordered as the variants are declared, regardless of contents.
+        assert!(Value::U8(u8::MAX) < Value::Bool(false));
+        assert!(Value::U64(u64::MAX) < Value::F64(f64::MIN));
+        assert!(Value::from("zzz") < Value::from(ObjectPath::try_from("/").unwrap()));
+
+        // Floats are compared by their bit patterns.
+        assert_eq!(Value::F64(f64::NAN), Value::F64(f64::NAN));
+        assert_ne!(Value::F64(0.0), Value::F64(-0.0));
+        assert!(Value::F64(-1.0) < Value::F64(1.0));
+
+        // Containers.
+        assert!(Value::new(vec![1u32, 2]) < Value::new(vec![1u32, 3]));
+        assert!(Value::new((1u8, "a")) < Value::new((1u8, "b")));
+        assert_eq!(Value::new(vec![1u32, 2]), Value::new(vec![1u32, 2]));
+        // Containers of different signatures are ordered by their signatures first.
+        assert!(Value::new((1u8, "b")) < Value::new((1u8, 0u32)));
+        let dict_su: Dict<'_, '_> = [("a", 1u32)].into_iter().collect();
+        let dict_ss: Dict<'_, '_> = [("b", "x")].into_iter().collect();
+        assert!(Value::from(dict_ss) < Value::from(dict_su));
+
+        let values = vec![
+            Value::new("b"),
+            Value::new(2u32),
+            Value::new("a"),
+            Value::new(1u32),
+            Value::new(2u32),
+            Value::F64(f64::NAN),
+            Value::new(vec![1u8]),
+        ];
+        let hashed: HashSet<_> = values.iter().cloned().collect();
+        assert_eq!(hashed.len(), 6);
+        let sorted: Vec<_> = values
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                Value::new(1u32),
+                Value::new(2u32),
+                Value::F64(f64::NAN),
+                Value::new("a"),
+                Value::new("b"),
+                Value::new(vec![1u8]),
+            ]
+        );
+
+        // Values can be used as dictionary keys.
+        let mut map = HashMap::new();
+        map.insert(Value::new(42u32), "answer");
+        assert_eq!(map[&Value::new(42u32)], "answer");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        use crate::JsonMode;
+        use serde_json::json;
+
+        let value = Value::new((
+            42u8,
+            -7i32,
+            ObjectPath::try_from("/a/b").unwrap(),
+            Value::new("inner"),
+            vec![true, false],
+        ));
+        let json = value.to_json(JsonMode::Strict).unwrap();
+        assert_eq!(json, json!([42, -7, "/a/b", "inner", [true, false]]));
+        assert_eq!(serde_json::Value::try_from(&value).unwrap(), json);
+
+        // Values JSON can't carry faithfully.
+        for value in [
+            Value::F64(f64::NAN),
+            Value::I64(i64::MIN),
+            Value::U64(1 << 53),
+        ] {
+            value.to_json(JsonMode::Strict).unwrap_err();
+        }
+        assert_eq!(
+            Value::F64(f64::INFINITY).to_json(JsonMode::Lossy).unwrap(),
+            json!(null)
+        );
+        assert_eq!(
+            Value::U64(1 << 53).to_json(JsonMode::Lossy).unwrap(),
+            json!(1u64 << 53)
+        );
+
+        // Non-string dictionary keys.
+        let mut dict = Dict::new(u32::signature(), <&str>::signature());
+        dict.append(Value::U32(1), Value::new("one")).unwrap();
+        let value = Value::Dict(dict);
+        value.to_json(JsonMode::Strict).unwrap_err();
+        assert_eq!(
+            value.to_json(JsonMode::Lossy).unwrap(),
+            json!({ "1": "one" })
+        );
+
+        // From JSON.
+        let json = json!({ "name": "zbus", "ids": [1, 2, u64::MAX], "ratio": 0.5 });
+        Value::from_json(&json, JsonMode::Strict).unwrap_err();
+        let value = Value::from_json(&json, JsonMode::Lossy).unwrap();
+        assert_eq!(value.value_signature(), "a{sv}");
+        let member = |name: &str| match &value {
+            Value::Dict(dict) => dict
+                .iter()
+                .find(|(k, _)| **k == Value::from(name))
+                .map(|(_, v)| v.clone())
+                .unwrap(),
+            _ => panic!("expected a dict"),
+        };
+        assert_eq!(member("ratio"), Value::Value(Box::new(Value::F64(0.5))));
+        match member("ids") {
+            Value::Value(ids) => assert_eq!(ids.value_signature(), "av"),
+            _ => panic!("expected a variant"),
+        }
+        assert_eq!(value.to_json(JsonMode::Lossy).unwrap(), json);
+
+        let json = json!([["a", "b"], ["c"]]);
+        let value = Value::try_from(&json).unwrap();
+        assert_eq!(value.value_signature(), "aas");
+        assert_eq!(serde_json::Value::try_from(&value).unwrap(), json);
+
+        let json = json!([1, null, 2]);
+        Value::from_json(&json, JsonMode::Strict).unwrap_err();
+        let value = Value::from_json(&json, JsonMode::Lossy).unwrap();
+        assert_eq!(value, Value::new(vec![1i64, 2]));
+    }
+
+    #[test]
+    fn owned_types() {
+        use crate::{DynamicType, OwnedArray, OwnedDict, OwnedStr, OwnedStructure, OwnedValue};
+
+        // Owned types outlive the data they were created from.
+        struct Stored {
+            name: OwnedStr,
+            ids: OwnedArray,
+            props: OwnedDict,
+            pair: OwnedStructure,
+        }
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let stored = {
+            let encoded = to_bytes(ctxt, &("zbus", vec![1u32, 2])).unwrap();
+            let (name, ids): (Str<'_>, Vec<u32>) = from_slice(&encoded, ctxt).unwrap().0;
+            let ids = Array::from(ids);
+            let mut props = HashMap::new();
+            props.insert("version", Value::from(4u32));
+            let props = Dict::from(props);
+            let pair = Structure::from((name.clone(), 42u8));
+
+            Stored {
+                name: name.into(),
+                ids: ids.into(),
+                props: props.into(),
+                pair: pair.into(),
+            }
+        };
+
+        assert_eq!(stored.name.as_str(), "zbus");
+        assert_eq!(stored.name.to_string(), "zbus");
+        assert_eq!(Str::from(&stored.name), "zbus");
+        assert_eq!(stored.ids.len(), 2);
+        assert_eq!(stored.ids.dynamic_signature(), "au");
+        assert_eq!(stored.props.dynamic_signature(), "a{sv}");
+        assert_eq!(stored.pair.fields()[1], Value::U8(42));
+
+        // Encoded just like their borrowed counterparts.
+        let encoded = to_bytes(ctxt, &stored.ids).unwrap();
+        assert_eq!(encoded, to_bytes(ctxt, &vec![1u32, 2]).unwrap());
+        let encoded = to_bytes(ctxt, &stored.name).unwrap();
+        let decoded: OwnedStr = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, stored.name);
+
+        // Conversions from and to (owned) values.
+        let value = OwnedValue::from(stored.pair.clone());
+        assert_eq!(OwnedStructure::try_from(value).unwrap(), stored.pair);
+        let value = Value::from(stored.props.clone());
+        assert_eq!(OwnedDict::try_from(value).unwrap(), stored.props);
+        OwnedArray::try_from(Value::from("not an array")).unwrap_err();
+    }
+
     #[test]
     fn enums() {
         use serde::{Deserialize, Serialize};
@@ -1614,6 +2102,43 @@ mod tests {
         assert_eq!(decoded, s);
     }
 
+    #[test]
+    fn byte_array_value() {
+        let bytes = vec![77u8; 100_000];
+        let value = Value::from(bytes.clone());
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let encoded = to_bytes(ctxt, &value).unwrap();
+        assert_eq!(encoded.len(), 100_008);
+        let decoded: Value<'_> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, value);
+
+        // Same encoding as the `Vec<u8>` itself.
+        let array = Array::from(&bytes);
+        let encoded = to_bytes(ctxt, &array).unwrap();
+        assert_eq!(encoded, to_bytes(ctxt, &bytes).unwrap());
+        let decoded: Array<'_> = crate::from_slice_for_dynamic_signature(&encoded, ctxt, "ay")
+            .unwrap()
+            .0;
+        assert_eq!(decoded, array);
+
+        // Also inside other containers.
+        let mut map = HashMap::new();
+        map.insert("data", value.clone());
+        let encoded = to_bytes(ctxt, &map).unwrap();
+        let decoded: HashMap<String, Value<'_>> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded["data"], value);
+
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::<LE>::new_gvariant(0);
+            let encoded = to_bytes(ctxt, &value).unwrap();
+            assert_eq!(encoded.len(), 100_003);
+            let decoded: Value<'_> = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, value);
+        }
+    }
+
     #[test]
     #[cfg(any(feature = "gvariant", feature = "option-as-array"))]
     fn option_value() {
@@ -1853,6 +2378,25 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "gvariant", not(feature = "option-as-array")))]
+    fn option_value_dbus() {
+        let ctxt = Context::<NativeEndian>::new_dbus(0);
+
+        for mn in [Some(16i16), None] {
+            match to_bytes(ctxt, &mn) {
+                Err(Error::IncompatibleFormat(sig, EncodingFormat::DBus)) => assert_eq!(sig, "mn"),
+                res => panic!("unexpected result: {res:?}"),
+            }
+        }
+
+        let res: Result<(Option<i16>, _)> = from_slice(&[0, 0, 0, 0], ctxt);
+        match res {
+            Err(Error::IncompatibleFormat(sig, EncodingFormat::DBus)) => assert_eq!(sig, "mn"),
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+
     #[test]
     fn struct_with_hashmap() {
         use serde::{Deserialize, Serialize};
@@ -1931,6 +2475,274 @@ mod tests {
         assert_eq!(localhost_v6, decoded);
     }
 
+    #[test]
+    fn cstring() {
+        use std::ffi::{CStr, CString};
+
+        assert_eq!(CString::signature(), "ay");
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let s = CString::new("hello").unwrap();
+        let encoded = to_bytes(ctxt, &s).unwrap();
+        assert_eq!(&encoded[4..], b"hello");
+        let decoded: CString = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, s);
+
+        let v = Value::from(s.as_c_str());
+        assert_eq!(v.value_signature(), "ay");
+        assert_eq!(CString::try_from(v).unwrap(), s);
+        assert_eq!(CString::try_from(Value::from("hello")).unwrap(), s);
+        CString::try_from(Value::from("hel\0lo")).unwrap_err();
+        CString::try_from(Value::from(42_u32)).unwrap_err();
+
+        let s: &CStr = &s;
+        let encoded = to_bytes(ctxt, s).unwrap();
+        assert_eq!(&encoded[4..], b"hello");
+    }
+
+    #[test]
+    fn signature_checked_deserialization() {
+        use crate::from_slice_with_signature;
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let value = (42_u32, vec![("hello", 1_u8)]);
+        let encoded = to_bytes(ctxt, &value).unwrap();
+
+        // Both the signature with and without the outer parentheses are accepted.
+        let (decoded, _): ((u32, Vec<(String, u8)>), _) =
+            from_slice_with_signature(&encoded, ctxt, "(ua(sy))").unwrap();
+        assert_eq!(decoded, (42, vec![("hello".to_string(), 1)]));
+        from_slice_with_signature::<_, _, (u32, Vec<(String, u8)>)>(&encoded, ctxt, "ua(sy)")
+            .unwrap();
+
+        let err = from_slice_with_signature::<_, _, (u32, Vec<(String, u16)>)>(
+            &encoded, ctxt, "(ua(sy))",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Signature mismatch: got `(ua(sy))`, expected `(ua(sq))` \
+             (differing at character 5, in field 1 of array element of field 1)",
+        );
+
+        let err = from_slice_with_signature::<_, _, HashMap<String, u32>>(&encoded, ctxt, "a{si}")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Signature mismatch: got `a{si}`, expected `a{su}` \
+             (differing at character 3, in dict value of array element)",
+        );
+        let err = from_slice_with_signature::<_, _, u32>(&encoded, ctxt, "s").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Signature mismatch: got `s`, expected `u` (differing at character 0)",
+        );
+    }
+
+    #[test]
+    fn file_path() {
+        use crate::FilePath;
+        use std::path::Path;
+
+        let ctxt = Context::<LE>::new_dbus(0);
+
+        let path = FilePath::from(Path::new("/tmp/file"));
+        let encoded = to_bytes(ctxt, &path).unwrap();
+        assert_eq!(&encoded[4..], b"/tmp/file");
+        let decoded: FilePath<'_> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, path);
+
+        // Nul-terminated, as GLib encodes it.
+        let encoded = to_bytes(ctxt, &b"/tmp/file\0".to_vec()).unwrap();
+        let decoded: FilePath<'_> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, path);
+
+        // Interior nul.
+        let encoded = to_bytes(ctxt, &b"/tmp\0/file".to_vec()).unwrap();
+        from_slice::<_, FilePath<'_>>(&encoded, ctxt).unwrap_err();
+
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+            // Not valid UTF-8.
+            let path = FilePath::from(OsStr::from_bytes(b"/tmp/\xff\xfe"));
+            let encoded = to_bytes(ctxt, &path).unwrap();
+            let decoded: FilePath<'_> = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, path);
+        }
+    }
+
+    #[test]
+    fn fixed_array() {
+        use crate::FixedArray;
+
+        let ctxt = Context::<NativeEndian>::new_dbus(0);
+        let data = (7u8, vec![1u32, 2, 3], vec![0xffu8; 5], -1i64);
+        let encoded = to_bytes(ctxt, &data).unwrap();
+        let decoded: (u8, FixedArray<'_, u32>, FixedArray<'_, u8>, i64) =
+            from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded.0, 7);
+        assert_eq!(&*decoded.1, &[1, 2, 3]);
+        assert_eq!(&*decoded.2, &[0xff; 5]);
+        assert_eq!(decoded.3, -1);
+        assert!(decoded.2.is_borrowed());
+        if encoded.as_ptr() as usize % std::mem::align_of::<u32>() == 0 {
+            assert!(decoded.1.is_borrowed());
+        }
+
+        // Non-native byte order is decoded into an owned array.
+        #[cfg(target_endian = "little")]
+        let ctxt = Context::<BE>::new_dbus(0);
+        #[cfg(target_endian = "big")]
+        let ctxt = Context::<LE>::new_dbus(0);
+        let encoded = to_bytes(ctxt, &data).unwrap();
+        let decoded: (u8, FixedArray<'_, u32>, FixedArray<'_, u8>, i64) =
+            from_slice(&encoded, ctxt).unwrap().0;
+        assert!(!decoded.1.is_borrowed());
+        assert_eq!(decoded.1.into_vec(), data.1);
+        assert_eq!(decoded.3, -1);
+
+        // Serialization is the same as that of a slice.
+        let array = FixedArray::from(&data.1[..]);
+        assert_eq!(
+            to_bytes(ctxt, &array).unwrap(),
+            to_bytes(ctxt, &data.1).unwrap()
+        );
+
+        // The elements must be of the exact type.
+        let ctxt = Context::<NativeEndian>::new_dbus(0);
+        let encoded = to_bytes(ctxt, &vec![1i32, 2]).unwrap();
+        from_slice_for_signature::<_, _, FixedArray<'_, u32>>(&encoded, ctxt, "ai").unwrap_err();
+        let encoded = to_bytes(ctxt, &vec![0u8; 16]).unwrap();
+        from_slice_for_signature::<_, _, FixedArray<'_, u64>>(&encoded, ctxt, "ay").unwrap_err();
+        let decoded: FixedArray<'_, u8> = from_slice_for_signature(&encoded, ctxt, "ay").unwrap().0;
+        assert_eq!(decoded.len(), 16);
+
+        // Other deserializers can only hand out bytes for arrays of bytes.
+        let bytes = [0u8; 8];
+        let de = serde::de::value::BytesDeserializer::<serde::de::value::Error>::new(&bytes);
+        assert!(FixedArray::<'_, u64>::deserialize(de).is_err());
+        assert_eq!(FixedArray::<'_, u8>::deserialize(de).unwrap().len(), 8);
+        let decoded: FixedArray<'_, u32> = serde_json::from_str("[1, 2]").unwrap();
+        assert_eq!(&*decoded, &[1, 2]);
+    }
+
+    #[test]
+    fn stream() {
+        use crate::to_stream;
+
+        let mut map = HashMap::new();
+        map.insert("nested", vec![vec![1u8, 2], vec![], vec![3]]);
+        map.insert("empty", vec![]);
+        let data = (
+            1u8,
+            map,
+            vec!["one", "two", "three"],
+            Value::new(vec![4u64, 5]),
+        );
+
+        let ctxt = Context::<LE>::new_dbus(3);
+        let mut encoded = vec![];
+        let len = to_stream(&mut encoded, ctxt, &data).unwrap();
+        assert_eq!(len, encoded.len());
+        assert_eq!(encoded, to_bytes(ctxt, &data).unwrap());
+
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::<LE>::new_gvariant(0);
+            let mut encoded = vec![];
+            to_stream(&mut encoded, ctxt, &data).unwrap();
+            assert_eq!(encoded, to_bytes(ctxt, &data).unwrap());
+        }
+    }
+
+    #[test]
+    fn reader() {
+        use crate::{from_reader, to_writer, OwnedValue};
+        use std::io::Cursor;
+
+        type First = (u8, String, Vec<(u64, OwnedValue)>);
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let first = (
+            1u8,
+            "hello".to_string(),
+            vec![(
+                2u64,
+                Value::new((3u16, ObjectPath::try_from("/a").unwrap())),
+            )],
+        );
+        let second = 0xdead_beef_u32;
+        let mut cursor = Cursor::new(vec![]);
+        let first_len = to_writer(&mut cursor, ctxt, &first).unwrap();
+        let ctxt = Context::<LE>::new_dbus(first_len);
+        to_writer(&mut cursor, ctxt, &second).unwrap();
+        let ctxt = Context::<LE>::new_dbus(0);
+        let encoded = cursor.into_inner();
+
+        let mut reader = Cursor::new(&encoded[..]);
+        let (decoded, len): (First, _) = from_reader(&mut reader, ctxt).unwrap();
+        assert_eq!(len, first_len);
+        assert_eq!(reader.position() as usize, first_len);
+        assert_eq!(decoded.0, first.0);
+        assert_eq!(decoded.1, first.1);
+        assert_eq!(*decoded.2[0].1, first.2[0].1);
+
+        let ctxt = Context::<LE>::new_dbus(first_len);
+        let (decoded, _): (u32, _) = from_reader(&mut reader, ctxt).unwrap();
+        assert_eq!(decoded, second);
+
+        // Truncated input.
+        let ctxt = Context::<LE>::new_dbus(0);
+        let mut reader = Cursor::new(&encoded[..first_len - 1]);
+        let res: Result<(First, _)> = from_reader(&mut reader, ctxt);
+        assert!(matches!(res, Err(Error::InputOutput(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn async_io() {
+        use crate::{from_async_reader, to_async_writer, OwnedValue};
+        use futures_util::{future::FutureExt, io::Cursor};
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let data = (
+            "hello".to_string(),
+            vec![1u32, 2],
+            OwnedValue::from(Value::new(vec!["world"])),
+        );
+        let mut cursor = Cursor::new(vec![]);
+        let len = to_async_writer(&mut cursor, ctxt, &data)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(cursor.get_ref(), &to_bytes(ctxt, &data).unwrap());
+
+        // Trailing data isn't read.
+        cursor.get_mut().extend([0xff; 4]);
+        cursor.set_position(0);
+        let (decoded, decoded_len): ((String, Vec<u32>, OwnedValue), _) =
+            from_async_reader(&mut cursor, ctxt)
+                .now_or_never()
+                .unwrap()
+                .unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(decoded_len, len);
+        assert_eq!(cursor.position() as usize, len);
+
+        // Large enough to be written in multiple chunks.
+        let data = vec![vec!["hello world".repeat(10); 10]; 200];
+        let mut cursor = Cursor::new(vec![]);
+        let len = to_async_writer(&mut cursor, ctxt, &data)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let encoded = to_bytes(ctxt, &data).unwrap();
+        assert_eq!(len, encoded.len());
+        assert_eq!(cursor.get_ref(), &encoded);
+    }
+
     #[cfg(feature = "ostree-tests")]
     #[test]
     fn ostree_de() {
@@ -1950,6 +2762,61 @@ mod tests {
         // data.
     }
 
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn uuid() {
+        let ctxt = Context::<LE>::new_dbus(0);
+        let uuid = uuid::Uuid::from_u128(0x1234_5678_9abc_def0_1234_5678_9abc_def0);
+        let encoded = to_bytes(ctxt, &uuid).unwrap();
+        assert_eq!(encoded.len(), 20);
+        let decoded: uuid::Uuid = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(uuid, decoded);
+
+        let encoded = to_bytes(ctxt, &uuid.hyphenated()).unwrap();
+        let decoded: &str = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, "12345678-9abc-def0-1234-56789abcdef0");
+    }
+
+    #[test]
+    #[cfg(feature = "int128")]
+    fn int128() {
+        let ctxt = Context::<LE>::new_dbus(0);
+        assert_eq!(i128::signature(), "(tt)");
+        let n = 0x1234_5678_9abc_def0_1234_5678_9abc_def0_u128;
+        let encoded = to_bytes(ctxt, &n).unwrap();
+        assert_eq!(encoded.len(), 16);
+        let decoded: u128 = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, n);
+        // The high bits come first.
+        let (high, low): (u64, u64) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(high, 0x1234_5678_9abc_def0);
+        assert_eq!(low, 0x1234_5678_9abc_def0);
+
+        let encoded = to_bytes(ctxt, &(i128::MIN, -1_i128)).unwrap();
+        let decoded: (i128, i128) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, (i128::MIN, -1));
+
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::<LE>::new_gvariant(0);
+            let encoded = to_bytes(ctxt, &-42_i128).unwrap();
+            let decoded: i128 = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, -42);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "camino")]
+    fn camino() {
+        let ctxt = Context::<LE>::new_dbus(0);
+        let path = camino::Utf8PathBuf::from("/usr/share/dbus-1");
+        let encoded = to_bytes(ctxt, &path).unwrap();
+        let decoded: camino::Utf8PathBuf = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(path, decoded);
+        let decoded: &str = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, "/usr/share/dbus-1");
+    }
+
     #[test]
     #[cfg(feature = "time")]
     fn time() {
@@ -2045,4 +2912,234 @@ mod tests {
         // * Test deserializers.
         // * Test gvariant format.
     }
+
+    #[test]
+    fn custom_limits() {
+        use crate::{from_reader, Limits, MaxSizeExceeded};
+
+        fn check(ctxt: Context<LE>) {
+            // Nested variants beyond the default container depth.
+            let mut value = Value::from(0u8);
+            for _ in 0..100 {
+                value = Value::Value(Box::new(value));
+            }
+            assert_eq!(
+                to_bytes(ctxt, &value).unwrap_err(),
+                Error::MaxDepthExceeded(MaxDepthExceeded::Container)
+            );
+            let deep = ctxt.with_limits(Limits::default().with_max_container_depth(128));
+            let encoded = to_bytes(deep, &value).unwrap();
+            assert_eq!(
+                from_slice::<_, Value<'_>>(&encoded, ctxt).unwrap_err(),
+                Error::MaxDepthExceeded(MaxDepthExceeded::Container)
+            );
+            let decoded: Value<'_> = from_slice(&encoded, deep).unwrap().0;
+            assert_eq!(decoded, value);
+
+            // Array size.
+            let array = vec![42u32; 10];
+            let encoded = to_bytes(ctxt, &(&array, "hello")).unwrap();
+            let limits = Limits::default().with_max_array_size(39);
+            assert_eq!(
+                from_slice::<_, (Vec<u32>, &str)>(&encoded, ctxt.with_limits(limits)).unwrap_err(),
+                Error::MaxSizeExceeded(MaxSizeExceeded::Array)
+            );
+            let limits = limits.with_max_array_size(40);
+            let decoded: (Vec<u32>, &str) =
+                from_slice(&encoded, ctxt.with_limits(limits)).unwrap().0;
+            assert_eq!(decoded, (array, "hello"));
+
+            // Total size, also when reading.
+            let limits = limits.with_max_size(encoded.len() - 1);
+            assert_eq!(
+                from_slice::<_, (Vec<u32>, &str)>(&encoded, ctxt.with_limits(limits)).unwrap_err(),
+                Error::MaxSizeExceeded(MaxSizeExceeded::Total)
+            );
+            let mut reader = std::io::Cursor::new(&encoded);
+            assert_eq!(
+                from_reader::<_, _, (Vec<u32>, String)>(&mut reader, ctxt.with_limits(limits))
+                    .unwrap_err(),
+                Error::MaxSizeExceeded(MaxSizeExceeded::Total)
+            );
+        }
+
+        check(Context::<LE>::new_dbus(0));
+        #[cfg(feature = "gvariant")]
+        check(Context::<LE>::new_gvariant(0));
+    }
+
+    #[test]
+    fn value_for_signature() {
+        let ctxt = Context::<LE>::new_dbus(0);
+        let data = (
+            Vec::<Vec<u32>>::new(),
+            HashMap::<&str, Vec<(u8, &str)>>::new(),
+            Value::new(Value::new(Array::new(
+                Signature::from_static_str_unchecked("s"),
+            ))),
+            vec![Value::from(42_u32)],
+        );
+        let signature = "(aaua{sa(ys)}vav)";
+        let encoded = to_bytes(ctxt, &data).unwrap();
+        let (value, size) = Value::deserialize_for_signature(&encoded, ctxt, signature).unwrap();
+        assert_eq!(size, encoded.len());
+        assert_eq!(value.value_signature(), signature);
+        let fields = Structure::try_from(value).unwrap().into_fields();
+        assert_eq!(fields[2].value_signature(), "v");
+        let array = Value::new(Array::new(<&str>::signature()));
+        assert_eq!(fields[2], Value::new(Value::new(array)));
+        assert_eq!(fields[3], Value::new(vec![Value::from(42_u32)]));
+
+        // A variant signature gives a `Value::Value`.
+        let encoded = to_bytes(ctxt, &Value::from(0_u8)).unwrap();
+        let (value, _) = Value::deserialize_for_signature(&encoded, ctxt, "v").unwrap();
+        assert_eq!(value, Value::new(Value::from(0_u8)));
+
+        assert!(matches!(
+            Value::deserialize_for_signature(&encoded, ctxt, "yy"),
+            Err(Error::SignatureMismatch(..))
+        ));
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let mut map = HashMap::new();
+        map.insert("Devices", vec![Value::from(1_u32), Value::from("two")]);
+        let value = Value::from(map);
+        let err = HashMap::<String, Vec<u32>>::try_from(value).unwrap_err();
+        assert_eq!(
+            err,
+            Error::TypeMismatch {
+                expected: "`u`".to_string(),
+                found: Signature::from_static_str_unchecked("s"),
+                path: r#"["Devices"][1]"#.to_string(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            r#"Type mismatch: expected `u`, found `s` at `["Devices"][1]`"#,
+        );
+
+        let value = Value::from((42_u32, "forty-two"));
+        assert!(matches!(
+            <(u32, u32)>::try_from(value),
+            Err(Error::TypeMismatch { path, .. }) if path == ".1"
+        ));
+
+        // A mismatch of the value itself has an empty path.
+        assert!(matches!(
+            u32::try_from(Value::from("42")),
+            Err(Error::TypeMismatch { path, .. }) if path.is_empty()
+        ));
+    }
+
+    #[test]
+    fn deserialize_error_location() {
+        use crate::from_slice_located;
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        let mut encoded = to_bytes(ctxt, &(42_u32, ("ab", true))).unwrap();
+        // Corrupt the boolean.
+        encoded[16] = 2;
+        let err = from_slice_located::<_, (u32, (&str, bool))>(&encoded, ctxt).unwrap_err();
+        assert_eq!(err.path(), ".1.1");
+        assert_eq!(err.offset(), 16);
+        assert_eq!(
+            err.to_string(),
+            "invalid value: integer `2`, expected 0 or 1 (at byte 16, in `.1.1`)",
+        );
+        // The error itself is the same as the one `from_slice` returns.
+        assert_eq!(
+            err.into_error(),
+            from_slice::<_, (u32, (&str, bool))>(&encoded, ctxt).unwrap_err(),
+        );
+
+        // The offset is relative to the given bytes, whatever the position of the context.
+        let ctxt = Context::<LE>::new_dbus(4);
+        let mut encoded = to_bytes(ctxt, &vec![true, false]).unwrap();
+        encoded[8] = 2;
+        let err = from_slice_located::<_, Vec<bool>>(&encoded, ctxt).unwrap_err();
+        assert_eq!(err.path(), "[1]");
+        assert_eq!(err.offset(), 8);
+
+        // A failure of the value itself has an empty path.
+        let err = from_slice_located::<_, bool>(&[2, 0, 0, 0], ctxt).unwrap_err();
+        assert_eq!(err.path(), "");
+        assert_eq!(err.offset(), 0);
+
+        // Values nested in GVariant framing are located just the same.
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::<LE>::new_gvariant(0);
+            let mut encoded = to_bytes(ctxt, &(42_u32, ("ab", true))).unwrap();
+            // Corrupt the boolean, right after the string and its padding.
+            encoded[8] = 2;
+            let err = from_slice_located::<_, (u32, (&str, bool))>(&encoded, ctxt).unwrap_err();
+            assert_eq!(err.path(), ".1.1");
+            assert_eq!(err.offset(), 8);
+        }
+    }
+
+    #[cfg(feature = "gvariant")]
+    #[test]
+    fn gvariant_normal_form() {
+        use crate::gvariant::to_normal_form;
+
+        let le = Context::<LE>::new_gvariant(0);
+        let be = Context::<BE>::new_gvariant(0);
+        let fields = (
+            0x0102_u16,
+            "hello",
+            vec![Value::new(0x0102_0304_u32), Value::new("world")],
+            HashMap::from([("pi", std::f64::consts::PI)]),
+        );
+        let value = Value::new(fields.clone());
+        let signature = "(qsava{sd})";
+
+        // Normal form is left as is.
+        let encoded = to_bytes(be, &value).unwrap();
+        assert_eq!(to_normal_form(&encoded, be, "v").unwrap(), encoded);
+        let fields = to_bytes_for_signature(le, signature, &fields).unwrap();
+        assert_eq!(to_normal_form(&fields, le, signature).unwrap(), fields);
+
+        // Unused bytes in a variant are dropped.
+        let encoded = b"\x2a\0\0\0\xff\xff\xff\xff\0u";
+        assert_eq!(to_normal_form(encoded, le, "v").unwrap(), b"\x2a\0\0\0\0u");
+        assert!(to_normal_form(b"\x2a\0\0\0\0", le, "v").is_err());
+
+        // Decoding with the wrong byte order only swaps the numbers.
+        let encoded = to_bytes(be, &value).unwrap();
+        let decoded: Value<'_> = from_slice(&encoded, le).unwrap().0;
+        assert_ne!(decoded, value);
+        assert_eq!(decoded.byteswap(), value);
+        assert_eq!(value.byteswap().byteswap(), value);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_values() {
+        use arbitrary::{Arbitrary, Unstructured};
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        #[cfg(feature = "gvariant")]
+        let ctxt = Context::<LE>::new_gvariant(0);
+        #[cfg(not(feature = "gvariant"))]
+        let ctxt = Context::<LE>::new_dbus(0);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let data: Vec<u8> = (0..1024).map(|_| rng.gen()).collect();
+            let mut u = Unstructured::new(&data);
+
+            let signature = Signature::arbitrary(&mut u).unwrap();
+            Signature::try_from(signature.as_str()).unwrap();
+            let path = ObjectPath::arbitrary(&mut u).unwrap();
+            ObjectPath::try_from(path.as_str()).unwrap();
+
+            let value = Value::arbitrary(&mut u).unwrap();
+            let encoded = to_bytes(ctxt, &value).unwrap();
+            let decoded: Value<'_> = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, value);
+        }
+    }
 }
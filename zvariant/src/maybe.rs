@@ -1,6 +1,6 @@
 use serde::ser::{Serialize, Serializer};
 use static_assertions::assert_impl_all;
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display};
 
 use crate::{value_display_fmt, Error, Signature, Type, Value};
 
@@ -9,7 +9,7 @@ use crate::{value_display_fmt, Error, Signature, Type, Value};
 /// API is provided to convert from, and to `Option<T>`.
 ///
 /// [`Value`]: enum.Value.html
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Maybe<'a> {
     value: Box<Option<Value<'a>>>,
     value_signature: Signature<'a>,
@@ -70,7 +70,11 @@ impl<'a> Maybe<'a> {
         T: TryFrom<Value<'a>>,
     {
         self.value
-            .map(|v| v.downcast().ok_or(Error::IncorrectType))
+            .map(|v| {
+                let err = Error::type_mismatch_for::<T>(&v);
+
+                v.downcast().ok_or(err)
+            })
             .transpose()
     }
 
@@ -101,6 +105,29 @@ impl<'a> Maybe<'a> {
             signature: self.signature.to_owned(),
         }
     }
+
+    pub(crate) fn byteswap(&self) -> Maybe<'static> {
+        Maybe {
+            value_signature: self.value_signature.to_owned(),
+            value: Box::new(self.value.as_ref().as_ref().map(Value::byteswap)),
+            signature: self.signature.to_owned(),
+        }
+    }
+}
+
+// Compare the signatures first, like the other containers do.
+impl PartialOrd for Maybe<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Maybe<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.signature
+            .cmp(&other.signature)
+            .then_with(|| self.value.cmp(&other.value))
+    }
 }
 
 impl Display for Maybe<'_> {
@@ -32,7 +32,7 @@ use crate::{Basic, EncodingFormat, Error, Result, Signature, Str, Type};
 /// ObjectPath::try_from("/end/with/slash/").unwrap_err();
 /// ObjectPath::try_from("/ha.d").unwrap_err();
 /// ```
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct ObjectPath<'a>(Str<'a>);
 
 assert_impl_all!(ObjectPath<'_>: Send, Sync, Unpin);
@@ -112,6 +112,189 @@ impl<'a> ObjectPath<'a> {
     pub fn into_owned(self) -> ObjectPath<'static> {
         ObjectPath(self.0.into_owned())
     }
+
+    /// The parent of this path, or `None` if this is the root path (`/`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/zbus/Object").unwrap();
+    /// assert_eq!(path.parent().unwrap(), "/org/zbus");
+    /// assert_eq!(path.parent().unwrap().parent().unwrap().parent().unwrap(), "/");
+    /// assert!(ObjectPath::try_from("/").unwrap().parent().is_none());
+    /// ```
+    pub fn parent(&self) -> Option<ObjectPath<'_>> {
+        let path = self.as_str();
+        match path.rfind('/')? {
+            _ if path.len() == 1 => None,
+            0 => Some(ObjectPath::from_static_str_unchecked("/")),
+            i => Some(ObjectPath::from_str_unchecked(&path[..i])),
+        }
+    }
+
+    /// Create a child path of this path, by appending the given `component` to it.
+    ///
+    /// # Errors
+    ///
+    /// If `component` isn't a valid path component, i.e it's empty or contains characters other
+    /// than ASCII alphanumerics and `_`. Use [`ObjectPath::escape_component`] to create a valid
+    /// component from an arbitrary string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let root = ObjectPath::try_from("/").unwrap();
+    /// let path = root.child("org").unwrap().child("zbus").unwrap();
+    /// assert_eq!(path, "/org/zbus");
+    /// path.child("not/a/component").unwrap_err();
+    /// ```
+    pub fn child(&self, component: &str) -> Result<ObjectPath<'static>> {
+        ensure_correct_object_path_component(component.as_bytes())?;
+
+        let mut path = String::with_capacity(self.len() + component.len() + 1);
+        if self.as_str() != "/" {
+            path.push_str(self.as_str());
+        }
+        path.push('/');
+        path.push_str(component);
+
+        Ok(ObjectPath::from_string_unchecked(path))
+    }
+
+    /// An iterator over the components of this path.
+    ///
+    /// The root path (`/`) has no components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/zbus/Object").unwrap();
+    /// assert_eq!(path.components().collect::<Vec<_>>(), ["org", "zbus", "Object"]);
+    /// assert_eq!(ObjectPath::try_from("/").unwrap().components().count(), 0);
+    /// ```
+    pub fn components(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.as_str().split('/').filter(|c| !c.is_empty())
+    }
+
+    /// If `base` is this path or one of its ancestors.
+    ///
+    /// Unlike [`str::starts_with`], this compares whole components, so `/org/zbus` has `/org` as a
+    /// namespace prefix but `/organization` doesn't. `base` can be an [`ObjectPath`] or a plain
+    /// string, in which case a trailing `/` is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/zbus/Object").unwrap();
+    /// assert!(path.has_namespace_prefix(&ObjectPath::try_from("/").unwrap()));
+    /// assert!(path.has_namespace_prefix(&ObjectPath::try_from("/org/zbus").unwrap()));
+    /// assert!(path.has_namespace_prefix(&path));
+    /// assert!(!path.has_namespace_prefix(&ObjectPath::try_from("/org/zb").unwrap()));
+    /// assert!(path.has_namespace_prefix("/org/"));
+    /// assert!(!path.has_namespace_prefix("/org/zbus/Obj"));
+    /// ```
+    pub fn has_namespace_prefix(&self, base: impl AsRef<str>) -> bool {
+        let base = base.as_ref().trim_end_matches('/');
+        if base.is_empty() {
+            return true;
+        }
+
+        match self.as_str().strip_prefix(base) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// Escape an arbitrary string into a valid path component.
+    ///
+    /// Each byte other than an ASCII alphanumeric is replaced by `_` followed by its value as two
+    /// lowercase hexadecimal digits, and an empty string becomes `_`. This is the same escaping as
+    /// GLib's `g_dbus_escape_object_path`, so the resulting paths are compatible with GLib-based
+    /// services. Use [`ObjectPath::unescape_component`] to get the original string back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let component = ObjectPath::escape_component("foo-bar_baz");
+    /// assert_eq!(component, "foo_2dbar_5fbaz");
+    /// let path = ObjectPath::try_from("/org/zbus").unwrap().child(&component).unwrap();
+    /// assert_eq!(path, "/org/zbus/foo_2dbar_5fbaz");
+    /// assert_eq!(ObjectPath::escape_component(""), "_");
+    /// ```
+    pub fn escape_component(component: &str) -> String {
+        if component.is_empty() {
+            return String::from("_");
+        }
+
+        let mut escaped = String::with_capacity(component.len());
+        for b in component.bytes() {
+            if b.is_ascii_alphanumeric() {
+                escaped.push(b as char);
+            } else {
+                escaped.push_str(&format!("_{b:02x}"));
+            }
+        }
+
+        escaped
+    }
+
+    /// Reverse the escaping done by [`ObjectPath::escape_component`].
+    ///
+    /// Returns `None` if `component` isn't in the escaped form, or doesn't decode to valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let unescaped = ObjectPath::unescape_component("foo_2dbar_5fbaz").unwrap();
+    /// assert_eq!(unescaped, "foo-bar_baz");
+    /// assert_eq!(ObjectPath::unescape_component("_").unwrap(), "");
+    /// assert!(ObjectPath::unescape_component("foo_2").is_none());
+    /// ```
+    pub fn unescape_component(component: &str) -> Option<String> {
+        if component == "_" {
+            return Some(String::new());
+        }
+
+        let bytes = component.as_bytes();
+        let mut unescaped = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'_' => {
+                    let hex = bytes.get(i + 1..i + 3)?;
+                    if !hex.iter().all(u8::is_ascii_hexdigit) {
+                        return None;
+                    }
+                    let b = u8::from_str_radix(str::from_utf8(hex).ok()?, 16).ok()?;
+                    // Alphanumerics are never escaped.
+                    if b.is_ascii_alphanumeric() {
+                        return None;
+                    }
+                    unescaped.push(b);
+                    i += 3;
+                }
+                b if b.is_ascii_alphanumeric() => {
+                    unescaped.push(b);
+                    i += 1;
+                }
+                _ => return None,
+            }
+        }
+
+        String::from_utf8(unescaped).ok()
+    }
 }
 
 impl std::default::Default for ObjectPath<'_> {
@@ -194,6 +377,18 @@ impl<'a> std::ops::Deref for ObjectPath<'a> {
     }
 }
 
+impl AsRef<str> for ObjectPath<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for OwnedObjectPath {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl<'a> PartialEq<str> for ObjectPath<'a> {
     fn eq(&self, other: &str) -> bool {
         self.as_str() == other
@@ -257,6 +452,46 @@ impl<'de> Visitor<'de> for ObjectPathVisitor {
 }
 
 fn ensure_correct_object_path_str(path: &[u8]) -> Result<()> {
+    match check_object_path(path) {
+        Ok(()) => Ok(()),
+        Err(ObjectPathError::Empty) => Err(serde::de::Error::invalid_length(0, &"> 0 character")),
+        Err(ObjectPathError::MissingSlash) => Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Char(path[0] as char),
+            &"/",
+        )),
+        Err(ObjectPathError::EmptyElement(i)) if path[i - 1] == b'/' => Err(
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str("//"), &"/"),
+        ),
+        Err(ObjectPathError::EmptyElement(_)) => Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Char('/'),
+            &"an alphanumeric character or `_`",
+        )),
+        Err(ObjectPathError::InvalidByte(i)) => Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Char(path[i] as char),
+            &"an alphanumeric character, `_` or `/`",
+        )),
+    }
+}
+
+/// The first error found in an object path by [`check_object_path`].
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectPathError {
+    /// The path is empty.
+    Empty,
+    /// The path doesn't start with a `/`.
+    MissingSlash,
+    /// The `/` at the given position ends an empty element (`//` or a trailing `/`).
+    EmptyElement(usize),
+    /// The byte at the given position is not allowed.
+    InvalidByte(usize),
+}
+
+/// Check that `path` is a valid object path.
+///
+/// This is the validation behind [`ObjectPath`], shared with `zbus_names::validate_object_path`.
+#[doc(hidden)]
+pub fn check_object_path(path: &[u8]) -> std::result::Result<(), ObjectPathError> {
     let mut prev = b'\0';
 
     // Rules
@@ -267,32 +502,16 @@ fn ensure_correct_object_path_str(path: &[u8]) -> Result<()> {
     // * No `//`
     // * Only ASCII alphanumeric, `_` or '/'
     if path.is_empty() {
-        return Err(serde::de::Error::invalid_length(0, &"> 0 character"));
+        return Err(ObjectPathError::Empty);
     }
 
-    for i in 0..path.len() {
-        let c = path[i];
-
+    for (i, &c) in path.iter().enumerate() {
         if i == 0 && c != b'/' {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c as char),
-                &"/",
-            ));
-        } else if c == b'/' && prev == b'/' {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Str("//"),
-                &"/",
-            ));
-        } else if path.len() > 1 && i == (path.len() - 1) && c == b'/' {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char('/'),
-                &"an alphanumeric character or `_`",
-            ));
+            return Err(ObjectPathError::MissingSlash);
+        } else if c == b'/' && (prev == b'/' || (path.len() > 1 && i == (path.len() - 1))) {
+            return Err(ObjectPathError::EmptyElement(i));
         } else if !c.is_ascii_alphanumeric() && c != b'/' && c != b'_' {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c as char),
-                &"an alphanumeric character, `_` or `/`",
-            ));
+            return Err(ObjectPathError::InvalidByte(i));
         }
         prev = c;
     }
@@ -300,6 +519,23 @@ fn ensure_correct_object_path_str(path: &[u8]) -> Result<()> {
     Ok(())
 }
 
+fn ensure_correct_object_path_component(component: &[u8]) -> Result<()> {
+    if component.is_empty() {
+        return Err(serde::de::Error::invalid_length(0, &"> 0 character"));
+    }
+
+    match component
+        .iter()
+        .find(|c| !c.is_ascii_alphanumeric() && **c != b'_')
+    {
+        Some(c) => Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Char(*c as char),
+            &"an alphanumeric character or `_`",
+        )),
+        None => Ok(()),
+    }
+}
+
 /// Owned [`ObjectPath`](struct.ObjectPath.html)
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, Type)]
 pub struct OwnedObjectPath(ObjectPath<'static>);
@@ -387,4 +623,20 @@ mod unit {
         let json_str = "\"/some/path\"";
         serde_json::de::from_reader::<_, OwnedObjectPath>(json_str.as_bytes()).unwrap();
     }
+
+    #[test]
+    fn escape_component() {
+        for s in ["", "_", "foo", "foo bar", "/org/zbus", "_2d", "ünïcödé"] {
+            let component = ObjectPath::escape_component(s);
+            ensure_correct_object_path_component(component.as_bytes()).unwrap();
+            assert_eq!(ObjectPath::unescape_component(&component).unwrap(), s);
+        }
+
+        for invalid in ["foo-bar", "_2", "_zz", "_+f", "_41", "_ff"] {
+            assert!(
+                ObjectPath::unescape_component(invalid).is_none(),
+                "{invalid}"
+            );
+        }
+    }
 }
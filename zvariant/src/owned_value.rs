@@ -1,10 +1,13 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use static_assertions::assert_impl_all;
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
 
 use crate::{
-    Array, Dict, NoneValue, ObjectPath, Optional, OwnedObjectPath, OwnedSignature, Signature, Str,
-    Structure, Type, Value,
+    Array, Dict, NoneValue, ObjectPath, Optional, OwnedArray, OwnedDict, OwnedObjectPath,
+    OwnedSignature, OwnedStr, OwnedStructure, Signature, Str, Structure, Type, Value,
 };
 
 #[cfg(unix)]
@@ -17,7 +20,7 @@ use crate::Maybe;
 // https://github.com/dbus2/zbus/issues/138
 
 /// Owned [`Value`](enum.Value.html)
-#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Type)]
 pub struct OwnedValue(pub(crate) Value<'static>);
 
 assert_impl_all!(OwnedValue: Send, Sync, Unpin);
@@ -70,6 +73,10 @@ ov_try_from!(Signature<'static>);
 ov_try_from!(OwnedSignature);
 ov_try_from!(ObjectPath<'static>);
 ov_try_from!(OwnedObjectPath);
+ov_try_from!(OwnedStr);
+ov_try_from!(OwnedArray);
+ov_try_from!(OwnedDict);
+ov_try_from!(OwnedStructure);
 ov_try_from!(Array<'static>);
 ov_try_from!(Dict<'static, 'static>);
 #[cfg(feature = "gvariant")]
@@ -111,7 +118,7 @@ where
         if let Value::Array(v) = value.0 {
             Self::try_from(v)
         } else {
-            Err(crate::Error::IncorrectType)
+            Err(crate::Error::type_mismatch("an array", &value.0))
         }
     }
 }
@@ -143,7 +150,7 @@ where
         if let Value::Dict(v) = value.0 {
             Self::try_from(v)
         } else {
-            Err(crate::Error::IncorrectType)
+            Err(crate::Error::type_mismatch("a dict", &value.0))
         }
     }
 }
@@ -159,6 +166,34 @@ where
     }
 }
 
+impl<'k, 'v, K, V> TryFrom<OwnedValue> for BTreeMap<K, V>
+where
+    K: crate::Basic + TryFrom<Value<'k>> + std::cmp::Ord,
+    V: TryFrom<Value<'v>>,
+    K::Error: Into<crate::Error>,
+    V::Error: Into<crate::Error>,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
+        if let Value::Dict(v) = value.0 {
+            Self::try_from(v)
+        } else {
+            Err(crate::Error::type_mismatch("a dict", &value.0))
+        }
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for OwnedValue
+where
+    K: Type + Into<Value<'static>> + std::cmp::Ord,
+    V: Type + Into<Value<'static>>,
+{
+    fn from(value: BTreeMap<K, V>) -> Self {
+        Self(value.into())
+    }
+}
+
 impl<'a, T> TryFrom<OwnedValue> for Optional<T>
 where
     T: TryFrom<Value<'a>> + NoneValue + PartialEq<<T as NoneValue>::NoneType>,
@@ -225,6 +260,23 @@ to_value!(ObjectPath<'a>);
 #[cfg(unix)]
 to_value!(Fd);
 
+macro_rules! owned_to_value {
+    ($from:ty) => {
+        impl From<$from> for OwnedValue {
+            fn from(v: $from) -> Self {
+                OwnedValue(v.into())
+            }
+        }
+    };
+}
+
+owned_to_value!(OwnedStr);
+owned_to_value!(OwnedSignature);
+owned_to_value!(OwnedObjectPath);
+owned_to_value!(OwnedArray);
+owned_to_value!(OwnedDict);
+owned_to_value!(OwnedStructure);
+
 impl From<OwnedValue> for Value<'static> {
     fn from(v: OwnedValue) -> Value<'static> {
         v.into_inner()
@@ -257,7 +309,11 @@ impl<'de> Deserialize<'de> for OwnedValue {
 #[cfg(test)]
 mod tests {
     use byteorder::LE;
-    use std::{collections::HashMap, error::Error, result::Result};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        error::Error,
+        result::Result,
+    };
 
     use crate::{from_slice, to_bytes, EncodingContext, OwnedValue, Value};
 
@@ -309,4 +365,18 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn btree_map_conversion() -> Result<(), Box<dyn Error>> {
+        let mut map = BTreeMap::<String, u32>::new();
+        map.insert("two".to_string(), 2);
+        map.insert("one".to_string(), 1);
+        let value = OwnedValue::from(map.clone());
+        assert_eq!(value.to_string(), r#"{"one": uint32 1, "two": 2}"#);
+        // Now convert back
+        let map2 = <BTreeMap<String, u32>>::try_from(value)?;
+        assert_eq!(map, map2);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,339 @@
+use static_assertions::assert_impl_all;
+use std::fmt::{self, Display, Write};
+
+use crate::{value_display_fmt, Value, ValuePathSegment, ValueVisitor, Visit};
+
+/// A configurable printer of [`Value`]s, for logging message bodies safely.
+///
+/// The output is based on the [GVariant text format] used by the `Display` implementation of
+/// [`Value`], without the type annotations. In addition, the printer can:
+///
+/// * [truncate](PrettyPrinter::max_bytes) long byte arrays,
+/// * [redact](PrettyPrinter::redact_key) the values of dictionary entries whose (string) key matches
+///   a pattern, e.g `password` or `secret`,
+/// * [limit the depth](PrettyPrinter::max_depth) of nested containers printed,
+/// * print containers over [multiple lines](PrettyPrinter::indent).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{PrettyPrinter, Value};
+///
+/// let mut options = HashMap::new();
+/// options.insert("user", Value::from("alice"));
+/// options.insert("Password", Value::from("hunter2"));
+/// options.insert("avatar", Value::from(vec![0x89_u8, 0x50, 0x4e, 0x47, 0x0d, 0x0a]));
+/// let value = Value::from(options);
+///
+/// let printer = PrettyPrinter::new().redact_key("password").max_bytes(4);
+/// let printed = printer.display(&value).to_string();
+/// assert!(printed.contains(r#""user": <"alice">"#));
+/// assert!(printed.contains(r#""Password": <redacted>"#));
+/// assert!(printed.contains(r#""avatar": <[0x89, 0x50, 0x4e, 0x47, … (2 more)]>"#));
+/// ```
+///
+/// [GVariant text format]: https://docs.gtk.org/glib/gvariant-text.html
+#[derive(Debug, Clone, Default)]
+pub struct PrettyPrinter {
+    max_bytes: Option<usize>,
+    redacted_keys: Vec<String>,
+    max_depth: Option<usize>,
+    indent: Option<usize>,
+}
+
+assert_impl_all!(PrettyPrinter: Send, Sync, Unpin);
+
+impl PrettyPrinter {
+    /// Create a printer that prints values in full.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only print the first `max` bytes of byte arrays, followed by the number of bytes omitted.
+    #[must_use]
+    pub fn max_bytes(mut self, max: usize) -> Self {
+        self.max_bytes = Some(max);
+
+        self
+    }
+
+    /// Redact the values of dictionary entries with string keys containing `pattern`.
+    ///
+    /// The matching is case-insensitive. This can be called multiple times to redact several
+    /// patterns.
+    #[must_use]
+    pub fn redact_key<P>(mut self, pattern: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.redacted_keys.push(pattern.into().to_lowercase());
+
+        self
+    }
+
+    /// Don't print the contents of containers nested more than `max` levels deep.
+    ///
+    /// The root value is at depth 0 and each level of array, dictionary, structure, variant or
+    /// maybe adds one. Containers that are too deep are printed as `…`.
+    #[must_use]
+    pub fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+
+        self
+    }
+
+    /// Print each element of arrays, dictionaries and structures on its own line, indented by
+    /// `width` spaces per level of nesting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{PrettyPrinter, Value};
+    ///
+    /// let value = Value::new((42_u32, vec!["foo", "bar"], Vec::<u32>::new()));
+    /// let printer = PrettyPrinter::new().indent(2);
+    /// assert_eq!(
+    ///     printer.display(&value).to_string(),
+    ///     "(\n  42,\n  [\n    \"foo\",\n    \"bar\"\n  ],\n  []\n)",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn indent(mut self, width: usize) -> Self {
+        self.indent = Some(width);
+
+        self
+    }
+
+    /// A wrapper of `value` implementing [`Display`] with this printer's configuration.
+    pub fn display<'p>(&'p self, value: &'p Value<'p>) -> PrettyValue<'p> {
+        PrettyValue {
+            printer: self,
+            value,
+        }
+    }
+
+    fn is_redacted(&self, key: &Value<'_>) -> bool {
+        match key.as_str() {
+            Some(key) if !self.redacted_keys.is_empty() => {
+                let key = key.to_lowercase();
+
+                self.redacted_keys
+                    .iter()
+                    .any(|pattern| key.contains(pattern.as_str()))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A [`Value`] printed with a [`PrettyPrinter`].
+///
+/// Returned by [`PrettyPrinter::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct PrettyValue<'p> {
+    printer: &'p PrettyPrinter,
+    value: &'p Value<'p>,
+}
+
+impl Display for PrettyValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = Writer {
+            printer: self.printer,
+            out: String::new(),
+            frames: vec![],
+        };
+        self.value.visit(&mut writer);
+
+        f.write_str(&writer.out)
+    }
+}
+
+// A value being printed.
+struct Frame {
+    // What to write after the children.
+    close: &'static str,
+    // If no child has been written yet.
+    first: bool,
+    // If the children are written on their own lines.
+    multiline: bool,
+}
+
+struct Writer<'p> {
+    printer: &'p PrettyPrinter,
+    out: String,
+    frames: Vec<Frame>,
+}
+
+impl Writer<'_> {
+    fn open(&mut self, open: &str, close: &'static str, multiline: bool) -> Visit {
+        self.out.push_str(open);
+        self.frames.push(Frame {
+            close,
+            first: true,
+            multiline: multiline && self.printer.indent.is_some(),
+        });
+
+        Visit::Continue
+    }
+
+    // Start a new line, indented for the current depth.
+    fn new_line(&mut self) {
+        let depth = self.frames.iter().filter(|f| f.multiline).count();
+        let width = self.printer.indent.unwrap_or_default();
+        self.out.push('\n');
+        self.out.extend(std::iter::repeat(' ').take(depth * width));
+    }
+
+    // Write `value` in full, without visiting its children.
+    fn leaf(&mut self, value: &dyn Display) -> Visit {
+        let _ = write!(self.out, "{value}");
+        self.frames.push(Frame {
+            close: "",
+            first: true,
+            multiline: false,
+        });
+
+        Visit::SkipChildren
+    }
+
+    fn bytes(&mut self, bytes: &[Value<'_>], max: usize) -> Visit {
+        self.out.push('[');
+        for (i, byte) in bytes.iter().take(max).enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            let _ = write!(self.out, "{}", Unannotated(byte));
+        }
+        let sep = if max == 0 { "" } else { ", " };
+        let _ = write!(self.out, "{sep}… ({} more)]", bytes.len() - max);
+
+        self.leaf(&"")
+    }
+}
+
+impl ValueVisitor for Writer<'_> {
+    fn visit(&mut self, path: &[ValuePathSegment<'_>], value: &Value<'_>) -> Visit {
+        if let Some(parent) = self.frames.last_mut() {
+            let (first, multiline) = (parent.first, parent.multiline);
+            parent.first = false;
+            match (first, multiline) {
+                (true, false) => (),
+                (false, false) => self.out.push_str(", "),
+                (true, true) => self.new_line(),
+                (false, true) => {
+                    self.out.push(',');
+                    self.new_line();
+                }
+            }
+        }
+        if let Some(ValuePathSegment::Key(key)) = path.last() {
+            let _ = write!(self.out, "{}: ", Unannotated(key));
+            if self.printer.is_redacted(key) {
+                return self.leaf(&"<redacted>");
+            }
+        }
+
+        let is_container = matches!(
+            value,
+            Value::Value(_) | Value::Array(_) | Value::Dict(_) | Value::Structure(_)
+        );
+        #[cfg(feature = "gvariant")]
+        let is_container = is_container || matches!(value, Value::Maybe(_));
+        if is_container
+            && self
+                .printer
+                .max_depth
+                .map_or(false, |max| path.len() >= max)
+        {
+            return self.leaf(&"…");
+        }
+
+        match value {
+            Value::Value(_) => self.open("<", ">", false),
+            Value::Array(array) => match self.printer.max_bytes {
+                Some(max) if array.element_signature().as_str() == "y" && array.len() > max => {
+                    self.bytes(array.get(), max)
+                }
+                _ => self.open("[", "]", true),
+            },
+            Value::Dict(_) => self.open("{", "}", true),
+            Value::Structure(_) => self.open("(", ")", true),
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(maybe) => match maybe.inner() {
+                Some(_) => self.open("just ", "", false),
+                None => self.leaf(&"nothing"),
+            },
+            _ => self.leaf(&Unannotated(value)),
+        }
+    }
+
+    fn leave(&mut self, _path: &[ValuePathSegment<'_>], _value: &Value<'_>) {
+        if let Some(frame) = self.frames.pop() {
+            if frame.multiline && !frame.first {
+                self.new_line();
+            }
+            self.out.push_str(frame.close);
+        }
+    }
+}
+
+// A basic value, in the GVariant text format without type annotations.
+pub(crate) struct Unannotated<'a>(pub(crate) &'a Value<'a>);
+
+impl Display for Unannotated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        value_display_fmt(self.0, f, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn pretty_printer() {
+        let value = Value::new((42_u32, "foo", vec![1_u8, 2, 3], ((1, 2), vec!["bar"])));
+        let printer = PrettyPrinter::new();
+        assert_eq!(
+            printer.display(&value).to_string(),
+            r#"(42, "foo", [0x01, 0x02, 0x03], ((1, 2), ["bar"]))"#
+        );
+
+        let printer = PrettyPrinter::new().max_depth(2).max_bytes(0);
+        assert_eq!(
+            printer.display(&value).to_string(),
+            r#"(42, "foo", [… (3 more)], (…, …))"#
+        );
+
+        let mut secrets = HashMap::new();
+        secrets.insert("API_SECRET", Value::new(vec!["a", "b"]));
+        let value = Value::from(vec![secrets]);
+        let printer = PrettyPrinter::new().redact_key("secret");
+        assert_eq!(
+            printer.display(&value).to_string(),
+            r#"[{"API_SECRET": <redacted>}]"#
+        );
+
+        let value = Value::new((
+            42_u32,
+            HashMap::from([("k", Value::new(vec![true]))]),
+            Vec::<u32>::new(),
+        ));
+        let printer = PrettyPrinter::new().indent(4);
+        assert_eq!(
+            printer.display(&value).to_string(),
+            r#"(
+    42,
+    {
+        "k": <[
+            true
+        ]>
+    },
+    []
+)"#
+        );
+    }
+}
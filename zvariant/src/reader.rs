@@ -0,0 +1,325 @@
+use serde::de::DeserializeOwned;
+
+use std::io::Read;
+
+#[cfg(feature = "gvariant")]
+use crate::from_slice_for_signature;
+#[cfg(all(feature = "async", unix))]
+use crate::Fd;
+#[cfg(feature = "async")]
+use crate::{
+    container_depths::ContainerDepths, signature_parser::SignatureParser, utils::*, Basic,
+    MaxSizeExceeded, ObjectPath,
+};
+use crate::{
+    dbus::ReaderDeserializer, EncodingContext, EncodingFormat, Error, Result, Signature, Type,
+};
+
+/// Deserialize `T` from the given `reader`.
+///
+/// See [`from_reader_for_signature`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{from_reader, to_bytes, EncodingContext};
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let mut encoded = to_bytes(ctxt, "hello").unwrap();
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(encoded.len());
+/// encoded.extend(to_bytes(ctxt, "world").unwrap());
+///
+/// let mut reader = std::io::Cursor::new(encoded);
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let (first, len): (String, _) = from_reader(&mut reader, ctxt).unwrap();
+/// assert_eq!(first, "hello");
+/// assert_eq!(len, 10);
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(len);
+/// let (second, _): (String, _) = from_reader(&mut reader, ctxt).unwrap();
+/// assert_eq!(second, "world");
+/// ```
+///
+/// [`from_reader_for_signature`]: fn.from_reader_for_signature.html
+pub fn from_reader<B, R, T>(reader: &mut R, ctxt: EncodingContext<B>) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+    T: DeserializeOwned + Type,
+{
+    let signature = T::signature();
+
+    from_reader_for_signature(reader, ctxt, &signature)
+}
+
+/// Deserialize `T` with the given signature from the given `reader`.
+///
+/// In the D-Bus format, the value is deserialized as it's read. Only the encoding of the basic
+/// value being deserialized (e.g a string) is kept in memory, so the encoding of a large value
+/// never needs to be in memory as a whole. Moreover, only the bytes making up the value are read
+/// from the `reader`, which is left at the end of the value. This allows reading values one by one
+/// from a large stream (e.g a D-Bus capture).
+///
+/// Since the data doesn't stay around, nothing can be borrowed from it. File descriptors can't be
+/// passed along with a reader either, so values containing any fail to deserialize with
+/// [`Error::UnknownFd`].
+///
+/// In the GVariant format, the framing offsets of a container are at its end and the size of a
+/// value is only given by its container. Hence the `reader` is read to the end and the value is
+/// deserialized from the bytes read.
+///
+/// The [`Limits`] of `ctxt` are enforced as the data is read, so that a malicious source can't
+/// make this function read more than the maximum size.
+///
+/// Use this function instead of [`from_reader`] if the value being deserialized does not
+/// implement [`Type`].
+///
+/// # Return value
+///
+/// A tuple containing the deserialized value and the number of bytes read from `reader`.
+///
+/// [`from_reader`]: fn.from_reader.html
+/// [`Error::UnknownFd`]: enum.Error.html#variant.UnknownFd
+/// [`Type`]: trait.Type.html
+/// [`Limits`]: struct.Limits.html
+pub fn from_reader_for_signature<'s, B, R, S, T>(
+    reader: &mut R,
+    ctxt: EncodingContext<B>,
+    signature: S,
+) -> Result<(T, usize)>
+where
+    B: byteorder::ByteOrder,
+    R: Read,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: DeserializeOwned,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+    match ctxt.format() {
+        EncodingFormat::DBus => {
+            let mut de = ReaderDeserializer::new(reader, signature, ctxt);
+            let v = T::deserialize(&mut de)?;
+
+            Ok((v, de.pos()))
+        }
+        #[cfg(feature = "gvariant")]
+        EncodingFormat::GVariant => {
+            let mut bytes = vec![];
+            // Reading one byte beyond the limit is enough for the deserializer to reject it.
+            (&mut *reader)
+                .take(ctxt.limits().max_size() as u64 + 1)
+                .read_to_end(&mut bytes)
+                .map_err(|e| Error::InputOutput(e.into()))?;
+
+            from_slice_for_signature(&bytes, ctxt, signature)
+        }
+    }
+}
+
+pub(crate) fn check_read_len(read: usize, expected: u64) -> Result<()> {
+    if read as u64 != expected {
+        return Err(Error::InputOutput(
+            std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Determines the bytes making up a D-Bus-encoded value, as they are read.
+//
+// Since the encoding is self-delimiting, the scanner only ever asks for the exact number of bytes
+// it needs next, so the source isn't read beyond the end of the value. Notably, array contents are
+// never inspected, as their length is part of the encoding. The reading itself is left to the
+// caller, which is how values are read from async sources, before being deserialized.
+#[cfg(feature = "async")]
+pub(crate) struct DBusValueScanner<B> {
+    ctxt: EncodingContext<B>,
+    steps: Vec<Step>,
+    container_depths: ContainerDepths,
+}
+
+#[cfg(feature = "async")]
+enum Step {
+    // Padding followed by the value.
+    Value(Signature<'static>),
+    // The value itself, after the padding.
+    Data(Signature<'static>),
+    StrLen,
+    SignatureLen { variant: bool },
+    SignatureData { len: usize, variant: bool },
+    ArrayLen { element_alignment: usize },
+    ArrayData { len: u32 },
+    RestoreDepths(ContainerDepths),
+}
+
+#[cfg(feature = "async")]
+impl<B> DBusValueScanner<B>
+where
+    B: byteorder::ByteOrder,
+{
+    pub(crate) fn new(ctxt: EncodingContext<B>, signature: &Signature<'_>) -> Result<Self> {
+        let mut scanner = Self {
+            ctxt,
+            steps: vec![],
+            container_depths: ContainerDepths::new(ctxt.limits()),
+        };
+        scanner.push_values(signature)?;
+
+        Ok(scanner)
+    }
+
+    // The number of bytes to append to `bytes` before the next call.
+    //
+    // `bytes` are all the bytes read so far and `None` is returned once the value is complete.
+    pub(crate) fn next_read(&mut self, bytes: &[u8]) -> Result<Option<u64>> {
+        let len = self.next_step(bytes)?;
+        if let Some(len) = len {
+            if bytes.len() as u64 + len > self.ctxt.limits().max_size() as u64 {
+                return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Total));
+            }
+        }
+
+        Ok(len)
+    }
+
+    fn next_step(&mut self, bytes: &[u8]) -> Result<Option<u64>> {
+        while let Some(step) = self.steps.pop() {
+            match step {
+                Step::Value(signature) => {
+                    let alignment = alignment_for_signature(&signature, EncodingFormat::DBus)?;
+                    let padding =
+                        padding_for_n_bytes(self.ctxt.position() + bytes.len(), alignment);
+                    self.steps.push(Step::Data(signature));
+                    if padding > 0 {
+                        return Ok(Some(padding as u64));
+                    }
+                }
+                Step::Data(signature) => {
+                    if let Some(len) = self.data_step(&signature)? {
+                        return Ok(Some(len));
+                    }
+                }
+                Step::StrLen => {
+                    // Including the trailing nul byte.
+                    return Ok(Some(u64::from(last_u32::<B>(bytes)) + 1));
+                }
+                Step::SignatureLen { variant } => {
+                    let len = usize::from(bytes[bytes.len() - 1]);
+                    self.steps.push(Step::SignatureData { len, variant });
+
+                    // Including the trailing nul byte.
+                    return Ok(Some(len as u64 + 1));
+                }
+                Step::SignatureData { len, variant } => {
+                    if variant {
+                        let start = bytes.len() - len - 1;
+                        let signature = std::str::from_utf8(&bytes[start..start + len])
+                            .map_err(Error::Utf8)?
+                            .to_owned();
+                        let signature = Signature::try_from(signature)?;
+                        if signature.n_complete_types()? != 1 {
+                            return Err(serde::de::Error::invalid_value(
+                                serde::de::Unexpected::Str(signature.as_str()),
+                                &"a single complete type",
+                            ));
+                        }
+
+                        self.steps.push(Step::RestoreDepths(self.container_depths));
+                        self.container_depths = self.container_depths.inc_variant()?;
+                        self.steps.push(Step::Value(signature));
+                    }
+                }
+                Step::ArrayLen { element_alignment } => {
+                    let len = last_u32::<B>(bytes);
+                    if len as usize > self.ctxt.limits().max_array_size() {
+                        return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Array));
+                    }
+                    self.steps.push(Step::ArrayData { len });
+                    // The padding for the first element is there, even if the array is empty.
+                    let padding =
+                        padding_for_n_bytes(self.ctxt.position() + bytes.len(), element_alignment);
+                    if padding > 0 {
+                        return Ok(Some(padding as u64));
+                    }
+                }
+                Step::ArrayData { len } => {
+                    if len > 0 {
+                        return Ok(Some(len.into()));
+                    }
+                }
+                Step::RestoreDepths(container_depths) => self.container_depths = container_depths,
+            }
+        }
+
+        Ok(None)
+    }
+
+    // `signature` must be **one** complete and correct signature.
+    fn data_step(&mut self, signature: &Signature<'_>) -> Result<Option<u64>> {
+        let len = match signature.as_bytes()[0] as char {
+            u8::SIGNATURE_CHAR => 1,
+            i16::SIGNATURE_CHAR | u16::SIGNATURE_CHAR => 2,
+            bool::SIGNATURE_CHAR | i32::SIGNATURE_CHAR | u32::SIGNATURE_CHAR => 4,
+            #[cfg(unix)]
+            Fd::SIGNATURE_CHAR => 4,
+            i64::SIGNATURE_CHAR | u64::SIGNATURE_CHAR | f64::SIGNATURE_CHAR => 8,
+            <&str>::SIGNATURE_CHAR | ObjectPath::SIGNATURE_CHAR => {
+                self.steps.push(Step::StrLen);
+
+                4
+            }
+            Signature::SIGNATURE_CHAR => {
+                self.steps.push(Step::SignatureLen { variant: false });
+
+                1
+            }
+            VARIANT_SIGNATURE_CHAR => {
+                self.steps.push(Step::SignatureLen { variant: true });
+
+                1
+            }
+            ARRAY_SIGNATURE_CHAR => {
+                let element_signature = signature.slice(1..);
+                let element_alignment =
+                    alignment_for_signature(&element_signature, EncodingFormat::DBus)?;
+                self.steps.push(Step::ArrayLen { element_alignment });
+
+                4
+            }
+            STRUCT_SIG_START_CHAR => {
+                self.steps.push(Step::RestoreDepths(self.container_depths));
+                self.container_depths = self.container_depths.inc_structure()?;
+                self.push_values(&signature.slice(1..signature.len() - 1))?;
+
+                return Ok(None);
+            }
+            _ => {
+                return Err(Error::IncompatibleFormat(
+                    signature.to_owned(),
+                    EncodingFormat::DBus,
+                ))
+            }
+        };
+
+        Ok(Some(len))
+    }
+
+    // Push the steps for each complete type in `signature`, to be processed in order.
+    fn push_values(&mut self, signature: &Signature<'_>) -> Result<()> {
+        let mut parser = SignatureParser::new(signature.as_ref());
+        let start = self.steps.len();
+        while !parser.done() {
+            let signature = parser.parse_next_signature()?;
+            self.steps.push(Step::Value(signature.to_owned()));
+        }
+        self.steps[start..].reverse();
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+fn last_u32<B: byteorder::ByteOrder>(bytes: &[u8]) -> u32 {
+    B::read_u32(&bytes[bytes.len() - 4..])
+}
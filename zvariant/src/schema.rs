@@ -0,0 +1,433 @@
+use static_assertions::assert_impl_all;
+use std::{
+    collections::BTreeMap,
+    error, fmt,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{pretty::Unannotated, Error, Result, Signature, Value};
+
+/// A declarative description of the [`Value`]s accepted somewhere, e.g in an `a{sv}` options
+/// dictionary.
+///
+/// A schema is built from the expected signature, plus optional constraints:
+///
+/// * [`range`](Schema::range) for integers,
+/// * [`one_of`](Schema::one_of) for enumerations of allowed values,
+/// * [`max_len`](Schema::max_len) for strings, arrays and dictionaries,
+/// * [`field`](Schema::field), [`required`](Schema::required) and
+///   [`deny_unknown_fields`](Schema::deny_unknown_fields) for dictionaries with string keys,
+/// * [`items`](Schema::items) for the elements of arrays and the values of dictionaries.
+///
+/// [`Schema::validate`] then checks a value against the schema, reporting the first violation
+/// found along with where it is in the value. Variants are looked through transparently, unless
+/// the schema signature is itself `v`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{Schema, SchemaViolation, Value};
+///
+/// let schema = Schema::new("a{sv}")?
+///     .field("timeout", Schema::new("u")?.range(1..=3600))
+///     .field("mode", Schema::new("s")?.one_of(["fast", "safe"]))
+///     .field("tags", Schema::new("as")?.max_len(4))
+///     .required("mode")
+///     .deny_unknown_fields();
+///
+/// let mut options = HashMap::new();
+/// options.insert("mode", Value::from("fast"));
+/// options.insert("timeout", Value::from(30_u32));
+/// schema.validate(&Value::from(options.clone())).unwrap();
+///
+/// options.insert("timeout", Value::from(0_u32));
+/// let e = schema.validate(&Value::from(options.clone())).unwrap_err();
+/// assert_eq!(e.path(), r#"["timeout"]"#);
+/// assert_eq!(e.violation(), &SchemaViolation::OutOfRange(0));
+///
+/// options.remove("timeout");
+/// options.insert("mode", Value::from(42_u32));
+/// let e = schema.validate(&Value::from(options)).unwrap_err();
+/// assert_eq!(
+///     e.to_string(),
+///     r#"invalid value at `["mode"]`: expected signature `s`, found `u`"#,
+/// );
+/// # Ok::<(), zvariant::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schema {
+    signature: Signature<'static>,
+    range: Option<(Bound<i128>, Bound<i128>)>,
+    allowed: Vec<Value<'static>>,
+    max_len: Option<usize>,
+    fields: BTreeMap<String, Schema>,
+    required: Vec<String>,
+    deny_unknown_fields: bool,
+    items: Option<Box<Schema>>,
+}
+
+assert_impl_all!(Schema: Send, Sync, Unpin);
+
+impl Schema {
+    /// Create a schema accepting any value of the given signature.
+    pub fn new<S>(signature: S) -> Result<Self>
+    where
+        S: TryInto<Signature<'static>>,
+        S::Error: Into<Error>,
+    {
+        Ok(Self {
+            signature: signature.try_into().map_err(Into::into)?,
+            range: None,
+            allowed: vec![],
+            max_len: None,
+            fields: BTreeMap::new(),
+            required: vec![],
+            deny_unknown_fields: false,
+            items: None,
+        })
+    }
+
+    /// The signature of the values accepted.
+    pub fn signature(&self) -> &Signature<'static> {
+        &self.signature
+    }
+
+    /// Only accept integers in `range`.
+    ///
+    /// This has no effect on non-integer values.
+    #[must_use]
+    pub fn range<R>(mut self, range: R) -> Self
+    where
+        R: RangeBounds<i128>,
+    {
+        self.range = Some((range.start_bound().cloned(), range.end_bound().cloned()));
+
+        self
+    }
+
+    /// Only accept the given values.
+    #[must_use]
+    pub fn one_of<I, V>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value<'static>>,
+    {
+        self.allowed.extend(values.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Only accept strings (in bytes), arrays and dictionaries of at most `max` elements.
+    #[must_use]
+    pub fn max_len(mut self, max: usize) -> Self {
+        self.max_len = Some(max);
+
+        self
+    }
+
+    /// Validate the value associated with the string `key` of dictionaries using `schema`.
+    #[must_use]
+    pub fn field<K>(mut self, key: K, schema: Schema) -> Self
+    where
+        K: Into<String>,
+    {
+        self.fields.insert(key.into(), schema);
+
+        self
+    }
+
+    /// Require dictionaries to contain the string `key`.
+    #[must_use]
+    pub fn required<K>(mut self, key: K) -> Self
+    where
+        K: Into<String>,
+    {
+        self.required.push(key.into());
+
+        self
+    }
+
+    /// Reject dictionaries with keys no [`field`](Schema::field) schema was given for.
+    #[must_use]
+    pub fn deny_unknown_fields(mut self) -> Self {
+        self.deny_unknown_fields = true;
+
+        self
+    }
+
+    /// Validate the elements of arrays, and the values of dictionaries, using `schema`.
+    ///
+    /// For dictionaries, the [`field`](Schema::field) schemas take precedence.
+    #[must_use]
+    pub fn items(mut self, schema: Schema) -> Self {
+        self.items = Some(Box::new(schema));
+
+        self
+    }
+
+    /// Check if `value` conforms to this schema.
+    ///
+    /// # Errors
+    ///
+    /// The first violation of the schema found, along with its location in `value`.
+    pub fn validate(&self, value: &Value<'_>) -> std::result::Result<(), SchemaError> {
+        self.validate_at(value, &mut String::new())
+    }
+
+    fn validate_at(
+        &self,
+        value: &Value<'_>,
+        path: &mut String,
+    ) -> std::result::Result<(), SchemaError> {
+        let value = match value {
+            Value::Value(inner) if self.signature != "v" => &**inner,
+            value => value,
+        };
+        let signature = value.value_signature();
+        if signature != self.signature {
+            return Err(error(
+                path,
+                SchemaViolation::Signature {
+                    expected: self.signature.to_string(),
+                    found: signature.to_string(),
+                },
+            ));
+        }
+
+        if let (Some(range), Some(n)) = (&self.range, integer(value)) {
+            if !range.contains(&n) {
+                return Err(error(path, SchemaViolation::OutOfRange(n)));
+            }
+        }
+
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|allowed| allowed == value) {
+            return Err(error(
+                path,
+                SchemaViolation::NotAllowed(Unannotated(value).to_string()),
+            ));
+        }
+
+        if let (Some(max), Some(len)) = (self.max_len, len(value)) {
+            if len > max {
+                return Err(error(path, SchemaViolation::TooLong { max, len }));
+            }
+        }
+
+        match value {
+            Value::Array(array) => {
+                if let Some(items) = &self.items {
+                    for (i, item) in array.iter().enumerate() {
+                        with_segment(path, format_args!("[{i}]"), |path| {
+                            items.validate_at(item, path)
+                        })?;
+                    }
+                }
+            }
+            Value::Dict(dict) => {
+                for key in &self.required {
+                    if !dict.iter().any(|(k, _)| k.as_str() == Some(key.as_str())) {
+                        return Err(error(path, SchemaViolation::MissingField(key.clone())));
+                    }
+                }
+
+                for (key, value) in dict.iter() {
+                    let schema = key.as_str().and_then(|key| self.fields.get(key));
+                    let schema = match (schema, self.items.as_deref()) {
+                        (Some(schema), _) | (None, Some(schema)) => schema,
+                        (None, None) if self.deny_unknown_fields => {
+                            return Err(error(
+                                path,
+                                SchemaViolation::UnknownField(Unannotated(key).to_string()),
+                            ));
+                        }
+                        (None, None) => continue,
+                    };
+                    with_segment(path, format_args!("[{}]", Unannotated(key)), |path| {
+                        schema.validate_at(value, path)
+                    })?;
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+fn error(path: &str, violation: SchemaViolation) -> SchemaError {
+    SchemaError {
+        path: path.to_string(),
+        violation,
+    }
+}
+
+fn with_segment<F>(
+    path: &mut String,
+    segment: fmt::Arguments<'_>,
+    f: F,
+) -> std::result::Result<(), SchemaError>
+where
+    F: FnOnce(&mut String) -> std::result::Result<(), SchemaError>,
+{
+    let len = path.len();
+    fmt::Write::write_fmt(path, segment).expect("formatting into a String can't fail");
+    let res = f(path);
+    path.truncate(len);
+
+    res
+}
+
+fn integer(value: &Value<'_>) -> Option<i128> {
+    match value {
+        Value::U8(n) => Some((*n).into()),
+        Value::I16(n) => Some((*n).into()),
+        Value::U16(n) => Some((*n).into()),
+        Value::I32(n) => Some((*n).into()),
+        Value::U32(n) => Some((*n).into()),
+        Value::I64(n) => Some((*n).into()),
+        Value::U64(n) => Some((*n).into()),
+        _ => None,
+    }
+}
+
+fn len(value: &Value<'_>) -> Option<usize> {
+    match value {
+        Value::Str(s) => Some(s.as_str().len()),
+        Value::Signature(s) => Some(s.len()),
+        Value::ObjectPath(p) => Some(p.len()),
+        Value::Array(array) => Some(array.len()),
+        Value::Dict(dict) => Some(dict.iter().count()),
+        _ => None,
+    }
+}
+
+/// A violation of a [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaViolation {
+    /// The value doesn't have the expected signature.
+    Signature {
+        /// The signature of the schema.
+        expected: String,
+        /// The signature of the value.
+        found: String,
+    },
+    /// The integer is outside the allowed range.
+    OutOfRange(i128),
+    /// The value, formatted in the GVariant text format, is not one of the allowed values.
+    NotAllowed(String),
+    /// The string, array or dictionary is longer than allowed.
+    TooLong {
+        /// The maximum length allowed.
+        max: usize,
+        /// The length of the value.
+        len: usize,
+    },
+    /// The dictionary doesn't contain the required key.
+    MissingField(String),
+    /// The dictionary contains the key (formatted in the GVariant text format), which no schema
+    /// was given for.
+    UnknownField(String),
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaViolation::Signature { expected, found } => {
+                write!(f, "expected signature `{expected}`, found `{found}`")
+            }
+            SchemaViolation::OutOfRange(n) => write!(f, "{n} is out of the allowed range"),
+            SchemaViolation::NotAllowed(v) => write!(f, "{v} is not one of the allowed values"),
+            SchemaViolation::TooLong { max, len } => {
+                write!(f, "length {len} is more than the maximum allowed ({max})")
+            }
+            SchemaViolation::MissingField(key) => write!(f, "missing required field `{key}`"),
+            SchemaViolation::UnknownField(key) => write!(f, "unknown field {key}"),
+        }
+    }
+}
+
+/// The error returned by [`Schema::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    path: String,
+    violation: SchemaViolation,
+}
+
+assert_impl_all!(SchemaError: Send, Sync, Unpin);
+
+impl SchemaError {
+    /// The location of the offending value, from the root of the value validated.
+    ///
+    /// The path is made of `[<index>]` segments for array elements and `[<key>]` segments for
+    /// dictionary values, with keys in the GVariant text format (e.g `[2]["timeout"]`). It's empty
+    /// if the root value itself is invalid.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The violation of the schema.
+    pub fn violation(&self) -> &SchemaViolation {
+        &self.violation
+    }
+}
+
+impl error::Error for SchemaError {}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "invalid value: {}", self.violation)
+        } else {
+            write!(f, "invalid value at `{}`: {}", self.path, self.violation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn nested() {
+        let schema = Schema::new("aa{sv}").unwrap().max_len(2).items(
+            Schema::new("a{sv}")
+                .unwrap()
+                .field("id", Schema::new("x").unwrap().range(..0))
+                .required("id"),
+        );
+
+        let entry = |id: i64| HashMap::from([("id", Value::from(id))]);
+        schema
+            .validate(&Value::from(vec![entry(-1), entry(-2)]))
+            .unwrap();
+
+        let e = schema
+            .validate(&Value::from(vec![entry(-1), entry(2)]))
+            .unwrap_err();
+        assert_eq!(e.path(), r#"[1]["id"]"#);
+        assert_eq!(e.violation(), &SchemaViolation::OutOfRange(2));
+
+        let e = schema
+            .validate(&Value::from(vec![entry(-1), HashMap::new()]))
+            .unwrap_err();
+        assert_eq!(e.path(), "[1]");
+        assert_eq!(e.violation(), &SchemaViolation::MissingField("id".into()));
+
+        let e = schema
+            .validate(&Value::from(vec![entry(-1); 3]))
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid value: length 3 is more than the maximum allowed (2)"
+        );
+
+        // Variants are looked through, unless the schema is for a variant.
+        let variant = Value::new(Value::from("foo"));
+        Schema::new("s").unwrap().validate(&variant).unwrap();
+        Schema::new("v").unwrap().validate(&variant).unwrap();
+    }
+}
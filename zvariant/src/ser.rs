@@ -353,6 +353,356 @@ where
     Ok((cursor.into_inner(), fds))
 }
 
+/// Serialize `T` to the given `writer`, that doesn't need to implement [`Seek`].
+///
+/// Unlike [`to_writer`], this function doesn't require the `writer` to be seekable, so the
+/// encoded data can be streamed directly to e.g a socket or a pipe, without first collecting it in
+/// memory. Since the D-Bus format prefixes arrays with their length, this is achieved by
+/// serializing the value twice: first to only calculate the array lengths and then to actually
+/// write the encoded data. Hence for seekable writers, you want to use [`to_writer`] instead.
+///
+/// This function returns the number of bytes written to the given `writer`.
+///
+/// # Panics
+///
+/// This function will panic if the value to serialize contains file descriptors. Use
+/// [`to_stream_fds`] if you'd want to potentially pass FDs.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{EncodingContext, from_slice, to_stream};
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let mut encoded = vec![];
+/// to_stream(&mut encoded, ctxt, &vec!["hello", "world"]).unwrap();
+/// let value: Vec<&str> = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(value, ["hello", "world"]);
+/// ```
+///
+/// [`to_writer`]: fn.to_writer.html
+/// [`to_stream_fds`]: fn.to_stream_fds.html
+pub fn to_stream<B, W, T>(writer: &mut W, ctxt: EncodingContext<B>, value: &T) -> Result<usize>
+where
+    B: byteorder::ByteOrder,
+    W: Write,
+    T: ?Sized + Serialize + DynamicType,
+{
+    let signature = value.dynamic_signature();
+
+    to_stream_for_signature(writer, ctxt, &signature, value)
+}
+
+/// Serialize `T` that (potentially) contains FDs, to the given `writer`, that doesn't need to
+/// implement [`Seek`].
+///
+/// See [`to_stream`] for details. This function returns the number of bytes written to the given
+/// `writer` and the file descriptor vector, which needs to be transferred via an out-of-band
+/// platform specific mechanism.
+///
+/// This function is not available on Windows.
+///
+/// [`to_stream`]: fn.to_stream.html
+#[cfg(unix)]
+pub fn to_stream_fds<B, W, T>(
+    writer: &mut W,
+    ctxt: EncodingContext<B>,
+    value: &T,
+) -> Result<(usize, Vec<RawFd>)>
+where
+    B: byteorder::ByteOrder,
+    W: Write,
+    T: ?Sized + Serialize + DynamicType,
+{
+    let signature = value.dynamic_signature();
+
+    to_stream_fds_for_signature(writer, ctxt, &signature, value)
+}
+
+/// Serialize `T` that has the given signature, to the given `writer`, that doesn't need to
+/// implement [`Seek`].
+///
+/// Use this function instead of [`to_stream`] if the value being serialized does not implement
+/// [`Type`].
+///
+/// This function returns the number of bytes written to the given `writer`.
+///
+/// [`to_stream`]: fn.to_stream.html
+/// [`Type`]: trait.Type.html
+pub fn to_stream_for_signature<'s, B, W, S, T>(
+    writer: &mut W,
+    ctxt: EncodingContext<B>,
+    signature: S,
+    value: &T,
+) -> Result<usize>
+where
+    B: byteorder::ByteOrder,
+    W: Write,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: ?Sized + Serialize,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+
+    let mut recorder = PatchRecorder::default();
+    to_writer_for_signature(&mut recorder, ctxt, &signature, value)?;
+    let patches = recorder.into_patches();
+    let mut writer = PatchingWriter::new(writer, &patches);
+    to_writer_for_signature(&mut writer, ctxt, &signature, value)
+}
+
+/// Serialize `T` that (potentially) contains FDs and has the given signature, to the given
+/// `writer`, that doesn't need to implement [`Seek`].
+///
+/// Use this function instead of [`to_stream_fds`] if the value being serialized does not
+/// implement [`Type`].
+///
+/// This function returns the number of bytes written to the given `writer` and the file descriptor
+/// vector, which needs to be transferred via an out-of-band platform specific mechanism.
+///
+/// This function is not available on Windows.
+///
+/// [`to_stream_fds`]: fn.to_stream_fds.html
+/// [`Type`]: trait.Type.html
+#[cfg(unix)]
+pub fn to_stream_fds_for_signature<'s, B, W, S, T>(
+    writer: &mut W,
+    ctxt: EncodingContext<B>,
+    signature: S,
+    value: &T,
+) -> Result<(usize, Vec<RawFd>)>
+where
+    B: byteorder::ByteOrder,
+    W: Write,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: ?Sized + Serialize,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+
+    let mut recorder = PatchRecorder::default();
+    to_writer_fds_for_signature(&mut recorder, ctxt, &signature, value)?;
+    let patches = recorder.into_patches();
+    let mut writer = PatchingWriter::new(writer, &patches);
+    to_writer_fds_for_signature(&mut writer, ctxt, &signature, value)
+}
+
+// Keeps track of the bytes the serializer goes back to overwrite (i-e array lengths), without
+// keeping any of the data written.
+#[derive(Debug, Default)]
+struct PatchRecorder {
+    pos: u64,
+    len: u64,
+    patches: Vec<(u64, [u8; 4])>,
+}
+
+impl PatchRecorder {
+    fn into_patches(mut self) -> Vec<(u64, [u8; 4])> {
+        // Nested arrays are patched before their parent but are located after it.
+        self.patches.sort_unstable_by_key(|(offset, _)| *offset);
+
+        self.patches
+    }
+}
+
+impl Write for PatchRecorder {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.pos < self.len {
+            let patch = buf.try_into().map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "unexpected overwrite of serialized data",
+                )
+            })?;
+            self.patches.push((self.pos, patch));
+        }
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for PatchRecorder {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek_pos(self.pos, self.len, pos)?;
+
+        Ok(self.pos)
+    }
+}
+
+// Streams the data written to the underlying writer, with the recorded patches applied in place of
+// the bytes they overwrite. Any subsequent overwrites are ignored.
+struct PatchingWriter<'w, 'p, W> {
+    writer: &'w mut W,
+    pos: u64,
+    len: u64,
+    patches: std::iter::Peekable<std::slice::Iter<'p, (u64, [u8; 4])>>,
+}
+
+impl<'w, 'p, W> PatchingWriter<'w, 'p, W> {
+    fn new(writer: &'w mut W, patches: &'p [(u64, [u8; 4])]) -> Self {
+        Self {
+            writer,
+            pos: 0,
+            len: 0,
+            patches: patches.iter().peekable(),
+        }
+    }
+}
+
+impl<W> Write for PatchingWriter<'_, '_, W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.pos >= self.len {
+            match self.patches.next_if(|(offset, _)| *offset == self.pos) {
+                Some((_, patch)) if patch.len() == buf.len() => self.writer.write_all(patch)?,
+                Some(_) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "unexpected overwrite of serialized data",
+                    ))
+                }
+                None => self.writer.write_all(buf)?,
+            }
+        }
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W> Seek for PatchingWriter<'_, '_, W> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek_pos(self.pos, self.len, pos)?;
+
+        Ok(self.pos)
+    }
+}
+
+// The encoding of a value, produced in chunks for writers that can't be handed a `Write`, i-e async
+// ones. Like `to_stream`, a first pass records the patches and the length of the encoding. Then
+// each chunk is produced by another pass that only keeps the bytes of that chunk.
+#[cfg(feature = "async")]
+pub(crate) struct ChunkedEncoding<'s, 'v, B, T: ?Sized> {
+    ctxt: EncodingContext<B>,
+    signature: Signature<'s>,
+    value: &'v T,
+    patches: Vec<(u64, [u8; 4])>,
+    len: u64,
+    pos: u64,
+    chunk_size: u64,
+}
+
+#[cfg(feature = "async")]
+impl<'s, 'v, B, T> ChunkedEncoding<'s, 'v, B, T>
+where
+    B: byteorder::ByteOrder,
+    T: ?Sized + Serialize,
+{
+    // Chunks are at least this large, so small values only take the two passes of `to_stream`.
+    const MIN_CHUNK_SIZE: u64 = 64 * 1024;
+    // Larger values get larger chunks, so they're not serialized more than this many times.
+    const MAX_CHUNKS: u64 = 8;
+
+    pub(crate) fn new(
+        ctxt: EncodingContext<B>,
+        signature: Signature<'s>,
+        value: &'v T,
+    ) -> Result<Self> {
+        let mut recorder = PatchRecorder::default();
+        to_writer_for_signature(&mut recorder, ctxt, &signature, value)?;
+        let len = recorder.len;
+        let chunk_size = Self::MIN_CHUNK_SIZE.max((len + Self::MAX_CHUNKS - 1) / Self::MAX_CHUNKS);
+
+        Ok(Self {
+            ctxt,
+            signature,
+            value,
+            patches: recorder.into_patches(),
+            len,
+            pos: 0,
+            chunk_size,
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub(crate) fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        if self.pos == self.len {
+            return Ok(None);
+        }
+
+        let end = self.len.min(self.pos + self.chunk_size);
+        let mut window = WindowWriter {
+            pos: 0,
+            range: self.pos..end,
+            buf: Vec::with_capacity((end - self.pos) as usize),
+        };
+        let mut writer = PatchingWriter::new(&mut window, &self.patches);
+        to_writer_for_signature(&mut writer, self.ctxt, &self.signature, self.value)?;
+        self.pos = end;
+
+        Ok(Some(window.buf))
+    }
+}
+
+// Only keeps the bytes written within `range`.
+#[cfg(feature = "async")]
+struct WindowWriter {
+    pos: u64,
+    range: std::ops::Range<u64>,
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "async")]
+impl Write for WindowWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let end = self.pos + buf.len() as u64;
+        let from = self.pos.max(self.range.start);
+        let to = end.min(self.range.end);
+        if from < to {
+            self.buf
+                .extend_from_slice(&buf[(from - self.pos) as usize..(to - self.pos) as usize]);
+        }
+        self.pos = end;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn seek_pos(current: u64, len: u64, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+    let (base, offset) = match pos {
+        std::io::SeekFrom::Start(offset) => return Ok(offset),
+        std::io::SeekFrom::Current(offset) => (current, offset),
+        std::io::SeekFrom::End(offset) => (len, offset),
+    };
+
+    base.checked_add_signed(offset).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
+
 /// Context for all our serializers and provides shared functionality.
 pub(crate) struct SerializerCommon<'ser, 'sig, B, W> {
     pub(crate) ctxt: EncodingContext<B>,
@@ -571,6 +921,10 @@ where
     serialize_method!(serialize_u16(u: u16));
     serialize_method!(serialize_u32(u: u32));
     serialize_method!(serialize_u64(u: u64));
+    #[cfg(feature = "int128")]
+    serialize_method!(serialize_i128(i: i128));
+    #[cfg(feature = "int128")]
+    serialize_method!(serialize_u128(u: u128));
     serialize_method!(serialize_f32(f: f32));
     serialize_method!(serialize_f64(f: f64));
     serialize_method!(serialize_char(c: char));
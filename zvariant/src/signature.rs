@@ -1,5 +1,7 @@
 use core::{
+    cmp::Ordering,
     fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
     str,
 };
 use serde::{
@@ -13,7 +15,17 @@ use std::{
     sync::Arc,
 };
 
-use crate::{signature_parser::SignatureParser, Basic, EncodingFormat, Error, Result, Type};
+#[cfg(unix)]
+use crate::Fd;
+#[cfg(feature = "gvariant")]
+use crate::MAYBE_SIGNATURE_CHAR;
+use crate::{
+    container_depths::ContainerDepths, signature_parser::SignatureParser, Basic, EncodingFormat,
+    Error, ObjectPath, Result, SignatureError, SignatureNode, Type, ARRAY_SIGNATURE_CHAR,
+    ARRAY_SIGNATURE_STR, DICT_ENTRY_SIG_END_CHAR, DICT_ENTRY_SIG_END_STR,
+    DICT_ENTRY_SIG_START_CHAR, DICT_ENTRY_SIG_START_STR, STRUCT_SIG_END_CHAR, STRUCT_SIG_END_STR,
+    STRUCT_SIG_START_CHAR, STRUCT_SIG_START_STR, VARIANT_SIGNATURE_CHAR,
+};
 
 // A data type similar to Cow and [`bytes::Bytes`] but unlike the former won't allow us to only keep
 // the owned bytes in Arc and latter doesn't have a notion of borrowed data and would require API
@@ -96,7 +108,7 @@ impl<'b> std::ops::Deref for Bytes<'b> {
 ///
 /// [identifies]: https://dbus.freedesktop.org/doc/dbus-specification.html#type-system
 /// [`slice`]: #method.slice
-#[derive(Hash, Clone)]
+#[derive(Clone)]
 pub struct Signature<'a> {
     bytes: Bytes<'a>,
     pos: usize,
@@ -192,7 +204,7 @@ impl<'a> Signature<'a> {
     /// [`Signature::into_owned`] do not clone the underlying bytes.
     pub fn from_static_str(signature: &'static str) -> Result<Self> {
         let bytes = signature.as_bytes();
-        SignatureParser::validate(bytes)?;
+        Validator::validate(bytes)?;
 
         Ok(Self {
             bytes: Bytes::Static(bytes),
@@ -207,7 +219,7 @@ impl<'a> Signature<'a> {
     /// `&'static [u8]`. The former will ensure that [`Signature::to_owned`] and
     /// [`Signature::into_owned`] do not clone the underlying bytes.
     pub fn from_static_bytes(bytes: &'static [u8]) -> Result<Self> {
-        SignatureParser::validate(bytes)?;
+        Validator::validate(bytes)?;
 
         Ok(Self {
             bytes: Bytes::Static(bytes),
@@ -294,6 +306,189 @@ impl<'a> Signature<'a> {
         clone
     }
 
+    /// Returns a slice of `self` for the provided range, if it's a valid signature.
+    ///
+    /// Unlike [`Signature::slice`], this doesn't panic if the range is out of bounds but returns
+    /// [`Error::OutOfBounds`], and it checks that the slice is a valid signature on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let signature = Signature::try_from("(sa{sv})").unwrap();
+    /// assert_eq!(signature.try_slice(2..7).unwrap(), "a{sv}");
+    /// signature.try_slice(2..5).unwrap_err();
+    /// signature.try_slice(2..10).unwrap_err();
+    /// ```
+    ///
+    /// [`Signature::slice`]: #method.slice
+    /// [`Error::OutOfBounds`]: enum.Error.html#variant.OutOfBounds
+    pub fn try_slice(&self, range: impl RangeBounds<usize>) -> Result<Self> {
+        let len = self.len();
+        let pos = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).ok_or(Error::OutOfBounds)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).ok_or(Error::OutOfBounds)?,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        if pos > end || end > len {
+            return Err(Error::OutOfBounds);
+        }
+
+        let slice = self.slice(pos..end);
+        Validator::validate(slice.as_bytes())?;
+
+        Ok(slice)
+    }
+
+    /// Concatenate `self` and `other` into a new signature.
+    ///
+    /// # Errors
+    ///
+    /// If the resulting signature is too long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let signature = Signature::try_from("sa{sv}").unwrap();
+    /// let other = Signature::try_from("as").unwrap();
+    /// assert_eq!(signature.concat(&other).unwrap(), "sa{sv}as");
+    /// ```
+    pub fn concat(&self, other: &Signature<'_>) -> Result<Signature<'static>> {
+        Self::compose([self.as_str(), other.as_str()])
+    }
+
+    /// Create the signature of an array of `element`.
+    ///
+    /// # Errors
+    ///
+    /// If `element` is not exactly one complete type or the resulting signature is invalid (e.g
+    /// too deeply nested).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let element = Signature::try_from("(so)").unwrap();
+    /// assert_eq!(Signature::array_of(&element).unwrap(), "a(so)");
+    ///
+    /// let elements = Signature::try_from("so").unwrap();
+    /// Signature::array_of(&elements).unwrap_err();
+    /// ```
+    pub fn array_of(element: &Signature<'_>) -> Result<Signature<'static>> {
+        element.ensure_single_complete_type("the array element")?;
+
+        Self::compose([ARRAY_SIGNATURE_STR, element.as_str()])
+    }
+
+    /// Create the signature of a structure with the given `fields`.
+    ///
+    /// # Errors
+    ///
+    /// If there are no fields, any of the fields is not exactly one complete type or the resulting
+    /// signature is invalid (e.g too long).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let fields = [
+    ///     Signature::try_from("s").unwrap(),
+    ///     Signature::try_from("a{sv}").unwrap(),
+    /// ];
+    /// assert_eq!(Signature::structure_of(&fields).unwrap(), "(sa{sv})");
+    /// Signature::structure_of([]).unwrap_err();
+    /// ```
+    pub fn structure_of<'f, 's: 'f, I>(fields: I) -> Result<Signature<'static>>
+    where
+        I: IntoIterator<Item = &'f Signature<'s>>,
+    {
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                field.ensure_single_complete_type("a structure field")?;
+
+                Ok(field.as_str())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::compose(
+            std::iter::once(STRUCT_SIG_START_STR)
+                .chain(fields)
+                .chain(std::iter::once(STRUCT_SIG_END_STR)),
+        )
+    }
+
+    /// Create the signature of a dictionary (an array of dict entries) with the given `key` and
+    /// `value` types.
+    ///
+    /// # Errors
+    ///
+    /// If `key` is not a basic type, `value` is not exactly one complete type or the resulting
+    /// signature is otherwise invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let key = Signature::try_from("s").unwrap();
+    /// let value = Signature::try_from("v").unwrap();
+    /// assert_eq!(Signature::dict_of(&key, &value).unwrap(), "a{sv}");
+    /// Signature::dict_of(&value, &key).unwrap_err();
+    ///
+    /// // The key and value must each be exactly one complete type.
+    /// let empty = Signature::try_from("").unwrap();
+    /// Signature::dict_of(&Signature::try_from("sv").unwrap(), &empty).unwrap_err();
+    /// ```
+    pub fn dict_of(key: &Signature<'_>, value: &Signature<'_>) -> Result<Signature<'static>> {
+        key.ensure_single_complete_type("the dict key")?;
+        if !key.chars().next().map_or(false, is_basic_signature_char) {
+            return Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(key.as_str()),
+                &"a basic type for the dict key",
+            ));
+        }
+        value.ensure_single_complete_type("the dict value")?;
+
+        Self::compose([
+            ARRAY_SIGNATURE_STR,
+            DICT_ENTRY_SIG_START_STR,
+            key.as_str(),
+            value.as_str(),
+            DICT_ENTRY_SIG_END_STR,
+        ])
+    }
+
+    // Ensure `self` is exactly one complete type, to be used as `what` in a container signature.
+    fn ensure_single_complete_type(&self, what: &str) -> Result<()> {
+        let n_complete_types = self.n_complete_types()?;
+        if n_complete_types != 1 {
+            return Err(serde::de::Error::invalid_length(
+                n_complete_types,
+                &format!("exactly one complete type for {what}").as_str(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Concatenate `parts` into a new validated signature.
+    fn compose<'p>(parts: impl IntoIterator<Item = &'p str>) -> Result<Signature<'static>> {
+        let signature: String = parts.into_iter().collect();
+
+        Signature::try_from(signature)
+    }
+
     /// The number of complete types for the signature.
     ///
     /// # Errors
@@ -308,6 +503,61 @@ impl<'a> Signature<'a> {
         }
         Ok(count)
     }
+
+    /// Iterate over the complete types of the signature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let signature = Signature::try_from("ya{sv}(ub)").unwrap();
+    /// let types: Vec<_> = signature.complete_types().collect::<Result<_, _>>().unwrap();
+    /// assert_eq!(types, ["y", "a{sv}", "(ub)"]);
+    /// ```
+    pub fn complete_types(&self) -> impl Iterator<Item = Result<Signature<'_>>> {
+        SignatureParser::new(self.as_ref())
+    }
+
+    /// Parse the signature into a tree of [`SignatureNode`], one for each complete type.
+    ///
+    /// See [`SignatureNode`] documentation for an example.
+    ///
+    /// # Errors
+    ///
+    /// If the signature is invalid, returns the first error.
+    ///
+    /// [`SignatureNode`]: enum.SignatureNode.html
+    pub fn parsed(&self) -> Result<Vec<SignatureNode>> {
+        SignatureNode::parse_all(self)
+    }
+
+    /// Check if `signature` is a valid signature.
+    ///
+    /// Unlike the generic [`Error`] returned by the fallible constructors, the returned
+    /// [`SignatureError`] tells exactly what is wrong with the signature and where.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Signature, SignatureError};
+    ///
+    /// assert_eq!(Signature::validate("a{sv}"), Ok(()));
+    /// assert_eq!(
+    ///     Signature::validate("a{vs}"),
+    ///     Err(SignatureError::NonBasicDictKey { position: 2 }),
+    /// );
+    /// assert_eq!(
+    ///     Signature::validate("(su"),
+    ///     Err(SignatureError::Unclosed { position: 0 }),
+    /// );
+    /// ```
+    ///
+    /// [`Error`]: enum.Error.html
+    /// [`SignatureError`]: enum.SignatureError.html
+    pub fn validate(signature: &str) -> std::result::Result<(), SignatureError> {
+        Validator::validate(signature.as_bytes())
+    }
 }
 
 impl<'a> Debug for Signature<'a> {
@@ -345,9 +595,9 @@ impl<'a> TryFrom<&'a [u8]> for Signature<'a> {
     type Error = Error;
 
     fn try_from(value: &'a [u8]) -> Result<Self> {
-        SignatureParser::validate(value)?;
+        Validator::validate(value)?;
 
-        // SAFETY: validate checks UTF8
+        // SAFETY: validate checks the signature is all ASCII
         unsafe { Ok(Self::from_bytes_unchecked(value)) }
     }
 }
@@ -377,7 +627,7 @@ impl<'a> TryFrom<String> for Signature<'a> {
     type Error = Error;
 
     fn try_from(value: String) -> Result<Self> {
-        SignatureParser::validate(value.as_bytes())?;
+        Validator::validate(value.as_bytes())?;
 
         Ok(Self::from_string_unchecked(value))
     }
@@ -451,6 +701,25 @@ impl<'a> PartialEq<&str> for Signature<'a> {
 // are `Eq`. Hence the manual implementation.
 impl Eq for Signature<'_> {}
 
+// Must be consistent with the `PartialEq` implementation above.
+impl Hash for Signature<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        without_outer_parentheses(self).hash(state)
+    }
+}
+
+impl PartialOrd for Signature<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Signature<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        without_outer_parentheses(self).cmp(without_outer_parentheses(other))
+    }
+}
+
 impl<'a> Display for Signature<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         std::fmt::Display::fmt(&self.as_str(), f)
@@ -540,9 +809,163 @@ impl std::fmt::Display for OwnedSignature {
     }
 }
 
+// Validates signatures, keeping track of the position for detailed errors.
+struct Validator<'b> {
+    bytes: &'b [u8],
+    pos: usize,
+    container_depths: ContainerDepths,
+}
+
+impl<'b> Validator<'b> {
+    fn validate(bytes: &'b [u8]) -> std::result::Result<(), SignatureError> {
+        if bytes.len() > 255 {
+            return Err(SignatureError::TooLong(bytes.len()));
+        }
+
+        let mut validator = Self {
+            bytes,
+            pos: 0,
+            container_depths: ContainerDepths::default(),
+        };
+        while validator.peek().is_some() {
+            validator.complete_type()?;
+        }
+
+        Ok(())
+    }
+
+    fn complete_type(&mut self) -> std::result::Result<(), SignatureError> {
+        let position = self.pos;
+        let character = self.peek().ok_or(SignatureError::Unclosed { position })?;
+        self.pos += 1;
+
+        match character {
+            c if is_basic_signature_char(c) || c == VARIANT_SIGNATURE_CHAR => Ok(()),
+            ARRAY_SIGNATURE_CHAR => {
+                let container_depths = self.enter(position, ContainerDepths::inc_array)?;
+                self.element_type(position)?;
+                self.container_depths = container_depths;
+
+                Ok(())
+            }
+            #[cfg(feature = "gvariant")]
+            MAYBE_SIGNATURE_CHAR => self.element_type(position),
+            STRUCT_SIG_START_CHAR => {
+                let container_depths = self.enter(position, ContainerDepths::inc_structure)?;
+                if self.peek() == Some(STRUCT_SIG_END_CHAR) {
+                    return Err(SignatureError::EmptyStructure { position });
+                }
+                loop {
+                    match self.peek() {
+                        Some(STRUCT_SIG_END_CHAR) => break,
+                        Some(_) => self.complete_type()?,
+                        None => return Err(SignatureError::Unclosed { position }),
+                    }
+                }
+                self.pos += 1;
+                self.container_depths = container_depths;
+
+                Ok(())
+            }
+            DICT_ENTRY_SIG_START_CHAR => {
+                let key_position = self.pos;
+                match self.peek() {
+                    Some(c) if is_basic_signature_char(c) => self.pos += 1,
+                    Some(DICT_ENTRY_SIG_END_CHAR) => {
+                        return Err(SignatureError::InvalidDictEntry { position })
+                    }
+                    Some(_) => {
+                        // Report invalid characters as such.
+                        self.complete_type()?;
+
+                        return Err(SignatureError::NonBasicDictKey {
+                            position: key_position,
+                        });
+                    }
+                    None => return Err(SignatureError::Unclosed { position }),
+                }
+                match self.peek() {
+                    Some(DICT_ENTRY_SIG_END_CHAR) => {
+                        return Err(SignatureError::InvalidDictEntry { position })
+                    }
+                    Some(_) => self.complete_type()?,
+                    None => return Err(SignatureError::Unclosed { position }),
+                }
+                match self.peek() {
+                    Some(DICT_ENTRY_SIG_END_CHAR) => {
+                        self.pos += 1;
+
+                        Ok(())
+                    }
+                    Some(_) => Err(SignatureError::InvalidDictEntry { position }),
+                    None => Err(SignatureError::Unclosed { position }),
+                }
+            }
+            STRUCT_SIG_END_CHAR | DICT_ENTRY_SIG_END_CHAR => Err(SignatureError::UnexpectedClose {
+                position,
+                character,
+            }),
+            _ => Err(SignatureError::InvalidChar {
+                position,
+                character,
+            }),
+        }
+    }
+
+    // The single element type of the container at `position`.
+    fn element_type(&mut self, position: usize) -> std::result::Result<(), SignatureError> {
+        match self.peek() {
+            None | Some(STRUCT_SIG_END_CHAR) | Some(DICT_ENTRY_SIG_END_CHAR) => {
+                Err(SignatureError::MissingElementType { position })
+            }
+            Some(_) => self.complete_type(),
+        }
+    }
+
+    // Enter the container at `position`, returning the depths to restore on leaving it.
+    fn enter(
+        &mut self,
+        position: usize,
+        inc: fn(ContainerDepths) -> Result<ContainerDepths>,
+    ) -> std::result::Result<ContainerDepths, SignatureError> {
+        let container_depths = self.container_depths;
+        self.container_depths = inc(container_depths).map_err(|e| match e {
+            Error::MaxDepthExceeded(kind) => SignatureError::MaxDepthExceeded { position, kind },
+            e => unreachable!("unexpected error: {e}"),
+        })?;
+
+        Ok(container_depths)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.bytes.get(self.pos).map(|b| *b as char)
+    }
+}
+
+fn is_basic_signature_char(c: char) -> bool {
+    match c {
+        u8::SIGNATURE_CHAR
+        | bool::SIGNATURE_CHAR
+        | i16::SIGNATURE_CHAR
+        | u16::SIGNATURE_CHAR
+        | i32::SIGNATURE_CHAR
+        | u32::SIGNATURE_CHAR
+        | i64::SIGNATURE_CHAR
+        | u64::SIGNATURE_CHAR
+        | f64::SIGNATURE_CHAR
+        | <&str>::SIGNATURE_CHAR
+        | ObjectPath::SIGNATURE_CHAR
+        | Signature::SIGNATURE_CHAR => true,
+        #[cfg(unix)]
+        Fd::SIGNATURE_CHAR => true,
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Signature;
+    use crate::{Error, MaxDepthExceeded, SignatureError};
 
     #[test]
     fn signature_slicing() {
@@ -575,4 +998,107 @@ mod tests {
         let sig_b = Signature::from_str_unchecked("(so)u");
         assert_ne!(sig_a, sig_b);
     }
+
+    #[test]
+    fn signature_validation() {
+        for valid in [
+            "",
+            "y",
+            "ybnqiuxtdsogv",
+            "a{sv}",
+            "(ua(yy)a{o(x)})",
+            "aav",
+            "{sv}",
+        ] {
+            assert_eq!(Signature::validate(valid), Ok(()), "{valid}");
+        }
+
+        let invalid = [
+            (
+                "z",
+                SignatureError::InvalidChar {
+                    position: 0,
+                    character: 'z',
+                },
+            ),
+            ("a", SignatureError::MissingElementType { position: 0 }),
+            ("(ya)", SignatureError::MissingElementType { position: 2 }),
+            ("u()", SignatureError::EmptyStructure { position: 1 }),
+            ("(x(s)", SignatureError::Unclosed { position: 0 }),
+            (
+                "xs)",
+                SignatureError::UnexpectedClose {
+                    position: 2,
+                    character: ')',
+                },
+            ),
+            (
+                "(x}",
+                SignatureError::UnexpectedClose {
+                    position: 2,
+                    character: '}',
+                },
+            ),
+            ("a{(y)s}", SignatureError::NonBasicDictKey { position: 2 }),
+            ("a{s}", SignatureError::InvalidDictEntry { position: 1 }),
+            ("a{sss}", SignatureError::InvalidDictEntry { position: 1 }),
+            ("a{s", SignatureError::Unclosed { position: 1 }),
+            (
+                "a{yz}",
+                SignatureError::InvalidChar {
+                    position: 3,
+                    character: 'z',
+                },
+            ),
+        ];
+        for (signature, error) in invalid {
+            assert_eq!(Signature::validate(signature), Err(error), "{signature}");
+        }
+
+        let signature = format!("{}y", "a".repeat(33));
+        assert_eq!(
+            Signature::validate(&signature),
+            Err(SignatureError::MaxDepthExceeded {
+                position: 32,
+                kind: MaxDepthExceeded::Array
+            })
+        );
+        assert_eq!(
+            Signature::validate(&"y".repeat(256)),
+            Err(SignatureError::TooLong(256))
+        );
+
+        // Constructors report the same errors.
+        assert_eq!(
+            Signature::try_from("a{vs}").unwrap_err(),
+            Error::InvalidSignature(SignatureError::NonBasicDictKey { position: 2 })
+        );
+    }
+
+    #[test]
+    fn signature_composition() {
+        let sig = |s| Signature::try_from(s).unwrap();
+
+        assert_eq!(Signature::array_of(&sig("(so)")).unwrap(), "a(so)");
+        assert_eq!(
+            Signature::structure_of(&[sig("s"), sig("a{sv}")]).unwrap(),
+            "(sa{sv})"
+        );
+        assert_eq!(Signature::dict_of(&sig("s"), &sig("av")).unwrap(), "a{sav}");
+
+        // Each input must be exactly one complete type.
+        Signature::array_of(&sig("")).unwrap_err();
+        Signature::array_of(&sig("so")).unwrap_err();
+        Signature::structure_of(&[sig("s"), sig("")]).unwrap_err();
+        Signature::structure_of(&[sig("sv")]).unwrap_err();
+        Signature::dict_of(&sig("sv"), &sig("")).unwrap_err();
+        Signature::dict_of(&sig(""), &sig("sv")).unwrap_err();
+        Signature::dict_of(&sig("s"), &sig("vs")).unwrap_err();
+        Signature::dict_of(&sig("s"), &sig("")).unwrap_err();
+
+        // The key must be basic.
+        Signature::dict_of(&sig("v"), &sig("s")).unwrap_err();
+        Signature::dict_of(&sig("(s)"), &sig("s")).unwrap_err();
+        Signature::dict_of(&sig("as"), &sig("s")).unwrap_err();
+    }
 }
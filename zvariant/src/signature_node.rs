@@ -0,0 +1,141 @@
+use std::fmt::{self, Display, Formatter};
+
+use static_assertions::assert_impl_all;
+
+#[cfg(feature = "gvariant")]
+use crate::utils::MAYBE_SIGNATURE_CHAR;
+use crate::{
+    signature_parser::SignatureParser,
+    utils::{
+        ARRAY_SIGNATURE_CHAR, DICT_ENTRY_SIG_END_CHAR, DICT_ENTRY_SIG_START_CHAR,
+        STRUCT_SIG_END_CHAR, STRUCT_SIG_START_CHAR, VARIANT_SIGNATURE_CHAR,
+    },
+    Result, Signature,
+};
+
+/// A parsed complete type of a [`Signature`].
+///
+/// Use [`Signature::parsed`] to get these. Converting a node back into a string (through its
+/// [`Display`] implementation) gives the signature it was parsed from.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{Signature, SignatureNode};
+///
+/// let signature = Signature::try_from("a{sv}(ub)").unwrap();
+/// let nodes = signature.parsed().unwrap();
+/// assert_eq!(
+///     nodes,
+///     [
+///         SignatureNode::Array(Box::new(SignatureNode::DictEntry(
+///             Box::new(SignatureNode::Basic('s')),
+///             Box::new(SignatureNode::Variant),
+///         ))),
+///         SignatureNode::Struct(vec![SignatureNode::Basic('u'), SignatureNode::Basic('b')]),
+///     ]
+/// );
+/// assert_eq!(nodes[1].to_string(), "(ub)");
+/// ```
+///
+/// [`Signature`]: struct.Signature.html
+/// [`Signature::parsed`]: struct.Signature.html#method.parsed
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SignatureNode {
+    /// A basic type, identified by its signature character.
+    Basic(char),
+    /// The variant type.
+    Variant,
+    /// An array of the given element type.
+    Array(Box<SignatureNode>),
+    /// A structure of the given field types.
+    Struct(Vec<SignatureNode>),
+    /// A dictionary entry, with its key and value type.
+    DictEntry(Box<SignatureNode>, Box<SignatureNode>),
+    /// A GVariant maybe type of the given inner type.
+    #[cfg(feature = "gvariant")]
+    Maybe(Box<SignatureNode>),
+}
+
+assert_impl_all!(SignatureNode: Send, Sync, Unpin);
+
+impl SignatureNode {
+    /// The child nodes.
+    ///
+    /// Fields for structures, the key and value for dictionary entries and the (single) element
+    /// or inner type for the other containers. Basic types and variants have no children.
+    pub fn children(&self) -> Vec<&SignatureNode> {
+        match self {
+            SignatureNode::Basic(_) | SignatureNode::Variant => vec![],
+            SignatureNode::Array(element) => vec![&**element],
+            SignatureNode::Struct(fields) => fields.iter().collect(),
+            SignatureNode::DictEntry(key, value) => vec![&**key, &**value],
+            #[cfg(feature = "gvariant")]
+            SignatureNode::Maybe(inner) => vec![&**inner],
+        }
+    }
+
+    // `signature` must be **one** complete signature.
+    pub(crate) fn parse(signature: &Signature<'_>) -> Result<Self> {
+        let inner = || signature.slice(1..signature.len() - 1);
+        let node = match signature.as_bytes()[0] as char {
+            VARIANT_SIGNATURE_CHAR => SignatureNode::Variant,
+            ARRAY_SIGNATURE_CHAR => {
+                SignatureNode::Array(Box::new(Self::parse(&signature.slice(1..))?))
+            }
+            STRUCT_SIG_START_CHAR => SignatureNode::Struct(Self::parse_all(&inner())?),
+            DICT_ENTRY_SIG_START_CHAR => {
+                let mut children = Self::parse_all(&inner())?.into_iter();
+                match (children.next(), children.next(), children.next()) {
+                    (Some(key), Some(value), None) => {
+                        SignatureNode::DictEntry(Box::new(key), Box::new(value))
+                    }
+                    _ => {
+                        return Err(serde::de::Error::invalid_length(
+                            signature.len(),
+                            &"a key and a value type in dict entry",
+                        ))
+                    }
+                }
+            }
+            #[cfg(feature = "gvariant")]
+            MAYBE_SIGNATURE_CHAR => {
+                SignatureNode::Maybe(Box::new(Self::parse(&signature.slice(1..))?))
+            }
+            c => SignatureNode::Basic(c),
+        };
+
+        Ok(node)
+    }
+
+    pub(crate) fn parse_all(signature: &Signature<'_>) -> Result<Vec<Self>> {
+        SignatureParser::new(signature.as_ref())
+            .map(|s| s.and_then(|s| Self::parse(&s)))
+            .collect()
+    }
+}
+
+impl Display for SignatureNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureNode::Basic(c) => write!(f, "{c}"),
+            SignatureNode::Variant => write!(f, "{VARIANT_SIGNATURE_CHAR}"),
+            SignatureNode::Array(element) => write!(f, "{ARRAY_SIGNATURE_CHAR}{element}"),
+            SignatureNode::Struct(fields) => {
+                write!(f, "{STRUCT_SIG_START_CHAR}")?;
+                for field in fields {
+                    write!(f, "{field}")?;
+                }
+                write!(f, "{STRUCT_SIG_END_CHAR}")
+            }
+            SignatureNode::DictEntry(key, value) => write!(
+                f,
+                "{DICT_ENTRY_SIG_START_CHAR}{key}{value}{DICT_ENTRY_SIG_END_CHAR}"
+            ),
+            #[cfg(feature = "gvariant")]
+            SignatureNode::Maybe(inner) => write!(f, "{MAYBE_SIGNATURE_CHAR}{inner}"),
+        }
+    }
+}
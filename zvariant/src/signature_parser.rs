@@ -42,14 +42,6 @@ impl<'s> SignatureParser<'s> {
         Ok(Self::new(signature))
     }
 
-    pub fn validate(signature: &'s [u8]) -> Result<()> {
-        // SAFETY: the parser is only used to validate the signature
-        for s in unsafe { Self::from_bytes_unchecked(signature)? } {
-            s?;
-        }
-        Ok(())
-    }
-
     pub fn signature(&self) -> Signature<'_> {
         self.signature.slice(self.pos..self.end)
     }
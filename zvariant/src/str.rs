@@ -14,9 +14,33 @@ use crate::{Basic, EncodingFormat, Signature, Type};
 /// This is used for keeping strings in a [`Value`]. API is provided to convert from, and to a
 /// [`&str`] and [`String`].
 ///
+/// Deserializing a `Str` borrows from the input and hence doesn't allocate, just like `&str`. Note
+/// that serde always allocates when deserializing a [`Cow`]`<str>` on its own. A `Cow<str>` field
+/// of a struct borrows only if marked with the `#[serde(borrow)]` attribute:
+///
+/// ```
+/// use serde::Deserialize;
+/// use std::borrow::Cow;
+/// use zvariant::{from_slice, to_bytes, EncodingContext, Str, Type};
+///
+/// #[derive(Deserialize, Type)]
+/// struct Node<'a> {
+///     #[serde(borrow)]
+///     xml: Cow<'a, str>,
+///     names: Vec<Str<'a>>,
+/// }
+///
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &("<node/>", vec!["org.freedesktop.DBus"])).unwrap();
+/// let mut node: Node<'_> = from_slice(&encoded, ctxt).unwrap().0;
+/// assert!(matches!(node.xml, Cow::Borrowed("<node/>")));
+/// assert!(matches!(Cow::from(node.names.remove(0)), Cow::Borrowed(_)));
+/// ```
+///
 /// [`Value`]: enum.Value.html#variant.Str
 /// [`&str`]: https://doc.rust-lang.org/std/str/index.html
 /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+/// [`Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize)]
 #[serde(rename(serialize = "zvariant::Str", deserialize = "zvariant::Str"))]
 pub struct Str<'a>(#[serde(borrow)] Inner<'a>);
@@ -179,6 +203,17 @@ impl<'a> From<Str<'a>> for String {
     }
 }
 
+/// Only allocates if `value` owns its string.
+impl<'a> From<Str<'a>> for Cow<'a, str> {
+    fn from(value: Str<'a>) -> Cow<'a, str> {
+        match value.0 {
+            Inner::Static(s) => Cow::Borrowed(s),
+            Inner::Borrowed(s) => Cow::Borrowed(s),
+            Inner::Owned(s) => Cow::Owned(s.to_string()),
+        }
+    }
+}
+
 impl<'a> From<&'a Str<'a>> for &'a str {
     fn from(value: &'a Str<'a>) -> &'a str {
         value.as_str()
@@ -217,6 +252,83 @@ impl<'a> std::fmt::Display for Str<'a> {
     }
 }
 
+/// Owned [`Str`](struct.Str.html)
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Type)]
+pub struct OwnedStr(Str<'static>);
+
+assert_impl_all!(OwnedStr: Send, Sync, Unpin);
+
+impl OwnedStr {
+    pub fn into_inner(self) -> Str<'static> {
+        self.0
+    }
+}
+
+impl std::ops::Deref for OwnedStr {
+    type Target = Str<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<OwnedStr> for Str<'static> {
+    fn from(o: OwnedStr) -> Self {
+        o.into_inner()
+    }
+}
+
+impl<'unowned, 'owned: 'unowned> From<&'owned OwnedStr> for Str<'unowned> {
+    fn from(o: &'owned OwnedStr) -> Self {
+        o.0.as_ref()
+    }
+}
+
+impl<'a> From<Str<'a>> for OwnedStr {
+    fn from(s: Str<'a>) -> Self {
+        OwnedStr(s.into_owned())
+    }
+}
+
+impl From<&str> for OwnedStr {
+    fn from(s: &str) -> Self {
+        OwnedStr(Str::from(s).into_owned())
+    }
+}
+
+impl From<String> for OwnedStr {
+    fn from(s: String) -> Self {
+        OwnedStr(Str::from(s))
+    }
+}
+
+impl From<OwnedStr> for String {
+    fn from(o: OwnedStr) -> Self {
+        o.into_inner().into()
+    }
+}
+
+impl From<OwnedStr> for crate::Value<'static> {
+    fn from(o: OwnedStr) -> Self {
+        o.into_inner().into()
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl std::fmt::Display for OwnedStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Str;
@@ -4,7 +4,10 @@ use serde::{
     ser::{Serialize, SerializeTupleStruct, Serializer},
 };
 use static_assertions::assert_impl_all;
-use std::fmt::{Display, Write};
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Write},
+};
 
 use crate::{
     signature_parser::SignatureParser, value::SignatureSeed, value_display_fmt, DynamicDeserialize,
@@ -112,7 +115,10 @@ impl<'a> TryFrom<Signature<'a>> for StructureSeed<'a> {
         if signature.starts_with(zvariant::STRUCT_SIG_START_CHAR) {
             Ok(StructureSeed(signature))
         } else {
-            Err(zvariant::Error::IncorrectType)
+            Err(zvariant::Error::SignatureMismatch(
+                signature.to_owned(),
+                "a structure signature".to_string(),
+            ))
         }
     }
 }
@@ -155,7 +161,7 @@ impl<'de> Visitor<'de> for StructureVisitor<'de> {
 /// API is provided to convert from, and to tuples.
 ///
 /// [`Value`]: enum.Value.html
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Structure<'a> {
     fields: Vec<Value<'a>>,
     signature: Signature<'a>,
@@ -169,6 +175,49 @@ impl<'a> Structure<'a> {
         &self.fields
     }
 
+    /// Get a reference to the field at `index`.
+    ///
+    /// Returns `Ok(None)` if `self` has no field at `index`, and an error if the field is not a
+    /// `T`. Fields wrapped in a [`Value::Value`] are looked through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::StructureBuilder;
+    ///
+    /// let s = StructureBuilder::new()
+    ///     .add_field(42_u32)
+    ///     .add_field("hello")
+    ///     .add_field(true)
+    ///     .build();
+    /// assert_eq!(s.get::<u32>(0).unwrap(), Some(&42));
+    /// assert_eq!(s.get::<str>(1).unwrap(), Some("hello"));
+    /// assert_eq!(s.get::<bool>(2).unwrap(), Some(&true));
+    /// assert!(s.get::<str>(0).is_err());
+    /// assert_eq!(s.get::<u32>(3).unwrap(), None);
+    ///
+    /// // All fields at once.
+    /// let (n, s, b): (u32, String, bool) = s.try_into().unwrap();
+    /// assert_eq!((n, s.as_str(), b), (42, "hello", true));
+    /// ```
+    ///
+    /// [`Value::Value`]: enum.Value.html#variant.Value
+    pub fn get<'s, T>(&'s self, index: usize) -> crate::Result<Option<&'s T>>
+    where
+        T: ?Sized,
+        &'s T: TryFrom<&'s Value<'s>>,
+    {
+        match self.fields.get(index) {
+            Some(field) => field
+                .downcast_ref::<T>()
+                .ok_or_else(|| {
+                    crate::Error::type_mismatch_for::<T>(field).in_path(format!(".{index}"))
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
     /// Converts `self` to a `Vec` containing all its fields.
     pub fn into_fields(self) -> Vec<Value<'a>> {
         self.fields
@@ -195,6 +244,28 @@ impl<'a> Structure<'a> {
             signature: self.signature.to_owned(),
         }
     }
+
+    pub(crate) fn byteswap(&self) -> Structure<'static> {
+        Structure {
+            fields: self.fields.iter().map(Value::byteswap).collect(),
+            signature: self.signature.to_owned(),
+        }
+    }
+}
+
+// As documented on `Value`, structures order by signature before their fields.
+impl PartialOrd for Structure<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Structure<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.signature
+            .cmp(&other.signature)
+            .then_with(|| self.fields.cmp(&other.fields))
+    }
 }
 
 impl Display for Structure<'_> {
@@ -323,7 +394,9 @@ macro_rules! tuple_impls {
                 fn try_from(mut s: Structure<'a>) -> core::result::Result<Self, Self::Error> {
                     Ok((
                     $(
-                         $name::try_from(s.fields.remove(0))?,
+                         $name::try_from(s.fields.remove(0)).map_err(|e| {
+                             crate::Error::from(e).in_path(concat!(".", stringify!($n)))
+                         })?,
                     )+
                     ))
                 }
@@ -387,3 +460,56 @@ fn create_signature_from_fields(fields: &[Value<'_>]) -> Signature<'static> {
 
     Signature::from_string_unchecked(signature)
 }
+
+/// Owned [`Structure`](struct.Structure.html)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedStructure(Structure<'static>);
+
+assert_impl_all!(OwnedStructure: Send, Sync, Unpin);
+
+impl OwnedStructure {
+    pub fn into_inner(self) -> Structure<'static> {
+        self.0
+    }
+}
+
+impl std::ops::Deref for OwnedStructure {
+    type Target = Structure<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<OwnedStructure> for Structure<'static> {
+    fn from(o: OwnedStructure) -> Self {
+        o.into_inner()
+    }
+}
+
+impl<'a> From<Structure<'a>> for OwnedStructure {
+    fn from(o: Structure<'a>) -> Self {
+        OwnedStructure(o.to_owned())
+    }
+}
+
+impl DynamicType for OwnedStructure {
+    fn dynamic_signature(&self) -> Signature<'_> {
+        self.0.dynamic_signature()
+    }
+}
+
+impl Serialize for OwnedStructure {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Display for OwnedStructure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{utils::*, Signature};
 use serde::de::{Deserialize, DeserializeSeed};
 use std::{
+    ffi::{CStr, CString},
     marker::PhantomData,
     net::{IpAddr, Ipv4Addr, Ipv6Addr},
     path::{Path, PathBuf},
@@ -398,6 +399,34 @@ impl Type for serde_bytes::ByteBuf {
     }
 }
 
+// Serde encodes these as bytes, without the trailing nul byte.
+impl Type for CStr {
+    fn signature() -> Signature<'static> {
+        Signature::from_static_str_unchecked("ay")
+    }
+}
+
+impl Type for CString {
+    fn signature() -> Signature<'static> {
+        Signature::from_static_str_unchecked("ay")
+    }
+}
+
+// There are no 128-bit integer types in D-Bus or GVariant.
+#[cfg(feature = "int128")]
+impl Type for i128 {
+    fn signature() -> Signature<'static> {
+        Signature::from_static_str_unchecked("(tt)")
+    }
+}
+
+#[cfg(feature = "int128")]
+impl Type for u128 {
+    fn signature() -> Signature<'static> {
+        Signature::from_static_str_unchecked("(tt)")
+    }
+}
+
 #[allow(unused)]
 macro_rules! static_str_type {
     ($ty:ty) => {
@@ -419,9 +448,24 @@ impl Type for uuid::Uuid {
     }
 }
 
+// The `fmt` adapters are always serialized as strings, unlike `Uuid` itself.
+#[cfg(feature = "uuid")]
+static_str_type!(uuid::fmt::Hyphenated);
+#[cfg(feature = "uuid")]
+static_str_type!(uuid::fmt::Simple);
+#[cfg(feature = "uuid")]
+static_str_type!(uuid::fmt::Urn);
+#[cfg(feature = "uuid")]
+static_str_type!(uuid::fmt::Braced);
+
 #[cfg(feature = "url")]
 static_str_type!(url::Url);
 
+#[cfg(feature = "camino")]
+static_str_type!(camino::Utf8Path);
+#[cfg(feature = "camino")]
+static_str_type!(camino::Utf8PathBuf);
+
 // FIXME: Ignoring the `serde-human-readable` feature of `time` crate in these impls:
 // https://github.com/time-rs/time/blob/f9398b9598757508ca3815694f23203843e0011b/src/serde/mod.rs#L110
 #[cfg(feature = "time")]
@@ -157,6 +157,11 @@ pub(crate) fn is_fixed_sized_signature<'a>(signature: &'a Signature<'a>) -> Resu
     }
 }
 
+// Whether data encoded with byte order `B` is in the native byte order.
+pub(crate) fn is_native_endian<B: byteorder::ByteOrder>() -> bool {
+    B::read_u16(&1u16.to_ne_bytes()) == 1
+}
+
 // Given an &str, create an owned (String-based) Signature w/ appropriate capacity
 macro_rules! signature_string {
     ($signature:expr) => {{
@@ -1,6 +1,8 @@
 use core::str;
 use std::{
+    cmp::Ordering,
     fmt::{Display, Write},
+    hash::{Hash, Hasher},
     marker::PhantomData,
 };
 
@@ -16,7 +18,7 @@ use static_assertions::assert_impl_all;
 use crate::{
     array_display_fmt, dict_display_fmt, signature_parser::SignatureParser, structure_display_fmt,
     utils::*, Array, Basic, Dict, DynamicType, ObjectPath, OwnedValue, Signature, Str, Structure,
-    StructureBuilder, Type,
+    StructureBuilder, TryDowncastError, Type,
 };
 #[cfg(feature = "gvariant")]
 use crate::{maybe_display_fmt, Maybe};
@@ -71,8 +73,23 @@ use crate::Fd;
 /// );
 /// ```
 ///
+/// # Equality, hashing and ordering
+///
+/// `Value` implements [`Eq`], [`Hash`] and [`Ord`], so it can be used as a key in maps and sets,
+/// and sorted deterministically. Values of different kinds are ordered in the same order as the
+/// variants of this enum are declared (e.g any `U8` is less than any `Bool`, which is less than any
+/// `I16`) while values of the same kind are ordered by their contents. Containers compare their
+/// signatures first, followed by their elements in order.
+///
+/// `F64` values are compared by their bit patterns, through [`f64::total_cmp`]. Hence `NaN` is
+/// equal to itself and `0.0` is not equal to `-0.0`, unlike with the primitive type.
+///
 /// [D-Bus specification]: https://dbus.freedesktop.org/doc/dbus-specification.html#container-types
-#[derive(Debug, Clone, PartialEq)]
+/// [`Eq`]: https://doc.rust-lang.org/std/cmp/trait.Eq.html
+/// [`Hash`]: https://doc.rust-lang.org/std/hash/trait.Hash.html
+/// [`Ord`]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
+/// [`f64::total_cmp`]: https://doc.rust-lang.org/std/primitive.f64.html#method.total_cmp
+#[derive(Debug, Clone)]
 pub enum Value<'a> {
     // Simple types
     U8(u8),
@@ -132,7 +149,63 @@ macro_rules! serialize_value {
     }
 }
 
+macro_rules! value_accessors {
+    ($($(#[$attr:meta])* $name:ident, $variant:ident, $ty:ty;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(&self) -> Option<$ty> {
+                match self {
+                    Value::$variant(v) => Some(*v),
+                    Value::Value(v) => v.$name(),
+                    _ => None,
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! value_ref_accessors {
+    ($($(#[$attr:meta])* $name:ident, $variant:ident, $ty:ty;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(&self) -> Option<&$ty> {
+                match self {
+                    Value::$variant(v) => Some(v),
+                    Value::Value(v) => v.$name(),
+                    _ => None,
+                }
+            }
+        )*
+    };
+}
+
 impl<'a> Value<'a> {
+    // The position of the variant kind in the ordering of values of different kinds.
+    fn kind_index(&self) -> u8 {
+        match self {
+            Value::U8(_) => 0,
+            Value::Bool(_) => 1,
+            Value::I16(_) => 2,
+            Value::U16(_) => 3,
+            Value::I32(_) => 4,
+            Value::U32(_) => 5,
+            Value::I64(_) => 6,
+            Value::U64(_) => 7,
+            Value::F64(_) => 8,
+            Value::Str(_) => 9,
+            Value::Signature(_) => 10,
+            Value::ObjectPath(_) => 11,
+            Value::Value(_) => 12,
+            Value::Array(_) => 13,
+            Value::Dict(_) => 14,
+            Value::Structure(_) => 15,
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(_) => 16,
+            #[cfg(unix)]
+            Value::Fd(_) => 17,
+        }
+    }
+
     /// Make a [`Value`] for a given value.
     ///
     /// In general, you can use [`Into`] trait on basic types, except
@@ -163,6 +236,123 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Parse a value from its [GVariant text format] representation.
+    ///
+    /// This is the inverse of the `Display` implementation, similar to `g_variant_parse` in GLib.
+    /// The type of the value is inferred from the text: integers are `int32` and floating point
+    /// numbers are `double`, unless annotated with a type keyword (e.g `uint32 7`) or a signature
+    /// (e.g `@a{sv} {}`). Empty containers and `nothing` must be annotated. Use
+    /// [`Value::parse_with_signature`] if the type is known beforehand. The same parsing is
+    /// available through the [`FromStr`](std::str::FromStr) implementation.
+    ///
+    /// File descriptors (`handle`) can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Value;
+    ///
+    /// let value = Value::parse(r#"{"name": <"zbus">, "version": <uint32 5>}"#).unwrap();
+    /// assert_eq!(value.value_signature(), "a{sv}");
+    /// assert_eq!(Value::parse(&value.to_string()).unwrap(), value);
+    ///
+    /// let value: Value<'_> = "@a(ix) [(1, 2)]".parse().unwrap();
+    /// assert_eq!(value.value_signature(), "a(ix)");
+    /// ```
+    ///
+    /// [GVariant text format]: https://docs.gtk.org/glib/gvariant-text.html
+    pub fn parse(text: &str) -> crate::Result<Value<'static>> {
+        crate::value_parser::parse(text, None)
+    }
+
+    /// Parse a value of type `signature` from its [GVariant text format] representation.
+    ///
+    /// Same as [`Value::parse`], except that the type doesn't need to be inferred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Value;
+    ///
+    /// let value = Value::parse_with_signature("[1, 2]", "at").unwrap();
+    /// assert_eq!(value, Value::from(vec![1_u64, 2]));
+    /// let value = Value::parse_with_signature("{}", "a{sv}").unwrap();
+    /// assert_eq!(value.value_signature(), "a{sv}");
+    /// ```
+    ///
+    /// [GVariant text format]: https://docs.gtk.org/glib/gvariant-text.html
+    pub fn parse_with_signature<'s, S>(text: &str, signature: S) -> crate::Result<Value<'static>>
+    where
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<crate::Error>,
+    {
+        let signature = signature.try_into().map_err(Into::into)?;
+
+        crate::value_parser::parse(text, Some(&signature))
+    }
+
+    /// Deserialize a value of type `signature` from `bytes`.
+    ///
+    /// Unlike deserializing a `Value` through [`from_slice`] and friends, which expects a variant
+    /// (i.e. the signature `v`), this decodes `bytes` as the encoding of a value of `signature`
+    /// itself. The [`value_signature`] of the resulting `Value` is guaranteed to be exactly
+    /// `signature`, even for empty containers or when `signature` is that of a variant (in which
+    /// case a [`Value::Value`] is returned). This makes it ideal for handling data of types only
+    /// known at runtime, e.g. message bodies.
+    ///
+    /// # Errors
+    ///
+    /// Besides the usual decoding errors, [`Error::SignatureMismatch`] is returned if `signature`
+    /// isn't a single complete type. Use a structure signature to decode multiple values.
+    ///
+    /// # Return value
+    ///
+    /// A tuple containing the deserialized value and the number of bytes parsed from `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use byteorder::LE;
+    /// use zvariant::{to_bytes, EncodingContext, Value};
+    ///
+    /// let ctxt = EncodingContext::<LE>::new_dbus(0);
+    /// let encoded = to_bytes(ctxt, &(vec![Vec::<u32>::new()], HashMap::<&str, Value<'_>>::new()))
+    ///     .unwrap();
+    /// let (value, _) = Value::deserialize_for_signature(&encoded, ctxt, "(aaua{sv})").unwrap();
+    /// assert_eq!(value.value_signature(), "(aaua{sv})");
+    ///
+    /// let encoded = to_bytes(ctxt, &Value::from("hello")).unwrap();
+    /// let (value, _) = Value::deserialize_for_signature(&encoded, ctxt, "v").unwrap();
+    /// assert_eq!(value, Value::new(Value::from("hello")));
+    /// ```
+    ///
+    /// [`from_slice`]: fn.from_slice.html
+    /// [`value_signature`]: #method.value_signature
+    /// [`Value::Value`]: #variant.Value
+    /// [`Error::SignatureMismatch`]: enum.Error.html#variant.SignatureMismatch
+    pub fn deserialize_for_signature<B, S>(
+        bytes: &'a [u8],
+        ctxt: crate::EncodingContext<B>,
+        signature: S,
+    ) -> crate::Result<(Value<'a>, usize)>
+    where
+        B: byteorder::ByteOrder,
+        S: TryInto<Signature<'a>>,
+        S::Error: Into<crate::Error>,
+    {
+        let signature = signature.try_into().map_err(Into::into)?;
+        let parser = SignatureParser::new(signature.as_ref());
+        if parser.next_signature()?.len() != signature.len() {
+            return Err(crate::Error::SignatureMismatch(
+                signature.to_owned(),
+                "a single complete type".to_string(),
+            ));
+        }
+
+        crate::from_slice_with_seed(bytes, ctxt, ValueSeed::new(signature))
+    }
+
     /// Create an owned version of `self`.
     ///
     /// Ideally, we should implement [`std::borrow::ToOwned`] trait for `Value`, but that's
@@ -226,6 +416,48 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Swap the byte order of all the multi-byte numbers in `self`.
+    ///
+    /// This is the equivalent of GLib's `g_variant_byteswap` and is useful for fixing up values
+    /// that were decoded using the wrong byte order, e.g from data that doesn't carry any byte
+    /// order information. Values of all other types (including file descriptors) are left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Value;
+    ///
+    /// let v = Value::new((0x0102_u16, "hello", vec![0x01020304_u32]));
+    /// let swapped = v.byteswap();
+    /// assert_eq!(swapped, Value::new((0x0201_u16, "hello", vec![0x04030201_u32])));
+    /// assert_eq!(swapped.byteswap(), v);
+    /// ```
+    pub fn byteswap(&self) -> Value<'static> {
+        match self {
+            Value::U8(v) => Value::U8(*v),
+            Value::Bool(v) => Value::Bool(*v),
+            Value::I16(v) => Value::I16(v.swap_bytes()),
+            Value::U16(v) => Value::U16(v.swap_bytes()),
+            Value::I32(v) => Value::I32(v.swap_bytes()),
+            Value::U32(v) => Value::U32(v.swap_bytes()),
+            Value::I64(v) => Value::I64(v.swap_bytes()),
+            Value::U64(v) => Value::U64(v.swap_bytes()),
+            Value::F64(v) => Value::F64(f64::from_bits(v.to_bits().swap_bytes())),
+            Value::Str(v) => Value::Str(v.to_owned()),
+            Value::Signature(v) => Value::Signature(v.to_owned()),
+            Value::ObjectPath(v) => Value::ObjectPath(v.to_owned()),
+            Value::Value(v) => Value::Value(Box::new(v.byteswap())),
+
+            Value::Array(v) => Value::Array(v.byteswap()),
+            Value::Dict(v) => Value::Dict(v.byteswap()),
+            Value::Structure(v) => Value::Structure(v.byteswap()),
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(v) => Value::Maybe(v.byteswap()),
+            #[cfg(unix)]
+            Value::Fd(v) => Value::Fd(*v),
+        }
+    }
+
     pub(crate) fn serialize_value_as_struct_field<S>(
         &self,
         name: &'static str,
@@ -266,6 +498,18 @@ impl<'a> Value<'a> {
         serialize_value!(self serializer.serialize_some)
     }
 
+    // Serialize the enclosed value on its own, i.e without the variant signature.
+    #[cfg(feature = "gvariant")]
+    pub(crate) fn serialize_value_as_newtype_struct<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_value!(self serializer.serialize_newtype_struct "zvariant::Value")
+    }
+
     /// Try to get the underlying type `T`.
     ///
     /// Note that [`TryFrom<Value>`] is implemented for various types, and it's usually best to use
@@ -317,6 +561,61 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// Try to get the underlying type `T`, getting `self` back if it doesn't hold a `T`.
+    ///
+    /// Same as [`downcast`] except that the signature of the underlying value is checked against
+    /// that of `T` first, so `self` isn't lost on type mismatch. Like [`downcast`], the contained
+    /// data (strings, arrays, dictionaries etc) is moved out of `self` rather than copied.
+    ///
+    /// # Errors
+    ///
+    /// [`TryDowncastError::Mismatch`], holding `self`, if it doesn't hold a value of `T`'s
+    /// signature, or [`TryDowncastError::Conversion`] if the [`TryFrom<Value>`] implementation of
+    /// `T` fails to convert it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{TryDowncastError, Value};
+    ///
+    /// let v = Value::new(Value::from(vec!["hello", "world"]));
+    /// let v = match v.try_downcast::<Vec<u32>>() {
+    ///     Err(TryDowncastError::Mismatch(v)) => v,
+    ///     _ => panic!("expected a mismatch"),
+    /// };
+    /// let strings: Vec<String> = v.try_downcast().unwrap();
+    /// assert_eq!(strings, ["hello", "world"]);
+    /// ```
+    ///
+    /// [`downcast`]: enum.Value.html#method.downcast
+    /// [`TryDowncastError::Mismatch`]: enum.TryDowncastError.html#variant.Mismatch
+    /// [`TryDowncastError::Conversion`]: enum.TryDowncastError.html#variant.Conversion
+    /// [`TryFrom<Value>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+    // Giving `self` back on mismatch is the whole point of this method.
+    #[allow(clippy::result_large_err)]
+    pub fn try_downcast<T>(self) -> Result<T, TryDowncastError<'a, T::Error>>
+    where
+        T: TryFrom<Value<'a>> + Type,
+    {
+        let signature = T::signature();
+        let inner = match &self {
+            Value::Value(v) => &**v,
+            v => v,
+        };
+        // Any value can be converted to a `Value`.
+        if signature != VARIANT_SIGNATURE_STR && inner.value_signature() != signature {
+            return Err(TryDowncastError::Mismatch(self));
+        }
+
+        let res = if let Value::Value(v) = self {
+            T::try_from(*v)
+        } else {
+            T::try_from(self)
+        };
+
+        res.map_err(TryDowncastError::Conversion)
+    }
+
     /// Try to get a reference to the underlying type `T`.
     ///
     /// Same as [`downcast`] except it doesn't consume `self` and get a reference to the underlying
@@ -364,6 +663,177 @@ impl<'a> Value<'a> {
             <&T>::try_from(self).ok()
         }
     }
+
+    /// Patch `self` with `other`.
+    ///
+    /// If both `self` and `other` hold dictionaries of the same signature, `other` is merged into
+    /// `self` recursively using [`Dict::merge`]. Otherwise, `self` is replaced by (an owned copy
+    /// of) `other`. [`Value::Value`] wrappers are looked through in both cases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zvariant::Value;
+    ///
+    /// let mut settings = Value::from(HashMap::from([("volume", 50_u32), ("balance", 0)]));
+    /// settings
+    ///     .patch(&Value::from(HashMap::from([("volume", 70_u32)])))
+    ///     .unwrap();
+    /// let settings = settings.as_dict().unwrap();
+    /// assert_eq!(settings.get::<_, u32>("volume").unwrap(), Some(&70));
+    /// assert_eq!(settings.get::<_, u32>("balance").unwrap(), Some(&0));
+    ///
+    /// let mut v = Value::from(42_u32);
+    /// v.patch(&Value::from("hello")).unwrap();
+    /// assert_eq!(v, Value::from("hello"));
+    /// ```
+    pub fn patch(&mut self, other: &Value<'_>) -> crate::Result<()> {
+        let other_inner = match other {
+            Value::Value(v) => &**v,
+            v => v,
+        };
+        {
+            let inner = match &mut *self {
+                Value::Value(v) => &mut **v,
+                v => v,
+            };
+            if let (Value::Dict(dict), Value::Dict(other_dict)) = (inner, other_inner) {
+                if dict.full_signature() == other_dict.full_signature() {
+                    return dict.merge(other_dict);
+                }
+            }
+        }
+
+        *self = other.to_owned().into_inner();
+
+        Ok(())
+    }
+
+    value_accessors! {
+        /// Get the enclosed `u8`, if `self` holds one.
+        as_u8, U8, u8;
+        /// Get the enclosed `bool`, if `self` holds one.
+        as_bool, Bool, bool;
+        /// Get the enclosed `i16`, if `self` holds one.
+        as_i16, I16, i16;
+        /// Get the enclosed `u16`, if `self` holds one.
+        as_u16, U16, u16;
+        /// Get the enclosed `i32`, if `self` holds one.
+        as_i32, I32, i32;
+        /// Get the enclosed `u32`, if `self` holds one.
+        as_u32, U32, u32;
+        /// Get the enclosed `i64`, if `self` holds one.
+        as_i64, I64, i64;
+        /// Get the enclosed `u64`, if `self` holds one.
+        as_u64, U64, u64;
+        /// Get the enclosed `f64`, if `self` holds one.
+        as_f64, F64, f64;
+    }
+
+    /// Get the enclosed integer as `T`, converting between integer types if needed.
+    ///
+    /// Unlike [`downcast`] and the `as_*` accessors, this accepts any of the integer variants
+    /// (i.e all but `bool` and `f64` of the basic numeric types), as long as the value fits in a
+    /// `T`. This is useful when services aren't careful about the exact integer type they use.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TypeMismatch`] if `self` doesn't hold an integer.
+    /// * [`Error::OutOfBounds`] if the value doesn't fit in a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Error, Value};
+    ///
+    /// assert_eq!(Value::U32(42).try_into_num::<u64>(), Ok(42));
+    /// assert_eq!(Value::I64(-42).try_into_num::<i16>(), Ok(-42));
+    /// assert_eq!(Value::new(Value::U8(42)).try_into_num::<i32>(), Ok(42));
+    /// assert_eq!(Value::I64(-1).try_into_num::<u32>(), Err(Error::OutOfBounds));
+    /// assert_eq!(Value::U64(u64::MAX).try_into_num::<i64>(), Err(Error::OutOfBounds));
+    /// assert!(matches!(
+    ///     Value::F64(42.).try_into_num::<u64>(),
+    ///     Err(Error::TypeMismatch { .. }),
+    /// ));
+    /// ```
+    ///
+    /// [`downcast`]: enum.Value.html#method.downcast
+    /// [`Error::TypeMismatch`]: enum.Error.html#variant.TypeMismatch
+    /// [`Error::OutOfBounds`]: enum.Error.html#variant.OutOfBounds
+    pub fn try_into_num<T>(&self) -> crate::Result<T>
+    where
+        T: TryFrom<i128>,
+    {
+        // All the integer types fit in an `i128`.
+        let value = match self {
+            Value::U8(v) => i128::from(*v),
+            Value::I16(v) => i128::from(*v),
+            Value::U16(v) => i128::from(*v),
+            Value::I32(v) => i128::from(*v),
+            Value::U32(v) => i128::from(*v),
+            Value::I64(v) => i128::from(*v),
+            Value::U64(v) => i128::from(*v),
+            Value::Value(v) => return v.try_into_num(),
+            _ => return Err(crate::Error::type_mismatch("an integer", self)),
+        };
+
+        T::try_from(value).map_err(|_| crate::Error::OutOfBounds)
+    }
+
+    value_ref_accessors! {
+        /// Get a reference to the enclosed [`Signature`], if `self` holds one.
+        as_signature, Signature, Signature<'a>;
+        /// Get a reference to the enclosed [`ObjectPath`], if `self` holds one.
+        as_object_path, ObjectPath, ObjectPath<'a>;
+        /// Get a reference to the enclosed [`Array`], if `self` holds one.
+        as_array, Array, Array<'a>;
+        /// Get a reference to the enclosed [`Dict`], if `self` holds one.
+        as_dict, Dict, Dict<'a, 'a>;
+        /// Get a reference to the enclosed [`Structure`], if `self` holds one.
+        as_structure, Structure, Structure<'a>;
+    }
+
+    /// Get the enclosed string, if `self` holds one.
+    ///
+    /// Like all the `as_*` accessors, this looks through a [`Value::Value`] wrapper.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Value;
+    ///
+    /// let v = Value::new(Value::from("hello"));
+    /// assert_eq!(v.as_str(), Some("hello"));
+    /// assert_eq!(v.as_u64(), None);
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s.as_str()),
+            Value::Value(v) => v.as_str(),
+            _ => None,
+        }
+    }
+
+    /// Get a reference to the enclosed [`Maybe`], if `self` holds one.
+    #[cfg(feature = "gvariant")]
+    pub fn as_maybe(&self) -> Option<&Maybe<'a>> {
+        match self {
+            Value::Maybe(m) => Some(m),
+            Value::Value(v) => v.as_maybe(),
+            _ => None,
+        }
+    }
+
+    /// Get the enclosed [`Fd`], if `self` holds one.
+    #[cfg(unix)]
+    pub fn as_fd(&self) -> Option<Fd> {
+        match self {
+            Value::Fd(fd) => Some(*fd),
+            Value::Value(v) => v.as_fd(),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Value<'_> {
@@ -372,6 +842,85 @@ impl Display for Value<'_> {
     }
 }
 
+impl str::FromStr for Value<'_> {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl PartialEq for Value<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value<'_> {}
+
+impl PartialOrd for Value<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::U8(a), Value::U8(b)) => a.cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::I16(a), Value::I16(b)) => a.cmp(b),
+            (Value::U16(a), Value::U16(b)) => a.cmp(b),
+            (Value::I32(a), Value::I32(b)) => a.cmp(b),
+            (Value::U32(a), Value::U32(b)) => a.cmp(b),
+            (Value::I64(a), Value::I64(b)) => a.cmp(b),
+            (Value::U64(a), Value::U64(b)) => a.cmp(b),
+            (Value::F64(a), Value::F64(b)) => a.total_cmp(b),
+            (Value::Str(a), Value::Str(b)) => a.cmp(b),
+            (Value::Signature(a), Value::Signature(b)) => a.cmp(b),
+            (Value::ObjectPath(a), Value::ObjectPath(b)) => a.cmp(b),
+            (Value::Value(a), Value::Value(b)) => a.cmp(b),
+            (Value::Array(a), Value::Array(b)) => a.cmp(b),
+            (Value::Dict(a), Value::Dict(b)) => a.cmp(b),
+            (Value::Structure(a), Value::Structure(b)) => a.cmp(b),
+            #[cfg(feature = "gvariant")]
+            (Value::Maybe(a), Value::Maybe(b)) => a.cmp(b),
+            #[cfg(unix)]
+            (Value::Fd(a), Value::Fd(b)) => a.cmp(b),
+            _ => self.kind_index().cmp(&other.kind_index()),
+        }
+    }
+}
+
+impl Hash for Value<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind_index().hash(state);
+        match self {
+            Value::U8(value) => value.hash(state),
+            Value::Bool(value) => value.hash(state),
+            Value::I16(value) => value.hash(state),
+            Value::U16(value) => value.hash(state),
+            Value::I32(value) => value.hash(state),
+            Value::U32(value) => value.hash(state),
+            Value::I64(value) => value.hash(state),
+            Value::U64(value) => value.hash(state),
+            // Consistent with `total_cmp`, which considers values equal iff their bits are.
+            Value::F64(value) => value.to_bits().hash(state),
+            Value::Str(value) => value.hash(state),
+            Value::Signature(value) => value.hash(state),
+            Value::ObjectPath(value) => value.hash(state),
+            Value::Value(value) => value.hash(state),
+            Value::Array(value) => value.hash(state),
+            Value::Dict(value) => value.hash(state),
+            Value::Structure(value) => value.hash(state),
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(value) => value.hash(state),
+            #[cfg(unix)]
+            Value::Fd(value) => value.hash(state),
+        }
+    }
+}
+
 /// Implemented based on https://gitlab.gnome.org/GNOME/glib/-/blob/e1d47f0b0d0893ac9171e24cc7bf635495376546/glib/gvariant.c#L2213
 pub(crate) fn value_display_fmt(
     value: &Value<'_>,
@@ -603,11 +1152,27 @@ impl<'de, T> From<ValueSeed<'de, T>> for SignatureSeed<'de> {
     }
 }
 
-struct ValueSeed<'de, T> {
+pub(crate) struct ValueSeed<'de, T> {
     signature: Signature<'de>,
     phantom: PhantomData<T>,
 }
 
+impl<'de> ValueSeed<'de, Value<'de>> {
+    // A seed to deserialize a `Value` with the given signature, rather than a variant.
+    pub(crate) fn new(signature: Signature<'de>) -> Self {
+        ValueSeed {
+            signature,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> DynamicType for ValueSeed<'de, T> {
+    fn dynamic_signature(&self) -> Signature<'_> {
+        self.signature.as_ref()
+    }
+}
+
 impl<'de, T> ValueSeed<'de, T>
 where
     T: Deserialize<'de>,
@@ -728,6 +1293,18 @@ where
     }
 
     value_seed_str_method!(visit_borrowed_str, &'de str, from_str_unchecked);
+    value_seed_str_method!(visit_string, String, from_string_unchecked);
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Value<'de>, E>
+    where
+        E: serde::de::Error,
+    {
+        if self.signature != "ay" {
+            return Err(Error::invalid_type(Unexpected::Bytes(value), &self));
+        }
+
+        Ok(Value::Array(Array::from(value)))
+    }
 
     fn visit_seq<V>(self, visitor: V) -> Result<Value<'de>, V::Error>
     where
@@ -833,7 +1410,12 @@ where
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(self)
+        // Byte arrays can be read in one go.
+        if self.signature == "ay" {
+            deserializer.deserialize_bytes(self)
+        } else {
+            deserializer.deserialize_any(self)
+        }
     }
 }
 
@@ -1041,4 +1623,60 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn value_accessors() {
+        let v = Value::new((42_u64, "hello", vec![1_i32, 2]));
+        let fields = v.as_structure().unwrap().fields();
+        assert_eq!(fields[0].as_u64(), Some(42));
+        assert_eq!(fields[0].as_u32(), None);
+        assert_eq!(fields[1].as_str(), Some("hello"));
+        assert_eq!(fields[2].as_array().unwrap().len(), 2);
+        assert!(v.as_dict().is_none());
+
+        let dict: HashMap<&str, Value<'_>> = [("key", Value::new(true))].into_iter().collect();
+        let v = Value::new(Value::new(dict));
+        let dict = v.as_dict().unwrap();
+        assert_eq!(
+            dict.get::<_, Value<'_>>("key").unwrap().unwrap().as_bool(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn try_downcast() {
+        // Only converts from even numbers.
+        #[derive(Debug, PartialEq)]
+        struct Even(u32);
+
+        impl Type for Even {
+            fn signature() -> Signature<'static> {
+                u32::signature()
+            }
+        }
+
+        impl TryFrom<Value<'_>> for Even {
+            type Error = crate::Error;
+
+            fn try_from(value: Value<'_>) -> crate::Result<Self> {
+                match u32::try_from(value)? {
+                    n if n % 2 == 0 => Ok(Even(n)),
+                    n => Err(crate::Error::Message(format!("{n} is odd"))),
+                }
+            }
+        }
+
+        assert_eq!(
+            Value::new(Value::U32(4)).try_downcast::<Even>().unwrap(),
+            Even(4)
+        );
+        match Value::from("4").try_downcast::<Even>() {
+            Err(TryDowncastError::Mismatch(v)) => assert_eq!(v, Value::from("4")),
+            res => panic!("unexpected result: {res:?}"),
+        }
+        match Value::U32(3).try_downcast::<Even>() {
+            Err(TryDowncastError::Conversion(e)) => assert_eq!(e.to_string(), "3 is odd"),
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
 }
@@ -0,0 +1,677 @@
+#[cfg(feature = "gvariant")]
+use crate::Maybe;
+use crate::{
+    signature_parser::SignatureParser, Array, Dict, Error, MaxDepthExceeded, ObjectPath, Result,
+    Signature, StructureBuilder, Value,
+};
+
+// The maximum nesting of containers, same as the one allowed by the D-Bus specification.
+const MAX_DEPTH: usize = 64;
+
+/// Parse `text`, in the GVariant text format, into a [`Value`].
+///
+/// If `signature` isn't given, the type of the value is inferred from the text.
+pub(crate) fn parse(text: &str, signature: Option<&Signature<'_>>) -> Result<Value<'static>> {
+    if let Some(signature) = signature {
+        check_single_type(signature)?;
+    }
+    let mut parser = Parser { text, pos: 0 };
+    let ast = parser.parse_value(0)?;
+    parser.skip_whitespace();
+    if parser.pos != text.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+
+    match signature {
+        Some(signature) => resolve(ast, signature),
+        None => {
+            let signature = infer(&ast)?.ok_or_else(|| {
+                Error::Message(
+                    "can't infer the type of the value, use a type annotation (e.g `@as []`)"
+                        .into(),
+                )
+            })?;
+
+            resolve(ast, &signature)
+        }
+    }
+}
+
+// A parsed value, before its type is known.
+#[derive(Debug)]
+enum Ast {
+    Bool(bool),
+    // The literal and whether it's a floating point number.
+    Number(String, bool),
+    Str(String),
+    ByteStr(Vec<u8>),
+    Array(Vec<Ast>),
+    Dict(Vec<(Ast, Ast)>),
+    Tuple(Vec<Ast>),
+    Variant(Box<Ast>),
+    Just(Box<Ast>),
+    Nothing,
+    Typed(Signature<'static>, Box<Ast>),
+}
+
+struct Parser<'t> {
+    text: &'t str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn parse_value(&mut self, depth: usize) -> Result<Ast> {
+        if depth > MAX_DEPTH {
+            return Err(Error::MaxDepthExceeded(MaxDepthExceeded::Container));
+        }
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+                let start = self.pos;
+                while let Some(c) = self.peek().filter(|c| !c.is_whitespace()) {
+                    self.pos += c.len_utf8();
+                }
+                let signature = Signature::try_from(&self.text[start..self.pos])
+                    .and_then(|signature| {
+                        check_single_type(&signature)?;
+
+                        Ok(signature.to_owned())
+                    })
+                    .map_err(|_| self.error("invalid type annotation"))?;
+                let value = self.parse_value(depth + 1)?;
+
+                Ok(Ast::Typed(signature, Box::new(value)))
+            }
+            Some('<') => {
+                self.pos += 1;
+                let value = self.parse_value(depth + 1)?;
+                self.expect('>')?;
+
+                Ok(Ast::Variant(Box::new(value)))
+            }
+            Some('[') => {
+                self.pos += 1;
+                let elements = self.parse_list(']', depth)?;
+
+                Ok(Ast::Array(elements))
+            }
+            Some('(') => {
+                self.pos += 1;
+                let mut fields = vec![];
+                loop {
+                    self.skip_whitespace();
+                    if self.eat(')') {
+                        break;
+                    }
+                    fields.push(self.parse_value(depth + 1)?);
+                    self.skip_whitespace();
+                    if !self.eat(',') {
+                        self.expect(')')?;
+                        break;
+                    }
+                }
+
+                Ok(Ast::Tuple(fields))
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut entries = vec![];
+                loop {
+                    self.skip_whitespace();
+                    if self.eat('}') {
+                        break;
+                    }
+                    let key = self.parse_value(depth + 1)?;
+                    self.expect(':')?;
+                    let value = self.parse_value(depth + 1)?;
+                    entries.push((key, value));
+                    self.skip_whitespace();
+                    if !self.eat(',') {
+                        self.expect('}')?;
+                        break;
+                    }
+                }
+
+                Ok(Ast::Dict(entries))
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+
+                self.parse_string(quote).map(Ast::Str)
+            }
+            Some('b') if matches!(self.peek_nth(1), Some('"' | '\'')) => {
+                let quote = self.peek_nth(1).unwrap();
+                self.pos += 2;
+                let mut bytes = self.parse_string(quote)?.into_bytes();
+                bytes.push(b'\0');
+
+                Ok(Ast::ByteStr(bytes))
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                Ok(self.parse_number())
+            }
+            Some(c) if c.is_ascii_alphabetic() => self.parse_keyword(depth),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of text")),
+        }
+    }
+
+    // Parse values separated by commas, until `end`.
+    fn parse_list(&mut self, end: char, depth: usize) -> Result<Vec<Ast>> {
+        let mut values = vec![];
+        loop {
+            self.skip_whitespace();
+            if self.eat(end) {
+                break;
+            }
+            values.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            if !self.eat(',') {
+                self.expect(end)?;
+                break;
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn parse_keyword(&mut self, depth: usize) -> Result<Ast> {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_alphanumeric()) {
+            self.pos += 1;
+        }
+        let keyword = &self.text[start..self.pos];
+        let signature = match keyword {
+            "true" => return Ok(Ast::Bool(true)),
+            "false" => return Ok(Ast::Bool(false)),
+            "nothing" => return Ok(Ast::Nothing),
+            "just" => return Ok(Ast::Just(Box::new(self.parse_value(depth + 1)?))),
+            "inf" | "nan" | "NaN" => return Ok(Ast::Number(keyword.into(), true)),
+            "boolean" => "b",
+            "byte" => "y",
+            "int16" => "n",
+            "uint16" => "q",
+            "int32" => "i",
+            "uint32" => "u",
+            "int64" => "x",
+            "uint64" => "t",
+            "double" => "d",
+            "string" => "s",
+            "objectpath" => "o",
+            "signature" => "g",
+            "handle" => "h",
+            _ => {
+                self.pos = start;
+
+                return Err(self.error("unknown keyword"));
+            }
+        };
+        let value = self.parse_value(depth + 1)?;
+
+        Ok(Ast::Typed(
+            Signature::from_static_str_unchecked(signature),
+            Box::new(value),
+        ))
+    }
+
+    fn parse_number(&mut self) -> Ast {
+        let start = self.pos;
+        let mut prev = None;
+        while let Some(c) = self.peek() {
+            let exponent_sign = (c == '-' || c == '+') && matches!(prev, Some('e' | 'E'));
+            let sign = (c == '-' || c == '+') && self.pos == start;
+            if !(c.is_ascii_alphanumeric() || c == '.' || sign || exponent_sign) {
+                break;
+            }
+            prev = Some(c);
+            self.pos += 1;
+        }
+        let literal = &self.text[start..self.pos];
+        let digits = literal.trim_start_matches(['-', '+']);
+        let hex = digits.starts_with("0x") || digits.starts_with("0X");
+        let float = !hex
+            && (digits.contains(['.', 'e', 'E'])
+                || digits.starts_with("inf")
+                || digits.eq_ignore_ascii_case("nan"));
+
+        Ast::Number(literal.into(), float)
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String> {
+        let mut string = String::new();
+        loop {
+            let c = self
+                .next()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            match c {
+                c if c == quote => return Ok(string),
+                '\\' => {
+                    let escaped = match self
+                        .next()
+                        .ok_or_else(|| self.error("unterminated string"))?
+                    {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        'a' => '\x07',
+                        'b' => '\x08',
+                        'f' => '\x0c',
+                        'v' => '\x0b',
+                        '0' => '\0',
+                        'u' if self.eat('{') => {
+                            let start = self.pos;
+                            while let Some(c) = self.peek().filter(|c| *c != '}') {
+                                self.pos += c.len_utf8();
+                            }
+                            let code = &self.text[start..self.pos];
+                            self.expect('}')?;
+
+                            self.unicode_char(code)?
+                        }
+                        'u' => self.unicode_escape(4)?,
+                        'U' => self.unicode_escape(8)?,
+                        c => c,
+                    };
+                    string.push(escaped);
+                }
+                c => string.push(c),
+            }
+        }
+    }
+
+    // Parse the `len` hexadecimal digits of a unicode escape.
+    fn unicode_escape(&mut self, len: usize) -> Result<char> {
+        let code = self
+            .text
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let c = self.unicode_char(code)?;
+        self.pos += len;
+
+        Ok(c)
+    }
+
+    fn unicode_char(&self, code: &str) -> Result<char> {
+        u32::from_str_radix(code, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map_or(false, char::is_whitespace) {
+            self.pos += self.peek().map_or(0, char::len_utf8);
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.text[self.pos..].chars().nth(n)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        self.skip_whitespace();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{c}`")))
+        }
+    }
+
+    fn error(&self, msg: &str) -> Error {
+        Error::Message(format!("{msg} at position {}", self.pos))
+    }
+}
+
+// Infer the signature of `ast`, if possible from its contents alone.
+//
+// Fails if the inferred signature isn't valid, e.g for an empty tuple, a dictionary with keys of a
+// container type or a too deep nesting.
+fn infer(ast: &Ast) -> Result<Option<Signature<'static>>> {
+    macro_rules! infer {
+        ($signature:expr) => {
+            match $signature? {
+                Some(signature) => signature,
+                None => return Ok(None),
+            }
+        };
+    }
+
+    let signature = match ast {
+        Ast::Bool(_) => Signature::from_static_str_unchecked("b"),
+        Ast::Number(_, true) => Signature::from_static_str_unchecked("d"),
+        Ast::Number(_, false) => Signature::from_static_str_unchecked("i"),
+        Ast::Str(_) => Signature::from_static_str_unchecked("s"),
+        Ast::ByteStr(_) => Signature::from_static_str_unchecked("ay"),
+        Ast::Array(elements) => Signature::array_of(&infer!(infer_common(elements.iter())))?,
+        Ast::Dict(entries) => {
+            let key = infer!(infer_common(entries.iter().map(|(k, _)| k)));
+            let value = infer!(infer_common(entries.iter().map(|(_, v)| v)));
+
+            Signature::dict_of(&key, &value)?
+        }
+        Ast::Tuple(fields) => {
+            let mut signatures = Vec::with_capacity(fields.len());
+            for field in fields {
+                signatures.push(infer!(infer(field)));
+            }
+
+            Signature::structure_of(&signatures)?
+        }
+        Ast::Variant(_) => Signature::from_static_str_unchecked("v"),
+        Ast::Just(value) => Signature::try_from(format!("m{}", infer!(infer(value))))?,
+        Ast::Nothing => return Ok(None),
+        Ast::Typed(signature, _) => signature.clone(),
+    };
+
+    Ok(Some(signature))
+}
+
+// Infer the signature shared by the elements of a container.
+//
+// Integers and floating point numbers can be mixed, in which case they're all doubles.
+fn infer_common<'a>(mut asts: impl Iterator<Item = &'a Ast>) -> Result<Option<Signature<'static>>> {
+    let signature = loop {
+        match asts.next() {
+            Some(ast) => {
+                if let Some(signature) = infer(ast)? {
+                    break signature;
+                }
+            }
+            None => return Ok(None),
+        }
+    };
+    if signature == "i" && asts.any(|ast| matches!(ast, Ast::Number(_, true))) {
+        return Ok(Some(Signature::from_static_str_unchecked("d")));
+    }
+
+    Ok(Some(signature))
+}
+
+// Turn `ast` into a value of type `signature`.
+fn resolve(ast: Ast, signature: &Signature<'_>) -> Result<Value<'static>> {
+    let mismatch = |what: &str| {
+        Error::SignatureMismatch(signature.to_owned(), format!("{what} can't have this type"))
+    };
+
+    let ast = match ast {
+        Ast::Typed(annotated, ast) => {
+            if annotated != *signature {
+                return Err(Error::SignatureMismatch(
+                    signature.to_owned(),
+                    format!("the value is annotated with type `{annotated}`"),
+                ));
+            }
+
+            *ast
+        }
+        ast => ast,
+    };
+
+    let value = match (signature.as_bytes()[0], ast) {
+        (b'b', Ast::Bool(b)) => Value::Bool(b),
+        (b'y', Ast::Number(n, false)) => Value::U8(integer(&n, signature)?),
+        (b'n', Ast::Number(n, false)) => Value::I16(integer(&n, signature)?),
+        (b'q', Ast::Number(n, false)) => Value::U16(integer(&n, signature)?),
+        (b'i', Ast::Number(n, false)) => Value::I32(integer(&n, signature)?),
+        (b'u', Ast::Number(n, false)) => Value::U32(integer(&n, signature)?),
+        (b'x', Ast::Number(n, false)) => Value::I64(integer(&n, signature)?),
+        (b't', Ast::Number(n, false)) => Value::U64(integer(&n, signature)?),
+        (b'd', Ast::Number(n, _)) => Value::F64(
+            n.parse()
+                .map_err(|_| Error::Message(format!("invalid number `{n}`")))?,
+        ),
+        (b's', Ast::Str(s)) => Value::from(s),
+        (b'o', Ast::Str(s)) => Value::ObjectPath(ObjectPath::try_from(s)?),
+        (b'g', Ast::Str(s)) => Value::Signature(Signature::try_from(s)?),
+        (b'v', Ast::Variant(ast)) => {
+            let signature = infer(&ast)?.ok_or_else(|| mismatch("an untyped variant"))?;
+
+            Value::Value(Box::new(resolve(*ast, &signature)?))
+        }
+        (b'a', Ast::ByteStr(bytes)) if signature == "ay" => Value::Array(Array::from(bytes)),
+        (b'a', Ast::Array(elements)) if signature.as_bytes()[1] == b'{' => {
+            if !elements.is_empty() {
+                return Err(mismatch("an array"));
+            }
+
+            Value::Dict(new_dict(signature))
+        }
+        (b'a', Ast::Array(elements)) => {
+            let element_signature = signature.slice(1..).to_owned();
+            let mut array = Array::new(element_signature.clone());
+            for element in elements {
+                array.append(resolve(element, &element_signature)?)?;
+            }
+
+            Value::Array(array)
+        }
+        (b'a', Ast::Dict(entries)) if signature.as_bytes()[1] == b'{' => {
+            let mut dict = new_dict(signature);
+            let key_signature = signature.slice(2..3);
+            let value_signature = signature.slice(3..signature.len() - 1);
+            for (key, value) in entries {
+                dict.append(
+                    resolve(key, &key_signature)?,
+                    resolve(value, &value_signature)?,
+                )?;
+            }
+
+            Value::Dict(dict)
+        }
+        (b'(', Ast::Tuple(fields)) => {
+            let field_signatures = SignatureParser::new(signature.slice(1..signature.len() - 1));
+            let mut builder = StructureBuilder::new();
+            let mut fields = fields.into_iter();
+            for field_signature in field_signatures {
+                let field = fields.next().ok_or_else(|| mismatch("a shorter tuple"))?;
+                builder.push_value(resolve(field, &field_signature?)?);
+            }
+            if fields.next().is_some() {
+                return Err(mismatch("a longer tuple"));
+            }
+
+            Value::Structure(builder.build())
+        }
+        #[cfg(feature = "gvariant")]
+        (b'm', ast) => {
+            let value_signature = signature.slice(1..).to_owned();
+            match ast {
+                Ast::Nothing => Value::Maybe(Maybe::nothing(value_signature)),
+                Ast::Just(ast) => Value::Maybe(Maybe::just(resolve(*ast, &value_signature)?)),
+                ast => Value::Maybe(Maybe::just(resolve(ast, &value_signature)?)),
+            }
+        }
+        (b'h', _) => {
+            return Err(Error::Message(
+                "file descriptors can't be parsed from text".into(),
+            ))
+        }
+        (_, ast) => return Err(mismatch(ast.description())),
+    };
+
+    Ok(value)
+}
+
+fn check_single_type(signature: &Signature<'_>) -> Result<()> {
+    if signature.n_complete_types()? != 1 {
+        return Err(Error::SignatureMismatch(
+            signature.to_owned(),
+            "expected a single complete type".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn new_dict(signature: &Signature<'_>) -> Dict<'static, 'static> {
+    Dict::new(
+        signature.slice(2..3).to_owned(),
+        signature.slice(3..signature.len() - 1).to_owned(),
+    )
+}
+
+// Parse the integer literal `n` (decimal, hexadecimal or octal) as a `signature` integer.
+fn integer<T>(n: &str, signature: &Signature<'_>) -> Result<T>
+where
+    T: TryFrom<i128>,
+{
+    let (negative, digits) = match n.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, n.strip_prefix('+').unwrap_or(n)),
+    };
+    let (radix, digits) = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        (16, hex)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (8, &digits[1..])
+    } else {
+        (10, digits)
+    };
+    let n = i128::from_str_radix(digits, radix)
+        .ok()
+        .map(|n| if negative { -n } else { n })
+        .ok_or_else(|| Error::Message(format!("invalid number `{n}`")))?;
+
+    T::try_from(n)
+        .map_err(|_| Error::SignatureMismatch(signature.to_owned(), format!("{n} is out of range")))
+}
+
+impl Ast {
+    fn description(&self) -> &'static str {
+        match self {
+            Ast::Bool(_) => "a boolean",
+            Ast::Number(_, true) => "a floating point number",
+            Ast::Number(_, false) => "an integer",
+            Ast::Str(_) => "a string",
+            Ast::ByteStr(_) => "a bytestring",
+            Ast::Array(_) => "an array",
+            Ast::Dict(_) => "a dictionary",
+            Ast::Tuple(_) => "a tuple",
+            Ast::Variant(_) => "a variant",
+            Ast::Just(_) | Ast::Nothing => "a maybe",
+            Ast::Typed(_, _) => "a typed value",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let value = Value::parse("(true, byte 0x2a, -7, uint64 12, 1.5, 'it''s', objectpath '/a')");
+        assert!(value.is_err());
+        let value =
+            Value::parse(r#"(true, byte 0x2a, -7, uint64 012, 1.5, "it's", objectpath "/a")"#)
+                .unwrap();
+        assert_eq!(value.value_signature(), "(byitdso)");
+        let fields = match &value {
+            Value::Structure(s) => s.fields(),
+            _ => panic!("not a structure"),
+        };
+        assert_eq!(fields[1], Value::U8(42));
+        assert_eq!(fields[3], Value::U64(10));
+        assert_eq!(fields[5], Value::from("it's"));
+
+        // Integers and floating point numbers mix as doubles.
+        let value = Value::parse("[1, 2.5]").unwrap();
+        assert_eq!(value, Value::from(vec![1., 2.5]));
+        // The first annotation gives the type of all the elements.
+        let value = Value::parse("[uint16 1, 2]").unwrap();
+        assert_eq!(value, Value::from(vec![1_u16, 2]));
+
+        let value = Value::parse(r#"b"ab\n""#).unwrap();
+        assert_eq!(value, Value::from(b"ab\n\0".to_vec()));
+
+        assert!(Value::parse("[]").is_err());
+        assert!(Value::parse("[1, \"a\"]").is_err());
+        assert!(Value::parse("byte 256").is_err());
+        assert!(Value::parse("@ai [1").is_err());
+        assert!(Value::parse("1 2").is_err());
+        assert!(Value::parse_with_signature("(1, 2)", "(i)").is_err());
+        assert!(Value::parse_with_signature("1", "ii").is_err());
+        assert!(Value::parse_with_signature("uint32 1", "i").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut dict = HashMap::new();
+        dict.insert(
+            "path",
+            Value::from(ObjectPath::try_from("/org/zbus").unwrap()),
+        );
+        dict.insert("sig", Value::from(Signature::try_from("a{sv}").unwrap()));
+        dict.insert("nested", Value::from(vec![vec![1_i16], vec![]]));
+        dict.insert("text", Value::from("tab\t \"quote\" \u{1b} é"));
+        dict.insert("empty", Value::from(HashMap::<u32, bool>::new()));
+        let values = [
+            Value::from(dict),
+            Value::new(Value::new(42_u32)),
+            Value::from(vec![0_u8, 255]),
+            Value::from(b"bytes\0".to_vec()),
+            Value::from((-1_i64, f64::INFINITY, ("a",))),
+        ];
+        for value in values {
+            let text = value.to_string();
+            assert_eq!(Value::parse(&text).unwrap(), value, "{text}");
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let deep = format!("{}1{}", "[".repeat(40), "]".repeat(40));
+        for text in [
+            r"'\u{é}'",
+            "()",
+            "[()]",
+            "{[1]: 2}",
+            "{<1>: 2}",
+            deep.as_str(),
+        ] {
+            assert!(Value::parse(text).is_err(), "{text}");
+        }
+    }
+
+    #[cfg(feature = "gvariant")]
+    #[test]
+    fn maybe() {
+        let value = Value::parse("@mi nothing").unwrap();
+        assert_eq!(
+            value,
+            Value::Maybe(Maybe::nothing(<i32 as crate::Type>::signature()))
+        );
+        let value = Value::parse("just 5").unwrap();
+        assert_eq!(value, Value::Maybe(Maybe::just(Value::I32(5))));
+        let value = Value::parse_with_signature("[5, nothing]", "ami").unwrap();
+        assert_eq!(value.to_string(), "[@mi 5, nothing]");
+        assert_eq!(Value::parse(&value.to_string()).unwrap(), value);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::Value;
+
+/// A segment of the path from the root of a [`Value`] tree to a nested value.
+///
+/// See [`ValueVisitor`] for details.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ValuePathSegment<'v> {
+    /// The element at the given index of an array.
+    Index(usize),
+    /// The value associated with the given key of a dictionary.
+    Key(&'v Value<'v>),
+    /// The field at the given index of a structure.
+    Field(usize),
+    /// The value enclosed in a variant (i.e [`Value::Value`]).
+    Variant,
+    /// The value enclosed in a GVariant Maybe (i.e [`Value::Maybe`]).
+    #[cfg(feature = "gvariant")]
+    Just,
+}
+
+/// What to do after visiting a value.
+///
+/// Returned by [`ValueVisitor::visit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visit {
+    /// Continue the walk, visiting the children of the value, if any.
+    Continue,
+    /// Continue the walk but don't visit the children of the value.
+    SkipChildren,
+    /// Stop the walk altogether.
+    Break,
+}
+
+/// A visitor of [`Value`] trees.
+///
+/// [`Value::visit`] walks the tree depth-first, calling [`ValueVisitor::visit`] for each value
+/// before its children and [`ValueVisitor::leave`] after them. Both methods are given the path
+/// from the root to the value. The root value has an empty path.
+///
+/// For dictionaries, only the values are visited. The keys are available through
+/// [`ValuePathSegment::Key`] in the path instead.
+///
+/// # Examples
+///
+/// Collecting all the strings in a tree, along with their location:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{Value, ValuePathSegment, ValueVisitor, Visit};
+///
+/// #[derive(Default)]
+/// struct Strings(Vec<(String, String)>);
+///
+/// impl ValueVisitor for Strings {
+///     fn visit(&mut self, path: &[ValuePathSegment<'_>], value: &Value<'_>) -> Visit {
+///         if let Some(s) = value.as_str() {
+///             let path = path
+///                 .iter()
+///                 .map(|segment| match segment {
+///                     ValuePathSegment::Index(i) | ValuePathSegment::Field(i) => i.to_string(),
+///                     ValuePathSegment::Key(k) => k.as_str().unwrap_or("?").to_string(),
+///                     _ => "".to_string(),
+///                 })
+///                 .collect::<Vec<_>>()
+///                 .join("/");
+///             self.0.push((path, s.to_string()));
+///
+///             // No need to look inside the string variant.
+///             return Visit::SkipChildren;
+///         }
+///
+///         Visit::Continue
+///     }
+/// }
+///
+/// let mut user = HashMap::new();
+/// user.insert("name", Value::from("Alice"));
+/// let value = Value::new((42_u32, vec!["foo", "bar"], user));
+///
+/// let mut strings = Strings::default();
+/// value.visit(&mut strings);
+/// assert_eq!(
+///     strings.0,
+///     vec![
+///         ("1/0".to_string(), "foo".to_string()),
+///         ("1/1".to_string(), "bar".to_string()),
+///         ("2/name".to_string(), "Alice".to_string()),
+///     ],
+/// );
+/// ```
+pub trait ValueVisitor {
+    /// Visit `value`, located at `path` in the tree.
+    fn visit(&mut self, path: &[ValuePathSegment<'_>], value: &Value<'_>) -> Visit;
+
+    /// Leave `value`, located at `path` in the tree, after all its children have been visited.
+    ///
+    /// The default implementation does nothing.
+    fn leave(&mut self, path: &[ValuePathSegment<'_>], value: &Value<'_>) {
+        let _ = (path, value);
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Walk the tree of values rooted at `self`, using `visitor`.
+    ///
+    /// See [`ValueVisitor`] for details.
+    pub fn visit<V>(&self, visitor: &mut V)
+    where
+        V: ValueVisitor + ?Sized,
+    {
+        let mut path = vec![];
+        walk(self, &mut path, visitor);
+    }
+}
+
+// Returns `false` if the walk was stopped.
+fn walk<'v, V>(value: &'v Value<'v>, path: &mut Vec<ValuePathSegment<'v>>, visitor: &mut V) -> bool
+where
+    V: ValueVisitor + ?Sized,
+{
+    match visitor.visit(path, value) {
+        Visit::Break => return false,
+        Visit::SkipChildren => (),
+        Visit::Continue => {
+            let completed = match value {
+                Value::Value(v) => walk_child(ValuePathSegment::Variant, v, path, visitor),
+                Value::Array(array) => array
+                    .get()
+                    .iter()
+                    .enumerate()
+                    .all(|(i, v)| walk_child(ValuePathSegment::Index(i), v, path, visitor)),
+                Value::Dict(dict) => dict
+                    .iter()
+                    .all(|(k, v)| walk_child(ValuePathSegment::Key(k), v, path, visitor)),
+                Value::Structure(structure) => structure
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .all(|(i, v)| walk_child(ValuePathSegment::Field(i), v, path, visitor)),
+                #[cfg(feature = "gvariant")]
+                Value::Maybe(maybe) => match maybe.inner() {
+                    Some(v) => walk_child(ValuePathSegment::Just, v, path, visitor),
+                    None => true,
+                },
+                _ => true,
+            };
+            if !completed {
+                return false;
+            }
+        }
+    }
+    visitor.leave(path, value);
+
+    true
+}
+
+fn walk_child<'v, V>(
+    segment: ValuePathSegment<'v>,
+    child: &'v Value<'v>,
+    path: &mut Vec<ValuePathSegment<'v>>,
+    visitor: &mut V,
+) -> bool
+where
+    V: ValueVisitor + ?Sized,
+{
+    path.push(segment);
+    let completed = walk(child, path, visitor);
+    path.pop();
+
+    completed
+}
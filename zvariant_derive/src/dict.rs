@@ -36,14 +36,17 @@ pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error>
         _ => return Err(Error::new(input.span(), "only structs supported")),
     };
 
-    let StructAttributes { rename_all, .. } = StructAttributes::parse(&input.attrs)?;
+    let StructAttributes {
+        rename_all, repr, ..
+    } = StructAttributes::parse(&input.attrs)?;
+    reject_struct_repr(&repr)?;
 
     let zv = zvariant_path();
     let mut entries = quote! {};
     let mut num_entries: usize = 0;
 
     for f in &data.fields {
-        let FieldAttributes { rename } = FieldAttributes::parse(&f.attrs)?;
+        let FieldAttributes { rename, .. } = FieldAttributes::parse(&f.attrs)?;
 
         let name = &f.ident;
         let dict_name = dict_name_for_field(f, rename, rename_all.as_deref())?;
@@ -99,8 +102,10 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
     let StructAttributes {
         rename_all,
         deny_unknown_fields,
+        repr,
         ..
     } = StructAttributes::parse(&input.attrs)?;
+    reject_struct_repr(&repr)?;
 
     let visitor = format_ident!("{}Visitor", name);
     let zv = zvariant_path();
@@ -108,26 +113,61 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
     let mut req_fields = Vec::new();
     let mut dict_names = Vec::new();
     let mut entries = Vec::new();
+    let mut defaults = Vec::new();
 
     for f in &data.fields {
-        let FieldAttributes { rename } = FieldAttributes::parse(&f.attrs)?;
+        let FieldAttributes {
+            rename, default, ..
+        } = FieldAttributes::parse(&f.attrs)?;
 
         let name = &f.ident;
         let dict_name = dict_name_for_field(f, rename, rename_all.as_deref())?;
 
         let is_option = macros::ty_is_option(&f.ty);
 
-        entries.push(quote! {
-            #dict_name => {
-                // FIXME: add an option about strict parsing (instead of silently skipping the field)
-                #name = access.next_value::<#zv::DeserializeValue<_>>().map(|v| v.0).ok();
+        entries.push(if deny_unknown_fields {
+            quote! {
+                #dict_name => {
+                    if ::std::option::Option::is_some(&#name) {
+                        return ::std::result::Result::Err(
+                            <M::Error as #zv::export::serde::de::Error>::duplicate_field(
+                                #dict_name,
+                            ),
+                        );
+                    }
+                    #name = ::std::option::Option::Some(
+                        access.next_value::<#zv::DeserializeValue<_>>()?.0,
+                    );
+                }
+            }
+        } else {
+            quote! {
+                #dict_name => {
+                    // Silently skip the entry if its value is not of the expected type.
+                    let value = access.next_value::<#zv::DeserializeValueIfType<_>>()?.0;
+                    if ::std::option::Option::is_some(&value) {
+                        #name = value;
+                    }
+                }
             }
         });
 
         dict_names.push(dict_name);
         fields.push(name);
 
-        if !is_option {
+        if let Some(default) = default {
+            let default = match default {
+                Some(path) => syn::parse_str::<syn::ExprPath>(&path)
+                    .map_err(|e| Error::new(f.span(), format!("invalid `default` path: {e}")))?
+                    .to_token_stream(),
+                None => quote! { ::std::default::Default::default },
+            };
+            defaults.push(if is_option {
+                quote! { let #name = #name.or_else(#default); }
+            } else {
+                quote! { let #name = #name.unwrap_or_else(#default); }
+            });
+        } else if !is_option {
             req_fields.push(name);
         }
     }
@@ -194,7 +234,8 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
                     {
                         #( let mut #fields = ::std::default::Default::default(); )*
 
-                        // does not check duplicated fields, since those shouldn't exist in stream
+                        // only checks duplicated fields in strict mode, since those shouldn't
+                        // exist in stream
                         while let ::std::option::Option::Some(key) = access.next_key::<&str>()? {
                             match key {
                                 #(#entries)*
@@ -211,6 +252,8 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
                             );
                         };)*
 
+                        #(#defaults)*
+
                         ::std::result::Result::Ok(#name { #(#fields),* })
                     }
                 }
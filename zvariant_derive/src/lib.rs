@@ -14,6 +14,7 @@ use proc_macro::TokenStream;
 use syn::{self, DeriveInput};
 
 mod dict;
+mod repr;
 mod r#type;
 mod utils;
 mod value;
@@ -50,7 +51,9 @@ mod value;
 ///
 /// Same with enum, except that all variants of the enum must have the same number and types of
 /// fields (if any). If you want the encoding size of the (unit-type) enum to be dictated by
-/// `repr` attribute (like in the example below), you'll also need [serde_repr] crate.
+/// `repr` attribute (like in the example below), you'll also need [serde_repr] crate, or the
+/// [`SerializeRepr`] and [`DeserializeRepr`] macros. The latter also support specifying the
+/// encoding through a `#[zvariant(repr = "type")]` attribute, which takes precedence over `repr`.
 ///
 /// ```
 /// use zvariant::{EncodingContext, from_slice, to_bytes, Type};
@@ -103,6 +106,47 @@ mod value;
 /// assert_eq!(StructFields::signature(), "(u(qxs))");
 /// ```
 ///
+/// # Newtypes
+///
+/// Newtype structs (tuple structs with a single field) are transparent: they have the same
+/// signature as the type they wrap and (just like with serde) get encoded exactly like it. This
+/// is especially handy for giving domain-specific names to container types, which can then be
+/// used in proxies and interfaces directly. Structs with a single named field can opt into the
+/// same behavior through serde's `transparent` attribute:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{EncodingContext, from_slice, to_bytes, OwnedValue, Type, Value};
+/// use serde::{Deserialize, Serialize};
+/// use byteorder::LE;
+///
+/// #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+/// struct Props(HashMap<String, OwnedValue>);
+///
+/// #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+/// #[serde(transparent)]
+/// struct Names {
+///     names: Vec<String>,
+/// }
+///
+/// assert_eq!(Props::signature(), "a{sv}");
+/// assert_eq!(Names::signature(), "as");
+///
+/// let mut props = HashMap::new();
+/// props.insert("answer".to_string(), Value::from(42u32).into());
+/// let props = Props(props);
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &props).unwrap();
+/// assert_eq!(encoded, to_bytes(ctxt, &props.0).unwrap());
+/// let decoded: Props = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, props);
+///
+/// let names = Names { names: vec!["foo".to_string()] };
+/// let encoded = to_bytes(ctxt, &names).unwrap();
+/// let decoded: Names = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, names);
+/// ```
+///
 /// # Custom signatures
 ///
 /// There are times when you'd find yourself wanting to specify a hardcoded signature yourself for
@@ -158,10 +202,165 @@ mod value;
 /// assert_eq!(decoded, StrEnum::Variant2);
 /// ```
 ///
+/// # Generic types
+///
+/// A `Type` bound is added on each type parameter, so generic types get a `Type` implementation
+/// whenever their parameters implement it. If that's not what you want, e.g because a parameter is
+/// only used through an associated type, the bounds can be given explicitly through the `bound`
+/// attribute (an empty string meaning no bounds at all):
+///
+/// ```
+/// use zvariant::Type;
+///
+/// #[derive(Type)]
+/// struct Pair<T>(T, T);
+///
+/// assert_eq!(Pair::<u32>::signature(), "(uu)");
+/// assert_eq!(Pair::<(u8, String)>::signature(), "((ys)(ys))");
+///
+/// trait Unit {
+///     type Repr;
+/// }
+///
+/// // Not a `Type`.
+/// struct Meters;
+///
+/// impl Unit for Meters {
+///     type Repr = f64;
+/// }
+///
+/// #[derive(Type)]
+/// #[zvariant(bound = "U::Repr: Type")]
+/// ##[allow(unused)]
+/// struct Length<U: Unit> {
+///     value: U::Repr,
+/// }
+///
+/// assert_eq!(Length::<Meters>::signature(), "(d)");
+/// ```
+///
+/// # Fields of foreign types
+///
+/// If a field's type doesn't implement [`Type`], e.g a collection from a third-party crate, you
+/// can give the type it's to be (de)serialized as, through the `as` field attribute. The signature
+/// of the field is that of the given type, and the field is (de)serialized through a helper the
+/// derive generates under the name given by serde's `with` attribute, converting the field to and
+/// from the given type. The field type must therefore implement [`Clone`], while the given type
+/// must implement `TryFrom` (or `From`) of the field type and vice versa, for serialization and
+/// deserialization respectively.
+///
+/// ```
+/// use zvariant::{EncodingContext, from_slice, to_bytes, Type};
+/// use serde::{Deserialize, Serialize};
+/// use byteorder::LE;
+///
+/// // Imagine this type comes from another crate.
+/// #[derive(Clone, PartialEq, Debug)]
+/// struct Bytes(Vec<u8>);
+///
+/// impl From<Bytes> for Vec<u8> {
+///     fn from(bytes: Bytes) -> Self {
+///         bytes.0
+///     }
+/// }
+///
+/// impl From<Vec<u8>> for Bytes {
+///     fn from(bytes: Vec<u8>) -> Self {
+///         Bytes(bytes)
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+/// struct Packet {
+///     id: u32,
+///     #[serde(with = "payload_as_vec")]
+///     #[zvariant(as = "Vec<u8>")]
+///     payload: Bytes,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Packet::signature(), "(uay)");
+/// let packet = Packet {
+///     id: 42,
+///     payload: Bytes(vec![1, 2, 3]),
+/// };
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &packet).unwrap();
+/// let decoded: Packet = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, packet);
+/// # }
+/// ```
+///
+/// Without serde's `with` attribute, the field would be (de)serialized as its own type, regardless
+/// of the signature, so this fails to build:
+///
+/// ```compile_fail
+/// use zvariant::Type;
+/// use serde::Serialize;
+///
+/// #[derive(Clone, Serialize)]
+/// struct Bytes(Vec<u8>);
+///
+/// #[derive(Serialize, Type)]
+/// struct Packet {
+///     #[zvariant(as = "Vec<u8>")]
+///     payload: Bytes,
+/// }
+/// ```
+///
+/// # Bitflags
+///
+/// Flags arguments are very common in D-Bus APIs. With the `bitflags` feature of zvariant
+/// enabled, types generated by the [bitflags] crate can derive `Type` with the
+/// `#[zvariant(bitflags)]` attribute, which gives them the signature of their underlying integer
+/// type. When the `serde` feature of bitflags is enabled (which the `bitflags` feature takes care
+/// of), the flags are also (de)serialized as that integer, so no manual conversions are needed:
+///
+/// ```
+/// use zvariant::{EncodingContext, from_slice, to_bytes, Type};
+/// use serde::{Deserialize, Serialize};
+/// use byteorder::LE;
+///
+/// bitflags::bitflags! {
+///     #[derive(Deserialize, Serialize, Type, Debug, PartialEq)]
+///     #[zvariant(bitflags)]
+///     struct Capabilities: u32 {
+///         const READ = 1;
+///         const WRITE = 2;
+///     }
+/// }
+///
+/// assert_eq!(Capabilities::signature(), "u");
+/// let caps = Capabilities::READ | Capabilities::WRITE;
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &caps).unwrap();
+/// assert_eq!(from_slice::<_, u32>(&encoded, ctxt).unwrap().0, 3);
+/// let decoded: Capabilities = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, caps);
+/// ```
+///
+/// Since the encoding is dictated by the underlying integer type, the `signature` attribute can't
+/// be combined with `bitflags`:
+///
+/// ```compile_fail
+/// use zvariant::Type;
+///
+/// bitflags::bitflags! {
+///     #[derive(Type)]
+///     #[zvariant(bitflags, signature = "s")]
+///     struct Capabilities: u32 {
+///         const READ = 1;
+///     }
+/// }
+/// ```
+///
 /// [`Type`]: https://docs.rs/zvariant/latest/zvariant/trait.Type.html
 /// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 /// [serde_repr]: https://crates.io/crates/serde_repr
+/// [`SerializeRepr`]: derive.SerializeRepr.html
+/// [`DeserializeRepr`]: derive.DeserializeRepr.html
+/// [bitflags]: https://crates.io/crates/bitflags
 #[proc_macro_derive(Type, attributes(zvariant))]
 pub fn type_macro_derive(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(input).unwrap();
@@ -296,6 +495,56 @@ pub fn serialize_dict_macro_derive(input: TokenStream) -> TokenStream {
 /// * `"camelCase"`
 /// * `"snake_case"`
 ///
+/// # Strict mode
+///
+/// By default, entries with unknown keys are ignored, as are entries whose value is not of the
+/// expected type. Services that need to explicitly reject unexpected input can opt into strict
+/// parsing with the `#[zvariant(deny_unknown_fields)]` attribute on the structure. It makes
+/// deserialization fail on unknown keys, duplicate keys and values of unexpected types:
+///
+/// ```
+/// use zvariant::{DeserializeDict, Type};
+///
+/// #[derive(DeserializeDict, Type)]
+/// #[zvariant(signature = "a{sv}", deny_unknown_fields)]
+/// ##[allow(unused)]
+/// struct Struct {
+///     field1: u16,
+///     optional_field: Option<String>,
+/// }
+/// ```
+///
+/// # Default values
+///
+/// Dictionaries are typically extended over time, so it's often desirable to not fail when an
+/// entry is missing. Similar to serde, a `#[zvariant(default)]` attribute on a field makes the
+/// field take its [`Default`] value when the corresponding entry is missing. You can also
+/// provide the path to a function to use instead, with `#[zvariant(default = "path")]`:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{from_slice, to_bytes, DeserializeDict, EncodingContext, Type, Value};
+/// use byteorder::LE;
+///
+/// fn default_timeout() -> u32 {
+///     30
+/// }
+///
+/// #[derive(DeserializeDict, Type, PartialEq, Debug)]
+/// #[zvariant(signature = "a{sv}")]
+/// struct Struct {
+///     #[zvariant(default)]
+///     modal: bool,
+///     #[zvariant(default = "default_timeout")]
+///     timeout: u32,
+/// }
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &HashMap::<&str, Value<'_>>::new()).unwrap();
+/// let decoded: Struct = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, Struct { modal: false, timeout: 30 });
+/// ```
+///
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 #[proc_macro_derive(DeserializeDict, attributes(zvariant))]
 pub fn deserialize_dict_macro_derive(input: TokenStream) -> TokenStream {
@@ -305,6 +554,83 @@ pub fn deserialize_dict_macro_derive(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Adds [`Serialize`] implementation to unit-only enums to be serialized as an integer.
+///
+/// Each variant is serialized as its discriminant, using the integer type given through the
+/// `#[zvariant(repr = "type")]` attribute. The supported types are `u8`, `i16`, `u16`, `i32`,
+/// `u32`, `i64` and `u64`. Without the attribute, the type of Rust's `repr` attribute is used, and
+/// `u32` if there is none. Since the [`Type`] macro respects the same attributes, the signature of
+/// the enum is always consistent with its encoding.
+/// Discriminants that don't fit in the integer type are a build error.
+///
+/// This makes the [serde_repr] crate unnecessary when used together with [`DeserializeRepr`]:
+///
+/// ```
+/// use zvariant::{DeserializeRepr, EncodingContext, from_slice, SerializeRepr, to_bytes, Type};
+/// use byteorder::LE;
+///
+/// #[derive(SerializeRepr, DeserializeRepr, Type, Debug, PartialEq)]
+/// #[zvariant(repr = "u8")]
+/// enum State {
+///     Idle = 1,
+///     Busy = 2,
+/// }
+/// assert_eq!(State::signature(), u8::signature());
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &State::Busy).unwrap();
+/// assert_eq!(*encoded, [2]);
+/// let decoded: State = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, State::Busy);
+///
+/// // Unknown discriminants are rejected.
+/// let encoded = to_bytes(ctxt, &3_u8).unwrap();
+/// assert!(from_slice::<_, State>(&encoded, ctxt).is_err());
+/// ```
+///
+/// The `#[zvariant(repr = "type")]` attribute requires this macro, since serde's own [`Serialize`]
+/// macro ignores it and the signature would then not match the encoding. The [`Type`] macro fails
+/// the build in that case:
+///
+/// ```compile_fail
+/// use serde::Serialize;
+/// use zvariant::Type;
+///
+/// #[derive(Serialize, Type)]
+/// #[zvariant(repr = "u8")]
+/// enum State {
+///     Idle = 1,
+///     Busy = 2,
+/// }
+/// ```
+///
+/// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
+/// [`Type`]: derive.Type.html
+/// [`DeserializeRepr`]: derive.DeserializeRepr.html
+/// [serde_repr]: https://crates.io/crates/serde_repr
+#[proc_macro_derive(SerializeRepr, attributes(zvariant))]
+pub fn serialize_repr_macro_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).unwrap();
+    repr::expand_serialize_derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Adds [`Deserialize`] implementation to unit-only enums serialized as an integer.
+///
+/// The counterpart of [`SerializeRepr`], see its documentation for details and an example.
+/// Deserialization fails if the integer doesn't match the discriminant of any of the variants.
+///
+/// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
+/// [`SerializeRepr`]: derive.SerializeRepr.html
+#[proc_macro_derive(DeserializeRepr, attributes(zvariant))]
+pub fn deserialize_repr_macro_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).unwrap();
+    repr::expand_deserialize_derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Implements conversions for your type to/from [`Value`].
 ///
 /// Implements `TryFrom<Value>` and `Into<Value>` for your type.
@@ -0,0 +1,112 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DataEnum, DeriveInput, Error, Fields, Ident};
+
+use crate::utils::*;
+
+// The names of the unit variants of the enum, after ensuring there are no other kinds of variants.
+fn unit_variants(input: &DeriveInput) -> Result<(&Ident, Vec<&Ident>), Error> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::new(input.span(), "only enums supported")),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "generic enums are not supported",
+        ));
+    }
+
+    let DataEnum { variants, .. } = data;
+    let variants = variants
+        .iter()
+        .map(|variant| match variant.fields {
+            Fields::Unit => Ok(&variant.ident),
+            _ => Err(Error::new(variant.span(), "must be a unit variant")),
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok((&input.ident, variants))
+}
+
+// Fails the build if the discriminant of any of the variants doesn't fit in `repr`.
+fn check_discriminants(name: &Ident, variants: &[&Ident], repr: &TokenStream) -> TokenStream {
+    let messages = variants
+        .iter()
+        .map(|variant| format!("discriminant of `{name}::{variant}` doesn't fit in `{repr}`"));
+
+    quote! {
+        #[allow(deprecated)]
+        const _: () = {
+            #(
+                ::std::assert!(
+                    #name::#variants as i128 == #name::#variants as #repr as i128,
+                    #messages,
+                );
+            )*
+        };
+    }
+}
+
+pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let (name, variants) = unit_variants(&input)?;
+    let repr = enum_repr(&input.attrs)?;
+    let checks = check_discriminants(name, &variants, &repr);
+    let zv = zvariant_path();
+
+    Ok(quote! {
+        #checks
+
+        #[allow(deprecated)]
+        impl #zv::export::serde::ser::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: #zv::export::serde::ser::Serializer,
+            {
+                let v: #repr = match self {
+                    #(
+                        #name::#variants => #name::#variants as #repr,
+                    )*
+                };
+
+                <#repr as #zv::export::serde::ser::Serialize>::serialize(&v, serializer)
+            }
+        }
+
+        impl #zv::export::ReprEncoded for #name {}
+    })
+}
+
+pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let (name, variants) = unit_variants(&input)?;
+    let repr = enum_repr(&input.attrs)?;
+    let checks = check_discriminants(name, &variants, &repr);
+    let zv = zvariant_path();
+
+    Ok(quote! {
+        #checks
+
+        #[allow(deprecated)]
+        impl<'de> #zv::export::serde::de::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: #zv::export::serde::de::Deserializer<'de>,
+            {
+                let v = <#repr as #zv::export::serde::de::Deserialize>::deserialize(deserializer)?;
+                #(
+                    if v == #name::#variants as #repr {
+                        return ::std::result::Result::Ok(#name::#variants);
+                    }
+                )*
+
+                ::std::result::Result::Err(<D::Error as #zv::export::serde::de::Error>::custom(
+                    ::std::format_args!(
+                        "invalid discriminant `{}` for enum `{}`",
+                        v,
+                        ::std::stringify!(#name),
+                    ),
+                ))
+            }
+        }
+    })
+}
@@ -1,15 +1,56 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
-    self, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Fields, Generics, Ident,
+    self, parse_quote, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Field,
+    Fields, Generics, Ident, Type, WhereClause, WherePredicate,
 };
 
 use crate::utils::*;
 
 pub fn expand_derive(ast: DeriveInput) -> Result<TokenStream, Error> {
-    let StructAttributes { signature, .. } = StructAttributes::parse(&ast.attrs)?;
-
     let zv = zvariant_path();
+    let as_helpers = as_helpers(&ast.data, &zv)?;
+    let implementation = impl_type(ast, &zv)?;
+
+    Ok(quote! {
+        #as_helpers
+        #implementation
+    })
+}
+
+fn impl_type(ast: DeriveInput, zv: &TokenStream) -> Result<TokenStream, Error> {
+    let StructAttributes {
+        signature,
+        bound,
+        bitflags,
+        repr,
+        ..
+    } = StructAttributes::parse(&ast.attrs)?;
+    if let Data::Struct(_) = ast.data {
+        reject_struct_repr(&repr)?;
+    }
+
+    if bitflags {
+        if signature.is_some() {
+            return Err(Error::new(
+                ast.span(),
+                "`signature` and `bitflags` attributes can't be combined, bitflags are always \
+                 encoded as their underlying integer type",
+            ));
+        }
+
+        // Encoded as the underlying integer type.
+        let name = ast.ident;
+        let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+        return Ok(quote! {
+            impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
+                #[inline]
+                fn signature() -> #zv::Signature<'static> {
+                    <<Self as #zv::export::bitflags::Flags>::Bits as #zv::Type>::signature()
+                }
+            }
+        });
+    }
     if let Some(signature) = signature {
         let signature = match signature.as_str() {
             "dict" => "a{sv}".to_string(),
@@ -19,34 +60,44 @@ pub fn expand_derive(ast: DeriveInput) -> Result<TokenStream, Error> {
         // Signature already provided, easy then!
         let name = ast.ident;
         let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+        // FIXME: Would be nice if we had a parsed `Signature` in the macro code already so
+        // it's checked at the build time but currently that's not easily possible w/o
+        // zvariant_derive requiring zvaraint and we don't want it as it creates a cyclic
+        // dep. Maybe we can find a way to share the `Signature` type between the two
+        // crates?
+        //
+        // The signature doesn't depend on the generic parameters, so it can always be cached.
+        let signature = cached_signature(
+            quote! { #zv::Signature::from_static_str(#signature).unwrap() },
+            &Generics::default(),
+            zv,
+        );
         return Ok(quote! {
             impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
                 #[inline]
                 fn signature() -> #zv::Signature<'static> {
-                    // FIXME: Would be nice if we had a parsed `Signature` in the macro code already so
-                    // it's checked at the build time but currently that's not easily possible w/o
-                    // zvariant_derive requiring zvaraint and we don't want it as it creates a cyclic
-                    // dep. Maybe we can find a way to share the `Signature` type between the two
-                    // crates?
-                    #zv::Signature::from_static_str(#signature).unwrap()
+                    #signature
                 }
             }
         });
     }
 
+    let span = ast.span();
+    let generics = add_type_bounds(ast.generics, bound.as_deref(), span, zv)?;
     match ast.data {
+        Data::Struct(ds) if is_serde_transparent(&ast.attrs) => {
+            impl_transparent_struct(ast.ident, generics, ds.fields, zv)
+        }
         Data::Struct(ds) => match ds.fields {
-            Fields::Named(_) if ds.fields.is_empty() => {
-                impl_empty_struct(ast.ident, ast.generics, &zv)
-            }
+            Fields::Named(_) if ds.fields.is_empty() => impl_empty_struct(ast.ident, generics, zv),
             Fields::Named(_) | Fields::Unnamed(_) => {
-                impl_struct(ast.ident, ast.generics, ds.fields, &zv)
+                impl_struct(ast.ident, generics, ds.fields, zv)
             }
-            Fields::Unit => impl_unit_struct(ast.ident, ast.generics, &zv),
+            Fields::Unit => impl_unit_struct(ast.ident, generics, zv),
         },
-        Data::Enum(data) => impl_enum(ast.ident, ast.generics, ast.attrs, data, &zv),
+        Data::Enum(data) => impl_enum(ast.ident, generics, ast.attrs, repr.is_some(), data, zv),
         _ => Err(Error::new(
-            ast.span(),
+            span,
             "only structs and enums supported at the moment",
         )),
     }
@@ -58,6 +109,34 @@ pub fn expand_derive(ast: DeriveInput) -> Result<TokenStream, Error> {
     })
 }
 
+// Add a `Type` bound on each type parameter, unless the bounds are given explicitly through the
+// `bound` attribute.
+fn add_type_bounds(
+    mut generics: Generics,
+    bound: Option<&str>,
+    span: Span,
+    zv: &TokenStream,
+) -> Result<Generics, Error> {
+    let predicates: Vec<WherePredicate> = match bound {
+        Some(bound) => syn::parse_str::<WhereClause>(&format!("where {bound}"))
+            .map_err(|e| Error::new(span, format!("invalid `bound` attribute: {e}")))?
+            .predicates
+            .into_iter()
+            .collect(),
+        None => generics
+            .type_params()
+            .map(|param| {
+                let ident = &param.ident;
+
+                parse_quote! { #ident: #zv::Type }
+            })
+            .collect(),
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+
+    Ok(generics)
+}
+
 fn impl_struct(
     name: Ident,
     generics: Generics,
@@ -65,7 +144,7 @@ fn impl_struct(
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let signature = signature_for_struct(&fields, zv, false);
+    let signature = cached_signature(signature_for_struct(&fields, zv, false)?, &generics, zv);
 
     Ok(quote! {
         impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
@@ -77,12 +156,44 @@ fn impl_struct(
     })
 }
 
+fn impl_transparent_struct(
+    name: Ident,
+    generics: Generics,
+    fields: Fields,
+    zv: &TokenStream,
+) -> Result<TokenStream, Error> {
+    // Just like serde, we require exactly one field to forward to.
+    let mut fields = fields.iter();
+    let field_type = match (fields.next(), fields.next()) {
+        (Some(field), None) => field_type(field)?,
+        _ => {
+            return Err(Error::new(
+                name.span(),
+                "transparent structs must have exactly one field",
+            ))
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
+            #[inline]
+            fn signature() -> #zv::Signature<'static> {
+                <#field_type as #zv::Type>::signature()
+            }
+        }
+    })
+}
+
 fn signature_for_struct(
     fields: &Fields,
     zv: &TokenStream,
     insert_enum_variant: bool,
-) -> TokenStream {
-    let field_types = fields.iter().map(|field| field.ty.to_token_stream());
+) -> Result<TokenStream, Error> {
+    let field_types = fields
+        .iter()
+        .map(field_type)
+        .collect::<Result<Vec<_>, Error>>()?;
     let new_type = match fields {
         Fields::Named(_) => false,
         Fields::Unnamed(_) if field_types.len() == 1 => true,
@@ -107,7 +218,7 @@ fn signature_for_struct(
         }
     };
 
-    if insert_enum_variant {
+    Ok(if insert_enum_variant {
         quote! {
             let inner_signature = {
                 #inner_impl
@@ -121,7 +232,130 @@ fn signature_for_struct(
         }
     } else {
         inner_impl
+    })
+}
+
+// Compute the signature only once, on first use, since `Type::signature` is called a lot during
+// (de)serialization. This is only possible for types without type or const parameters, as a
+// `static` is shared between all instantiations of a generic type.
+fn cached_signature(signature: TokenStream, generics: &Generics, zv: &TokenStream) -> TokenStream {
+    if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+        return signature;
+    }
+
+    quote! {
+        static SIGNATURE: #zv::export::once_cell::sync::OnceCell<#zv::Signature<'static>> =
+            #zv::export::once_cell::sync::OnceCell::new();
+
+        ::std::clone::Clone::clone(SIGNATURE.get_or_init(|| { #signature }))
+    }
+}
+
+// The type to use for the signature of `field`: the type given through the `as` attribute, if
+// any, or the type of the field itself.
+fn field_type(field: &Field) -> Result<TokenStream, Error> {
+    let FieldAttributes { r#as, .. } = FieldAttributes::parse(&field.attrs)?;
+
+    match r#as {
+        Some(ty) => parse_as_type(field, &ty),
+        None => Ok(field.ty.to_token_stream()),
+    }
+}
+
+fn parse_as_type(field: &Field, ty: &str) -> Result<TokenStream, Error> {
+    syn::parse_str::<Type>(ty)
+        .map(|ty| ty.to_token_stream())
+        .map_err(|e| Error::new(field.span(), format!("invalid `as` type: {e}")))
+}
+
+// Fields with an `as` attribute are (de)serialized through a helper converting them from/to the
+// `as` type, so their encoding matches their signature. The helper is generated here, under the
+// name given through serde's `with` attribute.
+fn as_helpers(data: &Data, zv: &TokenStream) -> Result<TokenStream, Error> {
+    let fields: Vec<&Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => vec![],
+    };
+    let mut helpers: Vec<(Ident, String)> = vec![];
+    let mut tokens = TokenStream::new();
+    for field in fields {
+        let FieldAttributes { r#as, .. } = FieldAttributes::parse(&field.attrs)?;
+        let as_type = match r#as {
+            Some(as_type) => as_type,
+            None => continue,
+        };
+        let with = serde_with(&field.attrs).ok_or_else(|| {
+            Error::new(
+                field.span(),
+                "the `as` attribute requires serde's `with` attribute, naming the helper to \
+                 (de)serialize the field through",
+            )
+        })?;
+        let helper = with.parse::<Ident>().map_err(|_| {
+            Error::new(
+                with.span(),
+                "the helper for the `as` attribute is generated, so serde's `with` attribute \
+                 must be a single identifier",
+            )
+        })?;
+        let as_type = parse_as_type(field, &as_type)?;
+        // Fields sharing a helper share its definition, as long as they have the same `as` type.
+        match helpers.iter().find(|(ident, _)| *ident == helper) {
+            Some((_, ty)) if *ty == as_type.to_string() => continue,
+            Some(_) => {
+                return Err(Error::new(
+                    with.span(),
+                    "fields sharing a `with` helper must have the same `as` type",
+                ))
+            }
+            None => helpers.push((helper.clone(), as_type.to_string())),
+        }
+
+        tokens.extend(quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            struct #helper;
+
+            #[allow(dead_code)]
+            impl #helper {
+                fn serialize<F, S>(
+                    field: &F,
+                    serializer: S,
+                ) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    F: ::std::clone::Clone,
+                    #as_type: ::std::convert::TryFrom<F> + #zv::export::serde::Serialize,
+                    <#as_type as ::std::convert::TryFrom<F>>::Error: ::std::fmt::Display,
+                    S: #zv::export::serde::Serializer,
+                {
+                    let value = <#as_type as ::std::convert::TryFrom<F>>::try_from(
+                        ::std::clone::Clone::clone(field),
+                    )
+                    .map_err(<S::Error as #zv::export::serde::ser::Error>::custom)?;
+
+                    #zv::export::serde::Serialize::serialize(&value, serializer)
+                }
+
+                fn deserialize<'de, F, D>(deserializer: D) -> ::std::result::Result<F, D::Error>
+                where
+                    F: ::std::convert::TryFrom<#as_type>,
+                    <F as ::std::convert::TryFrom<#as_type>>::Error: ::std::fmt::Display,
+                    #as_type: #zv::export::serde::Deserialize<'de>,
+                    D: #zv::export::serde::Deserializer<'de>,
+                {
+                    let value = <#as_type as #zv::export::serde::Deserialize<'de>>::deserialize(
+                        deserializer,
+                    )?;
+
+                    <F as ::std::convert::TryFrom<#as_type>>::try_from(value)
+                        .map_err(<D::Error as #zv::export::serde::de::Error>::custom)
+                }
+            }
+        });
     }
+
+    Ok(tokens)
 }
 
 fn impl_unit_struct(
@@ -162,6 +396,7 @@ fn impl_enum(
     name: Ident,
     generics: Generics,
     attrs: Vec<Attribute>,
+    zvariant_repr: bool,
     data: DataEnum,
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
@@ -181,12 +416,22 @@ fn impl_enum(
         }
     }
 
+    let signature = cached_signature(signature, &generics, zv);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // `#[zvariant(repr = "...")]` only affects the encoding through `SerializeRepr`, without which
+    // the signature wouldn't match the encoding. So fail the build unless it's derived.
+    let repr_check = zvariant_repr.then(|| {
+        quote! {
+            fn zvariant_repr_requires_serialize_repr<T: ?Sized + #zv::export::ReprEncoded>() {}
+            zvariant_repr_requires_serialize_repr::<Self>();
+        }
+    });
 
     Ok(quote! {
         impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
             #[inline]
             fn signature() -> #zv::Signature<'static> {
+                #repr_check
                 #signature
             }
         }
@@ -198,17 +443,12 @@ fn signature_for_variant(
     attrs: &[Attribute],
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
-    let repr = attrs.iter().find(|attr| attr.path.is_ident("repr"));
     match &variant.fields {
         Fields::Unit => {
-            let repr = match repr {
-                Some(repr_attr) => repr_attr.parse_args()?,
-                None => quote! { u32 },
-            };
+            let repr = enum_repr(attrs)?;
 
             Ok(quote! { <#repr as #zv::Type>::signature() })
         }
-        Fields::Named(_) => Ok(signature_for_struct(&variant.fields, zv, true)),
-        Fields::Unnamed(_) => Ok(signature_for_struct(&variant.fields, zv, true)),
+        Fields::Named(_) | Fields::Unnamed(_) => signature_for_struct(&variant.fields, zv, true),
     }
 }
@@ -1,6 +1,7 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::{format_ident, quote};
+use syn::{Attribute, Lit, LitStr, Meta, MetaNameValue, NestedMeta};
 use zvariant_utils::def_attrs;
 
 pub fn zvariant_path() -> TokenStream {
@@ -19,7 +20,86 @@ def_attrs! {
     crate zvariant;
 
     /// Attributes defined on structures.
-    pub StructAttributes("struct") { signature str, rename_all str, deny_unknown_fields none };
+    pub StructAttributes("struct") {
+        signature str, rename_all str, deny_unknown_fields none, bound str, repr str,
+        bitflags none
+    };
     /// Attributes defined on fields.
-    pub FieldAttributes("field") { rename str };
+    pub FieldAttributes("field") { rename str, default str_or_none, r#as str };
+}
+
+/// The integer type unit-only enums are encoded as.
+///
+/// This is the type given through `#[zvariant(repr = "...")]` if any, otherwise the type of the
+/// Rust `#[repr(...)]` attribute, falling back to `u32`.
+pub fn enum_repr(attrs: &[Attribute]) -> Result<TokenStream, syn::Error> {
+    let StructAttributes { repr, .. } = StructAttributes::parse(attrs)?;
+    if let Some(repr) = repr {
+        return match repr.as_str() {
+            "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" => {
+                let ident = format_ident!("{}", repr);
+
+                Ok(quote! { #ident })
+            }
+            _ => Err(syn::Error::new(
+                Span::call_site(),
+                format!("invalid `repr` attribute value {repr}, expected a D-Bus integer type"),
+            )),
+        };
+    }
+
+    match attrs.iter().find(|attr| attr.path.is_ident("repr")) {
+        Some(repr_attr) => repr_attr.parse_args(),
+        None => Ok(quote! { u32 }),
+    }
+}
+
+/// Rejects the `#[zvariant(repr = "...")]` attribute on structures, as it only applies to enums.
+pub fn reject_struct_repr(repr: &Option<String>) -> Result<(), syn::Error> {
+    match repr {
+        Some(_) => Err(syn::Error::new(
+            Span::call_site(),
+            "`repr` attribute is only supported on enums",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks if the type is marked with `#[serde(transparent)]`.
+///
+/// Malformed `serde` attributes are ignored here, since serde's own derive macros will report them.
+pub fn is_serde_transparent(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list),
+            _ => None,
+        })
+        .flat_map(|list| list.nested)
+        .any(|nested| {
+            matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("transparent"))
+        })
+}
+
+/// The value of serde's `with` attribute, if any.
+///
+/// Malformed `serde` attributes are ignored here, since serde's own derive macros will report them.
+pub fn serde_with(attrs: &[Attribute]) -> Option<LitStr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list),
+            _ => None,
+        })
+        .flat_map(|list| list.nested)
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(with),
+                ..
+            })) if path.is_ident("with") => Some(with),
+            _ => None,
+        })
 }
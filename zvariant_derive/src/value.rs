@@ -18,7 +18,10 @@ pub fn expand_derive(ast: DeriveInput, value_type: ValueType) -> Result<TokenStr
     match &ast.data {
         Data::Struct(ds) => match &ds.fields {
             Fields::Named(_) | Fields::Unnamed(_) => {
-                let StructAttributes { signature, .. } = StructAttributes::parse(&ast.attrs)?;
+                let StructAttributes {
+                    signature, repr, ..
+                } = StructAttributes::parse(&ast.attrs)?;
+                reject_struct_repr(&repr)?;
                 let signature = signature.map(|signature| match signature.as_str() {
                     "dict" => "a{sv}".to_string(),
                     _ => signature,
@@ -30,6 +33,7 @@ pub fn expand_derive(ast: DeriveInput, value_type: ValueType) -> Result<TokenStr
                     ast.generics,
                     &ds.fields,
                     signature,
+                    is_serde_transparent(&ast.attrs),
                     &zv,
                 )
             }
@@ -49,6 +53,7 @@ fn impl_struct(
     generics: Generics,
     fields: &Fields,
     signature: Option<String>,
+    transparent: bool,
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
     let statc_lifetime = LifetimeDef::new(Lifetime::new("'static", Span::call_site()));
@@ -92,11 +97,64 @@ fn impl_struct(
     };
     let (impl_generics, ty_generics, _) = generics.split_for_impl();
     match fields {
+        Fields::Named(_) if transparent => {
+            // Just like serde, we require exactly one field to forward to.
+            let field_name = match fields.iter().collect::<Vec<_>>().as_slice() {
+                [field] => &field.ident,
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        "transparent structs must have exactly one field",
+                    ))
+                }
+            };
+
+            Ok(quote! {
+                impl #impl_generics ::std::convert::TryFrom<#value_type> for #name #ty_generics
+                    #from_value_where_clause
+                {
+                    type Error = #zv::Error;
+
+                    #[inline]
+                    fn try_from(value: #value_type) -> #zv::Result<Self> {
+                        ::std::convert::TryInto::try_into(value).map(|v| Self { #field_name: v })
+                    }
+                }
+
+                impl #impl_generics From<#name #ty_generics> for #value_type
+                    #into_value_where_clause
+                {
+                    #[inline]
+                    fn from(s: #name #ty_generics) -> Self {
+                        s.#field_name.into()
+                    }
+                }
+            })
+        }
         Fields::Named(_) => {
             let field_names: Vec<_> = fields
                 .iter()
                 .map(|field| field.ident.to_token_stream())
                 .collect();
+            let dict_field_values: Vec<_> = fields
+                .iter()
+                .map(|field| {
+                    let name = &field.ident;
+                    let path = quote! { ::std::format!("[\"{}\"]", stringify!(#name)) };
+
+                    downcast_field(&field.ty, path, zv)
+                })
+                .collect();
+            let struct_field_values: Vec<_> = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let path = format!(".{i}");
+                    let path = quote! { ::std::string::String::from(#path) };
+
+                    downcast_field(&field.ty, path, zv)
+                })
+                .collect();
             let (from_value_impl, into_value_impl) = match signature {
                 Some(signature) if signature == "a{sv}" => (
                     // User wants the type to be encoded as a dict.
@@ -106,12 +164,16 @@ fn impl_struct(
 
                         ::std::result::Result::Ok(Self {
                             #(
-                                #field_names:
-                                    fields
+                                #field_names: {
+                                    let value = fields
                                         .remove(stringify!(#field_names))
-                                        .ok_or_else(|| #zv::Error::IncorrectType)?
-                                        .downcast()
-                                        .ok_or_else(|| #zv::Error::IncorrectType)?
+                                        .ok_or_else(|| #zv::Error::Message(::std::format!(
+                                            "missing field `{}`",
+                                            stringify!(#field_names),
+                                        )))?;
+
+                                    #dict_field_values
+                                }
                             ),*
                         })
                     },
@@ -130,11 +192,11 @@ fn impl_struct(
 
                         ::std::result::Result::Ok(Self {
                             #(
-                                #field_names:
-                                    fields
-                                        .remove(0)
-                                        .downcast()
-                                        .ok_or_else(|| #zv::Error::IncorrectType)?
+                                #field_names: {
+                                    let value = fields.remove(0);
+
+                                    #struct_field_values
+                                }
                             ),*
                         })
                     },
@@ -199,6 +261,30 @@ fn impl_struct(
     }
 }
 
+// Convert the `Value` named `value` to a field of type `ty`, reporting a `TypeMismatch` error at
+// `path` on failure. The signature found is computed upfront since `downcast` consumes `value`,
+// looking through `Value::Value` like `downcast` does.
+fn downcast_field(ty: &syn::Type, path: TokenStream, zv: &TokenStream) -> TokenStream {
+    quote! {{
+        value.try_downcast::<#ty>().map_err(|e| {
+            let found = match e {
+                #zv::TryDowncastError::Mismatch(#zv::Value::Value(v)) => {
+                    v.value_signature().to_owned()
+                }
+                #zv::TryDowncastError::Mismatch(v) => v.value_signature().to_owned(),
+                // The signatures matched but the conversion failed.
+                #zv::TryDowncastError::Conversion(_) => <#ty as #zv::Type>::signature(),
+            };
+
+            #zv::Error::TypeMismatch {
+                expected: ::std::string::String::from(::std::any::type_name::<#ty>()),
+                found,
+                path: #path,
+            }
+        })?
+    }}
+}
+
 fn impl_enum(
     value_type: ValueType,
     name: Ident,
@@ -207,10 +293,7 @@ fn impl_enum(
     data: &DataEnum,
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
-    let repr: TokenStream = match attrs.iter().find(|attr| attr.path.is_ident("repr")) {
-        Some(repr_attr) => repr_attr.parse_args()?,
-        None => quote! { u32 },
-    };
+    let repr = enum_repr(&attrs)?;
 
     let mut variant_names = vec![];
     let mut variant_values = vec![];
@@ -256,7 +339,11 @@ fn impl_enum(
                     #(
                         #variant_values => #name::#variant_names
                      ),*,
-                    _ => return ::std::result::Result::Err(#zv::Error::IncorrectType),
+                    _ => return ::std::result::Result::Err(#zv::Error::Message(::std::format!(
+                        "invalid discriminant `{}` for enum `{}`",
+                        v,
+                        ::std::stringify!(#name),
+                    ))),
                 })
             }
         }
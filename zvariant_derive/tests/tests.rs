@@ -74,3 +74,252 @@ fn derive_dict() {
 
     assert_eq!(Test::signature(), "a{sv}")
 }
+
+#[test]
+fn derive_transparent() {
+    #[derive(serde::Serialize, serde::Deserialize, Type, Value, OwnedValue, PartialEq, Debug)]
+    #[serde(transparent)]
+    struct Props {
+        props: HashMap<String, OwnedValue>,
+    }
+
+    assert_eq!(Props::signature(), "a{sv}");
+
+    let mut props = HashMap::new();
+    props.insert("answer".to_string(), Value::from(42u32).into());
+    let props = Props { props };
+
+    let ctxt = EncodingContext::<LE>::new(EncodingFormat::DBus, 0);
+    let serialized = zvariant::to_bytes(ctxt, &props).unwrap();
+    assert_eq!(serialized, zvariant::to_bytes(ctxt, &props.props).unwrap());
+    let deserialized: Props = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(deserialized, props);
+
+    let value = OwnedValue::from(deserialized);
+    assert_eq!(value.value_signature(), "a{sv}");
+    assert_eq!(Props::try_from(value).unwrap(), props);
+}
+
+#[test]
+fn derive_as() {
+    use serde::{Deserialize, Serialize};
+
+    // Types not implementing `Type`, as if from another crate.
+    #[derive(Clone, Debug, PartialEq)]
+    struct SmallBytes([u8; 4]);
+    #[derive(Clone, Debug, PartialEq)]
+    struct Map<K, V>(Vec<(K, V)>);
+
+    impl From<SmallBytes> for Vec<u8> {
+        fn from(bytes: SmallBytes) -> Self {
+            bytes.0.to_vec()
+        }
+    }
+
+    impl TryFrom<Vec<u8>> for SmallBytes {
+        type Error = String;
+
+        fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+            bytes
+                .try_into()
+                .map(SmallBytes)
+                .map_err(|b: Vec<u8>| format!("expected 4 bytes, got {}", b.len()))
+        }
+    }
+
+    impl From<Map<String, u32>> for HashMap<String, u32> {
+        fn from(map: Map<String, u32>) -> Self {
+            map.0.into_iter().collect()
+        }
+    }
+
+    type Payload = SmallBytes;
+
+    #[derive(Serialize, Deserialize, Type, Debug, PartialEq)]
+    struct Packet {
+        id: u32,
+        #[serde(with = "payload_as_vec")]
+        #[zvariant(as = "Vec<u8>")]
+        payload: Payload,
+    }
+
+    #[derive(Serialize, Type)]
+    struct Counts {
+        #[serde(with = "counts_as_map")]
+        #[zvariant(as = "HashMap<String, u32>")]
+        counts: Map<String, u32>,
+    }
+
+    assert_eq!(Packet::signature(), "(uay)");
+    assert_eq!(Counts::signature(), "(a{su})");
+
+    let ctxt = EncodingContext::<LE>::new_dbus(0);
+    let packet = Packet {
+        id: 7,
+        payload: SmallBytes([1, 2, 3, 4]),
+    };
+    let encoded = zvariant::to_bytes(ctxt, &packet).unwrap();
+    let decoded: (u32, Vec<u8>) = zvariant::from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, (7, vec![1, 2, 3, 4]));
+    let decoded: Packet = zvariant::from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, packet);
+    // Conversion failures are reported as deserialization errors.
+    let encoded = zvariant::to_bytes(ctxt, &(7u32, vec![1u8, 2, 3])).unwrap();
+    assert!(zvariant::from_slice::<_, Packet>(&encoded, ctxt).is_err());
+
+    let counts = Counts {
+        counts: Map(vec![("a".to_string(), 1)]),
+    };
+    let encoded = zvariant::to_bytes(ctxt, &counts).unwrap();
+    let decoded: (HashMap<String, u32>,) = zvariant::from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded.0, HashMap::from([("a".to_string(), 1)]));
+
+    #[derive(Serialize, Type)]
+    enum Message {
+        Request(
+            #[serde(with = "request_as_vec")]
+            #[zvariant(as = "Vec<u8>")]
+            SmallBytes,
+            u32,
+        ),
+        Reply(Vec<u8>, u32),
+    }
+
+    assert_eq!(Message::signature(), "(u(ayu))");
+    let encoded = zvariant::to_bytes(ctxt, &Message::Request(SmallBytes([1, 2, 3, 4]), 5)).unwrap();
+    let decoded: (u32, (Vec<u8>, u32)) = zvariant::from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, (0, (vec![1, 2, 3, 4], 5)));
+}
+
+#[test]
+fn derive_generics() {
+    #[derive(Type)]
+    struct Pair<T>(T, T);
+
+    #[derive(Type)]
+    struct Tagged<'a, T, U: Clone>
+    where
+        U: Default,
+    {
+        tag: &'a str,
+        first: T,
+        rest: Vec<U>,
+    }
+
+    #[derive(Type)]
+    enum Either<L, R> {
+        Left(L, R),
+        Right(L, R),
+    }
+
+    assert_eq!(Pair::<u32>::signature(), "(uu)");
+    assert_eq!(Pair::<Pair<u8>>::signature(), "((yy)(yy))");
+    assert_eq!(Tagged::<'_, i16, String>::signature(), "(snas)");
+    assert_eq!(Either::<u8, String>::signature(), "(u(ys))");
+
+    // Explicit bounds, for a parameter that isn't a `Type` itself.
+    trait Unit {
+        type Repr;
+    }
+    struct Meters;
+    impl Unit for Meters {
+        type Repr = u64;
+    }
+
+    #[derive(Type)]
+    #[zvariant(bound = "U::Repr: Type")]
+    struct Length<U: Unit> {
+        value: U::Repr,
+        name: String,
+    }
+
+    assert_eq!(Length::<Meters>::signature(), "(ts)");
+}
+
+#[test]
+fn derive_cached_signature() {
+    #[derive(Type)]
+    struct Inner<'a>(u8, &'a str);
+
+    #[derive(Type)]
+    struct Outer<'a> {
+        inner: Vec<Inner<'a>>,
+        flag: bool,
+    }
+
+    // Lifetimes don't prevent caching but type parameters do, so these must not be mixed up.
+    #[derive(Type)]
+    struct Wrapper<T>(T, Outer<'static>);
+
+    for _ in 0..2 {
+        assert_eq!(Outer::signature(), "(a(ys)b)");
+        assert_eq!(Wrapper::<u16>::signature(), "(q(a(ys)b))");
+        assert_eq!(Wrapper::<i64>::signature(), "(x(a(ys)b))");
+    }
+}
+
+#[test]
+fn derive_repr() {
+    use zvariant::{from_slice, to_bytes, DeserializeRepr, SerializeRepr};
+
+    #[derive(SerializeRepr, DeserializeRepr, Type, Debug, PartialEq)]
+    #[zvariant(repr = "i16")]
+    enum Level {
+        Low = -1,
+        Normal = 0,
+        High = 300,
+    }
+
+    // Rust's `repr` is used, unless overridden.
+    #[derive(SerializeRepr, DeserializeRepr, Type, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Flag {
+        Off,
+        On,
+    }
+
+    #[derive(SerializeRepr, DeserializeRepr, Type, Debug, PartialEq)]
+    #[repr(u8)]
+    #[zvariant(repr = "u64")]
+    enum Wide {
+        A = 7,
+    }
+
+    assert_eq!(Level::signature(), "n");
+    assert_eq!(Flag::signature(), "y");
+    assert_eq!(Wide::signature(), "t");
+
+    let ctxt = EncodingContext::<LE>::new_dbus(0);
+    let encoded = to_bytes(ctxt, &(Level::Low, Level::High, Flag::On, Wide::A)).unwrap();
+    let decoded: (i16, i16, u8, u64) = from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, (-1, 300, 1, 7));
+    let decoded: (Level, Level, Flag, Wide) = from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, (Level::Low, Level::High, Flag::On, Wide::A));
+
+    let encoded = to_bytes(ctxt, &1_i16).unwrap();
+    assert!(from_slice::<_, Level>(&encoded, ctxt).is_err());
+}
+
+#[test]
+fn derive_bitflags() {
+    use serde::{Deserialize, Serialize};
+    use zvariant::{from_slice, to_bytes};
+
+    bitflags::bitflags! {
+        #[derive(Deserialize, Serialize, Type, Debug, PartialEq)]
+        #[zvariant(bitflags)]
+        struct Small: u8 {
+            const A = 1 << 0;
+            const B = 1 << 7;
+        }
+    }
+
+    assert_eq!(Small::signature(), "y");
+    assert_eq!(<(Small, String)>::signature(), "(ys)");
+
+    let ctxt = EncodingContext::<LE>::new_dbus(0);
+    let encoded = to_bytes(ctxt, &(Small::A | Small::B)).unwrap();
+    assert_eq!(*encoded, [0x81]);
+    let decoded: Small = from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, Small::all());
+}
@@ -32,6 +32,12 @@ fn get_meta_value<'a>(meta: &'a Meta, attr: &str) -> Result<&'a Lit> {
     }
 }
 
+/// Strips the `r#` prefix of raw identifiers, so that attributes can be named after keywords (e.g
+/// `r#as` for `as`).
+pub fn unraw(attr: &str) -> &str {
+    attr.strip_prefix("r#").unwrap_or(attr)
+}
+
 /// Compares `ident` and `attr` and in case they match ensures `value` is `Some` and contains a
 /// [`struct@LitStr`]. Returns `true` in case `ident` and `attr` match, otherwise false.
 ///
@@ -43,6 +49,7 @@ pub fn match_attribute_with_str_value<'a>(
     meta: &'a Meta,
     attr: &str,
 ) -> Result<Option<&'a LitStr>> {
+    let attr = unraw(attr);
     if meta.path().is_ident(attr) {
         match get_meta_value(meta, attr)? {
             Lit::Str(value) => Ok(Some(value)),
@@ -67,6 +74,7 @@ pub fn match_attribute_with_bool_value<'a>(
     meta: &'a Meta,
     attr: &str,
 ) -> Result<Option<&'a LitBool>> {
+    let attr = unraw(attr);
     if meta.path().is_ident(attr) {
         match get_meta_value(meta, attr)? {
             Lit::Bool(value) => Ok(Some(value)),
@@ -80,7 +88,30 @@ pub fn match_attribute_with_bool_value<'a>(
     }
 }
 
+/// Compares `ident` and `attr` and in case they match ensures `value` is either `None` or contains
+/// a [`struct@LitStr`]. Returns `Some(None)` for the former, `Some(Some(value))` for the latter and
+/// `None` in case `ident` and `attr` don't match.
+///
+/// # Errors
+///
+/// Returns an error in case `ident` and `attr` match but the value is not a [`struct@LitStr`].
+pub fn match_attribute_with_optional_str_value<'a>(
+    meta: &'a Meta,
+    attr: &str,
+) -> Result<Option<Option<&'a LitStr>>> {
+    let attr = unraw(attr);
+    if meta.path().is_ident(attr) {
+        match meta {
+            Meta::Path(_) => Ok(Some(None)),
+            _ => match_attribute_with_str_value(meta, attr).map(|v| v.map(Some)),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn match_attribute_with_str_list_value(meta: &Meta, attr: &str) -> Result<Option<Vec<String>>> {
+    let attr = unraw(attr);
     if meta.path().is_ident(attr) {
         match meta {
             Meta::List(list) => {
@@ -119,6 +150,7 @@ pub fn match_attribute_with_str_list_value(meta: &Meta, attr: &str) -> Result<Op
 ///
 /// Returns an error in case `ident` and `attr` match but the value is not `None`.
 pub fn match_attribute_without_value(meta: &Meta, attr: &str) -> Result<bool> {
+    let attr = unraw(attr);
     if meta.path().is_ident(attr) {
         match meta {
             Meta::Path(_) => Ok(true),
@@ -197,7 +229,12 @@ pub fn iter_meta_lists(
 /// * `str` - string literals;
 /// * `bool` - boolean literals;
 /// * `[str]` - lists of string literals (`#[macro_name(foo("bar", "baz"))]`);
-/// * `none` - no literal at all, the attribute is specified alone.
+/// * `none` - no literal at all, the attribute is specified alone;
+/// * `str_or_none` - either a string literal or no literal at all. The field type is
+///   `Option<Option<String>>`, with `Some(None)` meaning the attribute is specified alone.
+///
+/// Attributes named after a keyword are declared with a raw identifier (e.g `r#as str` for
+/// `#[macro_name(as = "...")]`).
 ///
 /// The strings between braces are embedded into error messages produced when an attribute defined
 /// for one attribute group is used on another group where it is not defined. For example, if the
@@ -249,6 +286,7 @@ macro_rules! def_attrs {
     (@attr_ty bool) => {::std::option::Option<bool>};
     (@attr_ty [str]) => {::std::option::Option<::std::vec::Vec<::std::string::String>>};
     (@attr_ty none) => {bool};
+    (@attr_ty str_or_none) => {::std::option::Option<::std::option::Option<::std::string::String>>};
     (@attr_ty {
         $(#[$m:meta])*
         $vis:vis $name:ident($what:literal) {
@@ -263,7 +301,10 @@ macro_rules! def_attrs {
             } else {
                 return ::std::result::Result::Err(::syn::Error::new(
                     $meta.span(),
-                    ::std::concat!("duplicate `", ::std::stringify!($attr_name), "` attribute")
+                    ::std::format!(
+                        "duplicate `{}` attribute",
+                        $crate::macros::unraw(::std::stringify!($attr_name)),
+                    )
                 ));
             }
         }
@@ -303,7 +344,31 @@ macro_rules! def_attrs {
             } else {
                 return ::std::result::Result::Err(::syn::Error::new(
                     $meta.span(),
-                    concat!("duplicate `", stringify!($attr_name), "` attribute")
+                    ::std::format!(
+                        "duplicate `{}` attribute",
+                        $crate::macros::unraw(::std::stringify!($attr_name)),
+                    )
+                ));
+            }
+        }
+    };
+    (@match_attr str_or_none $attr_name:ident, $meta:ident, $self:ident) => {
+        if let ::std::option::Option::Some(value) =
+            $crate::macros::match_attribute_with_optional_str_value(
+                $meta,
+                ::std::stringify!($attr_name),
+            )?
+        {
+            if $self.$attr_name.is_none() {
+                $self.$attr_name = ::std::option::Option::Some(value.map(|v| v.value()));
+                return Ok(());
+            } else {
+                return ::std::result::Result::Err(::syn::Error::new(
+                    $meta.span(),
+                    ::std::format!(
+                        "duplicate `{}` attribute",
+                        $crate::macros::unraw(::std::stringify!($attr_name)),
+                    )
                 ));
             }
         }
@@ -319,7 +384,10 @@ macro_rules! def_attrs {
             } else {
                 return ::std::result::Result::Err(::syn::Error::new(
                     $meta.span(),
-                    concat!("duplicate `", stringify!($attr_name), "` attribute")
+                    ::std::format!(
+                        "duplicate `{}` attribute",
+                        $crate::macros::unraw(::std::stringify!($attr_name)),
+                    )
                 ));
             }
         }
@@ -328,7 +396,7 @@ macro_rules! def_attrs {
         $(#[$m:meta])*
         $vis:vis $name:ident($what:literal) $body:tt
     } $attr_name:ident, $meta:expr, $self:ident) => {
-        if $meta.path().is_ident(::std::stringify!($attr_name)) {
+        if $meta.path().is_ident($crate::macros::unraw(::std::stringify!($attr_name))) {
             return if $self.$attr_name.is_none() {
                 match $meta {
                     ::syn::Meta::List(meta) => {
@@ -343,16 +411,19 @@ macro_rules! def_attrs {
                     }
                     ::syn::Meta::NameValue(_) => Err(::syn::Error::new(
                         $meta.span(),
-                        ::std::format!(::std::concat!(
-                            "attribute `", ::std::stringify!($attr_name),
-                            "` must be either a list or a path"
-                        )),
+                        ::std::format!(
+                            "attribute `{}` must be either a list or a path",
+                            $crate::macros::unraw(::std::stringify!($attr_name)),
+                        ),
                     ))
                 }
             } else {
                 ::std::result::Result::Err(::syn::Error::new(
                     $meta.span(),
-                    concat!("duplicate `", stringify!($attr_name), "` attribute")
+                    ::std::format!(
+                        "duplicate `{}` attribute",
+                        $crate::macros::unraw(::std::stringify!($attr_name)),
+                    )
                 ))
             }
         }
@@ -361,6 +432,7 @@ macro_rules! def_attrs {
     (@def_ty $list_name:ident bool) => {};
     (@def_ty $list_name:ident [str]) => {};
     (@def_ty $list_name:ident none) => {};
+    (@def_ty $list_name:ident str_or_none) => {};
     (
         @def_ty $list_name:ident {
             $(#[$m:meta])*
@@ -409,7 +481,9 @@ macro_rules! def_attrs {
                 )+
 
                 // None of the if blocks have been taken, return the appropriate error.
-                let is_valid_attr = ALLOWED_ATTRS.iter().any(|attr| meta.path().is_ident(attr));
+                let is_valid_attr = ALLOWED_ATTRS
+                    .iter()
+                    .any(|attr| meta.path().is_ident($crate::macros::unraw(attr)));
                 return ::std::result::Result::Err(::syn::Error::new(meta.span(), if is_valid_attr {
                     ::std::format!(
                         ::std::concat!("attribute `{}` is not allowed on ", $what),